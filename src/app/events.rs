@@ -1,12 +1,16 @@
-use crate::app::{App, FocusedPanel};
+use crate::app::{App, FocusedPanel, PendingMark, SelectionCopy};
+use crate::config::keys::Action;
+use crate::config::EnterAction;
 use crate::error::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 impl App {
     pub fn handle_navigation_keys(&mut self, key: KeyEvent) -> Result<bool> {
-        match (key.code, key.modifiers) {
-            (KeyCode::Up, KeyModifiers::NONE) | (KeyCode::Char('k'), KeyModifiers::NONE) => {
-                if self.show_commit_info {
+        match self.keymap.action_for(&key) {
+            Some(Action::MoveUp) => {
+                if self.file_stats_popup.is_some() {
+                    self.scroll_file_stats_up();
+                } else if self.show_commit_info {
                     self.scroll_commit_info_up();
                 } else if let Some(focused_panel) = self.get_focused_panel() {
                     match focused_panel {
@@ -14,13 +18,16 @@ impl App {
                         FocusedPanel::Diff => {
                             let layout_mode = self.effective_layout();
                             self.ui_state.move_cursor_up(&layout_mode);
+                            self.skip_collapsed_lines(false);
                         }
                     }
                 }
                 Ok(true)
             }
-            (KeyCode::Down, KeyModifiers::NONE) | (KeyCode::Char('j'), KeyModifiers::NONE) => {
-                if self.show_commit_info {
+            Some(Action::MoveDown) => {
+                if self.file_stats_popup.is_some() {
+                    self.scroll_file_stats_down();
+                } else if self.show_commit_info {
                     self.scroll_commit_info_down();
                 } else if let Some(focused_panel) = self.get_focused_panel() {
                     match focused_panel {
@@ -29,72 +36,94 @@ impl App {
                             let max_lines = self.get_diff_line_count();
                             let layout_mode = self.effective_layout();
                             self.ui_state.move_cursor_down(max_lines, &layout_mode);
+                            self.skip_collapsed_lines(true);
                         }
                     }
                 }
                 Ok(true)
             }
-            (KeyCode::Tab, KeyModifiers::NONE) => {
+            Some(Action::SwitchFocus) => {
                 self.switch_focus();
                 Ok(true)
             }
+            Some(Action::JumpToTop) => {
+                if self.show_commit_info || self.file_stats_popup.is_some() {
+                    return Ok(false);
+                }
+                // Like vim, it takes two presses (`gg`)
+                if self.pending_g {
+                    self.pending_g = false;
+                    self.jump_to_top()?;
+                } else {
+                    self.pending_g = true;
+                }
+                Ok(true)
+            }
+            Some(Action::JumpToBottom) => {
+                if self.show_commit_info || self.file_stats_popup.is_some() {
+                    return Ok(false);
+                }
+                self.jump_to_bottom()?;
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
 
     pub fn handle_scrolling_keys(&mut self, key: KeyEvent) -> Result<bool> {
-        match (key.code, key.modifiers) {
-            (KeyCode::PageUp, _) => {
-                // Always scroll diff for PageUp/PageDown regardless of focus
+        match self.keymap.action_for(&key) {
+            // Always scroll diff for page keys regardless of focus, vim-style: PageUp/PageDown
+            // and Ctrl+B/F by a page, Ctrl+U/D by half a page, Ctrl+Y/E by a line
+            Some(Action::PageUp) => {
                 self.ui_state.scroll_diff_page_up();
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
-            (KeyCode::PageDown, _) => {
-                // Always scroll diff for PageUp/PageDown regardless of focus
+            Some(Action::PageDown) => {
                 let max_lines = self.get_diff_line_count();
                 self.ui_state.scroll_diff_page_down(max_lines);
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
-            // Mac-friendly vim-style navigation
-            (KeyCode::Char('u'), KeyModifiers::CONTROL) => {
-                // Ctrl+U = Page Up (vim-style)
-                self.ui_state.scroll_diff_page_up();
+            Some(Action::HalfPageUp) => {
+                self.ui_state.scroll_diff_half_page_up();
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
-            (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-                // Ctrl+D = Page Down (vim-style)
+            Some(Action::HalfPageDown) => {
                 let max_lines = self.get_diff_line_count();
-                self.ui_state.scroll_diff_page_down(max_lines);
+                self.ui_state.scroll_diff_half_page_down(max_lines);
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
-            // Mac-friendly emacs-style navigation
-            (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
-                // Ctrl+B = Page Up (emacs-style)
-                self.ui_state.scroll_diff_page_up();
+            Some(Action::ScrollLineUp) => {
+                self.ui_state.scroll_diff_line_up();
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
-            (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
-                // Ctrl+F = Page Down (emacs-style)
+            Some(Action::ScrollLineDown) => {
                 let max_lines = self.get_diff_line_count();
-                self.ui_state.scroll_diff_page_down(max_lines);
+                self.ui_state.scroll_diff_line_down(max_lines);
+                self.keep_diff_cursor_in_view();
                 Ok(true)
             }
             // Horizontal scrolling (but not when in copy mode)
-            (KeyCode::Char('a'), KeyModifiers::NONE) => {
-                // Don't handle 'a' for scrolling when in copy mode
+            Some(Action::ScrollLeft) => {
+                // Copy targets share keys with scrolling
                 if self.copy_mode.is_some() {
                     return Ok(false); // Let copy handler deal with it
                 }
                 if let Some(focused_panel) = self.get_focused_panel() {
                     match focused_panel {
                         FocusedPanel::Commits => self.ui_state.scroll_commit_left(),
+                        // Wrapped lines have nothing off to the side
+                        FocusedPanel::Diff if self.wraps_diff() => {}
                         FocusedPanel::Diff => self.ui_state.scroll_diff_left(),
                     }
                 }
                 Ok(true)
             }
-            (KeyCode::Char('s'), KeyModifiers::NONE) => {
-                // Don't handle 's' for scrolling when in copy mode
+            Some(Action::ScrollRight) => {
                 if self.copy_mode.is_some() {
                     return Ok(false); // Let copy handler deal with it
                 }
@@ -104,6 +133,7 @@ impl App {
                             let max_width = self.calculate_max_commit_line_width();
                             self.ui_state.scroll_commit_right(max_width);
                         }
+                        FocusedPanel::Diff if self.wraps_diff() => {}
                         FocusedPanel::Diff => {
                             let max_width = self.calculate_max_diff_line_width();
                             self.ui_state.scroll_diff_right(max_width);
@@ -116,11 +146,39 @@ impl App {
         }
     }
 
+    /// An explicit scroll moves the view, not the cursor, unless it would leave the cursor
+    /// off screen for the next cursor move to jump back to
+    fn keep_diff_cursor_in_view(&mut self) {
+        let layout_mode = self.effective_layout();
+        self.ui_state.keep_cursor_in_view(&layout_mode);
+    }
+
     pub fn handle_ui_keys(&mut self, key: KeyEvent) -> Result<bool> {
-        match (key.code, key.modifiers) {
-            (KeyCode::Char('q'), KeyModifiers::NONE) => {
+        if key.code == KeyCode::Esc && self.file_stats_popup.is_some() {
+            self.hide_file_stats_popup();
+            return Ok(true);
+        }
+        if key.code == KeyCode::Esc && self.pickaxe_applied() {
+            self.clear_pickaxe()?;
+            return Ok(true);
+        }
+        if key.code == KeyCode::Esc && self.author_filter_applied() {
+            self.clear_author_filter()?;
+            return Ok(true);
+        }
+
+        match self.keymap.action_for(&key) {
+            Some(Action::QuitAndPrint) => {
+                self.quit_and_print();
+                Ok(true)
+            }
+            Some(Action::Quit) => {
                 if self.ui_state.show_help {
                     self.ui_state.show_help = false;
+                } else if self.copy_mode.is_some() && self.show_copy_help {
+                    self.show_copy_help = false;
+                } else if self.file_stats_popup.is_some() {
+                    self.hide_file_stats_popup();
                 } else if self.show_commit_info {
                     self.hide_commit_info_popup();
                 } else if self.diff_search_state.is_some() {
@@ -129,33 +187,45 @@ impl App {
                     self.cancel_copy_mode();
                 } else if self.diff_range_start.is_some() {
                     self.clear_diff_range_selection();
-                } else if self.came_from_file_picker {
-                    // Return to file picker if we came from there
-                    if let Err(e) = self.switch_to_file_picker() {
-                        self.error_message =
-                            Some(format!("Failed to return to file picker: {}", e));
-                    }
+                } else if self.blame_lines.is_some() {
+                    self.toggle_blame();
                 } else {
-                    self.quit();
+                    self.leave_history();
                 }
                 Ok(true)
             }
-            (KeyCode::Char('h'), KeyModifiers::NONE) => {
-                self.ui_state.decrease_split_ratio();
+            // Resize the commit list, whether it's a column or a strip below the diff
+            Some(Action::ShrinkSplit) => {
+                if self.commits_at_bottom() {
+                    self.ui_state.increase_vertical_split_ratio();
+                } else {
+                    self.ui_state.decrease_split_ratio();
+                }
                 Ok(true)
             }
-            (KeyCode::Char('l'), KeyModifiers::NONE) => {
-                self.ui_state.increase_split_ratio();
+            Some(Action::GrowSplit) => {
+                if self.commits_at_bottom() {
+                    self.ui_state.decrease_vertical_split_ratio();
+                } else {
+                    self.ui_state.increase_split_ratio();
+                }
                 Ok(true)
             }
-            (KeyCode::Char('f'), KeyModifiers::NONE) => {
+            Some(Action::FilePicker) => {
                 // Open file picker to switch files
                 if let Err(e) = self.switch_to_file_picker() {
                     self.error_message = Some(format!("Failed to open file picker: {}", e));
                 }
                 Ok(true)
             }
-            (KeyCode::Char('/'), KeyModifiers::NONE) => {
+            Some(Action::FilePickerInDirectory) => {
+                // Open file picker filtered to the current file's directory
+                if let Err(e) = self.switch_to_file_picker_in_directory() {
+                    self.error_message = Some(format!("Failed to open file picker: {}", e));
+                }
+                Ok(true)
+            }
+            Some(Action::Search) => {
                 if !self.show_commit_info && self.get_focused_panel() == Some(FocusedPanel::Diff) {
                     self.start_diff_search();
                     Ok(true)
@@ -163,59 +233,237 @@ impl App {
                     Ok(false) // Let other handlers deal with it
                 }
             }
-            (KeyCode::Char('d'), KeyModifiers::NONE) => {
+            Some(Action::Pickaxe) => {
+                self.start_pickaxe()?;
+                Ok(true)
+            }
+            Some(Action::AuthorFilter) => {
+                self.start_author_filter()?;
+                Ok(true)
+            }
+            Some(Action::SearchHistory) => {
+                self.start_history_search();
+                Ok(true)
+            }
+            Some(Action::GotoLine) => {
+                if !self.show_commit_info && self.get_focused_panel() == Some(FocusedPanel::Diff) {
+                    self.start_goto_line();
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Some(Action::GotoCommit) => {
+                self.goto_commit_input = Some(String::new());
+                Ok(true)
+            }
+            Some(Action::SetMark) => {
+                self.pending_mark = Some(PendingMark::Set);
+                Ok(true)
+            }
+            Some(Action::JumpToMark) => {
+                self.pending_mark = Some(PendingMark::Jump);
+                Ok(true)
+            }
+            Some(Action::DiffRange) => {
                 self.toggle_diff_range_selection()?;
                 Ok(true)
             }
-            (KeyCode::Char('?'), KeyModifiers::NONE) => {
+            Some(Action::ParentCommit) => {
+                self.go_to_parent_commit()?;
+                Ok(true)
+            }
+            Some(Action::ChildCommit) => {
+                self.go_to_child_commit()?;
+                Ok(true)
+            }
+            Some(Action::Help) => {
                 self.ui_state.toggle_help();
                 Ok(true)
             }
-            (KeyCode::Char('i'), KeyModifiers::NONE) | (KeyCode::Enter, KeyModifiers::NONE) => {
+            Some(Action::CommitInfo) => {
                 // Show commit info popup (only in commits panel)
                 if matches!(self.get_focused_panel(), Some(FocusedPanel::Commits)) {
                     self.show_commit_info_popup()?;
                 }
                 Ok(true)
             }
-            (KeyCode::Char('e'), KeyModifiers::NONE) => {
-                self.open_editor()?;
-                self.refresh_current_diff()?;
-                self.redraw_tui = true;
+            Some(Action::Select)
+                if self.blame_lines.is_some()
+                    && self.get_focused_panel() == Some(FocusedPanel::Diff) =>
+            {
+                // Jump to the commit that last changed the blame line under the cursor
+                self.jump_to_blame_commit()?;
+                Ok(true)
+            }
+            Some(Action::Select) => {
+                // Configurable action (only in commits panel, and not over the popup)
+                if matches!(self.get_focused_panel(), Some(FocusedPanel::Commits))
+                    && !self.show_commit_info
+                {
+                    match self.enter_action {
+                        EnterAction::Info => self.show_commit_info_popup()?,
+                        EnterAction::FocusDiff => self.focus_panel(FocusedPanel::Diff),
+                        EnterAction::OpenEditor => self.open_editor_and_refresh()?,
+                        EnterAction::None => {}
+                    }
+                }
+                Ok(true)
+            }
+            Some(Action::FileStats) => {
+                // Summarize the file across its loaded history
+                if self.file_stats_popup.is_some() {
+                    self.hide_file_stats_popup();
+                } else {
+                    self.show_file_stats_popup();
+                }
+                Ok(true)
+            }
+            Some(Action::CompareClipboard) => {
+                // Diff the clipboard against the file at the selected commit
+                self.compare_with_clipboard()?;
+                Ok(true)
+            }
+            Some(Action::InvertDiff) => {
+                // Invert the diff so the new side reads as the base
+                self.toggle_invert_diff()?;
+                Ok(true)
+            }
+            Some(Action::ToggleBlame) => {
+                // Show who last touched each line of the file at the selected commit
+                self.toggle_blame();
+                Ok(true)
+            }
+            Some(Action::CycleAuthor) => {
+                self.cycle_author_display();
+                Ok(true)
+            }
+            Some(Action::WordDiff) => {
+                // Emphasize the changed words within modified lines
+                self.ui_state.word_diff = !self.ui_state.word_diff;
+                Ok(true)
+            }
+            Some(Action::ShowWhitespace) => {
+                self.show_whitespace = !self.show_whitespace;
+                Ok(true)
+            }
+            Some(Action::WordWrap) => {
+                self.ui_state.word_wrap = !self.ui_state.word_wrap;
+                self.ui_state.diff_horizontal_scroll = 0;
+                Ok(true)
+            }
+            Some(Action::ToggleHighlight) => {
+                self.syntax_highlighting = !self.syntax_highlighting;
+                Ok(true)
+            }
+            Some(Action::IgnoreWhitespace) => {
+                self.toggle_ignore_whitespace()?;
+                Ok(true)
+            }
+            Some(Action::CombinedDiff) => {
+                self.toggle_combined_merge_diff()?;
+                Ok(true)
+            }
+            Some(Action::MoreContext) => {
+                self.adjust_context_lines(1)?;
+                Ok(true)
+            }
+            Some(Action::LessContext) => {
+                self.adjust_context_lines(-1)?;
+                Ok(true)
+            }
+            Some(Action::ToggleLayout) => {
+                self.toggle_layout();
+                Ok(true)
+            }
+            Some(Action::ToggleHunk) => {
+                if self.get_focused_panel() == Some(FocusedPanel::Diff) {
+                    self.toggle_hunk_under_cursor();
+                }
+                Ok(true)
+            }
+            Some(Action::FullFile) => {
+                // Show the whole file as of the selected commit instead of its diff
+                self.toggle_full_file_view()?;
+                Ok(true)
+            }
+            Some(Action::FollowRenames) => {
+                self.toggle_follow_renames()?;
+                Ok(true)
+            }
+            Some(Action::HeatMap) => {
+                self.toggle_heat_map()?;
+                Ok(true)
+            }
+            Some(Action::VisualSelect) => {
+                self.toggle_diff_selection();
+                Ok(true)
+            }
+            Some(Action::WholeCommit) => {
+                // Show every file the selected commit changed
+                self.toggle_whole_commit_view()?;
+                Ok(true)
+            }
+            Some(Action::StageFile) => {
+                self.stage_working_directory_file(true)?;
+                Ok(true)
+            }
+            Some(Action::UnstageFile) => {
+                self.stage_working_directory_file(false)?;
+                Ok(true)
+            }
+            Some(Action::OpenEditor) => {
+                self.open_editor_and_refresh()?;
+                Ok(true)
+            }
+            Some(Action::OpenInBrowser) => {
+                self.open_commit_in_browser()?;
+                Ok(true)
+            }
+            Some(Action::SavePatch) => {
+                self.save_patch()?;
                 Ok(true)
             }
             _ => Ok(false),
         }
     }
 
+    fn open_editor_and_refresh(&mut self) -> Result<()> {
+        if self.refuse_in_directory_mode("Opening an editor") {
+            return Ok(());
+        }
+        self.open_editor()?;
+        self.refresh_current_diff()?;
+        self.redraw_tui = true;
+        Ok(())
+    }
+
     pub fn handle_copy_keys(&mut self, key: KeyEvent) -> Result<bool> {
-        // Handle copy keys in commits panel and history mode, or in commit info popup
-        if !matches!(self.get_focused_panel(), Some(FocusedPanel::Commits))
-            && !self.show_commit_info
-        {
+        // Handle copy keys in either history panel, or in commit info popup
+        if self.get_focused_panel().is_none() && !self.show_commit_info {
             return Ok(false);
         }
 
-        match (key.code, key.modifiers) {
-            (KeyCode::Char('y'), KeyModifiers::NONE) => {
+        match self.keymap.action_for(&key) {
+            Some(Action::CopyMode) => {
                 match self.copy_mode.as_ref() {
                     None => {
-                        // First 'y' press - start copy mode
+                        // First press - start copy mode
                         self.start_copy_mode();
                     }
                     Some(crate::copy::CopyMode::WaitingForTarget) => {
-                        // Second 'y' press - copy full SHA
+                        // Second press - copy full SHA
                         self.copy_commit_sha(false)?;
                     }
                 }
                 Ok(true)
             }
-            (KeyCode::Char('Y'), KeyModifiers::SHIFT) => {
-                // Capital Y - copy short SHA directly
+            Some(Action::CopyShortSha) => {
+                // Copy short SHA directly
                 self.copy_commit_sha(true)?;
                 Ok(true)
             }
-            (KeyCode::Char('c'), KeyModifiers::NONE) => {
+            Some(Action::CopySha) => {
                 // Direct copy of full SHA (especially useful in popup)
                 if self.show_commit_info {
                     self.copy_commit_sha(false)?;
@@ -227,7 +475,7 @@ impl App {
             }
             // Note: 'm' key is only handled in copy mode section below
             _ => {
-                // Handle copy mode targets
+                // Copy mode targets are fixed letters, matched on the key itself
                 if matches!(
                     self.copy_mode,
                     Some(crate::copy::CopyMode::WaitingForTarget)
@@ -238,7 +486,14 @@ impl App {
                             Ok(true)
                         }
                         (KeyCode::Char('h'), KeyModifiers::NONE) => {
-                            self.copy_commit_sha(true)?;
+                            // The diff panel copies the hunk under the cursor instead
+                            if self.get_focused_panel() == Some(FocusedPanel::Diff)
+                                && !self.show_commit_info
+                            {
+                                self.copy_current_hunk()?;
+                            } else {
+                                self.copy_commit_sha(true)?;
+                            }
                             Ok(true)
                         }
                         (KeyCode::Char('m'), KeyModifiers::NONE) => {
@@ -257,10 +512,39 @@ impl App {
                             self.copy_github_url()?;
                             Ok(true)
                         }
+                        (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                            self.copy_file_line_reference()?;
+                            Ok(true)
+                        }
+                        (KeyCode::Char('b'), KeyModifiers::NONE) => {
+                            self.copy_blame_permalink()?;
+                            Ok(true)
+                        }
                         (KeyCode::Char('p'), KeyModifiers::NONE) => {
                             self.copy_file_relative_path()?;
                             Ok(true)
                         }
+                        (KeyCode::Char('A'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                            self.copy_file_absolute_path()?;
+                            Ok(true)
+                        }
+                        (KeyCode::Char('o'), KeyModifiers::NONE) => {
+                            self.copy_historical_path()?;
+                            Ok(true)
+                        }
+                        (KeyCode::Char('f'), KeyModifiers::NONE) => {
+                            self.copy_full_show()?;
+                            Ok(true)
+                        }
+                        (KeyCode::Char('P'), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                            self.copy_patch()?;
+                            Ok(true)
+                        }
+                        (KeyCode::Char('?'), _) => {
+                            // Stays in copy mode so a target can be picked from the overlay
+                            self.show_copy_help = !self.show_copy_help;
+                            Ok(true)
+                        }
                         _ => Ok(false),
                     }
                 } else {
@@ -277,12 +561,12 @@ impl App {
                 && !search_state.is_input_mode
                 && !search_state.results.is_empty()
             {
-                match (key.code, key.modifiers) {
-                    (KeyCode::Char('n'), KeyModifiers::NONE) => {
+                match self.keymap.action_for(&key) {
+                    Some(Action::NextChange) => {
                         self.navigate_to_next_search_result()?;
                         return Ok(true);
                     }
-                    (KeyCode::Char('N'), KeyModifiers::SHIFT) => {
+                    Some(Action::PreviousChange) => {
                         self.navigate_to_previous_search_result()?;
                         return Ok(true);
                     }
@@ -292,8 +576,8 @@ impl App {
         }
 
         // Existing hunk navigation logic
-        match (key.code, key.modifiers) {
-            (KeyCode::Char('n'), KeyModifiers::NONE) => {
+        match self.keymap.action_for(&key) {
+            Some(Action::NextChange) => {
                 if self.copy_mode.is_some() {
                     // Don't conflict with copy mode
                     return Ok(false);
@@ -301,16 +585,36 @@ impl App {
                 self.navigate_to_next_change()?;
                 Ok(true)
             }
-            (KeyCode::Char('N'), KeyModifiers::SHIFT) => {
+            Some(Action::PreviousChange) => {
                 self.navigate_to_previous_change()?;
                 Ok(true)
             }
+            Some(Action::NextFile) => {
+                self.next_file_in_diff();
+                Ok(true)
+            }
+            Some(Action::PreviousFile) => {
+                self.prev_file_in_diff();
+                Ok(true)
+            }
             _ => Ok(false),
         }
     }
 
     pub fn handle_search_input_keys(&mut self, key: KeyEvent) -> Result<bool> {
         if let Some(ref mut search_state) = self.diff_search_state {
+            // Option toggles work both while typing and while stepping through matches
+            match (key.code, key.modifiers) {
+                (KeyCode::Char('c'), KeyModifiers::ALT) => {
+                    self.toggle_search_case_sensitive()?;
+                    return Ok(true);
+                }
+                (KeyCode::Char('w'), KeyModifiers::ALT) => {
+                    self.toggle_search_whole_word()?;
+                    return Ok(true);
+                }
+                _ => {}
+            }
             if !search_state.is_input_mode {
                 return Ok(false);
             }
@@ -344,4 +648,255 @@ impl App {
             Ok(false)
         }
     }
+
+    /// Collect a pickaxe pattern; Tab switches between `-S` and regex `-G`, Enter filters the
+    /// commits and Esc cancels
+    pub fn handle_pickaxe_input_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut pickaxe) = self.pickaxe_state else {
+            return Ok(false);
+        };
+        if !pickaxe.is_input_mode {
+            return Ok(false);
+        }
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => pickaxe.pattern.push(c),
+            (KeyCode::Backspace, _) => {
+                pickaxe.pattern.pop();
+            }
+            (KeyCode::Tab, _) => pickaxe.is_regex = !pickaxe.is_regex,
+            (KeyCode::Enter, _) => self.apply_pickaxe()?,
+            (KeyCode::Esc, _) => self.clear_pickaxe()?,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Collect an author name or email to filter by; Enter filters the commits and Esc cancels
+    pub fn handle_author_filter_input_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut filter) = self.author_filter else {
+            return Ok(false);
+        };
+        if !filter.is_input_mode {
+            return Ok(false);
+        }
+
+        match (key.code, key.modifiers) {
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => filter.query.push(c),
+            (KeyCode::Backspace, _) => {
+                filter.query.pop();
+            }
+            (KeyCode::Enter, _) => self.apply_author_filter()?,
+            (KeyCode::Esc, _) => self.clear_author_filter()?,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Keys for the open history search: typing the pattern, then picking a hit to jump to
+    pub fn handle_history_search_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut popup) = self.history_search else {
+            return Ok(false);
+        };
+
+        if popup.is_input_mode {
+            match (key.code, key.modifiers) {
+                (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => popup.query.push(c),
+                (KeyCode::Backspace, _) => {
+                    popup.query.pop();
+                }
+                (KeyCode::Enter, _) => {
+                    let pattern = popup.query.clone();
+                    self.search_all_commit_diffs(&pattern)?;
+                }
+                (KeyCode::Esc, _) => self.hide_history_search(),
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => popup.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => popup.select_next(),
+            KeyCode::Enter => self.jump_to_history_search_hit()?,
+            KeyCode::Char('/') => popup.is_input_mode = true,
+            KeyCode::Esc | KeyCode::Char('q') => self.hide_history_search(),
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Search the commit info popup's message: `/` starts, `n`/`N` step through the matches
+    /// and Esc clears the search while leaving the popup open
+    pub fn handle_commit_info_search_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        if !self.show_commit_info {
+            return Ok(false);
+        }
+        let action = self.keymap.action_for(&key);
+        let typing = self
+            .commit_info_popup
+            .as_ref()
+            .is_some_and(|popup| popup.is_search_input);
+        if key.code == KeyCode::Char('M') && !typing {
+            self.copy_commit_info_markdown();
+            return Ok(true);
+        }
+        let Some(ref mut popup) = self.commit_info_popup else {
+            return Ok(false);
+        };
+
+        if popup.is_search_input {
+            match (key.code, key.modifiers) {
+                (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                    popup.search_query.push(c);
+                    popup.update_search();
+                }
+                (KeyCode::Backspace, _) => {
+                    popup.search_query.pop();
+                    popup.update_search();
+                }
+                (KeyCode::Enter, _) => popup.is_search_input = false,
+                (KeyCode::Esc, _) => popup.clear_search(),
+                _ => {}
+            }
+            return Ok(true);
+        }
+
+        match action {
+            Some(Action::Search) => popup.start_search(),
+            Some(Action::NextChange) if popup.is_searching() => popup.next_match(),
+            Some(Action::PreviousChange) if popup.is_searching() => popup.previous_match(),
+            _ if key.code == KeyCode::Esc && popup.is_searching() => popup.clear_search(),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Keys of a visual selection: `y` copies the diff lines, `Y` their code and `Alt+y` the
+    /// added lines only; Esc cancels. Other keys move the cursor as usual, extending it.
+    pub fn handle_diff_selection_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        if self.ui_state.diff_selection_anchor.is_none() {
+            return Ok(false);
+        }
+
+        let how = match (key.code, key.modifiers) {
+            (KeyCode::Char('y'), KeyModifiers::NONE) => SelectionCopy::Raw,
+            (KeyCode::Char('Y'), _) => SelectionCopy::Code,
+            (KeyCode::Char('y'), KeyModifiers::ALT) => SelectionCopy::Added,
+            (KeyCode::Esc, _) => {
+                self.cancel_diff_selection();
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+        self.copy_selected_diff_lines(how)?;
+        Ok(true)
+    }
+
+    /// Pick which parent of a merge to step to
+    pub fn handle_parent_chooser_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut chooser) = self.parent_chooser else {
+            return Ok(false);
+        };
+
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => chooser.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => chooser.select_next(),
+            KeyCode::Enter => self.choose_parent(None)?,
+            KeyCode::Char(c @ '1'..='9') => {
+                let choice = c as usize - '1' as usize;
+                if choice < chooser.choices.len() {
+                    self.choose_parent(Some(choice))?;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => self.parent_chooser = None,
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Collect the digits of a `:` line jump; Enter jumps and Esc cancels
+    pub fn handle_goto_line_input_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut input) = self.goto_line_input else {
+            return Ok(false);
+        };
+
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Some(line_number) = self.goto_line_input.take().and_then(|s| s.parse().ok())
+                {
+                    self.goto_line(line_number);
+                }
+            }
+            KeyCode::Esc => self.goto_line_input = None,
+            // Swallow everything else so a stray key can't trigger an action mid-number
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Collect the hash prefix of a `#` commit jump; Enter selects and Esc cancels
+    pub fn handle_goto_commit_input_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(ref mut input) = self.goto_commit_input else {
+            return Ok(false);
+        };
+
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_hexdigit() => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Enter => {
+                if let Some(prefix) = self.goto_commit_input.take() {
+                    self.select_commit_by_hash(&prefix)?;
+                }
+            }
+            KeyCode::Esc => self.goto_commit_input = None,
+            // Swallow everything else so a stray key can't trigger an action mid-hash
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Take the letter after `m` or `'`; any other key cancels. `''` returns to the commit
+    /// selected before the last jump.
+    pub fn handle_pending_mark_keys(&mut self, key: KeyEvent) -> Result<bool> {
+        let Some(pending) = self.pending_mark.take() else {
+            return Ok(false);
+        };
+
+        match (pending, key.code) {
+            (PendingMark::Set, KeyCode::Char(c)) if c.is_ascii_alphabetic() => self.set_mark(c),
+            (PendingMark::Jump, KeyCode::Char(c)) if c.is_ascii_alphabetic() || c == '\'' => {
+                self.jump_to_mark(c)?
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Collect the digits of a count for `d`, as in `5d`. Digits bound to an action keep it,
+    /// and a leading `0` isn't a count.
+    pub fn handle_count_keys(&mut self, key: KeyEvent) -> bool {
+        let KeyCode::Char(c) = key.code else {
+            return false;
+        };
+        let Some(digit) = c.to_digit(10) else {
+            return false;
+        };
+        if !key.modifiers.is_empty()
+            || self.keymap.action_for(&key).is_some()
+            || (digit == 0 && self.pending_count.is_none())
+        {
+            return false;
+        }
+
+        let count = self.pending_count.unwrap_or(0);
+        self.pending_count = Some(count.saturating_mul(10).saturating_add(digit as usize));
+        true
+    }
 }
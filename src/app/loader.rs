@@ -0,0 +1,170 @@
+use crate::commit::{Commit, CommitStats};
+use crate::error::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+
+/// What opening a file's history asks git for
+#[derive(Debug, Clone)]
+pub struct HistoryRequest {
+    pub repo_root: PathBuf,
+    pub file_path: PathBuf,
+    pub follow_renames: bool,
+    pub first_parent: bool,
+    /// Fetch the whole history rather than its first page (oldest-first lists aren't paged)
+    pub all_commits: bool,
+    /// Fetch the statistics of every commit too (`--stats`)
+    pub with_stats: bool,
+    /// List at most this many commits (`--max-count`)
+    pub max_commits: Option<usize>,
+}
+
+/// The start of a file's history, ready to be listed
+#[derive(Debug)]
+pub struct LoadedHistory {
+    pub file_path: PathBuf,
+    /// Newest first
+    pub commits: Vec<Commit>,
+    pub rename_map: HashMap<String, PathBuf>,
+    /// Whether `commits` is the whole history, or all of it `max_commits` allows
+    pub complete: bool,
+    /// Whether `max_commits` left older commits out
+    pub truncated: bool,
+    /// Empty without `with_stats`. A failure here only costs the annotations.
+    pub stats: Result<HashMap<String, CommitStats>>,
+    /// How many commits the whole history has, whatever `max_commits` lists
+    pub total_commits: Option<usize>,
+}
+
+impl HistoryRequest {
+    pub fn fetch(&self) -> Result<LoadedHistory> {
+        let stats = if self.with_stats {
+            crate::git::history::fetch_all_commit_stats(
+                &self.repo_root,
+                &self.file_path,
+                self.follow_renames,
+                self.first_parent,
+            )
+        } else {
+            Ok(HashMap::new())
+        };
+
+        let max_count = if self.all_commits {
+            None
+        } else {
+            Some(super::COMMIT_PAGE_SIZE)
+        };
+        let page = fetch_page(
+            &self.repo_root,
+            &self.file_path,
+            self.follow_renames,
+            self.first_parent,
+            0,
+            max_count,
+            self.max_commits,
+        )?;
+        // Only the status bar shows it, so a failure leaves it out
+        let total_commits = crate::git::history::count_commits(
+            &self.repo_root,
+            &self.file_path,
+            self.follow_renames,
+            self.first_parent,
+        )
+        .ok();
+
+        Ok(LoadedHistory {
+            file_path: self.file_path.clone(),
+            commits: page.commits,
+            rename_map: page.rename_map,
+            complete: page.complete,
+            truncated: page.truncated,
+            stats,
+            total_commits,
+        })
+    }
+
+    /// Fetch on a worker thread, so the UI keeps drawing meanwhile. The result arrives on
+    /// the returned channel.
+    pub fn spawn(self) -> Receiver<Result<LoadedHistory>> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            // Nobody is waiting any more if the receiver is gone
+            let _ = sender.send(self.fetch());
+        });
+        receiver
+    }
+}
+
+/// Commits of a file's history fetched by `fetch_page`
+#[derive(Debug)]
+pub struct Page {
+    pub commits: Vec<Commit>,
+    /// The path the file had at each of `commits` when following renames
+    pub rename_map: HashMap<String, PathBuf>,
+    /// Whether no commits follow these, or none that `max_commits` allows
+    pub complete: bool,
+    /// Whether `max_commits` left older commits out
+    pub truncated: bool,
+}
+
+/// `max_count` commits of the file's history after the first `skip`, stopping at
+/// `max_commits` in all. A page reaching the cap asks git for one commit more, to tell
+/// whether the cap cut the history short.
+pub fn fetch_page(
+    repo_root: &Path,
+    file_path: &Path,
+    follow_renames: bool,
+    first_parent: bool,
+    skip: usize,
+    max_count: Option<usize>,
+    max_commits: Option<usize>,
+) -> Result<Page> {
+    let remaining = max_commits.map(|max_commits| max_commits.saturating_sub(skip));
+    let reaches_cap = match (max_count, remaining) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(max_count), Some(remaining)) => max_count >= remaining,
+    };
+    let count = if reaches_cap {
+        remaining.map(|remaining| remaining + 1)
+    } else {
+        max_count
+    };
+
+    let mut commits = crate::git::history::fetch_commit_history(
+        repo_root,
+        file_path,
+        follow_renames,
+        first_parent,
+        skip,
+        count,
+    )?;
+
+    // Renames are tracked per file, so directories have none
+    let mut rename_map = if follow_renames && !repo_root.join(file_path).is_dir() {
+        crate::git::history::build_rename_map(repo_root, file_path, first_parent, skip, count)?
+    } else {
+        HashMap::new()
+    };
+
+    let mut truncated = false;
+    let complete = match (remaining, count) {
+        (Some(remaining), _) if reaches_cap => {
+            truncated = commits.len() > remaining;
+            let kept = remaining.min(commits.len());
+            for commit in commits.drain(kept..) {
+                rename_map.remove(&commit.hash);
+            }
+            true
+        }
+        (_, Some(count)) => commits.len() < count,
+        (_, None) => true,
+    };
+
+    Ok(Page {
+        commits,
+        rename_map,
+        complete,
+        truncated,
+    })
+}
@@ -1,16 +1,26 @@
 pub mod events;
+pub mod loader;
 
 use crate::cache::DiffCache;
 use crate::cli::LayoutMode;
 use crate::commit::Commit;
+use crate::config::keys::KeyMap;
+use crate::config::EnterAction;
 use crate::copy::{CommitCopier, CopyFormat, CopyMode};
 use crate::diff::side_by_side::SideBySideDiff;
 use crate::error::{self, Result};
+use crate::git::encoding::TextEncoding;
+use crate::git::files::{get_git_files, ExcludeFilter};
+use crate::git::show::PatchSource;
 use crate::ui::file_picker::FilePickerState;
 use crate::ui::state::UIState;
+use loader::{HistoryRequest, LoadedHistory};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::Line;
 use regex::Regex;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
 use std::{env, process::Command};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -46,13 +56,95 @@ pub struct DiffSearchState {
     pub results: Vec<SearchMatch>,     // All matches found
     pub current_result: Option<usize>, // Index of highlighted result
     pub regex: Option<Regex>,          // Compiled regex for performance
+    pub case_sensitive: bool,          // Match case exactly instead of ignoring it
+    pub whole_word: bool,              // Only match the query as a whole word
+}
+
+impl DiffSearchState {
+    /// The query as a regex, honoring the case and whole-word toggles
+    pub fn build_regex(&self) -> std::result::Result<Regex, regex::Error> {
+        let mut pattern = if self.whole_word {
+            format!(r"\b(?:{})\b", self.query)
+        } else {
+            self.query.clone()
+        };
+        if !self.case_sensitive {
+            pattern.insert_str(0, "(?i)");
+        }
+        Regex::new(&pattern)
+    }
+}
+
+/// Commits fetched per page of a file's history
+pub const COMMIT_PAGE_SIZE: usize = 200;
+
+/// How close to the end of the loaded commits the selection gets before the next page loads
+const COMMIT_PAGE_PREFETCH: usize = 20;
+
+/// Most commits a history search looks through, newest first
+pub const HISTORY_SEARCH_MAX_COMMITS: usize = 1000;
+
+/// Newer commits checked for a child of the selected one, a git call or two each
+const CHILD_SEARCH_MAX_COMMITS: usize = 100;
+
+/// Most context lines `+` shows around each change
+pub const MAX_CONTEXT_LINES: u32 = 50;
+
+/// Terminal width from which the auto layout goes side by side, unless `--auto-width` says
+pub const DEFAULT_AUTO_WIDTH: u16 = 120;
+
+/// A pickaxe filter narrowing the commits panel to commits that add or remove a string
+#[derive(Debug, Clone)]
+pub struct PickaxeState {
+    pub pattern: String,
+    pub is_regex: bool,      // `git log -G` instead of `-S`
+    pub is_input_mode: bool, // Currently typing the pattern
+    // The history the filter narrowed, restored when it is cleared
+    full_history: Option<Vec<Commit>>,
+}
+
+/// An author filter narrowing the commits panel to commits whose author name or email
+/// contains the query
+#[derive(Debug, Clone)]
+pub struct AuthorFilterState {
+    pub query: String,
+    pub is_input_mode: bool, // Currently typing the query
+    // The history the filter narrowed, restored when it is cleared
+    full_history: Option<Vec<Commit>>,
+}
+
+/// Everything besides the diff text that decides how the unified diff is styled
+#[derive(Debug, Clone, PartialEq)]
+struct StyledLinesInputs {
+    show_whitespace: bool,
+    syntax_highlighting: bool,
+    word_diff: bool,
+    search: Option<(Vec<SearchMatch>, Option<usize>)>,
+}
+
+/// A mark key (`m` or `'`) waiting for the letter naming the mark
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingMark {
+    Set,
+    Jump,
+}
+
+/// What `copy_selected_diff_lines` copies of the visual selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionCopy {
+    /// The diff lines as they are, markers and headers included
+    Raw,
+    /// The code of the lines, without `+`/`-` markers or headers
+    Code,
+    /// The code of the added lines only, i.e. the new version
+    Added,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct SearchMatch {
     pub line_index: usize, // Index in diff lines
-    pub char_start: usize, // Start position in line
-    pub char_end: usize,   // End position in line
+    pub char_start: usize, // Start position in line, in chars
+    pub char_end: usize,   // End position in line, in chars
     pub content: String,   // Matched text for highlighting
 }
 
@@ -73,6 +165,15 @@ pub struct App {
     pub current_diff: String,
     pub current_side_by_side_diff: Option<SideBySideDiff>,
     pub diff_cache: DiffCache,
+    pub invert_diff: bool, // Show the new side as the base (like `git diff -R`)
+    pub show_whitespace: bool, // Render spaces and tabs as visible glyphs
+    pub syntax_highlighting: bool, // Color code by its language (off with `--no-highlight`)
+    pub ignore_whitespace: bool, // Leave whitespace-only changes out of diffs (`git diff -w`)
+    pub combined_merge_diff: bool, // Show merges as a combined diff (`git show --cc`)
+    pub encoding_override: Option<&'static encoding_rs::Encoding>, // From --encoding
+    pub text_encoding: Option<TextEncoding>, // Resolved for the current file
+    pub syntax_override: Option<String>, // From --syntax
+    pub syntax_language: Option<String>, // Resolved for the current file
 
     // UI state (moved to separate struct)
     pub ui_state: UIState,
@@ -86,15 +187,34 @@ pub struct App {
     pub diff_range_start: Option<usize>,
     pub current_diff_range: Option<(usize, usize)>, // (older_index, newer_index)
 
+    // With `--compare`, the two refs listed instead of the history (older, newer)
+    pub compared_refs: Option<(String, String)>,
+
     // Copy functionality
     pub copy_mode: Option<CopyMode>,
     pub copier: CommitCopier,
     pub copy_message: Option<String>,
+    pub show_copy_help: bool, // Copy mode's key overlay, toggled with `?`
 
     // Commit info popup
     pub show_commit_info: bool,
     pub commit_info_popup: Option<crate::ui::commit_info::CommitInfoPopup>,
 
+    // File statistics popup, filled in while missing commit stats load
+    pub file_stats_popup: Option<crate::ui::file_stats::FileStatsPopup>,
+
+    // Search across every commit's diff, filled in while commits are searched
+    pub history_search: Option<crate::ui::history_search::HistorySearchPopup>,
+
+    // Pull requests as `gh` reported them, by number (None when it couldn't say)
+    pr_status_cache: HashMap<u32, Option<crate::commit::PullRequestInfo>>,
+    gh_unavailable: bool,
+
+    // Asks which parent to step to from a merge commit
+    pub parent_chooser: Option<crate::ui::parent_chooser::ParentChooser>,
+    // Hashes stepped away from with `[`, so `]` retraces the way back
+    parent_trail: Vec<String>,
+
     // Change navigation cache
     pub current_changes: Vec<usize>, // Line indices of all changes
     pub current_change_index: Option<usize>, // Index into current_changes array
@@ -105,23 +225,130 @@ pub struct App {
     // Diff search state
     pub diff_search_state: Option<DiffSearchState>,
 
+    // Pickaxe filter on the commits panel
+    pub pickaxe_state: Option<PickaxeState>,
+
+    // Author filter on the commits panel
+    pub author_filter: Option<AuthorFilterState>,
+
+    // Digits typed after `:` while jumping to a line of the file
+    pub goto_line_input: Option<String>,
+
+    // Hash prefix typed after `#` while jumping to a commit
+    pub goto_commit_input: Option<String>,
+
+    // A first `g` was pressed and waits for the second of `gg`
+    pub pending_g: bool,
+
+    // Digits typed before `d`, which diffs across that many commits (`5d`)
+    pub pending_count: Option<usize>,
+
+    // Commits marked with `m` and a letter, by hash so they outlive paging and reloads
+    pub marks: HashMap<char, String>,
+    pub pending_mark: Option<PendingMark>,
+    // The commit selected before the last jump to a mark, for `''`
+    mark_return: Option<String>,
+
+    // Hunks folded in the unified diff, by the line index of their `@@` header
+    pub collapsed_hunks: HashSet<usize>,
+
     // File picker navigation state
     pub came_from_file_picker: bool,
 
+    // Panel focused whenever a history view opens
+    pub initial_focus: FocusedPanel,
+
+    // What Enter does in the commits panel
+    pub enter_action: EnterAction,
+
+    // Terminal width from which the auto layout shows the diff side by side
+    pub auto_width: u16,
+
+    // Keys bound to each action in the history view
+    pub keymap: KeyMap,
+
+    // Paths hidden from the file picker, and whether they are shown anyway
+    pub exclude_filter: ExcludeFilter,
+    pub show_excluded_files: bool,
+
     // Signal for redrawing TUI.
     pub redraw_tui: bool,
 
     // Cached highlighted diff for performance and consistency
     pub cached_highlighted_diff: Option<crate::diff::HighlightedDiff>,
+
+    // Styled unified diff lines reused across frames, and the settings they were styled with
+    pub cached_styled_lines: Option<Vec<Line<'static>>>,
+    styled_lines_inputs: Option<StyledLinesInputs>,
+    // Changed words of the diff for the side-by-side panels, which style rows as they draw
+    pub cached_word_highlights: Option<crate::diff::words::WordHighlights>,
+
+    // The diff panel shows the clipboard compared with the selected commit's file
+    pub comparing_clipboard: bool,
+
+    // The diff panel shows the whole file as of the selected commit instead of its diff
+    pub full_file_view: bool,
+    pub heat_map: bool, // Color the full file view by how recently each line changed
+    pub line_ages: Vec<crate::git::blame::LineAge>, // Of each file line, for the heat map
+
+    // The diff panel shows every file the selected commit changed, not just this one
+    pub whole_commit_view: bool,
+
+    // List the oldest commit first instead of the newest
+    pub reverse: bool,
+
+    // Whether every commit of the file's history has been loaded, rather than the first pages
+    pub history_complete: bool,
+    // List at most this many commits (`--max-count`), and whether that left older ones out
+    pub max_commits: Option<usize>,
+    pub history_truncated: bool,
+    /// Commits in the file's whole history, not counting the working directory entry
+    pub total_commits: Option<usize>,
+
+    // Show each commit's insertions and deletions in the commits panel (`--stats`)
+    pub show_commit_stats: bool,
+    // Statistics of the whole history from one `git log --numstat`, applied to each page
+    commit_stats_by_hash: HashMap<String, crate::commit::CommitStats>,
+
+    // Blame of the selected commit's file, shown in place of the diff while set
+    pub blame_lines: Option<Vec<crate::git::blame::BlameLine>>,
+
+    // Where the splits, layout and last file are remembered; None with `--no-session`
+    pub session_file: Option<PathBuf>,
+
+    // Printed to stdout once the terminal is restored (`Q`, or any quit with `--print-on-exit`)
+    pub on_exit_output: Option<String>,
+    pub print_on_exit: bool,
+
+    // Where save_patch writes patches (`--patch-dir`, else the current directory)
+    pub patch_dir: PathBuf,
+
+    // Polling the file's mtime to keep the working directory entry live
+    pub watch_interval: Option<std::time::Duration>,
+    pub last_watch_check: Option<std::time::Instant>,
+    pub watched_mtime: Option<std::time::SystemTime>,
+
+    // When and on which row the file picker was last clicked, to spot a double click
+    pub last_picker_click: Option<(std::time::Instant, u16)>,
+
+    // The history being fetched on a worker thread, and since when (for the spinner)
+    history_receiver: Option<Receiver<Result<LoadedHistory>>>,
+    pub loading_started: Option<std::time::Instant>,
 }
 
 impl App {
+    /// Whether the diff panel wraps long lines: `z` is on and the layout is unified. Side by
+    /// side keeps scrolling sideways, since wrapping would misalign the two panes.
+    pub fn wraps_diff(&self) -> bool {
+        self.ui_state.word_wrap && self.effective_layout() != LayoutMode::SideBySide
+    }
+
     /// Get the effective layout mode based on terminal width (for Auto mode)
     pub fn effective_layout(&self) -> LayoutMode {
         match self.layout_mode {
             LayoutMode::Auto => {
-                // Use side-by-side if terminal is wide enough (120+ columns)
-                if self.ui_state.terminal_width >= 120 {
+                // Use side-by-side if terminal is wide enough (120+ columns by default)
+                if self.ui_state.terminal_width >= self.auto_width {
                     LayoutMode::SideBySide
                 } else {
                     LayoutMode::Unified
@@ -137,47 +364,27 @@ impl App {
         follow_renames: bool,
         first_parent: bool,
         layout_mode: LayoutMode,
+        exclude_filter: ExcludeFilter,
     ) -> Result<Self> {
-        use crate::git::files::get_git_files;
-
-        let files = get_git_files(&repo_root)?;
-        let file_picker_state = FilePickerState::new(files);
-
-        Ok(Self {
+        let mut app = Self::with_mode(
             repo_root,
-            should_quit: false,
+            AppMode::FilePicker {
+                state: FilePickerState::new(Vec::new()),
+                context: FilePickerContext::Initial,
+            },
             context_lines,
             follow_renames,
             first_parent,
-            mode: AppMode::FilePicker {
-                state: file_picker_state,
-                context: FilePickerContext::Initial,
-            },
-            commits: Vec::new(),
-            selected_index: 0,
-            rename_map: HashMap::new(),
-            current_diff: String::new(),
-            current_side_by_side_diff: None,
-            diff_cache: DiffCache::new(50),
-            ui_state: UIState::new(),
             layout_mode,
-            loading: false,
-            error_message: None,
-            diff_range_start: None,
-            current_diff_range: None,
-            copy_mode: None,
-            copier: CommitCopier::new(),
-            copy_message: None,
-            show_commit_info: false,
-            commit_info_popup: None,
-            current_changes: Vec::new(),
-            current_change_index: None,
-            message_timer: None,
-            diff_search_state: None,
-            came_from_file_picker: false,
-            redraw_tui: false,
-            cached_highlighted_diff: None,
-        })
+        );
+        app.exclude_filter = exclude_filter;
+
+        let file_picker_state = app.new_file_picker_state()?;
+        if let AppMode::FilePicker { ref mut state, .. } = app.mode {
+            *state = file_picker_state;
+        }
+
+        Ok(app)
     }
 
     pub fn new_history(
@@ -187,6 +394,27 @@ impl App {
         follow_renames: bool,
         first_parent: bool,
         layout_mode: LayoutMode,
+    ) -> Self {
+        Self::with_mode(
+            repo_root,
+            AppMode::History {
+                file_path,
+                focused_panel: FocusedPanel::Commits,
+            },
+            context_lines,
+            follow_renames,
+            first_parent,
+            layout_mode,
+        )
+    }
+
+    fn with_mode(
+        repo_root: PathBuf,
+        mode: AppMode,
+        context_lines: u32,
+        follow_renames: bool,
+        first_parent: bool,
+        layout_mode: LayoutMode,
     ) -> Self {
         Self {
             repo_root,
@@ -194,41 +422,97 @@ impl App {
             context_lines,
             follow_renames,
             first_parent,
-            mode: AppMode::History {
-                file_path,
-                focused_panel: FocusedPanel::Commits,
-            },
+            mode,
             commits: Vec::new(),
             selected_index: 0,
             rename_map: HashMap::new(),
             current_diff: String::new(),
             current_side_by_side_diff: None,
             diff_cache: DiffCache::new(50),
+            invert_diff: false,
+            show_whitespace: false,
+            syntax_highlighting: true,
+            ignore_whitespace: false,
+            combined_merge_diff: false,
+            encoding_override: None,
+            text_encoding: None,
+            syntax_override: None,
+            syntax_language: None,
             ui_state: UIState::new(),
             layout_mode,
             loading: false,
             error_message: None,
             diff_range_start: None,
             current_diff_range: None,
+            compared_refs: None,
             copy_mode: None,
+            show_copy_help: false,
             copier: CommitCopier::new(),
             copy_message: None,
             show_commit_info: false,
             commit_info_popup: None,
+            file_stats_popup: None,
+            history_search: None,
+            parent_chooser: None,
+            pr_status_cache: HashMap::new(),
+            gh_unavailable: false,
+            parent_trail: Vec::new(),
             current_changes: Vec::new(),
             current_change_index: None,
             message_timer: None,
             diff_search_state: None,
+            pickaxe_state: None,
+            author_filter: None,
+            goto_line_input: None,
+            goto_commit_input: None,
+            pending_g: false,
+            pending_count: None,
+            marks: HashMap::new(),
+            pending_mark: None,
+            mark_return: None,
+            collapsed_hunks: HashSet::new(),
             came_from_file_picker: false,
+            initial_focus: FocusedPanel::Commits,
+            enter_action: EnterAction::default(),
+            auto_width: DEFAULT_AUTO_WIDTH,
+            keymap: KeyMap::default(),
+            exclude_filter: ExcludeFilter::default(),
+            show_excluded_files: false,
             redraw_tui: false,
             cached_highlighted_diff: None,
+            cached_styled_lines: None,
+            styled_lines_inputs: None,
+            cached_word_highlights: None,
+            comparing_clipboard: false,
+            full_file_view: false,
+            heat_map: false,
+            line_ages: Vec::new(),
+            whole_commit_view: false,
+            reverse: false,
+            history_complete: false,
+            history_truncated: false,
+            total_commits: None,
+            max_commits: None,
+            show_commit_stats: false,
+            commit_stats_by_hash: HashMap::new(),
+            session_file: None,
+            on_exit_output: None,
+            print_on_exit: false,
+            patch_dir: env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            blame_lines: None,
+            watch_interval: None,
+            last_watch_check: None,
+            last_picker_click: None,
+            watched_mtime: None,
+            history_receiver: None,
+            loading_started: None,
         }
     }
 
     pub fn switch_to_history(&mut self, file_path: PathBuf, from_picker: bool) -> Result<()> {
         self.mode = AppMode::History {
             file_path,
-            focused_panel: FocusedPanel::Commits,
+            focused_panel: self.initial_focus,
         };
 
         // Track whether we came from file picker
@@ -246,20 +530,81 @@ impl App {
         self.clear_diff_search();
 
         // Load git data for the new file
-        self.load_git_data()
+        self.load_git_data_in_background()
+    }
+
+    /// Restore the splits of the session saved for this repository, and with `restore_layout`
+    /// its layout too. Returns the file viewed last, for reopening.
+    pub fn load_session(&mut self, restore_layout: bool) -> Option<PathBuf> {
+        let session = crate::session::load(self.session_file.as_deref()?, &self.repo_root)?;
+        self.ui_state.split_ratio = session.split_ratio.clamp(0.2, 0.7);
+        self.ui_state.vertical_split_ratio = session.vertical_split_ratio.clamp(0.3, 0.9);
+        if restore_layout {
+            self.layout_mode = session.layout_mode;
+        }
+        session.last_file
+    }
+
+    /// Remember the splits, layout and current file for the next run in this repository.
+    /// Quitting from the file picker keeps the last file already saved.
+    pub fn save_session(&self) -> Result<()> {
+        let Some(ref path) = self.session_file else {
+            return Ok(());
+        };
+        let last_file = match self.get_file_path() {
+            Some(file_path) => Some(file_path.clone()),
+            None => {
+                crate::session::load(path, &self.repo_root).and_then(|session| session.last_file)
+            }
+        };
+        crate::session::save(
+            path,
+            &self.repo_root,
+            crate::session::Session {
+                split_ratio: self.ui_state.split_ratio,
+                vertical_split_ratio: self.ui_state.vertical_split_ratio,
+                layout_mode: self.layout_mode,
+                last_file,
+            },
+        )
+    }
+
+    /// Offer the file viewed last by selecting it in the file picker, so Enter reopens it
+    pub fn select_in_file_picker(&mut self, file_path: &Path) -> bool {
+        match self.mode {
+            AppMode::FilePicker { ref mut state, .. } => state.select_path(file_path),
+            AppMode::History { .. } => false,
+        }
     }
 
     pub fn switch_to_file_picker(&mut self) -> Result<()> {
+        self.open_file_picker(false)
+    }
+
+    /// Switch to the file picker with the query pre-filled with the current file's directory
+    pub fn switch_to_file_picker_in_directory(&mut self) -> Result<()> {
+        self.open_file_picker(true)
+    }
+
+    fn open_file_picker(&mut self, in_current_directory: bool) -> Result<()> {
         // Only switch to file picker if we're currently in history mode
         let previous_file = match &self.mode {
             AppMode::History { file_path, .. } => file_path.clone(),
             AppMode::FilePicker { .. } => return Ok(()), // Already in file picker
         };
 
-        // Load git files
-        use crate::git::files::get_git_files;
-        let files = get_git_files(&self.repo_root)?;
-        let file_picker_state = FilePickerState::new(files);
+        let mut file_picker_state = self.new_file_picker_state()?;
+
+        if in_current_directory {
+            // Git paths always use '/', so "src/app/" ranks siblings of src/app/mod.rs first
+            let directory = previous_file
+                .parent()
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            if !directory.is_empty() {
+                file_picker_state.update_query(format!("{}/", directory));
+            }
+        }
 
         // Switch to file picker with context
         self.mode = AppMode::FilePicker {
@@ -269,53 +614,193 @@ impl App {
 
         // Clear the flag since we're now in file picker mode
         self.came_from_file_picker = false;
+        // A history still loading is for the file being left
+        self.history_receiver = None;
+        self.loading_started = None;
+        self.loading = false;
+
+        Ok(())
+    }
+
+    /// List the repository's files for the picker, hiding excluded paths unless toggled off
+    fn new_file_picker_state(&self) -> Result<FilePickerState> {
+        let hiding_excluded = !self.show_excluded_files && !self.exclude_filter.is_empty();
+        let files = if hiding_excluded {
+            get_git_files(&self.repo_root, &self.exclude_filter)?
+        } else {
+            get_git_files(&self.repo_root, &ExcludeFilter::default())?
+        };
+
+        let mut state = FilePickerState::new(files);
+        state.hiding_excluded = hiding_excluded;
+        Ok(state)
+    }
+
+    /// Show or hide the paths matched by the exclude globs in the file picker
+    pub fn toggle_excluded_files(&mut self) -> Result<()> {
+        self.show_excluded_files = !self.show_excluded_files;
+
+        let mut file_picker_state = self.new_file_picker_state()?;
+        if let AppMode::FilePicker { ref mut state, .. } = self.mode {
+            // Keep what the user has typed so far, the modified-only filter and the sort
+            if state.modified_only {
+                file_picker_state.toggle_modified_only();
+            }
+            file_picker_state.set_sort(state.sort);
+            file_picker_state.update_query(state.query.clone());
+            *state = file_picker_state;
+        }
 
         Ok(())
     }
 
     pub fn load_git_data(&mut self) -> Result<()> {
+        match self.begin_load()? {
+            Some(request) => self.finish_load(request.fetch()),
+            None => Ok(()),
+        }
+    }
+
+    /// Load the history on a worker thread so the UI stays responsive on large
+    /// repositories. `poll_history_load` picks up the result.
+    pub fn load_git_data_in_background(&mut self) -> Result<()> {
+        if let Some(request) = self.begin_load()? {
+            self.history_receiver = Some(request.spawn());
+            self.loading_started = Some(std::time::Instant::now());
+        }
+        Ok(())
+    }
+
+    /// Whether a history is being fetched in the background
+    pub fn is_loading(&self) -> bool {
+        self.history_receiver.is_some()
+    }
+
+    /// Show the background load's history once it has arrived
+    pub fn poll_history_load(&mut self) {
+        let Some(receiver) = &self.history_receiver else {
+            return;
+        };
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err(error::GeschichteError::GitCommandFailed {
+                command: "git log".to_string(),
+                output: "the loader thread stopped".to_string(),
+            }),
+        };
+        self.history_receiver = None;
+        self.loading_started = None;
+
+        // A history for a file that has since been closed is of no use
+        if let Ok(loaded) = &result {
+            if self.get_file_path() != Some(&loaded.file_path) {
+                self.loading = false;
+                return;
+            }
+        }
+        if let Err(e) = self.finish_load(result) {
+            self.loading = false;
+            self.error_message = Some(format!("Failed to load the history: {}", e));
+            self.start_message_timer();
+        }
+    }
+
+    /// Reset the history for a load, and say what to fetch. `None` when there is nothing
+    /// to fetch: outside History mode, or comparing two refs (which loads right away).
+    fn begin_load(&mut self) -> Result<Option<HistoryRequest>> {
         // Only load git data when in History mode
         let file_path = match &self.mode {
             AppMode::History { file_path, .. } => file_path.clone(),
-            AppMode::FilePicker { .. } => return Ok(()), // No-op for file picker mode
+            AppMode::FilePicker { .. } => return Ok(None), // No-op for file picker mode
         };
 
         self.loading = true;
         self.error_message = None;
+        // A load still running is for an older request
+        self.history_receiver = None;
+        self.loading_started = None;
 
-        // Load commits
-        let mut commits = crate::git::history::fetch_commit_history(
+        self.text_encoding = crate::git::encoding::resolve_encoding(
             &self.repo_root,
             &file_path,
-            self.follow_renames,
-            self.first_parent,
-        )?;
+            self.encoding_override,
+        );
+        self.syntax_language = crate::diff::syntax::file_language(
+            &self.repo_root,
+            &file_path,
+            self.syntax_override.as_deref(),
+        );
 
-        // Check for working directory changes and prepend if found
-        let wd_status =
-            crate::git::working::check_working_directory_status(&self.repo_root, &file_path)?;
+        if let Some((older, newer)) = self.compared_refs.clone() {
+            let result = self.load_compared_refs(&older, &newer);
+            self.loading = false;
+            return result.map(|_| None);
+        }
 
-        if wd_status != crate::git::working::WorkingDirectoryStatus::Clean {
-            let status_text = match wd_status {
-                crate::git::working::WorkingDirectoryStatus::Modified => "Modified".to_string(),
-                crate::git::working::WorkingDirectoryStatus::Staged => "Staged".to_string(),
-                crate::git::working::WorkingDirectoryStatus::ModifiedAndStaged => {
-                    "Modified + Staged".to_string()
-                }
-                crate::git::working::WorkingDirectoryStatus::Clean => unreachable!(),
-            };
+        // Load the first page of commits; the rest follow as the selection nears the end
+        self.commits.clear();
+        self.rename_map.clear();
+        self.commit_stats_by_hash.clear();
+        self.history_complete = false;
+        self.history_truncated = false;
+        self.total_commits = None;
+        self.pickaxe_state = None;
+        self.author_filter = None;
+
+        Ok(Some(HistoryRequest {
+            repo_root: self.repo_root.clone(),
+            file_path,
+            follow_renames: self.follow_renames,
+            first_parent: self.first_parent,
+            // Oldest first needs the whole history, so it isn't paged
+            all_commits: self.reverse,
+            with_stats: self.show_commit_stats,
+            max_commits: self.max_commits,
+        }))
+    }
 
-            let wd_commit = crate::commit::Commit::new_working_directory(status_text);
-            commits.insert(0, wd_commit);
+    /// List a fetched history and show the selected commit's diff
+    fn finish_load(&mut self, loaded: Result<LoadedHistory>) -> Result<()> {
+        let loaded = loaded?;
+        let file_path = loaded.file_path;
+
+        // With `--stats` the whole history's statistics come up front so every page of
+        // commits shows them. A failure only costs the annotations.
+        match loaded.stats {
+            Ok(stats) => self.commit_stats_by_hash = stats,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load commit stats: {}", e));
+                self.start_message_timer();
+            }
         }
 
-        self.commits = commits;
+        self.rename_map = loaded.rename_map;
+        self.history_complete = loaded.complete;
+        self.history_truncated = loaded.truncated;
+        self.total_commits = loaded.total_commits;
+        self.commits = loaded.commits;
+        self.attach_commit_stats(0);
+        if self.reverse {
+            self.commits.reverse();
+        }
 
-        // Build rename map
-        if self.follow_renames {
-            self.rename_map = crate::git::history::build_rename_map(&self.repo_root, &file_path)?;
+        // Check for working directory changes and add them at the newest end if found
+        self.watched_mtime = self.file_mtime(&file_path);
+        if let Some(wd_commit) = self.working_directory_entry(&file_path)? {
+            if self.reverse {
+                self.commits.push(wd_commit);
+            } else {
+                self.commits.insert(0, wd_commit);
+            }
         }
 
+        // A reload keeps the selection, which may lie beyond the first page
+        while self.selected_index >= self.commits.len() && self.load_more()? {}
+        self.selected_index = self
+            .selected_index
+            .min(self.commits.len().saturating_sub(1));
+
         // Load initial diff if we have commits
         if !self.commits.is_empty() {
             self.load_diff_for_selected_commit()?;
@@ -325,90 +810,964 @@ impl App {
         Ok(())
     }
 
-    pub fn load_diff_for_selected_commit(&mut self) -> Result<()> {
-        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
-            return Ok(());
+    /// List the commits the two refs point at in place of the history, and show the file's
+    /// diff between them
+    fn load_compared_refs(&mut self, older: &str, newer: &str) -> Result<()> {
+        let mut entries = Vec::new();
+        for rev in [newer, older] {
+            let mut commit = crate::git::history::fetch_ref_commit(&self.repo_root, rev)?;
+            // Label the entry with the ref, so titles read e.g. "Diff (main..feature)"
+            commit.short_hash = rev.to_string();
+            entries.push(commit);
         }
-
-        // Only load diff when in History mode
-        let file_path = match &self.mode {
-            AppMode::History { file_path, .. } => file_path.clone(),
-            AppMode::FilePicker { .. } => return Ok(()), // No-op for file picker mode
-        };
-
-        let commit = &self.commits[self.selected_index];
-
-        // Check cache first
-        if let Some(cached_diff) = self.diff_cache.get(&commit.hash).cloned() {
-            self.current_diff = cached_diff.clone();
-            self.update_side_by_side_diff(&cached_diff);
-            self.update_change_cache();
-            self.reset_diff_scroll();
-            return Ok(());
+        if self.reverse {
+            entries.reverse();
         }
 
-        let diff = if commit.is_working_directory {
-            // Handle working directory diff
-            crate::git::working::fetch_working_directory_diff(
-                &self.repo_root,
-                &file_path,
-                self.context_lines,
-            )?
-        } else {
-            // Handle regular commit diff
-            let parents = crate::git::history::get_commit_parents(&self.repo_root, &commit.hash)?;
-            let parent_hash = parents.first().map(|s| s.as_str());
+        self.commits = entries;
+        self.rename_map.clear();
+        self.history_complete = true;
+        self.pickaxe_state = None;
+        self.author_filter = None;
+        self.diff_range_start = None;
+        self.selected_index = 0;
+        self.show_diff_range(0, 1)
+    }
 
-            // Resolve file path at this commit
-            let commit_file_path = self
-                .rename_map
-                .get(&commit.hash)
-                .cloned()
-                .unwrap_or_else(|| file_path.clone());
+    /// Append the next `max_count` commits of the file's history (all of them for `None`),
+    /// along with their entries in the rename map
+    fn load_commit_page(&mut self, file_path: &Path, max_count: Option<usize>) -> Result<()> {
+        let skip = self
+            .commits
+            .iter()
+            .filter(|commit| !commit.is_working_directory)
+            .count();
+        let page = loader::fetch_page(
+            &self.repo_root,
+            file_path,
+            self.follow_renames,
+            self.first_parent,
+            skip,
+            max_count,
+            self.max_commits,
+        )?;
+        self.rename_map.extend(page.rename_map);
+        self.history_complete = page.complete;
+        self.history_truncated = page.truncated;
 
-            crate::git::diff::fetch_diff(
-                &self.repo_root,
-                &commit.hash,
-                parent_hash,
-                &commit_file_path,
-                self.context_lines,
-            )?
-        };
+        let first_new = self.commits.len();
+        self.commits.extend(page.commits);
+        self.attach_commit_stats(first_new);
+        Ok(())
+    }
 
-        // Cache and store
-        self.diff_cache.put(commit.hash.clone(), diff.clone());
-        self.current_diff = diff.clone();
-        self.update_side_by_side_diff(&diff);
-        self.update_change_cache();
+    /// Give the commits from `first` on their statistics fetched with `--stats`
+    fn attach_commit_stats(&mut self, first: usize) {
+        for commit in &mut self.commits[first..] {
+            if let Some(stats) = self.commit_stats_by_hash.get(&commit.hash) {
+                commit.stats = Some(stats.clone());
+            }
+        }
+    }
 
-        self.reset_diff_scroll();
+    /// Load the next page of the history. Returns whether any commits were added.
+    pub fn load_more(&mut self) -> Result<bool> {
+        if self.history_complete || self.commits_filtered() {
+            return Ok(false);
+        }
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(false);
+        };
 
-        Ok(())
+        let loaded = self.commits.len();
+        self.load_commit_page(&file_path, Some(COMMIT_PAGE_SIZE))?;
+        Ok(self.commits.len() > loaded)
     }
 
-    /// Update the side-by-side diff representation
-    fn update_side_by_side_diff(&mut self, diff: &str) {
-        if matches!(self.effective_layout(), LayoutMode::SideBySide) {
-            use crate::diff::HighlightedDiff;
-            let highlighted_diff =
-                HighlightedDiff::new(diff, self.get_file_path().map(|p| p.as_path()));
-            self.current_side_by_side_diff =
-                Some(SideBySideDiff::from_unified(&highlighted_diff.lines));
-        } else {
-            // Clear side-by-side diff when not needed
-            self.current_side_by_side_diff = None;
+    /// Load whatever is left of the history, for features that need every commit
+    pub fn load_all_commits(&mut self) -> Result<()> {
+        if self.history_complete || self.commits_filtered() {
+            return Ok(());
         }
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(());
+        };
+        self.load_commit_page(&file_path, None)
     }
 
-    fn reset_diff_scroll(&mut self) {
-        self.ui_state.reset_diff_scroll();
+    /// The pseudo-commit for uncommitted changes to the file, if there are any
+    fn working_directory_entry(&self, file_path: &Path) -> Result<Option<Commit>> {
+        let status =
+            crate::git::working::check_working_directory_status(&self.repo_root, file_path)?;
+        Ok(status
+            .label()
+            .map(|status_text| Commit::new_working_directory(status_text.to_string())))
     }
 
-    pub fn quit(&mut self) {
-        self.should_quit = true;
+    fn file_mtime(&self, file_path: &Path) -> Option<std::time::SystemTime> {
+        std::fs::metadata(self.repo_root.join(file_path))
+            .and_then(|metadata| metadata.modified())
+            .ok()
     }
 
-    pub fn move_selection_up(&mut self) -> Result<()> {
+    /// With `--watch-interval`, refresh the working directory entry when the file's mtime
+    /// changes. Only the cheap mtime check runs on each interval; git is asked for the status
+    /// once something actually changed.
+    pub fn poll_working_tree(&mut self) {
+        let Some(interval) = self.watch_interval else {
+            return;
+        };
+        if self.is_loading() {
+            return;
+        }
+        if self
+            .last_watch_check
+            .is_some_and(|checked| checked.elapsed() < interval)
+        {
+            return;
+        }
+        self.last_watch_check = Some(std::time::Instant::now());
+
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return;
+        };
+        let mtime = self.file_mtime(&file_path);
+        if mtime == self.watched_mtime {
+            return;
+        }
+        self.watched_mtime = mtime;
+
+        if let Err(e) = self.refresh_working_directory_entry(&file_path) {
+            self.error_message = Some(format!("Failed to refresh working directory: {}", e));
+            self.start_message_timer();
+        }
+    }
+
+    /// Index of the working directory entry, which sits at the newest end of the list
+    fn working_directory_index(&self) -> Option<usize> {
+        let index = if self.reverse {
+            self.commits.len().checked_sub(1)?
+        } else {
+            0
+        };
+        self.commits
+            .get(index)
+            .filter(|commit| commit.is_working_directory)
+            .map(|_| index)
+    }
+
+    /// Order two commit indices as `(older, newer)`. The list is newest first unless reversed.
+    pub fn older_and_newer(&self, a: usize, b: usize) -> (usize, usize) {
+        let (low, high) = (a.min(b), a.max(b));
+        if self.reverse {
+            (low, high)
+        } else {
+            (high, low)
+        }
+    }
+
+    /// Insert, update or remove the working directory entry, keeping the selection and any
+    /// diff range on the same commits
+    fn refresh_working_directory_entry(&mut self, file_path: &Path) -> Result<()> {
+        // Comparing two refs lists only them
+        if self.compared_refs.is_some() {
+            return Ok(());
+        }
+        let entry = self.working_directory_entry(file_path)?;
+        let entry_index = self.working_directory_index();
+        let viewing_entry = entry_index.is_some_and(|index| {
+            self.current_diff_range
+                .is_some_and(|(_, newer)| newer == index)
+                || (self.current_diff_range.is_none() && self.selected_index == index)
+        });
+
+        self.diff_cache.invalidate_containing("WORKING_DIR");
+
+        match (entry, entry_index) {
+            (Some(entry), Some(index)) => self.commits[index] = entry,
+            // At the end of a reversed list, no other commit moves
+            (Some(entry), None) if self.reverse => {
+                self.commits.push(entry);
+                return Ok(());
+            }
+            (Some(entry), None) => {
+                self.commits.insert(0, entry);
+                self.selected_index += 1;
+                self.diff_range_start = self.diff_range_start.map(|start| start + 1);
+                self.current_diff_range = self
+                    .current_diff_range
+                    .map(|(older, newer)| (older + 1, newer + 1));
+                // The view itself is unchanged
+                return Ok(());
+            }
+            (None, Some(index)) if self.reverse => {
+                self.commits.remove(index);
+                self.selected_index = self
+                    .selected_index
+                    .min(self.commits.len().saturating_sub(1));
+                self.diff_range_start = self.diff_range_start.filter(|&start| start != index);
+                self.current_diff_range =
+                    self.current_diff_range.filter(|&(_, newer)| newer != index);
+            }
+            (None, Some(_)) => {
+                self.commits.remove(0);
+                self.selected_index = self.selected_index.saturating_sub(1);
+                self.diff_range_start =
+                    self.diff_range_start.and_then(|start| start.checked_sub(1));
+                self.current_diff_range = self.current_diff_range.and_then(|(older, newer)| {
+                    Some((older.checked_sub(1)?, newer.checked_sub(1)?))
+                });
+            }
+            (None, None) => return Ok(()),
+        }
+
+        if viewing_entry {
+            match self.current_diff_range {
+                Some((older, newer)) => self.show_diff_range(older, newer)?,
+                None => self.load_diff_for_selected_commit()?,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn load_diff_for_selected_commit(&mut self) -> Result<()> {
+        self.comparing_clipboard = false;
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+        if self.blame_lines.is_some() {
+            self.load_blame_for_selected_commit();
+        }
+
+        // Only load diff when in History mode
+        let file_path = match &self.mode {
+            AppMode::History { file_path, .. } => file_path.clone(),
+            AppMode::FilePicker { .. } => return Ok(()), // No-op for file picker mode
+        };
+
+        if self.full_file_view {
+            self.load_full_file_for_selected_commit(&file_path);
+            return Ok(());
+        }
+
+        let diff = if self.showing_whole_commit() {
+            self.fetch_whole_commit_diff(self.selected_index)?
+        } else {
+            self.fetch_commit_diff(self.selected_index, &file_path)?
+        };
+        self.set_current_diff(diff);
+
+        Ok(())
+    }
+
+    /// Whether the diff shows an untracked file, its whole content as additions
+    pub fn showing_untracked_file(&self) -> bool {
+        let untracked = crate::git::working::WorkingDirectoryStatus::Untracked.label();
+        self.commits.get(self.selected_index).is_some_and(|commit| {
+            commit.is_working_directory && Some(commit.subject.as_str()) == untracked
+        })
+    }
+
+    /// Whether the diff panel shows the selected commit's whole diff. The working directory
+    /// entry is no single commit, so it keeps showing the file's changes.
+    pub fn showing_whole_commit(&self) -> bool {
+        self.whole_commit_view
+            && self
+                .commits
+                .get(self.selected_index)
+                .is_some_and(|commit| !commit.is_working_directory)
+    }
+
+    /// The diff of every file commit `index` changed, from the cache when possible
+    fn fetch_whole_commit_diff(&mut self, index: usize) -> Result<String> {
+        let hash = self.commits[index].hash.clone();
+        let cache_key = self.diff_cache_key(&format!("{}:whole", hash), Path::new(""));
+        if let Some(cached_diff) = self.diff_cache.get(&cache_key).cloned() {
+            return Ok(cached_diff);
+        }
+
+        let diff = crate::git::diff::fetch_commit_files_diff(
+            &self.repo_root,
+            &hash,
+            self.context_lines,
+            self.ignore_whitespace,
+            self.text_encoding,
+        )?;
+        self.diff_cache.put(cache_key, diff.clone());
+        Ok(diff)
+    }
+
+    /// The diff of `file_path` at commit `index`, from the cache when possible. Merges show
+    /// their combined diff when that is turned on.
+    fn fetch_commit_diff(&mut self, index: usize, file_path: &Path) -> Result<String> {
+        let commit = &self.commits[index];
+
+        // Check cache first
+        let cache_key = self.diff_cache_key(&commit.hash, file_path);
+        if let Some(cached_diff) = self.diff_cache.get(&cache_key).cloned() {
+            return Ok(cached_diff);
+        }
+
+        let diff = if commit.is_working_directory {
+            // Handle working directory diff
+            crate::git::working::fetch_working_directory_diff(
+                &self.repo_root,
+                file_path,
+                self.context_lines,
+                self.ignore_whitespace,
+                self.text_encoding,
+            )?
+        } else {
+            // Handle regular commit diff
+            let parents = crate::git::history::get_commit_parents(&self.repo_root, &commit.hash)?;
+            let parent_hash = parents.first().map(|s| s.as_str());
+
+            // Resolve file path at this commit
+            let commit_file_path = self
+                .rename_map
+                .get(&commit.hash)
+                .cloned()
+                .unwrap_or_else(|| file_path.to_path_buf());
+
+            if self.combined_merge_diff && parents.len() > 1 {
+                crate::git::diff::fetch_combined_diff(
+                    &self.repo_root,
+                    &commit.hash,
+                    &commit_file_path,
+                    self.context_lines,
+                    self.ignore_whitespace,
+                    self.text_encoding,
+                )?
+            } else {
+                crate::git::diff::fetch_diff(
+                    &self.repo_root,
+                    &commit.hash,
+                    parent_hash,
+                    &commit_file_path,
+                    self.context_lines,
+                    self.ignore_whitespace,
+                    self.text_encoding,
+                )?
+            }
+        };
+
+        // The working directory changes, so its diff is never persisted
+        if commit.is_working_directory {
+            self.diff_cache.put_in_memory(cache_key, diff.clone());
+        } else {
+            self.diff_cache.put(cache_key, diff.clone());
+        }
+        Ok(diff)
+    }
+
+    /// Show the whole file as of the selected commit, using its path at that commit so renamed
+    /// files keep working. The working directory entry reads the file from disk.
+    fn load_full_file_for_selected_commit(&mut self, file_path: &Path) {
+        let commit = &self.commits[self.selected_index];
+        let (commit_hash, path) = if commit.is_working_directory {
+            (None, file_path)
+        } else {
+            let path = self
+                .rename_map
+                .get(&commit.hash)
+                .map(PathBuf::as_path)
+                .unwrap_or(file_path);
+            (Some(commit.hash.clone()), path)
+        };
+        let contents = match commit_hash {
+            None => std::fs::read(self.repo_root.join(path)).map_err(|_| {
+                error::GeschichteError::FileNotFound {
+                    path: path.to_path_buf(),
+                }
+            }),
+            Some(ref hash) => crate::git::show::fetch_file_at_commit(&self.repo_root, hash, path),
+        };
+
+        self.line_ages.clear();
+        if self.heat_map {
+            self.line_ages = self.fetch_line_ages(path, commit_hash.as_deref());
+        }
+
+        match contents {
+            Ok(bytes) => {
+                let text = crate::git::encoding::decode(&bytes, self.text_encoding);
+                self.set_current_diff(crate::diff::parser::file_as_diff(&text));
+            }
+            Err(e) => {
+                self.set_current_diff(String::new());
+                self.error_message = Some(format!("Failed to load file: {}", e));
+                self.start_message_timer();
+            }
+        }
+    }
+
+    /// The age of every line of the file as of `commit_hash` (the working tree when `None`),
+    /// from its blame. A failed blame leaves the file without a heat map.
+    fn fetch_line_ages(
+        &self,
+        path: &Path,
+        commit_hash: Option<&str>,
+    ) -> Vec<crate::git::blame::LineAge> {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        crate::git::blame::fetch_blame(&self.repo_root, path, commit_hash, self.text_encoding)
+            .map(|lines| lines.iter().map(|line| line.age(now)).collect())
+            .unwrap_or_else(|e| {
+                log::debug!("No heat map for {}: {}", path.display(), e);
+                Vec::new()
+            })
+    }
+
+    /// Show or hide the heat map, which colors each line of the full file view by how
+    /// recently it changed. Turning it on opens the full file view.
+    pub fn toggle_heat_map(&mut self) -> Result<()> {
+        if !self.heat_map && self.refuse_in_directory_mode("The heat map") {
+            return Ok(());
+        }
+        self.heat_map = !self.heat_map;
+        if self.heat_map && !self.full_file_view {
+            self.toggle_full_file_view()
+        } else {
+            self.load_diff_for_selected_commit()
+        }
+    }
+
+    /// The heat map's age of each line of the full file view, by its index in the view
+    pub fn heat_map_age(&self, line_index: usize) -> Option<crate::git::blame::LineAge> {
+        if !self.heat_map || !self.full_file_view {
+            return None;
+        }
+        // Line N of the file follows the hunk header, at index N
+        line_index
+            .checked_sub(1)
+            .and_then(|index| self.line_ages.get(index))
+            .copied()
+    }
+
+    /// Toggle between the selected commit's diff and the whole file as of that commit. Entering
+    /// the full file keeps the cursor on the file line it was on in the diff.
+    pub fn toggle_full_file_view(&mut self) -> Result<()> {
+        if self.refuse_in_directory_mode("The full file view") {
+            return Ok(());
+        }
+        let cursor_file_line = self
+            .cached_highlighted_diff
+            .as_ref()
+            .and_then(|diff| diff.lines.get(self.ui_state.diff_cursor_line))
+            .and_then(|line| line.new_line_num.or(line.old_line_num));
+
+        self.full_file_view = !self.full_file_view;
+        self.clear_diff_range_selection();
+        self.clear_diff_search();
+        self.load_diff_for_selected_commit()?;
+
+        if self.full_file_view {
+            if let Some(file_line) = cursor_file_line {
+                // Line N of the file follows the hunk header, at index N
+                let last_line = self.get_diff_line_count().saturating_sub(1);
+                self.ui_state.diff_cursor_line = file_line.min(last_line);
+                let layout_mode = self.effective_layout();
+                self.ui_state.ensure_diff_line_visible(
+                    self.ui_state.diff_cursor_line,
+                    None,
+                    &layout_mode,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Toggle between the file's diff and the diff of every file the selected commit changed
+    pub fn toggle_whole_commit_view(&mut self) -> Result<()> {
+        if self.current_diff_range.is_some() {
+            self.error_message = Some("The whole commit view shows a single commit".to_string());
+            self.start_message_timer();
+            return Ok(());
+        }
+
+        self.whole_commit_view = !self.whole_commit_view;
+        self.full_file_view = false;
+        self.clear_diff_range_selection();
+        self.clear_diff_search();
+        self.load_diff_for_selected_commit()?;
+        self.ui_state.diff_cursor_line = 0;
+        self.ui_state.diff_scroll = 0;
+        Ok(())
+    }
+
+    /// Move the diff cursor to the header of the next file in a multi-file diff
+    pub fn next_file_in_diff(&mut self) {
+        let cursor = self.ui_state.diff_cursor_line;
+        let next = self
+            .file_header_lines()
+            .into_iter()
+            .find(|&line| line > cursor);
+        if let Some(line) = next {
+            self.ui_state.diff_cursor_line = line;
+            self.ui_state
+                .ensure_cursor_visible(&self.effective_layout());
+        }
+    }
+
+    /// Move the diff cursor to the header of the previous file in a multi-file diff
+    pub fn prev_file_in_diff(&mut self) {
+        let cursor = self.ui_state.diff_cursor_line;
+        let previous = self
+            .file_header_lines()
+            .into_iter()
+            .rev()
+            .find(|&line| line < cursor);
+        if let Some(line) = previous {
+            self.ui_state.diff_cursor_line = line;
+            self.ui_state
+                .ensure_cursor_visible(&self.effective_layout());
+        }
+    }
+
+    /// Indices of the `diff --git` lines starting each file of the current diff
+    fn file_header_lines(&self) -> Vec<usize> {
+        let Some(ref highlighted) = self.cached_highlighted_diff else {
+            return Vec::new();
+        };
+        highlighted
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.line_type == crate::diff::DiffLineType::Header
+                    && line.content.starts_with("diff --git")
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Display a freshly loaded diff, applying view transforms and refreshing derived state.
+    /// The diff cache always holds the raw git output.
+    fn set_current_diff(&mut self, diff: String) {
+        // A combined diff compares against several parents, so there's no single side to swap
+        self.current_diff = if self.invert_diff && !is_combined_diff(&diff) {
+            crate::diff::parser::invert_diff(&diff)
+        } else {
+            diff
+        };
+        self.update_side_by_side_diff(&self.current_diff.clone());
+        self.update_change_cache();
+        self.collapsed_hunks.clear();
+        self.reset_diff_scroll();
+    }
+
+    /// Show the clipboard text diffed against the file as of the selected commit
+    pub fn compare_with_clipboard(&mut self) -> Result<()> {
+        /// Larger clipboards are almost certainly not a snippet of this file
+        const MAX_CLIPBOARD_BYTES: usize = 1024 * 1024;
+
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+        if self.refuse_in_directory_mode("Comparing with the clipboard") {
+            return Ok(());
+        }
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(());
+        };
+
+        let text = match self.copier.paste_text() {
+            Ok(text) if text.is_empty() => Err("Clipboard is empty".to_string()),
+            Ok(text) if text.contains('\0') => {
+                Err("Clipboard content looks binary, not comparing".to_string())
+            }
+            Ok(text) if text.len() > MAX_CLIPBOARD_BYTES => Err(format!(
+                "Clipboard content is too large to compare ({} KB)",
+                text.len() / 1024
+            )),
+            other => other,
+        };
+        let text = match text {
+            Ok(text) => text,
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        let commit = &self.commits[self.selected_index];
+        let commit_hash = (!commit.is_working_directory).then(|| commit.hash.clone());
+        let commit_file_path = self
+            .rename_map
+            .get(&commit.hash)
+            .cloned()
+            .unwrap_or(file_path);
+
+        match crate::git::diff::diff_text_against_file(
+            &self.repo_root,
+            &text,
+            commit_hash.as_deref(),
+            &commit_file_path,
+            self.context_lines,
+            self.text_encoding,
+        ) {
+            Ok(diff) => {
+                self.clear_diff_search();
+                self.current_diff_range = None;
+                self.set_current_diff(diff);
+                self.comparing_clipboard = true;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to compare with clipboard: {}", e));
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Show or hide the blame of the selected commit's file in the diff panel
+    pub fn toggle_blame(&mut self) {
+        if self.blame_lines.is_none() && self.refuse_in_directory_mode("Blame") {
+            return;
+        }
+        if self.blame_lines.take().is_none() {
+            self.load_blame_for_selected_commit();
+        }
+        self.reset_diff_scroll();
+    }
+
+    /// Blame the file as of the selected commit, using its path at that commit so renamed
+    /// files keep working. Failures leave an empty blame and an error message.
+    fn load_blame_for_selected_commit(&mut self) {
+        let Some(commit) = self.commits.get(self.selected_index) else {
+            self.blame_lines = Some(Vec::new());
+            return;
+        };
+        let Some(file_path) = self.get_file_path() else {
+            return;
+        };
+
+        let (commit_hash, blame_path) = if commit.is_working_directory {
+            (None, file_path.clone())
+        } else {
+            let path = self
+                .rename_map
+                .get(&commit.hash)
+                .unwrap_or(file_path)
+                .clone();
+            (Some(commit.hash.clone()), path)
+        };
+
+        match crate::git::blame::fetch_blame(
+            &self.repo_root,
+            &blame_path,
+            commit_hash.as_deref(),
+            self.text_encoding,
+        ) {
+            Ok(lines) => self.blame_lines = Some(lines),
+            Err(e) => {
+                self.blame_lines = Some(Vec::new());
+                self.error_message = Some(format!("Failed to load blame: {}", e));
+                self.start_message_timer();
+            }
+        }
+    }
+
+    /// Select the commit whose hash starts with `prefix`, loading older pages when the loaded
+    /// ones don't have it. A commit outside the file's history selects the newest change to
+    /// the file before it.
+    pub fn select_commit_by_hash(&mut self, prefix: &str) -> Result<()> {
+        let prefix = prefix.trim().to_lowercase();
+        if prefix.is_empty() {
+            return Ok(());
+        }
+
+        let matching = |commits: &[Commit]| -> Vec<usize> {
+            commits
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| !c.is_working_directory && c.hash.starts_with(&prefix))
+                .map(|(index, _)| index)
+                .collect()
+        };
+        let mut found = matching(&self.commits);
+        if found.is_empty() {
+            // The commit may be older than the pages loaded so far
+            self.load_all_commits()?;
+            found = matching(&self.commits);
+        }
+
+        let target = match found.as_slice() {
+            [index] => Some(*index),
+            [] => self.find_file_commit_before(&prefix)?,
+            _ => {
+                self.error_message = Some(format!(
+                    "{} is ambiguous: {} commits match",
+                    prefix,
+                    found.len()
+                ));
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        if let Some(index) = target {
+            self.selected_index = index;
+            self.load_diff_for_selected_commit()?;
+        }
+        Ok(())
+    }
+
+    /// Mark the selected commit with `letter`, replacing any commit the letter marked before
+    pub fn set_mark(&mut self, letter: char) {
+        let Some(commit) = self.commits.get(self.selected_index) else {
+            return;
+        };
+        if commit.is_working_directory {
+            self.error_message = Some("The working directory entry can't be marked".to_string());
+            self.start_message_timer();
+            return;
+        }
+        self.copy_message = Some(format!("Marked {} as '{}", commit.short_hash, letter));
+        self.marks.insert(letter, commit.hash.clone());
+        self.start_message_timer();
+    }
+
+    /// Select the commit marked with `letter`, or with `'` the one selected before the last
+    /// jump, so two suspects can be flipped between
+    pub fn jump_to_mark(&mut self, letter: char) -> Result<()> {
+        let hash = if letter == '\'' {
+            self.mark_return.clone()
+        } else {
+            self.marks.get(&letter).cloned()
+        };
+        let Some(hash) = hash else {
+            self.error_message = Some(format!("Mark '{} isn't set", letter));
+            self.start_message_timer();
+            return Ok(());
+        };
+
+        let previous = self
+            .commits
+            .get(self.selected_index)
+            .map(|commit| commit.hash.clone());
+        self.select_commit_by_hash(&hash)?;
+        if self.commits.get(self.selected_index).map(|c| &c.hash) != previous.as_ref() {
+            self.mark_return = previous;
+        }
+        Ok(())
+    }
+
+    /// The letters marking the commit, in order (empty when unmarked)
+    pub fn mark_letters(&self, hash: &str) -> String {
+        let mut letters: Vec<char> = self
+            .marks
+            .iter()
+            .filter(|(_, marked)| marked.as_str() == hash)
+            .map(|(letter, _)| *letter)
+            .collect();
+        letters.sort_unstable();
+        letters.into_iter().collect()
+    }
+
+    /// The listed commit that last changed the file at or before the commit `rev` names, with
+    /// a message saying so. Shows an error when there is none.
+    fn find_file_commit_before(&mut self, rev: &str) -> Result<Option<usize>> {
+        let file_path = self.get_file_path().cloned();
+        let resolved = match (
+            crate::git::history::resolve_commit(&self.repo_root, rev)?,
+            file_path,
+        ) {
+            (Some(hash), Some(file_path)) => {
+                crate::git::history::find_file_commit(&self.repo_root, &hash, &file_path)?
+            }
+            _ => None,
+        };
+        let index = match resolved {
+            Some(hash) => self.find_listed_commit(&hash)?,
+            None => None,
+        };
+
+        match index {
+            Some(index) => {
+                self.error_message = Some(format!(
+                    "{} didn't change this file; selected {}, the change before it",
+                    rev, self.commits[index].short_hash
+                ));
+            }
+            None => self.error_message = Some(format!("No commit in this history matches {}", rev)),
+        }
+        self.start_message_timer();
+        Ok(index)
+    }
+
+    /// Select the commit that last changed the blame line under the cursor
+    pub fn jump_to_blame_commit(&mut self) -> Result<()> {
+        let Some(blame_line) = self
+            .blame_lines
+            .as_ref()
+            .and_then(|lines| lines.get(self.ui_state.diff_cursor_line))
+            .cloned()
+        else {
+            return Ok(());
+        };
+
+        let target = if blame_line.is_uncommitted() {
+            self.commits.iter().position(|c| c.is_working_directory)
+        } else {
+            match self.commits.iter().position(|c| c.hash == blame_line.hash) {
+                Some(index) => Some(index),
+                // The commit may be older than the pages loaded so far
+                None => {
+                    self.load_all_commits()?;
+                    self.commits.iter().position(|c| c.hash == blame_line.hash)
+                }
+            }
+        };
+
+        match target {
+            Some(index) => {
+                self.selected_index = index;
+                self.load_diff_for_selected_commit()?;
+            }
+            None => {
+                self.error_message = Some(format!(
+                    "Commit {} is not in this file's history",
+                    blame_line.short_hash
+                ));
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Toggle showing the diff inverted (new side as base) and reload the current view
+    pub fn toggle_invert_diff(&mut self) -> Result<()> {
+        self.invert_diff = !self.invert_diff;
+        self.clear_diff_search();
+        self.reload_current_diff()
+    }
+
+    /// Toggle leaving whitespace-only changes out of diffs and reload the current view.
+    /// Cached diffs were fetched with the old setting, so the cache is dropped.
+    pub fn toggle_ignore_whitespace(&mut self) -> Result<()> {
+        self.ignore_whitespace = !self.ignore_whitespace;
+        self.clear_diff_cache();
+        self.clear_diff_search();
+        self.reload_current_diff()
+    }
+
+    /// Toggle following the file across renames and reload its history from the newest commit.
+    /// Cached diffs were fetched at the paths the old history gave the file, so they're dropped.
+    pub fn toggle_follow_renames(&mut self) -> Result<()> {
+        if self.is_directory_mode() {
+            self.error_message = Some("Renames are only followed for files".to_string());
+            self.start_message_timer();
+            return Ok(());
+        }
+        self.follow_renames = !self.follow_renames;
+        self.selected_index = 0;
+        self.clear_diff_range_selection();
+        self.clear_diff_search();
+        self.ui_state.reset_diff_scroll();
+        self.refresh_current_diff()
+    }
+
+    /// Toggle showing merge commits as a combined diff against all their parents, rather
+    /// than a diff against the first parent, and reload the current view
+    pub fn toggle_combined_merge_diff(&mut self) -> Result<()> {
+        self.combined_merge_diff = !self.combined_merge_diff;
+        self.copy_message = Some(if self.combined_merge_diff {
+            "Merges: combined diff against all parents".to_string()
+        } else {
+            "Merges: diff against the first parent".to_string()
+        });
+        self.start_message_timer();
+        self.clear_diff_search();
+        self.reload_current_diff()
+    }
+
+    /// Whether the diff shown is a merge's combined diff
+    pub fn showing_combined_diff(&self) -> bool {
+        is_combined_diff(&self.current_diff)
+    }
+
+    /// Show `delta` more (or fewer) context lines around changes, within
+    /// `0..=MAX_CONTEXT_LINES`, and reload the current view
+    pub fn adjust_context_lines(&mut self, delta: i32) -> Result<()> {
+        // --full-file asks for far more context than the keys go up to
+        let current = self.context_lines.min(MAX_CONTEXT_LINES);
+        let context_lines = current.saturating_add_signed(delta).min(MAX_CONTEXT_LINES);
+
+        self.copy_message = Some(format!("Context: {}", context_lines));
+        self.start_message_timer();
+        if context_lines == self.context_lines {
+            return Ok(());
+        }
+
+        // Cached diffs were fetched with the old context
+        self.context_lines = context_lines;
+        self.clear_diff_cache();
+        self.clear_diff_search();
+        self.reload_current_diff()
+    }
+
+    /// Reload the diff panel: the marked range if one is shown, else the selected commit
+    fn reload_current_diff(&mut self) -> Result<()> {
+        match self.current_diff_range {
+            Some((older_index, newer_index)) => self.show_diff_range(older_index, newer_index),
+            None => self.load_diff_for_selected_commit(),
+        }
+    }
+
+    /// Update the side-by-side diff representation
+    fn update_side_by_side_diff(&mut self, diff: &str) {
+        if matches!(self.effective_layout(), LayoutMode::SideBySide) {
+            use crate::diff::HighlightedDiff;
+            let highlighted_diff = HighlightedDiff::new(diff, self.highlight_path());
+            self.current_side_by_side_diff =
+                Some(SideBySideDiff::from_unified(&highlighted_diff.lines));
+        } else {
+            // Clear side-by-side diff when not needed
+            self.current_side_by_side_diff = None;
+        }
+    }
+
+    fn reset_diff_scroll(&mut self) {
+        self.ui_state.reset_diff_scroll();
+    }
+
+    pub fn quit(&mut self) {
+        if self.print_on_exit {
+            self.keep_diff_for_exit();
+        }
+        self.should_quit = true;
+    }
+
+    /// Quit, printing the diff on screen to stdout once the terminal is restored
+    pub fn quit_and_print(&mut self) {
+        self.keep_diff_for_exit();
+        self.should_quit = true;
+    }
+
+    /// Return to the file picker when the history was opened from it, otherwise quit
+    pub fn leave_history(&mut self) {
+        if self.came_from_file_picker {
+            if let Err(e) = self.switch_to_file_picker() {
+                self.error_message = Some(format!("Failed to return to file picker: {}", e));
+            }
+        } else {
+            self.quit();
+        }
+    }
+
+    fn keep_diff_for_exit(&mut self) {
+        if self.get_file_path().is_some() && !self.current_diff.is_empty() {
+            self.on_exit_output = Some(self.current_diff.clone());
+        }
+    }
+
+    pub fn move_selection_up(&mut self) -> Result<()> {
         if self.selected_index > 0 {
             self.selected_index -= 1;
             // Clear range diff when navigating
@@ -423,6 +1782,14 @@ impl App {
     }
 
     pub fn move_selection_down(&mut self) -> Result<()> {
+        // Fetch the next page before the selection reaches the end of the loaded history
+        if self.selected_index + COMMIT_PAGE_PREFETCH >= self.commits.len() {
+            if let Err(e) = self.load_more() {
+                self.error_message = Some(format!("Failed to load more commits: {}", e));
+                self.start_message_timer();
+            }
+        }
+
         if self.selected_index + 1 < self.commits.len() {
             self.selected_index += 1;
             // Clear range diff when navigating
@@ -436,6 +1803,55 @@ impl App {
         Ok(())
     }
 
+    /// Select commit `index` (clamped to the list) and show its diff
+    fn select_commit(&mut self, index: usize) -> Result<()> {
+        let index = index.min(self.commits.len().saturating_sub(1));
+        if self.commits.is_empty() || index == self.selected_index {
+            return Ok(());
+        }
+        self.selected_index = index;
+        if self.diff_range_start.is_none() {
+            self.current_diff_range = None;
+        }
+        self.clear_diff_search();
+        self.load_diff_for_selected_commit()
+    }
+
+    /// Go to the first commit or the first diff line, depending on the focused panel
+    pub fn jump_to_top(&mut self) -> Result<()> {
+        match self.get_focused_panel() {
+            Some(FocusedPanel::Commits) => self.select_commit(0)?,
+            Some(FocusedPanel::Diff) => {
+                let layout_mode = self.effective_layout();
+                self.ui_state.diff_cursor_line = 0;
+                self.ui_state.ensure_cursor_visible(&layout_mode);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Go to the last commit (loading the rest of the history) or the last diff line
+    pub fn jump_to_bottom(&mut self) -> Result<()> {
+        match self.get_focused_panel() {
+            Some(FocusedPanel::Commits) => {
+                if let Err(e) = self.load_all_commits() {
+                    self.error_message = Some(format!("Failed to load more commits: {}", e));
+                    self.start_message_timer();
+                }
+                self.select_commit(self.commits.len().saturating_sub(1))?;
+            }
+            Some(FocusedPanel::Diff) => {
+                let layout_mode = self.effective_layout();
+                self.ui_state.diff_cursor_line = self.get_diff_line_count().saturating_sub(1);
+                self.ui_state.ensure_cursor_visible(&layout_mode);
+                self.skip_collapsed_lines(false);
+            }
+            None => {}
+        }
+        Ok(())
+    }
+
     pub fn handle_resize(&mut self, width: u16, height: u16) {
         let old_effective_layout = self.effective_layout();
 
@@ -448,15 +1864,110 @@ impl App {
         }
     }
 
+    /// Whether the commit list is a strip below the diff rather than a column beside it
+    pub fn commits_at_bottom(&self) -> bool {
+        self.effective_layout() == LayoutMode::SideBySide || self.ui_state.commits_bottom
+    }
+
+    /// Where the commit list is drawn, computed the same way the UI lays it out
+    pub fn commits_panel_area(&self) -> Rect {
+        let main_area = Rect::new(
+            0,
+            0,
+            self.ui_state.terminal_width,
+            self.ui_state.terminal_height.saturating_sub(1), // Status bar
+        );
+        if self.commits_at_bottom() {
+            return crate::ui::split_commits_bottom(main_area, self.ui_state.vertical_split_ratio)
+                .1;
+        }
+
+        let left_percent = (self.ui_state.split_ratio * 100.0) as u16;
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(left_percent),
+                Constraint::Percentage(100 - left_percent),
+            ])
+            .split(main_area)[0]
+    }
+
+    /// Commit drawn on a terminal row, accounting for how far the list has scrolled to keep
+    /// the selection visible
+    pub fn commit_at_row(&self, row: u16) -> Option<usize> {
+        let area = self.commits_panel_area();
+        // The top border (with the title) and the bottom border hold no commits
+        if row <= area.y || row >= area.bottom().saturating_sub(1) {
+            return None;
+        }
+
+        let first_visible = crate::ui::first_visible_commit(self.selected_index, area.height);
+        let index = first_visible + (row - area.y - 1) as usize;
+        (index < self.commits.len()).then_some(index)
+    }
+
+    /// Switch between unified and side-by-side, keeping the diff cursor and scroll as close to
+    /// where they were as the new layout allows
+    pub fn toggle_layout(&mut self) {
+        self.layout_mode = match self.effective_layout() {
+            LayoutMode::SideBySide => LayoutMode::Unified,
+            _ => LayoutMode::SideBySide,
+        };
+        self.update_side_by_side_diff(&self.current_diff.clone());
+
+        // Side-by-side pairs deletions with additions, so it can have fewer rows than unified
+        let last_line = self.get_diff_line_count().saturating_sub(1);
+        self.ui_state.diff_cursor_line = self.ui_state.diff_cursor_line.min(last_line);
+        self.ui_state.diff_scroll = self.ui_state.diff_scroll.min(last_line);
+        let layout_mode = self.effective_layout();
+        self.ui_state.ensure_cursor_visible(&layout_mode);
+    }
+
+    /// Set the panel focused when a history view opens, applying it to the current view
+    pub fn set_initial_focus(&mut self, panel: FocusedPanel) {
+        self.initial_focus = panel;
+        self.focus_panel(panel);
+    }
+
+    pub fn focus_panel(&mut self, panel: FocusedPanel) {
+        if let AppMode::History { focused_panel, .. } = &mut self.mode {
+            *focused_panel = panel;
+        }
+    }
+
+    /// Cycle focus: Commits → Diff → Commits, or Commits → Old → New → Commits in side-by-side
     pub fn switch_focus(&mut self) {
+        use crate::ui::state::SideBySideFocus;
+
+        let side_by_side = matches!(self.effective_layout(), LayoutMode::SideBySide);
         if let AppMode::History { focused_panel, .. } = &mut self.mode {
-            *focused_panel = match *focused_panel {
-                FocusedPanel::Commits => FocusedPanel::Diff,
-                FocusedPanel::Diff => FocusedPanel::Commits,
-            };
+            match *focused_panel {
+                FocusedPanel::Commits => {
+                    *focused_panel = FocusedPanel::Diff;
+                    self.ui_state.side_by_side_focus = SideBySideFocus::Old;
+                }
+                FocusedPanel::Diff
+                    if side_by_side && self.ui_state.side_by_side_focus == SideBySideFocus::Old =>
+                {
+                    self.ui_state.side_by_side_focus = SideBySideFocus::New;
+                }
+                FocusedPanel::Diff => *focused_panel = FocusedPanel::Commits,
+            }
         }
     }
 
+    /// The file path to pick a syntax by, or None to draw code in plain diff colors
+    pub fn highlight_path(&self) -> Option<&Path> {
+        self.get_file_path()
+            .filter(|_| self.syntax_highlighting)
+            .map(|path| path.as_path())
+    }
+
+    /// Language the current file's code is highlighted as, when its path doesn't tell
+    pub fn highlight_language(&self) -> Option<&str> {
+        self.syntax_language.as_deref()
+    }
+
     pub fn get_focused_panel(&self) -> Option<FocusedPanel> {
         match &self.mode {
             AppMode::History { focused_panel, .. } => Some(*focused_panel),
@@ -464,6 +1975,22 @@ impl App {
         }
     }
 
+    /// Whether the history is of a directory, showing every commit to the files under it
+    pub fn is_directory_mode(&self) -> bool {
+        self.get_file_path()
+            .is_some_and(|path| self.repo_root.join(path).is_dir())
+    }
+
+    /// Refuse a file-only feature while viewing a directory, saying why
+    fn refuse_in_directory_mode(&mut self, feature: &str) -> bool {
+        if !self.is_directory_mode() {
+            return false;
+        }
+        self.error_message = Some(format!("{} needs a single file, not a directory", feature));
+        self.start_message_timer();
+        true
+    }
+
     pub fn get_file_path(&self) -> Option<&PathBuf> {
         match &self.mode {
             AppMode::History { file_path, .. } => Some(file_path),
@@ -474,7 +2001,21 @@ impl App {
     pub fn toggle_diff_range_selection(&mut self) -> Result<()> {
         match &self.mode {
             AppMode::History { .. } => {
-                if let Some(start_index) = self.diff_range_start {
+                if let Some(count) = self.pending_count.take() {
+                    // A count diffs straight against the commit that many entries older
+                    self.diff_range_start = None;
+                    let start_index = self.selected_index;
+                    let end_index = if self.reverse {
+                        start_index.saturating_sub(count)
+                    } else {
+                        let wanted = start_index.saturating_add(count);
+                        while wanted >= self.commits.len() && self.load_more()? {}
+                        wanted.min(self.commits.len().saturating_sub(1))
+                    };
+                    if start_index != end_index {
+                        self.show_diff_range(start_index, end_index)?;
+                    }
+                } else if let Some(start_index) = self.diff_range_start {
                     // We have a start commit, create range diff
                     let end_index = self.selected_index;
                     if start_index != end_index {
@@ -504,6 +2045,7 @@ impl App {
     }
 
     fn show_diff_range(&mut self, start_index: usize, end_index: usize) -> Result<()> {
+        self.comparing_clipboard = false;
         if self.commits.is_empty()
             || start_index >= self.commits.len()
             || end_index >= self.commits.len()
@@ -511,37 +2053,31 @@ impl App {
             return Ok(());
         }
 
-        // Determine the correct chronological order (older commit first)
-        // In the commits list, newer commits are at the top (lower index)
-        // So lower index = newer, higher index = older
-        let (older_index, newer_index) = if start_index > end_index {
-            (start_index, end_index) // start is older (higher index)
-        } else {
-            (end_index, start_index) // end is older (higher index)
-        };
+        // Diff from the older commit to the newer one, whichever way the list is ordered
+        let (older_index, newer_index) = self.older_and_newer(start_index, end_index);
 
         let older_commit = &self.commits[older_index];
         let newer_commit = &self.commits[newer_index];
 
+        // Get the file path
+        let file_path = match &self.mode {
+            AppMode::History { file_path, .. } => file_path.clone(),
+            AppMode::FilePicker { .. } => return Ok(()), // Should not happen
+        };
+
         // Create cache key for the range diff (always older..newer)
-        let cache_key = format!("{}..{}", older_commit.hash, newer_commit.hash);
+        let cache_key = self.diff_cache_key(
+            &format!("{}..{}", older_commit.hash, newer_commit.hash),
+            &file_path,
+        );
 
         // Check cache first
         if let Some(cached_diff) = self.diff_cache.get(&cache_key).cloned() {
-            self.current_diff = cached_diff.clone();
-            self.update_side_by_side_diff(&cached_diff);
-            self.update_change_cache();
-            self.reset_diff_scroll();
+            self.set_current_diff(cached_diff);
             self.current_diff_range = Some((older_index, newer_index));
             return Ok(());
         }
 
-        // Get the file path
-        let file_path = match &self.mode {
-            AppMode::History { file_path, .. } => file_path.clone(),
-            AppMode::FilePicker { .. } => return Ok(()), // Should not happen
-        };
-
         // Generate diff between the two commits (older..newer)
         let diff = crate::git::diff::get_diff_between_commits(
             &self.repo_root,
@@ -549,14 +2085,17 @@ impl App {
             &newer_commit.hash,
             &file_path,
             self.context_lines,
+            self.ignore_whitespace,
+            self.text_encoding,
         )?;
 
         // Cache and set the diff
-        self.diff_cache.put(cache_key, diff.clone());
-        self.current_diff = diff.clone();
-        self.update_side_by_side_diff(&diff);
-        self.update_change_cache();
-        self.reset_diff_scroll();
+        if newer_commit.is_working_directory {
+            self.diff_cache.put_in_memory(cache_key, diff.clone());
+        } else {
+            self.diff_cache.put(cache_key, diff.clone());
+        }
+        self.set_current_diff(diff);
 
         // Store the current range for UI display
         self.current_diff_range = Some((older_index, newer_index));
@@ -565,16 +2104,72 @@ impl App {
     }
 
     pub fn handle_key(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
+        use crossterm::event::{KeyCode, KeyModifiers};
+
+        // Ctrl+C never reaches the signal handler in raw mode, so treat it as a quit
+        if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+            self.quit();
+            return Ok(());
+        }
+
+        // Nothing to act on until the history arrives, but leaving it stays possible
+        if self.is_loading() {
+            if self.keymap.action_for(&key) == Some(crate::config::keys::Action::Quit) {
+                self.leave_history();
+            }
+            return Ok(());
+        }
+
         // Handle search input first if active
         if self.handle_search_input_keys(key)? {
             return Ok(());
         }
+        if self.handle_pickaxe_input_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_author_filter_input_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_goto_line_input_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_goto_commit_input_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_history_search_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_parent_chooser_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_commit_info_search_keys(key)? {
+            return Ok(());
+        }
 
         // Handle file picker mode separately
         if matches!(self.mode, AppMode::FilePicker { .. }) {
             return self.handle_file_picker_key(key);
         }
 
+        if self.handle_pending_mark_keys(key)? {
+            return Ok(());
+        }
+        if self.handle_diff_selection_keys(key)? {
+            return Ok(());
+        }
+
+        // Any key but a second `g` cancels a pending `gg`
+        if self.keymap.action_for(&key) != Some(crate::config::keys::Action::JumpToTop) {
+            self.pending_g = false;
+        }
+        if self.handle_count_keys(key) {
+            return Ok(());
+        }
+        // A count only applies to the `d` right after it
+        if self.keymap.action_for(&key) != Some(crate::config::keys::Action::DiffRange) {
+            self.pending_count = None;
+        }
+
         // Try handling with the specialized event handlers
         if self.handle_navigation_keys(key)? {
             return Ok(());
@@ -595,6 +2190,51 @@ impl App {
         Ok(())
     }
 
+    /// Switch to the history of the file selected in the file picker
+    pub fn open_picked_file(&mut self) -> Result<()> {
+        if let AppMode::FilePicker { ref state, .. } = self.mode {
+            if let Some(selected_file) = state.get_selected_file() {
+                let file_path = selected_file.path.clone();
+                self.switch_to_history(file_path, true)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Select the file picker entry on screen row `row`. A second click on the same row soon
+    /// after opens the selected file, as Enter does.
+    pub fn click_file_picker(&mut self, row: u16) -> Result<()> {
+        const DOUBLE_CLICK_INTERVAL: std::time::Duration = std::time::Duration::from_millis(400);
+
+        let screen = Rect::new(
+            0,
+            0,
+            self.ui_state.terminal_width,
+            self.ui_state.terminal_height,
+        );
+        let AppMode::FilePicker { ref mut state, .. } = self.mode else {
+            return Ok(());
+        };
+        let Some(index) = state.file_at_row(crate::ui::file_picker::file_list_area(screen), row)
+        else {
+            self.last_picker_click = None;
+            return Ok(());
+        };
+
+        let double_click = self
+            .last_picker_click
+            .is_some_and(|(at, last_row)| last_row == row && at.elapsed() <= DOUBLE_CLICK_INTERVAL);
+        if double_click {
+            // The first click selected the file; the list may have scrolled since
+            self.last_picker_click = None;
+            return self.open_picked_file();
+        }
+
+        state.selected = index;
+        self.last_picker_click = Some((std::time::Instant::now(), row));
+        Ok(())
+    }
+
     fn handle_file_picker_key(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
         use crossterm::event::{KeyCode, KeyModifiers};
 
@@ -605,13 +2245,7 @@ impl App {
                 self.quit();
             }
             (KeyCode::Enter, KeyModifiers::NONE) => {
-                // Select file and switch to history mode
-                if let AppMode::FilePicker { ref state, .. } = self.mode {
-                    if let Some(selected_file) = state.get_selected_file() {
-                        let file_path = selected_file.path.clone();
-                        self.switch_to_history(file_path, true)?;
-                    }
-                }
+                self.open_picked_file()?;
             }
 
             // Navigation keys (arrow keys and Ctrl+N/P)
@@ -649,6 +2283,22 @@ impl App {
                     state.clear_query();
                 }
             }
+            (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+                // Ctrl+A = show/hide paths matched by the exclude globs
+                self.toggle_excluded_files()?;
+            }
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                // Ctrl+S = next sort order: name, modification time, status
+                if let AppMode::FilePicker { ref mut state, .. } = self.mode {
+                    state.cycle_sort();
+                }
+            }
+            // Ctrl+M arrives as Enter unless the terminal reports modifiers, so Alt+M also works
+            (KeyCode::Char('m'), KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                if let AppMode::FilePicker { ref mut state, .. } = self.mode {
+                    state.toggle_modified_only();
+                }
+            }
 
             // All regular characters for typing (including j, k, q, etc.)
             (KeyCode::Char(c), KeyModifiers::NONE) => {
@@ -671,6 +2321,15 @@ impl App {
 
     // Helper functions for calculating content width
     pub fn calculate_max_diff_line_width(&self) -> usize {
+        if let Some(ref blame_lines) = self.blame_lines {
+            let gutter = crate::ui::blame::gutter_width(blame_lines.len());
+            return blame_lines
+                .iter()
+                .map(|line| gutter + line.content.chars().count())
+                .max()
+                .unwrap_or(0);
+        }
+
         self.current_diff
             .lines()
             .map(|line| line.chars().count())
@@ -691,8 +2350,13 @@ impl App {
     }
 
     pub fn get_diff_line_count(&self) -> usize {
+        if let Some(ref blame_lines) = self.blame_lines {
+            return blame_lines.len();
+        }
+
         match self.effective_layout() {
-            crate::cli::LayoutMode::SideBySide => {
+            // The full file view is drawn by the unified panel in either layout
+            crate::cli::LayoutMode::SideBySide if !self.full_file_view => {
                 if let Some(ref side_by_side) = self.current_side_by_side_diff {
                     // Use the maximum of old_lines and new_lines length for side-by-side
                     side_by_side
@@ -710,29 +2374,389 @@ impl App {
         }
     }
 
-    // Delegate to UIState for scroll calculation
-    #[allow(dead_code)] // Used in tests
-    pub fn get_page_scroll_size(&self) -> usize {
-        self.ui_state.get_page_scroll_size()
+    // Delegate to UIState for scroll calculation
+    #[allow(dead_code)] // Used in tests
+    pub fn get_page_scroll_size(&self) -> usize {
+        self.ui_state.get_page_scroll_size()
+    }
+
+    // Copy functionality methods
+    pub fn copy_commit_sha(&mut self, short: bool) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+        let format = if short {
+            CopyFormat::ShortSha
+        } else {
+            CopyFormat::FullSha
+        };
+
+        match self.copier.copy_commit_info(commit, format) {
+            Ok(content) => {
+                self.copy_message = Some(format!("Copied: {}", content));
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn copy_commit_message(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+
+        match self.copier.copy_commit_info(commit, CopyFormat::Message) {
+            Ok(_) => {
+                self.copy_message = Some("Copied commit message".to_string());
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy the commit info popup's commit as Markdown
+    pub fn copy_commit_info_markdown(&mut self) {
+        let Some(markdown) = self.commit_info_popup.as_ref().map(|p| p.to_markdown()) else {
+            return;
+        };
+        match self.copier.copy_text(&markdown) {
+            Ok(()) => self.copy_message = Some("Copied commit as Markdown".to_string()),
+            Err(err) => self.error_message = Some(err),
+        }
+        self.start_message_timer();
+    }
+
+    pub fn copy_commit_author(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+
+        match self.copier.copy_commit_info(commit, CopyFormat::Author) {
+            Ok(content) => {
+                self.copy_message = Some(format!("Copied author: {}", content));
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn copy_commit_date(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+
+        match self.copier.copy_commit_info(commit, CopyFormat::Date) {
+            Ok(content) => {
+                self.copy_message = Some(format!("Copied date: {}", content));
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn copy_github_url(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+
+        match self.copier.copy_commit_info(commit, CopyFormat::GitHubUrl) {
+            Ok(content) => {
+                self.copy_message = Some(format!("Copied URL: {}", content));
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Web page of the selected commit on the `origin` remote
+    pub fn commit_web_url(&self) -> std::result::Result<String, String> {
+        let commit = self
+            .commits
+            .get(self.selected_index)
+            .ok_or_else(|| "No commit selected".to_string())?;
+        if commit.is_working_directory {
+            return Err("Working directory changes are not a commit".to_string());
+        }
+        self.copier
+            .remote()
+            .map(|remote| remote.commit_url(&commit.hash))
+            .ok_or_else(|| "No 'origin' remote to build a URL from".to_string())
+    }
+
+    /// Stage (or unstage) the file's uncommitted changes while the working directory entry is
+    /// selected, then refresh the entry so its status follows
+    pub fn stage_working_directory_file(&mut self, stage: bool) -> Result<()> {
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(());
+        };
+        if !self
+            .commits
+            .get(self.selected_index)
+            .is_some_and(|commit| commit.is_working_directory)
+        {
+            self.error_message =
+                Some("Select the working directory entry to stage or unstage".to_string());
+            self.start_message_timer();
+            return Ok(());
+        }
+
+        let result = if stage {
+            crate::git::working::stage_file(&self.repo_root, &file_path)
+        } else {
+            crate::git::working::unstage_file(&self.repo_root, &file_path)
+        };
+        let (verb, done) = if stage {
+            ("stage", "Staged")
+        } else {
+            ("unstage", "Unstaged")
+        };
+        match result.and_then(|_| self.refresh_working_directory_entry(&file_path)) {
+            Ok(()) => {
+                self.copy_message = Some(format!("{} {}", done, file_path.display()));
+            }
+            Err(e) => {
+                self.error_message =
+                    Some(format!("Failed to {} {}: {}", verb, file_path.display(), e))
+            }
+        }
+        self.start_message_timer();
+        Ok(())
+    }
+
+    /// Open the selected commit's web page with the platform's URL opener
+    pub fn open_commit_in_browser(&mut self) -> Result<()> {
+        let url = match self.commit_web_url() {
+            Ok(url) => url,
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        // Don't launch browsers from test runs
+        if !error::is_ci_environment() {
+            if let Err(e) = url_opener(&url)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .spawn()
+            {
+                self.error_message = Some(format!("Failed to open browser: {}", e));
+                self.start_message_timer();
+                return Ok(());
+            }
+        }
+
+        self.copy_message = Some(format!("Opened {}", url));
+        self.start_message_timer();
+        Ok(())
+    }
+
+    pub fn copy_full_show(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        let commit = &self.commits[self.selected_index];
+        if commit.is_working_directory {
+            self.error_message = Some("Working directory changes are not a commit".to_string());
+            self.start_message_timer();
+            return Ok(());
+        }
+
+        let result = crate::git::show::fetch_full_show(&self.repo_root, &commit.hash)
+            .map_err(|e| e.to_string())
+            .and_then(|show| self.copier.copy_text(&show));
+
+        match result {
+            Ok(_) => {
+                self.copy_message = Some("Copied full commit (git show)".to_string());
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// What a patch of the selection is made from: the marked range, the selected commit, or
+    /// the file's uncommitted changes
+    pub fn patch_source(&self) -> std::result::Result<PatchSource, String> {
+        if let Some((older_index, newer_index)) = self.current_diff_range {
+            let (Some(older), Some(newer)) =
+                (self.commits.get(older_index), self.commits.get(newer_index))
+            else {
+                return Err("No commit selected".to_string());
+            };
+            if newer.is_working_directory {
+                return Err("A range patch needs two commits".to_string());
+            }
+            return Ok(PatchSource::Range {
+                older: older.hash.clone(),
+                newer: newer.hash.clone(),
+            });
+        }
+
+        let commit = self
+            .commits
+            .get(self.selected_index)
+            .ok_or_else(|| "No commit selected".to_string())?;
+        if !commit.is_working_directory {
+            return Ok(PatchSource::Commit(commit.hash.clone()));
+        }
+
+        let file_path = self
+            .get_file_path()
+            .ok_or_else(|| "No file selected".to_string())?;
+        let status =
+            crate::git::working::check_working_directory_status(&self.repo_root, file_path)
+                .map_err(|e| e.to_string())?;
+        match status {
+            crate::git::working::WorkingDirectoryStatus::Clean => {
+                return Err("No uncommitted changes".to_string());
+            }
+            crate::git::working::WorkingDirectoryStatus::Untracked => {
+                return Err("The file is untracked; stage it to make a patch".to_string());
+            }
+            _ => {}
+        }
+        Ok(PatchSource::WorkingDirectory {
+            file_path: file_path.clone(),
+            status,
+        })
+    }
+
+    /// Copy a patch of the selection, for `git am` (or `git apply` for uncommitted changes)
+    pub fn copy_patch(&mut self) -> Result<()> {
+        let result = self
+            .patch_source()
+            .and_then(|source| self.copier.copy_patch(&self.repo_root, &source));
+
+        match result {
+            Ok(patch) => {
+                self.copy_message = Some(format!(
+                    "{} copied ({} lines)",
+                    CopyFormat::Patch,
+                    patch.lines().count()
+                ));
+                self.copy_mode = None;
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start a visual selection at the diff cursor, or drop the one in progress. The unified
+    /// layout only, where each row is one line of the diff.
+    pub fn toggle_diff_selection(&mut self) {
+        if self.get_focused_panel() != Some(FocusedPanel::Diff) {
+            return;
+        }
+        if self.ui_state.diff_selection_anchor.take().is_some() {
+            return;
+        }
+        if self.effective_layout() == LayoutMode::SideBySide && !self.full_file_view {
+            self.error_message = Some("Visual selection works in the unified layout".to_string());
+            self.start_message_timer();
+            return;
+        }
+        self.ui_state.diff_selection_anchor = Some(self.ui_state.diff_cursor_line);
+    }
+
+    pub fn cancel_diff_selection(&mut self) {
+        self.ui_state.diff_selection_anchor = None;
     }
 
-    // Copy functionality methods
-    pub fn copy_commit_sha(&mut self, short: bool) -> Result<()> {
-        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
-            return Ok(());
-        }
+    /// The text of the selected diff lines, as `how` asks, with a trailing newline
+    pub fn selected_diff_text(&self, how: SelectionCopy) -> Option<String> {
+        let (first, last) = self.ui_state.diff_selection()?;
+        let highlighted = self.cached_highlighted_diff.as_ref()?;
+        let last = last.min(highlighted.lines.len().checked_sub(1)?);
+        let lines = highlighted.lines.get(first..=last)?;
 
-        let commit = &self.commits[self.selected_index];
-        let format = if short {
-            CopyFormat::ShortSha
-        } else {
-            CopyFormat::FullSha
+        let text = lines
+            .iter()
+            .filter_map(|line| match (how, line.line_type) {
+                (SelectionCopy::Raw, _) => Some(line.content.as_str()),
+                (SelectionCopy::Added, crate::diff::DiffLineType::Addition)
+                | (
+                    SelectionCopy::Code,
+                    crate::diff::DiffLineType::Addition
+                    | crate::diff::DiffLineType::Deletion
+                    | crate::diff::DiffLineType::Context,
+                ) => Some(line.content.get(1..).unwrap_or("")),
+                _ => None,
+            })
+            .map(|line| format!("{}\n", line))
+            .collect();
+        Some(text)
+    }
+
+    /// Copy the visual selection to the clipboard and end it
+    pub fn copy_selected_diff_lines(&mut self, how: SelectionCopy) -> Result<()> {
+        let Some(text) = self.selected_diff_text(how) else {
+            return Ok(());
         };
 
-        match self.copier.copy_commit_info(commit, format) {
-            Ok(content) => {
-                self.copy_message = Some(format!("Copied: {}", content));
-                self.copy_mode = None;
+        match self.copier.copy_text(&text) {
+            Ok(_) => {
+                let what = match how {
+                    SelectionCopy::Raw => "diff lines",
+                    SelectionCopy::Code => "lines of code",
+                    SelectionCopy::Added => "added lines",
+                };
+                self.copy_message = Some(format!("Copied {} {}", text.lines().count(), what));
+                self.cancel_diff_selection();
                 self.start_message_timer();
             }
             Err(err) => {
@@ -744,16 +2768,60 @@ impl App {
         Ok(())
     }
 
-    pub fn copy_commit_message(&mut self) -> Result<()> {
-        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+    /// Write a patch of the selection into `patch_dir`, one `NNNN-subject.patch` per commit
+    pub fn save_patch(&mut self) -> Result<()> {
+        let result = self.patch_source().and_then(|source| {
+            crate::git::show::save_patch(&self.repo_root, &source, &self.patch_dir)
+                .map_err(|e| e.to_string())
+        });
+
+        match result {
+            Ok(written) => {
+                self.copy_message = Some(match written.as_slice() {
+                    [path] => format!("Saved {}", path.display()),
+                    _ => format!(
+                        "Saved {} patches to {}",
+                        written.len(),
+                        self.patch_dir.display()
+                    ),
+                });
+                self.start_message_timer();
+            }
+            Err(err) => {
+                self.error_message = Some(format!("Failed to save patch: {}", err));
+                self.start_message_timer();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copy the raw lines of the diff hunk under the cursor, including its `@@` header
+    pub fn copy_current_hunk(&mut self) -> Result<()> {
+        if self.blame_lines.is_some() {
+            self.error_message = Some("Hunks can only be copied from the diff view".to_string());
+            self.start_message_timer();
             return Ok(());
         }
 
-        let commit = &self.commits[self.selected_index];
+        let lines = crate::diff::parse_diff(&self.current_diff);
+        let Some((start, end)) = self.cursor_hunk_range(&lines) else {
+            self.error_message = Some("Cursor is not inside a hunk".to_string());
+            self.start_message_timer();
+            return Ok(());
+        };
 
-        match self.copier.copy_commit_info(commit, CopyFormat::Message) {
+        let hunk: String = self
+            .current_diff
+            .lines()
+            .skip(start)
+            .take(end - start)
+            .map(|line| format!("{}\n", line))
+            .collect();
+
+        match self.copier.copy_text(&hunk) {
             Ok(_) => {
-                self.copy_message = Some("Copied commit message".to_string());
+                self.copy_message = Some(format!("Copied hunk ({} lines)", end - start));
                 self.copy_mode = None;
                 self.start_message_timer();
             }
@@ -766,16 +2834,84 @@ impl App {
         Ok(())
     }
 
-    pub fn copy_commit_author(&mut self) -> Result<()> {
+    /// Line range in `lines` of the hunk containing the diff cursor, from its header up to
+    /// the next hunk or file header
+    fn cursor_hunk_range(&self, lines: &[crate::diff::DiffLine]) -> Option<(usize, usize)> {
+        use crate::diff::DiffLineType;
+
+        fn is_hunk_header(line: &crate::diff::DiffLine) -> bool {
+            line.line_type == DiffLineType::HunkHeader
+        }
+
+        let cursor = self.ui_state.diff_cursor_line;
+
+        // Side-by-side rows keep every hunk header in order, so count the headers the cursor
+        // has passed in whichever view is showing
+        let hunks_passed = match self.current_side_by_side_diff {
+            Some(ref side_by_side) if matches!(self.effective_layout(), LayoutMode::SideBySide) => {
+                side_by_side
+                    .old_lines
+                    .iter()
+                    .take(cursor + 1)
+                    .flatten()
+                    .filter(|line| is_hunk_header(line))
+                    .count()
+            }
+            _ => lines
+                .iter()
+                .take(cursor + 1)
+                .filter(|line| is_hunk_header(line))
+                .count(),
+        };
+
+        let (start, _) = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| is_hunk_header(line))
+            .nth(hunks_passed.checked_sub(1)?)?;
+        let end = lines
+            .iter()
+            .enumerate()
+            .skip(start + 1)
+            .find(|(_, line)| {
+                matches!(
+                    line.line_type,
+                    DiffLineType::HunkHeader | DiffLineType::Header
+                )
+            })
+            .map_or(lines.len(), |(index, _)| index);
+
+        Some((start, end))
+    }
+
+    /// Copy the path the file had at the selected commit, which differs from the current
+    /// path for commits before a rename
+    pub fn copy_historical_path(&mut self) -> Result<()> {
         if self.commits.is_empty() || self.selected_index >= self.commits.len() {
             return Ok(());
         }
+        let Some(file_path) = self.get_file_path() else {
+            return Ok(());
+        };
 
         let commit = &self.commits[self.selected_index];
+        let historical_path = self.rename_map.get(&commit.hash).unwrap_or(file_path);
+        let renamed = if historical_path != file_path {
+            " (renamed since)"
+        } else {
+            ""
+        };
+        let text = historical_path.to_string_lossy().to_string();
+        let message = format!(
+            "Copied path at {}: {}{}",
+            commit.short_hash,
+            historical_path.display(),
+            renamed
+        );
 
-        match self.copier.copy_commit_info(commit, CopyFormat::Author) {
-            Ok(content) => {
-                self.copy_message = Some(format!("Copied author: {}", content));
+        match self.copier.copy_text(&text) {
+            Ok(_) => {
+                self.copy_message = Some(message);
                 self.copy_mode = None;
                 self.start_message_timer();
             }
@@ -788,16 +2924,32 @@ impl App {
         Ok(())
     }
 
-    pub fn copy_commit_date(&mut self) -> Result<()> {
-        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
-            return Ok(());
-        }
+    /// A `path:line` reference to the file line under the diff cursor, or the first one below
+    /// it when the cursor is on a header or a deleted line. The path is the file's path at the
+    /// selected commit, which the line number belongs to.
+    pub fn file_line_reference(&self) -> Option<String> {
+        let commit = self.commits.get(self.selected_index)?;
+        let file_path = self.get_file_path()?;
+        let path = self.rename_map.get(&commit.hash).unwrap_or(file_path);
+        let line_number = self
+            .new_line_numbers_by_row()
+            .into_iter()
+            .skip(self.ui_state.diff_cursor_line)
+            .flatten()
+            .next()?;
+        Some(format!("{}:{}", path.display(), line_number))
+    }
 
-        let commit = &self.commits[self.selected_index];
+    pub fn copy_file_line_reference(&mut self) -> Result<()> {
+        let Some(reference) = self.file_line_reference() else {
+            self.error_message = Some("No file line at or below the cursor".to_string());
+            self.start_message_timer();
+            return Ok(());
+        };
 
-        match self.copier.copy_commit_info(commit, CopyFormat::Date) {
-            Ok(content) => {
-                self.copy_message = Some(format!("Copied date: {}", content));
+        match self.copier.copy_text(&reference) {
+            Ok(()) => {
+                self.copy_message = Some(format!("Copied: {}", reference));
                 self.copy_mode = None;
                 self.start_message_timer();
             }
@@ -810,16 +2962,73 @@ impl App {
         Ok(())
     }
 
-    pub fn copy_github_url(&mut self) -> Result<()> {
-        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
-            return Ok(());
+    /// Web permalink to the diff line under the cursor as the commit that introduced it
+    /// wrote it: that commit's hash, the file's path there and the line's number there
+    pub fn blame_permalink(&self) -> std::result::Result<String, String> {
+        let commit = self
+            .commits
+            .get(self.selected_index)
+            .ok_or_else(|| "No commit selected".to_string())?;
+        let file_path = self
+            .get_file_path()
+            .ok_or_else(|| "No file selected".to_string())?;
+
+        // The line as numbered in the file at the selected commit. The blame view lists the
+        // file's lines in order; in the diff it is the `+` side, or the `-` side of an inverted
+        // one.
+        let line = if let Some(ref blame_lines) = self.blame_lines {
+            let cursor = self.ui_state.diff_cursor_line;
+            (cursor < blame_lines.len()).then_some(cursor + 1)
+        } else {
+            self.cached_highlighted_diff
+                .as_ref()
+                .and_then(|diff| diff.lines.get(self.ui_state.diff_cursor_line))
+                .and_then(|line| {
+                    use crate::diff::DiffLineType::{Addition, Context, Deletion};
+                    match (line.line_type, self.invert_diff) {
+                        (Addition | Context, false) => line.new_line_num,
+                        (Deletion | Context, true) => line.old_line_num,
+                        _ => None,
+                    }
+                })
         }
+        .ok_or_else(|| "The cursor is not on a line of the file at this commit".to_string())?;
 
-        let commit = &self.commits[self.selected_index];
+        // Blame the file under the name it had at the selected commit
+        let (commit_hash, path) = if commit.is_working_directory {
+            (None, file_path)
+        } else {
+            (
+                Some(commit.hash.as_str()),
+                self.rename_map.get(&commit.hash).unwrap_or(file_path),
+            )
+        };
+        let origin = crate::git::blame::fetch_line_origin(&self.repo_root, path, commit_hash, line)
+            .map_err(|e| format!("Failed to blame the line: {}", e))?;
+        if origin.is_uncommitted() {
+            return Err("This line is not committed yet".to_string());
+        }
 
-        match self.copier.copy_commit_info(commit, CopyFormat::GitHubUrl) {
-            Ok(content) => {
-                self.copy_message = Some(format!("Copied URL: {}", content));
+        let remote = self
+            .copier
+            .remote()
+            .ok_or_else(|| "No 'origin' remote to build a URL from".to_string())?;
+        Ok(remote.blob_url(&origin.hash, &origin.path, origin.line))
+    }
+
+    pub fn copy_blame_permalink(&mut self) -> Result<()> {
+        let url = match self.blame_permalink() {
+            Ok(url) => url,
+            Err(err) => {
+                self.error_message = Some(err);
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        match self.copier.copy_text(&url) {
+            Ok(()) => {
+                self.copy_message = Some(format!("Copied permalink: {}", url));
                 self.copy_mode = None;
                 self.start_message_timer();
             }
@@ -837,30 +3046,42 @@ impl App {
             return Ok(());
         }
 
-        if let Some(file_path) = self.get_file_path() {
-            // In CI environments, skip actual clipboard operations
-            if error::is_ci_environment() {
-                self.copy_message = Some(format!("Copied Path: {}", file_path.display()));
-                self.copy_mode = None;
-                self.start_message_timer();
-                return Ok(());
+        if let Some(file_path) = self.get_file_path().cloned() {
+            match self.copier.copy_text(&file_path.to_string_lossy()) {
+                Ok(()) => {
+                    self.copy_message = Some(format!("Copied Path: {}", file_path.display()));
+                    self.copy_mode = None;
+                    self.start_message_timer();
+                }
+                Err(err) => {
+                    self.error_message = Some(err);
+                    self.start_message_timer();
+                }
             }
+        }
 
-            use arboard::Clipboard;
-            match Clipboard::new() {
-                Ok(mut clipboard) => match clipboard.set_text(file_path.to_string_lossy()) {
-                    Ok(_) => {
-                        self.copy_message = Some(format!("Copied Path: {}", file_path.display()));
-                        self.copy_mode = None;
-                        self.start_message_timer();
-                    }
-                    Err(err) => {
-                        self.error_message = Some(format!("Failed to copy to clipboard: {}", err));
-                        self.start_message_timer();
-                    }
-                },
+        Ok(())
+    }
+
+    /// Copy the file's absolute path, with symlinks resolved when the file still exists
+    pub fn copy_file_absolute_path(&mut self) -> Result<()> {
+        if self.commits.is_empty() || self.selected_index >= self.commits.len() {
+            return Ok(());
+        }
+
+        if let Some(file_path) = self.get_file_path() {
+            let joined = self.repo_root.join(file_path);
+            // The file may be gone from the working tree, deleted or renamed since
+            let absolute_path = std::fs::canonicalize(&joined).unwrap_or(joined);
+            match self.copier.copy_text(&absolute_path.to_string_lossy()) {
+                Ok(()) => {
+                    self.copy_message =
+                        Some(format!("Copied absolute path: {}", absolute_path.display()));
+                    self.copy_mode = None;
+                    self.start_message_timer();
+                }
                 Err(err) => {
-                    self.error_message = Some(format!("Failed to initialize clipboard: {}", err));
+                    self.error_message = Some(err);
                     self.start_message_timer();
                 }
             }
@@ -871,14 +3092,23 @@ impl App {
 
     pub fn start_copy_mode(&mut self) {
         self.copy_mode = Some(CopyMode::WaitingForTarget);
-        self.copy_message = Some(
-            "Copy mode: s=SHA, h=short, m=msg, a=author, d=date, u=URL, y=SHA, p=path".to_string(),
-        );
+        self.show_copy_help = false;
+        let short_target =
+            if self.get_focused_panel() == Some(FocusedPanel::Diff) && !self.show_commit_info {
+                "h=hunk"
+            } else {
+                "h=short"
+            };
+        self.copy_message = Some(format!(
+            "Copy mode: s=SHA, {}, m=msg, a=author, d=date, u=URL, b=blame permalink, p=path, A=absolute path, l=path:line, o=old path, f=show, y=SHA, ?=help",
+            short_target
+        ));
     }
 
     pub fn cancel_copy_mode(&mut self) {
         self.copy_mode = None;
         self.copy_message = None;
+        self.show_copy_help = false;
     }
 
     #[allow(dead_code)]
@@ -913,51 +3143,468 @@ impl App {
         self.load_enhanced_commit_data_by_index(selected_index)?;
 
         let enhanced_commit = self.commits[selected_index].clone();
-        self.commit_info_popup = Some(crate::ui::commit_info::CommitInfoPopup::new(
-            enhanced_commit,
-        ));
+        let mut popup = crate::ui::commit_info::CommitInfoPopup::new(enhanced_commit);
+        popup.author_display = self.ui_state.author_display;
+        popup.web_url = self.commit_web_url().ok();
+        self.commit_info_popup = Some(popup);
         self.show_commit_info = true;
 
         Ok(())
     }
 
-    pub fn hide_commit_info_popup(&mut self) {
-        self.show_commit_info = false;
-        self.commit_info_popup = None;
+    /// Cycle how authors are shown: hidden, name, email, then `name <email>`
+    pub fn cycle_author_display(&mut self) {
+        self.ui_state.author_display = self.ui_state.author_display.next();
+        if let Some(ref mut popup) = self.commit_info_popup {
+            popup.author_display = self.ui_state.author_display;
+        }
+    }
+
+    pub fn hide_commit_info_popup(&mut self) {
+        self.show_commit_info = false;
+        self.commit_info_popup = None;
+    }
+
+    pub fn scroll_commit_info_up(&mut self) {
+        if let Some(ref mut popup) = self.commit_info_popup {
+            popup.scroll_up();
+        }
+    }
+
+    pub fn scroll_commit_info_down(&mut self) {
+        if let Some(ref mut popup) = self.commit_info_popup {
+            let total_lines = popup.get_total_lines();
+            let viewport_height = 10; // Approximate viewport height
+            popup.scroll_down(total_lines, viewport_height);
+        }
+    }
+
+    // File statistics popup methods
+    pub fn show_file_stats_popup(&mut self) {
+        // The statistics cover the whole history, not just the pages loaded so far
+        if let Err(e) = self.load_all_commits() {
+            self.error_message = Some(format!("Failed to load the full history: {}", e));
+            self.start_message_timer();
+        }
+
+        let Some(file_path) = self.get_file_path() else {
+            return;
+        };
+        let file_name = file_path.display().to_string();
+
+        let mut popup = crate::ui::file_stats::FileStatsPopup::new(file_name, self.commits.len());
+        popup.stats = crate::commit::FileHistoryStats::from_commits(&self.commits);
+        self.file_stats_popup = Some(popup);
+    }
+
+    pub fn hide_file_stats_popup(&mut self) {
+        self.file_stats_popup = None;
+    }
+
+    pub fn scroll_file_stats_up(&mut self) {
+        if let Some(ref mut popup) = self.file_stats_popup {
+            popup.scroll_up();
+        }
+    }
+
+    pub fn scroll_file_stats_down(&mut self) {
+        if let Some(ref mut popup) = self.file_stats_popup {
+            let total_lines = popup.get_total_lines();
+            let viewport_height = 10; // Approximate viewport height
+            popup.scroll_down(total_lines, viewport_height);
+        }
+    }
+
+    /// Fetch missing commit stats for the open file statistics popup, a time-boxed batch per
+    /// call so the UI keeps redrawing the progress between batches
+    pub fn load_pending_file_stats(&mut self) {
+        const BATCH_BUDGET: std::time::Duration = std::time::Duration::from_millis(50);
+
+        let Some(ref mut popup) = self.file_stats_popup else {
+            return;
+        };
+        if !popup.is_loading() {
+            return;
+        }
+
+        let started = std::time::Instant::now();
+        while popup.loaded < popup.total.min(self.commits.len()) && started.elapsed() < BATCH_BUDGET
+        {
+            let commit = &mut self.commits[popup.loaded];
+            if !commit.is_working_directory && commit.stats.is_none() {
+                if let Ok(stats) =
+                    crate::git::history::fetch_commit_stats(&self.repo_root, &commit.hash)
+                {
+                    commit.stats = stats;
+                }
+            }
+            popup.loaded += 1;
+        }
+
+        if popup.loaded >= self.commits.len() {
+            popup.loaded = popup.total;
+        }
+        popup.stats = crate::commit::FileHistoryStats::from_commits(&self.commits);
+    }
+
+    /// Open the history search popup to type a pattern
+    pub fn start_history_search(&mut self) {
+        self.history_search = Some(crate::ui::history_search::HistorySearchPopup::new());
+    }
+
+    pub fn hide_history_search(&mut self) {
+        self.history_search = None;
+    }
+
+    /// Search the added and removed lines of every commit's diff for `pattern` (a
+    /// case-insensitive regex), like the diff search does for the current diff. Commits are
+    /// searched a batch at a time by `load_pending_history_search`, at most
+    /// `HISTORY_SEARCH_MAX_COMMITS` of them.
+    pub fn search_all_commit_diffs(&mut self, pattern: &str) -> Result<()> {
+        if pattern.is_empty() {
+            self.hide_history_search();
+            return Ok(());
+        }
+        if Regex::new(&format!("(?i){}", pattern)).is_err() {
+            self.error_message = Some(format!("Invalid search pattern: {}", pattern));
+            self.start_message_timer();
+            return Ok(());
+        }
+        self.load_all_commits()?;
+
+        let popup = self
+            .history_search
+            .get_or_insert_with(crate::ui::history_search::HistorySearchPopup::new);
+        popup.query = pattern.to_string();
+        popup.is_input_mode = false;
+        popup.hits.clear();
+        popup.selected = 0;
+        popup.searched = 0;
+        popup.history_len = self.commits.len();
+        popup.total = self.commits.len().min(HISTORY_SEARCH_MAX_COMMITS);
+        Ok(())
+    }
+
+    /// Search the diffs of the next commits for the open history search, a time-boxed batch
+    /// per call so the UI keeps redrawing the progress between batches
+    pub fn load_pending_history_search(&mut self) {
+        const BATCH_BUDGET: std::time::Duration = std::time::Duration::from_millis(50);
+
+        let Some(mut popup) = self.history_search.take() else {
+            return;
+        };
+        let file_path = match &self.mode {
+            AppMode::History { file_path, .. } => Some(file_path.clone()),
+            AppMode::FilePicker { .. } => None,
+        };
+        let regex = Regex::new(&format!("(?i){}", popup.query)).ok();
+        let (Some(file_path), Some(regex)) = (file_path, regex) else {
+            self.history_search = Some(popup);
+            return;
+        };
+
+        let started = std::time::Instant::now();
+        while popup.is_searching() && started.elapsed() < BATCH_BUDGET {
+            let index = popup.searched;
+            popup.searched += 1;
+            if index >= self.commits.len() {
+                continue;
+            }
+            let Ok(diff) = self.fetch_commit_diff(index, &file_path) else {
+                continue;
+            };
+            let commit = &self.commits[index];
+            for (line_index, line) in crate::diff::parse_diff(&diff).iter().enumerate() {
+                let changed = matches!(
+                    line.line_type,
+                    crate::diff::DiffLineType::Addition | crate::diff::DiffLineType::Deletion
+                );
+                // Match the code, not the leading +/- marker, so `^` anchors at its start
+                let code = line.content.get(1..).unwrap_or("");
+                if changed && regex.is_match(code) {
+                    popup
+                        .hits
+                        .push(crate::ui::history_search::HistorySearchHit {
+                            commit_index: index,
+                            commit_hash: commit.hash.clone(),
+                            short_hash: commit.short_hash.clone(),
+                            line_index,
+                            line: line.content.clone(),
+                        });
+                }
+            }
+        }
+
+        self.history_search = Some(popup);
+    }
+
+    /// Close the history search and show the selected hit: its commit, with the diff cursor
+    /// on the matching line
+    pub fn jump_to_history_search_hit(&mut self) -> Result<()> {
+        let Some(hit) = self
+            .history_search
+            .as_ref()
+            .and_then(|popup| popup.selected_hit())
+            .cloned()
+        else {
+            return Ok(());
+        };
+        self.hide_history_search();
+
+        // The commits panel may have been filtered since the search ran
+        let listed_at = |index: usize| {
+            self.commits
+                .get(index)
+                .is_some_and(|commit| commit.hash == hit.commit_hash)
+        };
+        let Some(index) = Some(hit.commit_index)
+            .filter(|&i| listed_at(i))
+            .or_else(|| {
+                self.commits
+                    .iter()
+                    .position(|commit| commit.hash == hit.commit_hash)
+            })
+        else {
+            self.error_message = Some(format!("Commit {} is not listed", hit.short_hash));
+            self.start_message_timer();
+            return Ok(());
+        };
+        self.select_commit(index)?;
+
+        let layout_mode = self.effective_layout();
+        self.focus_panel(FocusedPanel::Diff);
+        self.ui_state.diff_cursor_line = self
+            .displayed_row_for_line(hit.line_index)
+            .min(self.get_diff_line_count().saturating_sub(1));
+        self.ui_state.ensure_cursor_visible(&layout_mode);
+        Ok(())
+    }
+
+    /// Index of the listed commit `hash`, loading further pages of the history until it shows up
+    fn find_listed_commit(&mut self, hash: &str) -> Result<Option<usize>> {
+        loop {
+            if let Some(index) = self.commits.iter().position(|commit| commit.hash == hash) {
+                return Ok(Some(index));
+            }
+            if !self.load_more()? {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Listed commits the parents of commit `index` lead to: each parent itself, or when it
+    /// didn't change the file, the newest commit before it that did
+    fn listed_parents(&mut self, index: usize) -> Result<Vec<usize>> {
+        let Some(commit) = self.commits.get(index) else {
+            return Ok(Vec::new());
+        };
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(Vec::new());
+        };
+
+        // Uncommitted changes sit on top of HEAD
+        let parents = if commit.is_working_directory {
+            vec!["HEAD".to_string()]
+        } else {
+            crate::git::history::get_commit_parents(&self.repo_root, &commit.hash)?
+        };
+        // Before a rename the file went by its older name
+        let commit_file_path = self
+            .rename_map
+            .get(&commit.hash)
+            .cloned()
+            .unwrap_or(file_path);
+
+        let mut indices = Vec::new();
+        for parent in parents {
+            let listed = match self.commits.iter().position(|c| c.hash == parent) {
+                Some(index) => Some(index),
+                None => match crate::git::history::find_file_commit(
+                    &self.repo_root,
+                    &parent,
+                    &commit_file_path,
+                )? {
+                    Some(hash) => self.find_listed_commit(&hash)?,
+                    None => None,
+                },
+            };
+            if let Some(listed) = listed.filter(|listed| !indices.contains(listed)) {
+                indices.push(listed);
+            }
+        }
+        Ok(indices)
+    }
+
+    /// Step to the parent of the selected commit; a merge asks which parent
+    pub fn go_to_parent_commit(&mut self) -> Result<()> {
+        let Some(commit) = self.commits.get(self.selected_index) else {
+            return Ok(());
+        };
+        let short_hash = commit.short_hash.clone();
+
+        let parents = match self.listed_parents(self.selected_index) {
+            Ok(parents) => parents,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to find the parent: {}", e));
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        match parents.as_slice() {
+            [] => {
+                self.error_message = Some(format!(
+                    "No parent of {} in this file's history",
+                    short_hash
+                ));
+                self.start_message_timer();
+            }
+            [parent] => self.step_to_parent(*parent)?,
+            _ => {
+                let choices = parents
+                    .iter()
+                    .map(|&index| crate::ui::parent_chooser::ParentChoice {
+                        commit_index: index,
+                        short_hash: self.commits[index].short_hash.clone(),
+                        subject: self.commits[index].subject.clone(),
+                    })
+                    .collect();
+                self.parent_chooser = Some(crate::ui::parent_chooser::ParentChooser::new(choices));
+            }
+        }
+        Ok(())
+    }
+
+    /// Step to the parent picked in the chooser
+    pub fn choose_parent(&mut self, choice: Option<usize>) -> Result<()> {
+        let Some(chooser) = self.parent_chooser.take() else {
+            return Ok(());
+        };
+        let choice = choice.unwrap_or(chooser.selected);
+        if let Some(parent) = chooser.choices.get(choice) {
+            self.step_to_parent(parent.commit_index)?;
+        }
+        Ok(())
+    }
+
+    fn step_to_parent(&mut self, index: usize) -> Result<()> {
+        if let Some(commit) = self.commits.get(self.selected_index) {
+            self.parent_trail.push(commit.hash.clone());
+        }
+        self.select_commit(index)
     }
 
-    pub fn scroll_commit_info_up(&mut self) {
-        if let Some(ref mut popup) = self.commit_info_popup {
-            popup.scroll_up();
+    /// Step to a child of the selected commit: back the way `[` came, else the nearest newer
+    /// commit whose parent it is
+    pub fn go_to_child_commit(&mut self) -> Result<()> {
+        let current = self.selected_index;
+        let Some(short_hash) = self.commits.get(current).map(|c| c.short_hash.clone()) else {
+            return Ok(());
+        };
+
+        let result = self.find_child(current);
+
+        match result {
+            Ok(Some(index)) => self.select_commit(index)?,
+            Ok(None) => {
+                self.error_message =
+                    Some(format!("No child of {} in this file's history", short_hash));
+                self.start_message_timer();
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to find the child: {}", e));
+                self.start_message_timer();
+            }
         }
+        Ok(())
     }
 
-    pub fn scroll_commit_info_down(&mut self) {
-        if let Some(ref mut popup) = self.commit_info_popup {
-            let total_lines = popup.get_total_lines();
-            let viewport_height = 10; // Approximate viewport height
-            popup.scroll_down(total_lines, viewport_height);
+    fn find_child(&mut self, current: usize) -> Result<Option<usize>> {
+        while let Some(hash) = self.parent_trail.pop() {
+            if let Some(index) = self.commits.iter().position(|c| c.hash == hash) {
+                if self.listed_parents(index)?.contains(&current) {
+                    return Ok(Some(index));
+                }
+            }
         }
+
+        // Newer commits are above the selection, or below it with --reverse
+        let newer: Vec<usize> = if self.reverse {
+            (current + 1..self.commits.len()).collect()
+        } else {
+            (0..current).rev().collect()
+        };
+        for index in newer.into_iter().take(CHILD_SEARCH_MAX_COMMITS) {
+            if self.listed_parents(index)?.contains(&current) {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
     }
 
     /// Update the change cache when diff changes
     /// Call this in load_diff_for_selected_commit() and show_diff_range()
     fn update_change_cache(&mut self) {
-        let highlighted_diff = crate::diff::HighlightedDiff::new(
-            &self.current_diff,
-            self.get_file_path().map(|p| p.as_path()),
-        );
+        let highlighted_diff =
+            crate::diff::HighlightedDiff::new(&self.current_diff, self.highlight_path())
+                .with_language(self.highlight_language());
         self.current_changes = highlighted_diff.find_changes();
         self.current_change_index = None; // Reset position
 
         // Cache the highlighted diff for editor integration and consistency
         self.cached_highlighted_diff = Some(highlighted_diff);
+        self.cached_styled_lines = None;
+        self.cached_word_highlights = None;
     }
 
     /// Clear change cache when switching files or modes
     fn clear_change_cache(&mut self) {
         self.current_changes.clear();
         self.current_change_index = None;
+        self.cached_styled_lines = None;
+        self.cached_word_highlights = None;
+    }
+
+    /// Style the unified diff once per change to the diff, its view toggles or the search,
+    /// rather than re-parsing and re-highlighting it on every frame. Call before drawing.
+    pub fn prepare_styled_lines(&mut self) {
+        // Side-by-side draws the full file view with the unified panel too
+        let unified_panel = self.effective_layout() == LayoutMode::Unified || self.full_file_view;
+        if !unified_panel {
+            // Only the word pairs are worth keeping: they compare every changed line
+            if self.ui_state.word_diff && self.cached_word_highlights.is_none() {
+                let lines = crate::diff::parse_diff(&self.current_diff);
+                self.cached_word_highlights =
+                    Some(crate::diff::words::WordHighlights::from_lines(&lines));
+            }
+            return;
+        }
+        if self.blame_lines.is_some() {
+            return;
+        }
+
+        let inputs = StyledLinesInputs {
+            show_whitespace: self.show_whitespace,
+            syntax_highlighting: self.syntax_highlighting,
+            word_diff: self.ui_state.word_diff,
+            search: self
+                .diff_search_state
+                .as_ref()
+                .map(|search| (search.results.clone(), search.current_result)),
+        };
+        if self.cached_styled_lines.is_some() && self.styled_lines_inputs.as_ref() == Some(&inputs)
+        {
+            return;
+        }
+
+        let highlighted_diff =
+            crate::diff::HighlightedDiff::new(&self.current_diff, self.highlight_path())
+                .with_language(self.highlight_language())
+                .with_whitespace_markers(self.show_whitespace)
+                .with_word_diff(self.ui_state.word_diff);
+        self.cached_styled_lines =
+            Some(highlighted_diff.to_styled_lines_with_search(self.diff_search_state.as_ref()));
+        self.styled_lines_inputs = Some(inputs);
     }
 
     /// Get current change status for UI display
@@ -980,11 +3627,19 @@ impl App {
         let current_line = self.ui_state.diff_cursor_line;
 
         // Binary search for next change position
-        let next_index = match self.current_changes.binary_search(&current_line) {
+        let mut next_index = match self.current_changes.binary_search(&current_line) {
             Ok(idx) => idx + 1, // Currently on a change, go to next
             Err(idx) => idx,    // Insert position is the next change
         };
 
+        // Changes inside folded hunks can't be shown
+        let collapsed = self.collapsed_hunk_ranges();
+        while next_index < self.current_changes.len()
+            && is_hidden_line(&collapsed, self.current_changes[next_index])
+        {
+            next_index += 1;
+        }
+
         if next_index < self.current_changes.len() {
             let next_change_line = self.current_changes[next_index];
             self.ui_state.diff_cursor_line = next_change_line;
@@ -1026,6 +3681,14 @@ impl App {
             }
         };
 
+        // Changes inside folded hunks can't be shown
+        let collapsed = self.collapsed_hunk_ranges();
+        let prev_index = prev_index.and_then(|index| {
+            (0..=index)
+                .rev()
+                .find(|&i| !is_hidden_line(&collapsed, self.current_changes[i]))
+        });
+
         if let Some(index) = prev_index {
             let prev_change_line = self.current_changes[index];
             self.ui_state.diff_cursor_line = prev_change_line;
@@ -1047,17 +3710,9 @@ impl App {
             return Ok(());
         }
 
-        // Load refs if not already loaded
-        if commit.refs.is_empty() {
-            if let Ok(refs) = crate::git::history::fetch_commit_refs(&self.repo_root, &commit.hash)
-            {
-                commit.refs = refs;
-            }
-        }
-
         // Load PR info if not already loaded
         if commit.pr_info.is_none() {
-            commit.pr_info = crate::git::history::detect_pr_info(commit);
+            commit.pr_info = crate::git::history::detect_pr_info(commit, self.copier.remote());
         }
 
         // Load stats if not already loaded
@@ -1069,9 +3724,98 @@ impl App {
             }
         }
 
+        // Load the signature status if not already loaded
+        if commit.signature.is_none() {
+            if let Ok(signature) =
+                crate::git::history::fetch_commit_signature(&self.repo_root, &commit.hash)
+            {
+                commit.signature = signature;
+            }
+        }
+
+        // The guess from the subject gets the real state, title and URL when `gh` can tell
+        if let Some(number) = self.commits[index]
+            .pr_info
+            .as_ref()
+            .map(|pr_info| pr_info.number)
+        {
+            if let Some(pr_info) = self.fetch_pr_status(number) {
+                self.commits[index].pr_info = Some(pr_info);
+            }
+        }
+
+        // Load the file's line count change if not already tried
+        if self.commits[index].line_counts.is_none() {
+            self.commits[index].line_counts = Some(self.fetch_line_counts(index));
+        }
+
         Ok(())
     }
 
+    /// A pull request as GitHub reports it, asked of `gh` once per number. Without `gh`, a
+    /// GitHub remote or a signed-in `gh`, the guess from the commit subject stands.
+    fn fetch_pr_status(&mut self, number: u32) -> Option<crate::commit::PullRequestInfo> {
+        if let Some(cached) = self.pr_status_cache.get(&number) {
+            return cached.clone();
+        }
+        if self.gh_unavailable {
+            return None;
+        }
+        let remote = self
+            .copier
+            .remote()
+            .filter(|remote| remote.host == crate::git::remote::RemoteHost::GitHub)?;
+        let (owner, repo) = remote.path.split_once('/')?;
+
+        let pr_info = match crate::git::pr::fetch_pr_status(owner, repo, number) {
+            Ok(Some(pr_info)) => Some(pr_info),
+            Ok(None) => {
+                log::debug!("gh is not installed; PR states stay guessed");
+                self.gh_unavailable = true;
+                return None;
+            }
+            Err(e) => {
+                log::debug!("Failed to fetch PR #{}: {}", number, e);
+                None
+            }
+        };
+        self.pr_status_cache.insert(number, pr_info.clone());
+        pr_info
+    }
+
+    /// Count the file's lines at a commit and in its first parent, following renames
+    fn fetch_line_counts(&self, index: usize) -> Option<crate::commit::FileLineCounts> {
+        let file_path = self.get_file_path()?;
+        let commit = &self.commits[index];
+        let path_at = |hash: &str| self.rename_map.get(hash).unwrap_or(file_path);
+
+        let after_path = path_at(&commit.hash);
+        // The next older commit in the list knows the file's previous name after a rename. It
+        // is below the selection, or above it with --reverse.
+        let older_index = if self.reverse {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1)
+        };
+        let before_path = older_index
+            .and_then(|older| self.commits.get(older))
+            .map(|older| path_at(&older.hash))
+            .unwrap_or(after_path);
+
+        let after =
+            crate::git::history::fetch_file_line_count(&self.repo_root, &commit.hash, after_path)
+                .ok()?;
+        // A root commit has no parent to read, so the file is new there like any added file
+        let before = crate::git::history::fetch_file_line_count(
+            &self.repo_root,
+            &format!("{}^", commit.hash),
+            before_path,
+        )
+        .unwrap_or(None);
+
+        Some(crate::commit::FileLineCounts { before, after })
+    }
+
     // Diff search functionality
     pub fn start_diff_search(&mut self) {
         self.diff_search_state = Some(DiffSearchState {
@@ -1081,6 +3825,8 @@ impl App {
             results: Vec::new(),
             current_result: None,
             regex: None,
+            case_sensitive: false,
+            whole_word: false,
         });
     }
 
@@ -1093,8 +3839,8 @@ impl App {
                 return Ok(());
             }
 
-            // Compile regex (case-insensitive by default, true regex search)
-            let regex = match Regex::new(&format!("(?i){}", &search_state.query)) {
+            // Compile regex (case-insensitive unless toggled, true regex search)
+            let regex = match search_state.build_regex() {
                 Ok(r) => r,
                 Err(_e) => {
                     // Clear search state on invalid regex and show error in status
@@ -1120,11 +3866,13 @@ impl App {
                     | crate::diff::DiffLineType::Deletion
                     | crate::diff::DiffLineType::Context => {
                         // Search in this line's content
-                        for mat in regex.find_iter(&parsed_line.content) {
+                        let content = &parsed_line.content;
+                        let char_offset = |byte: usize| content[..byte].chars().count();
+                        for mat in regex.find_iter(content) {
                             results.push(SearchMatch {
                                 line_index: line_idx,
-                                char_start: mat.start(),
-                                char_end: mat.end(),
+                                char_start: char_offset(mat.start()),
+                                char_end: char_offset(mat.end()),
                                 content: mat.as_str().to_string(),
                             });
                         }
@@ -1182,23 +3930,474 @@ impl App {
         Ok(())
     }
 
+    /// The diff panel row showing the unified diff line at `line_index`. Side-by-side rows
+    /// drop and pad lines, so the unified index needs mapping there.
+    fn displayed_row_for_line(&self, line_index: usize) -> usize {
+        match (self.effective_layout(), &self.current_side_by_side_diff) {
+            (LayoutMode::SideBySide, Some(side_by_side)) => {
+                side_by_side.row_for_line(line_index).unwrap_or(line_index)
+            }
+            _ => line_index,
+        }
+    }
+
     pub fn scroll_to_search_result(&mut self, result_index: usize) -> Result<()> {
         if let Some(ref search_state) = self.diff_search_state {
             if let Some(search_match) = search_state.results.get(result_index) {
-                // Scroll diff view to ensure the match is visible
-                let target_line = search_match.line_index;
+                // Scroll diff view to ensure the match is visible, sideways too on long lines
                 let layout_mode = self.effective_layout();
+                let target_line = self.displayed_row_for_line(search_match.line_index);
+                let columns = (search_match.char_start, search_match.char_end);
                 self.ui_state
-                    .ensure_diff_line_visible(target_line, &layout_mode);
+                    .ensure_diff_line_visible(target_line, Some(columns), &layout_mode);
             }
         }
         Ok(())
     }
 
+    /// Flip between ignoring and matching case, then search again
+    pub fn toggle_search_case_sensitive(&mut self) -> Result<()> {
+        if let Some(ref mut search_state) = self.diff_search_state {
+            search_state.case_sensitive = !search_state.case_sensitive;
+        }
+        self.refresh_search_results()
+    }
+
+    /// Flip between matching anywhere and only whole words, then search again
+    pub fn toggle_search_whole_word(&mut self) -> Result<()> {
+        if let Some(ref mut search_state) = self.diff_search_state {
+            search_state.whole_word = !search_state.whole_word;
+        }
+        self.refresh_search_results()
+    }
+
+    /// Recompute the matches after a search option changed. Outside input mode the first
+    /// match becomes current, since the old position may no longer exist.
+    fn refresh_search_results(&mut self) -> Result<()> {
+        self.update_search_results()?;
+        let Some(ref mut search_state) = self.diff_search_state else {
+            return Ok(());
+        };
+        if search_state.is_input_mode || search_state.results.is_empty() {
+            search_state.current_result = None;
+            return Ok(());
+        }
+        search_state.current_result = Some(0);
+        self.scroll_to_search_result(0)
+    }
+
     pub fn clear_diff_search(&mut self) {
         self.diff_search_state = None;
     }
 
+    /// Start typing a pickaxe pattern, dropping any filter already applied
+    pub fn start_pickaxe(&mut self) -> Result<()> {
+        self.clear_author_filter()?;
+        self.clear_pickaxe()?;
+        self.pickaxe_state = Some(PickaxeState {
+            pattern: String::new(),
+            is_regex: false,
+            is_input_mode: true,
+            full_history: None,
+        });
+        Ok(())
+    }
+
+    /// Narrow the commits panel to the commits `git log -S`/`-G` finds for the typed pattern
+    pub fn apply_pickaxe(&mut self) -> Result<()> {
+        let Some(ref mut pickaxe) = self.pickaxe_state else {
+            return Ok(());
+        };
+        pickaxe.is_input_mode = false;
+        let (pattern, is_regex) = (pickaxe.pattern.clone(), pickaxe.is_regex);
+        let Some(file_path) = self.get_file_path().cloned() else {
+            return Ok(());
+        };
+        if pattern.is_empty() {
+            self.pickaxe_state = None;
+            return Ok(());
+        }
+
+        let matches = match crate::git::history::fetch_commit_history_pickaxe(
+            &self.repo_root,
+            &file_path,
+            &pattern,
+            is_regex,
+        ) {
+            Ok(matches) => matches,
+            Err(e) => {
+                self.pickaxe_state = None;
+                self.error_message = Some(format!("Pickaxe search failed: {}", e));
+                self.start_message_timer();
+                return Ok(());
+            }
+        };
+
+        // Keep the shown history's order and entries, which may follow first parents only
+        self.load_all_commits()?;
+        let hashes: HashSet<&str> = matches.iter().map(|c| c.hash.as_str()).collect();
+        let filtered: Vec<Commit> = self
+            .commits
+            .iter()
+            .filter(|c| hashes.contains(c.hash.as_str()))
+            .cloned()
+            .collect();
+        if filtered.is_empty() {
+            self.pickaxe_state = None;
+            self.error_message = Some(format!("No commits add or remove \"{}\"", pattern));
+            self.start_message_timer();
+            return Ok(());
+        }
+
+        let selected_hash = self.selected_commit_hash();
+        let full_history = std::mem::replace(&mut self.commits, filtered);
+        if let Some(ref mut pickaxe) = self.pickaxe_state {
+            pickaxe.full_history = Some(full_history);
+        }
+        self.reselect_after_commits_changed(selected_hash)
+    }
+
+    /// Drop the pickaxe filter, restoring the full history if one was applied
+    pub fn clear_pickaxe(&mut self) -> Result<()> {
+        let Some(pickaxe) = self.pickaxe_state.take() else {
+            return Ok(());
+        };
+        match pickaxe.full_history {
+            Some(full_history) => {
+                let selected_hash = self.selected_commit_hash();
+                self.commits = full_history;
+                self.reselect_after_commits_changed(selected_hash)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Start typing an author to filter by, dropping any filter already applied
+    pub fn start_author_filter(&mut self) -> Result<()> {
+        self.clear_pickaxe()?;
+        self.clear_author_filter()?;
+        self.author_filter = Some(AuthorFilterState {
+            query: String::new(),
+            is_input_mode: true,
+            full_history: None,
+        });
+        Ok(())
+    }
+
+    /// Narrow the commits panel to commits whose author name or email contains the typed
+    /// query, ignoring case
+    pub fn apply_author_filter(&mut self) -> Result<()> {
+        let Some(ref mut filter) = self.author_filter else {
+            return Ok(());
+        };
+        filter.is_input_mode = false;
+        let query = filter.query.clone();
+        if query.is_empty() {
+            self.author_filter = None;
+            return Ok(());
+        }
+
+        self.load_all_commits()?;
+        let needle = query.to_lowercase();
+        let filtered: Vec<Commit> = self
+            .commits
+            .iter()
+            .filter(|c| {
+                c.author_name.to_lowercase().contains(&needle)
+                    || c.author_email.to_lowercase().contains(&needle)
+            })
+            .cloned()
+            .collect();
+        if filtered.is_empty() {
+            self.author_filter = None;
+            self.error_message = Some(format!("No commits by \"{}\"", query));
+            self.start_message_timer();
+            return Ok(());
+        }
+
+        let selected_hash = self.selected_commit_hash();
+        let full_history = std::mem::replace(&mut self.commits, filtered);
+        if let Some(ref mut filter) = self.author_filter {
+            filter.full_history = Some(full_history);
+        }
+        self.reselect_after_commits_changed(selected_hash)
+    }
+
+    /// Drop the author filter, restoring the full history if one was applied
+    pub fn clear_author_filter(&mut self) -> Result<()> {
+        let Some(filter) = self.author_filter.take() else {
+            return Ok(());
+        };
+        match filter.full_history {
+            Some(full_history) => {
+                let selected_hash = self.selected_commit_hash();
+                self.commits = full_history;
+                self.reselect_after_commits_changed(selected_hash)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the commits panel is narrowed by an author filter
+    pub fn author_filter_applied(&self) -> bool {
+        self.author_filter
+            .as_ref()
+            .is_some_and(|filter| filter.full_history.is_some())
+    }
+
+    /// Whether the commits panel shows a filtered subset of the history
+    fn commits_filtered(&self) -> bool {
+        self.pickaxe_applied() || self.author_filter_applied()
+    }
+
+    /// Describes the pickaxe or author filter for the commits panel title, once it is applied,
+    /// or says the history was cut short by `--max-count`
+    pub fn commits_filter_label(&self) -> Option<String> {
+        if let Some(filter) = self.author_filter.as_ref() {
+            if filter.full_history.is_some() {
+                return Some(format!("author: {}", filter.query));
+            }
+        }
+        if let Some(pickaxe) = self.pickaxe_state.as_ref() {
+            if pickaxe.full_history.is_some() {
+                let kind = if pickaxe.is_regex {
+                    "pickaxe regex"
+                } else {
+                    "pickaxe"
+                };
+                return Some(format!("{}: \"{}\"", kind, pickaxe.pattern));
+            }
+        }
+        // Ranges reaching past the cap would otherwise look like they span the whole history
+        self.history_truncated.then(|| "truncated".to_string())
+    }
+
+    /// The size of the file's history for the status bar: "342 commits", or "showing 50 of 342
+    /// commits" when `--max-count` lists fewer
+    pub fn commit_count_label(&self) -> Option<String> {
+        let total = self.total_commits?;
+        let noun = if total == 1 { "commit" } else { "commits" };
+        match self.max_commits {
+            Some(max_commits) if self.history_truncated => Some(format!(
+                "showing {} of {} {}",
+                max_commits.min(total),
+                total,
+                noun
+            )),
+            _ => Some(format!("{} {}", total, noun)),
+        }
+    }
+
+    /// Whether the commits panel is narrowed by a pickaxe filter
+    pub fn pickaxe_applied(&self) -> bool {
+        self.pickaxe_state
+            .as_ref()
+            .is_some_and(|pickaxe| pickaxe.full_history.is_some())
+    }
+
+    fn selected_commit_hash(&self) -> Option<String> {
+        self.commits
+            .get(self.selected_index)
+            .map(|commit| commit.hash.clone())
+    }
+
+    /// Select `selected_hash` again after `commits` was swapped out, falling back to the
+    /// newest commit, and reload the diff
+    fn reselect_after_commits_changed(&mut self, selected_hash: Option<String>) -> Result<()> {
+        self.selected_index = selected_hash
+            .and_then(|hash| self.commits.iter().position(|c| c.hash == hash))
+            .unwrap_or(0);
+        self.clear_diff_range_selection();
+        if self.commits.is_empty() {
+            return Ok(());
+        }
+        self.load_diff_for_selected_commit()
+    }
+
+    /// Folded hunks of the unified diff: each `@@` header's line index with the range of body
+    /// lines it hides. Only the unified diff folds, so other views have none.
+    pub fn collapsed_hunk_ranges(&self) -> Vec<(usize, std::ops::Range<usize>)> {
+        if self.collapsed_hunks.is_empty()
+            || self.effective_layout() != LayoutMode::Unified
+            || self.full_file_view
+            || self.blame_lines.is_some()
+        {
+            return Vec::new();
+        }
+        let Some(ref highlighted) = self.cached_highlighted_diff else {
+            return Vec::new();
+        };
+
+        let mut ranges: Vec<(usize, std::ops::Range<usize>)> = self
+            .collapsed_hunks
+            .iter()
+            .filter(|&&header| {
+                highlighted
+                    .lines
+                    .get(header)
+                    .is_some_and(|line| line.line_type == crate::diff::DiffLineType::HunkHeader)
+            })
+            .map(|&header| {
+                // The body runs to the next hunk or file header
+                let end = highlighted.lines[header + 1..]
+                    .iter()
+                    .position(|line| {
+                        matches!(
+                            line.line_type,
+                            crate::diff::DiffLineType::HunkHeader
+                                | crate::diff::DiffLineType::Header
+                        )
+                    })
+                    .map_or(highlighted.lines.len(), |offset| header + 1 + offset);
+                (header, header + 1..end)
+            })
+            .collect();
+        ranges.sort_by_key(|(header, _)| *header);
+        ranges
+    }
+
+    /// Fold or unfold the hunk under the diff cursor, leaving the cursor on its header
+    pub fn toggle_hunk_under_cursor(&mut self) {
+        let header = self
+            .cached_highlighted_diff
+            .as_ref()
+            .and_then(|highlighted| {
+                let cursor = self
+                    .ui_state
+                    .diff_cursor_line
+                    .min(highlighted.lines.len().saturating_sub(1));
+                highlighted
+                    .lines
+                    .get(..=cursor)?
+                    .iter()
+                    .rposition(|line| {
+                        matches!(
+                            line.line_type,
+                            crate::diff::DiffLineType::HunkHeader
+                                | crate::diff::DiffLineType::Header
+                        )
+                    })
+                    .filter(|&index| {
+                        highlighted.lines[index].line_type == crate::diff::DiffLineType::HunkHeader
+                    })
+            });
+
+        let Some(header) = header else {
+            self.error_message = Some("No hunk under the cursor".to_string());
+            self.start_message_timer();
+            return;
+        };
+
+        if !self.collapsed_hunks.remove(&header) {
+            self.collapsed_hunks.insert(header);
+        }
+        let layout_mode = self.effective_layout();
+        self.ui_state
+            .ensure_diff_line_visible(header, None, &layout_mode);
+    }
+
+    /// Step the diff cursor off lines hidden in a folded hunk: down to the line after the
+    /// hunk, or up to its header
+    pub fn skip_collapsed_lines(&mut self, downward: bool) {
+        let collapsed = self.collapsed_hunk_ranges();
+        let cursor = self.ui_state.diff_cursor_line;
+        let Some((header, range)) = collapsed.iter().find(|(_, range)| range.contains(&cursor))
+        else {
+            return;
+        };
+
+        let target = if downward && range.end < self.get_diff_line_count() {
+            range.end
+        } else {
+            *header
+        };
+        let layout_mode = self.effective_layout();
+        self.ui_state.diff_cursor_line = target;
+        self.ui_state.ensure_cursor_visible(&layout_mode);
+    }
+
+    pub fn start_goto_line(&mut self) {
+        if self.refuse_in_directory_mode("Going to a line") {
+            return;
+        }
+        self.goto_line_input = Some(String::new());
+    }
+
+    /// The new file's line number shown on each diff cursor row, if any
+    fn new_line_numbers_by_row(&self) -> Vec<Option<usize>> {
+        if let Some(ref blame_lines) = self.blame_lines {
+            (1..=blame_lines.len()).map(Some).collect()
+        } else if self.effective_layout() == LayoutMode::SideBySide && !self.full_file_view {
+            self.current_side_by_side_diff
+                .as_ref()
+                .map(|side_by_side| {
+                    side_by_side
+                        .new_lines
+                        .iter()
+                        .map(|line| line.as_ref().and_then(|line| line.new_line_num))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            match self.cached_highlighted_diff {
+                Some(ref highlighted) => highlighted.lines.iter().map(|l| l.new_line_num).collect(),
+                None => crate::diff::parse_diff(&self.current_diff)
+                    .iter()
+                    .map(|l| l.new_line_num)
+                    .collect(),
+            }
+        }
+    }
+
+    /// Move the diff cursor to the row showing `line_number` of the new file. When that line
+    /// isn't shown (it lies outside the diff's context), the nearest shown line is used.
+    pub fn goto_line(&mut self, line_number: usize) {
+        let layout_mode = self.effective_layout();
+        let rows = self.new_line_numbers_by_row();
+
+        let nearest = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(row, num)| num.map(|num| (row, num)))
+            .min_by_key(|&(_, num)| num.abs_diff(line_number));
+
+        let Some((row, shown_line)) = nearest else {
+            self.error_message = Some("No file lines shown to jump to".to_string());
+            self.start_message_timer();
+            return;
+        };
+
+        if shown_line != line_number {
+            self.error_message = Some(format!(
+                "Line {} is not shown; jumped to line {}",
+                line_number, shown_line
+            ));
+            self.start_message_timer();
+        }
+
+        self.ui_state.diff_cursor_line = row;
+        self.ui_state.ensure_cursor_visible(&layout_mode);
+    }
+
+    /// Key for a cached diff of `revisions` (a commit hash or `older..newer`). It covers every
+    /// setting that changes the diff text, so entries can be reused across sessions.
+    fn diff_cache_key(&self, revisions: &str, file_path: &Path) -> String {
+        let encoding = match self.text_encoding {
+            None => "auto".to_string(),
+            Some(TextEncoding::Forced(encoding)) => encoding.name().to_string(),
+            Some(TextEncoding::Fallback(encoding)) => format!("fallback-{}", encoding.name()),
+        };
+        format!(
+            "{}:{}:U{}:{}{}:{}",
+            revisions,
+            file_path.display(),
+            self.context_lines,
+            if self.ignore_whitespace { "w" } else { "" },
+            if self.combined_merge_diff { "c" } else { "" },
+            encoding
+        )
+    }
+
     pub fn clear_diff_cache(&mut self) {
         self.diff_cache.clear();
         self.cached_highlighted_diff = None;
@@ -1210,61 +4409,115 @@ impl App {
         self.load_diff_for_selected_commit()
     }
     pub fn open_editor(&mut self) -> Result<()> {
-        let current_file_path = self.get_file_path().expect("a legit path in string.");
+        let Some(current_file_path) = self.get_file_path() else {
+            return Ok(());
+        };
 
-        // Get file line number from cached highlighted diff
-        let file_line_number = self
-            .cached_highlighted_diff
-            .as_ref()
-            .and_then(|diff| diff.lines.get(self.ui_state.diff_cursor_line))
+        let editor = env::var("EDITOR").unwrap_or_default();
+        let command_line =
+            editor_command_line(&editor, current_file_path, self.editor_line_number());
+        let mut cmd = Command::new(&command_line[0]);
+        cmd.args(&command_line[1..]);
+
+        cmd.status()?;
+        Ok(())
+    }
+
+    /// Line of the file under the diff cursor, for opening it in an editor. A cursor past the
+    /// end of the diff uses the last line; rows that aren't file lines (headers) give line 1.
+    pub fn editor_line_number(&self) -> usize {
+        let Some(diff) = self.cached_highlighted_diff.as_ref() else {
+            return 1;
+        };
+        let cursor = self
+            .ui_state
+            .diff_cursor_line
+            .min(diff.lines.len().saturating_sub(1));
+
+        diff.lines
+            .get(cursor)
             .and_then(|line| match line.line_type {
+                // An inverted diff has the working file on its old side
+                crate::diff::DiffLineType::Context if self.invert_diff => line.old_line_num,
                 crate::diff::DiffLineType::Addition | crate::diff::DiffLineType::Context => {
                     line.new_line_num
                 }
                 crate::diff::DiffLineType::Deletion => line.old_line_num,
                 // Headers and hunk headers don't correspond to file lines
                 crate::diff::DiffLineType::Header | crate::diff::DiffLineType::HunkHeader => None,
-            });
+            })
+            .unwrap_or(1)
+    }
+}
 
-        let editor_name = env::var("EDITOR").unwrap_or_else(|_| "vim".to_string());
-        let mut cmd = Command::new(editor_name.as_str());
+/// The command that opens `url` in the default browser
+fn url_opener(url: &str) -> Command {
+    let mut cmd = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        // `start` is a cmd builtin; its first quoted argument is the window title
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    };
+    cmd.arg(url);
+    cmd
+}
 
-        // Only add line number if we found a valid one
-        if let Some(line_num) = file_line_number {
-            // INFO: try to be inclusive
-            match editor_name.as_str() {
-                e if ["vi", "vim", "nvim", "kak", "nano"].contains(&e) => {
-                    cmd.arg(format!("+{line_num}")).arg(current_file_path);
-                }
-                e if ["hx", "helix", "subl", "sublime_text", "edit", "zed"].contains(&e) => {
-                    cmd.arg(format!(
-                        "{}:{}",
-                        current_file_path.to_string_lossy(),
-                        line_num
-                    ));
-                }
-                e if ["code", "code-insiders", "codium", "vscodium"].contains(&e) => {
-                    cmd.arg("-g").arg(format!(
-                        "{}:{}",
-                        current_file_path.to_string_lossy(),
-                        line_num
-                    ));
-                }
-                e if ["emacs", "emacsclient"].contains(&e) => {
-                    cmd.arg(format!("+{line_num}:0")).arg(current_file_path);
-                }
-                "notepad++" => {
-                    cmd.arg(current_file_path).arg(format!("-n{line_num}"));
-                }
-                _ => {
-                    cmd.arg(current_file_path);
-                }
+/// Program and arguments that open `file_path` at `line_num` in `editor` (the value of
+/// `$EDITOR`, or vim when empty). Extra words such as `code --wait` are passed through.
+pub fn editor_command_line(editor: &str, file_path: &Path, line_num: usize) -> Vec<String> {
+    let mut words = editor.split_whitespace().map(str::to_string);
+    let program = words.next().unwrap_or_else(|| "vim".to_string());
+    let mut command_line = vec![program.clone()];
+    command_line.extend(words);
+
+    // Match on the binary's name so `/usr/bin/nvim` is recognized too
+    let name = Path::new(&program)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or(program);
+    let path = file_path.to_string_lossy().into_owned();
+
+    // INFO: try to be inclusive
+    match name.as_str() {
+        e if ["vi", "vim", "nvim", "kak", "nano"].contains(&e) => {
+            command_line.extend([format!("+{line_num}"), path]);
+        }
+        e if ["hx", "helix", "subl", "sublime_text", "edit", "zed"].contains(&e) => {
+            command_line.push(format!("{path}:{line_num}"));
+        }
+        e if ["code", "code-insiders", "codium", "vscodium"].contains(&e) => {
+            // `-g` may already be part of $EDITOR
+            if !command_line
+                .iter()
+                .any(|arg| arg == "-g" || arg == "--goto")
+            {
+                command_line.push("-g".to_string());
             }
-        } else {
-            cmd.arg(current_file_path);
+            command_line.push(format!("{path}:{line_num}"));
+        }
+        e if ["emacs", "emacsclient"].contains(&e) => {
+            command_line.extend([format!("+{line_num}:0"), path]);
+        }
+        "notepad++" => {
+            command_line.extend([path, format!("-n{line_num}")]);
+        }
+        _ => {
+            command_line.push(path);
         }
-
-        cmd.status()?;
-        Ok(())
     }
+    command_line
+}
+
+/// Whether diff line `index` is inside one of the folded hunk bodies in `collapsed`
+pub fn is_hidden_line(collapsed: &[(usize, std::ops::Range<usize>)], index: usize) -> bool {
+    collapsed.iter().any(|(_, range)| range.contains(&index))
+}
+
+/// Whether `diff` is a combined diff of a merge (`git show --cc`)
+fn is_combined_diff(diff: &str) -> bool {
+    diff.starts_with("diff --cc") || diff.starts_with("diff --combined")
 }
@@ -1,8 +1,19 @@
 use lru::LruCache;
+use std::fs;
+use std::io::Write;
 use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Most diffs kept on disk per repository; the least recently written are pruned first
+pub const DISK_CACHE_MAX_ENTRIES: usize = 2000;
+
+/// Diffs on disk older than this are ignored and pruned
+pub const DISK_CACHE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
 
 pub struct DiffCache {
     cache: LruCache<String, String>,
+    disk: Option<DiskCache>,
 }
 
 impl DiffCache {
@@ -10,14 +21,47 @@ impl DiffCache {
         let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(50).unwrap());
         Self {
             cache: LruCache::new(capacity),
+            disk: None,
         }
     }
 
+    /// Back the cache with a directory so diffs survive restarts. Stale entries there are
+    /// pruned right away.
+    pub fn set_disk_dir(&mut self, dir: PathBuf) {
+        self.set_disk_dir_with_limits(dir, DISK_CACHE_MAX_ENTRIES, DISK_CACHE_TTL);
+    }
+
+    /// Like `set_disk_dir`, keeping at most `max_entries` diffs no older than `ttl`
+    pub fn set_disk_dir_with_limits(&mut self, dir: PathBuf, max_entries: usize, ttl: Duration) {
+        let mut disk = DiskCache {
+            dir,
+            max_entries,
+            ttl,
+            entries: 0,
+        };
+        disk.prune();
+        self.disk = Some(disk);
+    }
+
+    /// Look up a diff in memory, then on disk
     pub fn get(&mut self, key: &str) -> Option<&String> {
+        if !self.cache.contains(key) {
+            let value = self.disk.as_ref()?.read(key)?;
+            self.cache.put(key.to_string(), value);
+        }
         self.cache.get(key)
     }
 
+    /// Cache a diff of committed history, writing it through to disk
     pub fn put(&mut self, key: String, value: String) {
+        if let Some(ref mut disk) = self.disk {
+            disk.write(&key, &value);
+        }
+        self.cache.put(key, value);
+    }
+
+    /// Cache a diff only for this session, e.g. one involving the working directory
+    pub fn put_in_memory(&mut self, key: String, value: String) {
         self.cache.put(key, value);
     }
 
@@ -26,6 +70,21 @@ impl DiffCache {
         self.cache.contains(key)
     }
 
+    /// Drop every entry whose key contains `needle`, e.g. all diffs involving one commit
+    pub fn invalidate_containing(&mut self, needle: &str) {
+        let stale: Vec<String> = self
+            .cache
+            .iter()
+            .filter(|(key, _)| key.contains(needle))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            self.cache.pop(&key);
+        }
+    }
+
+    /// Empty the in-memory cache. Entries on disk stay, since their keys already say
+    /// everything the diff depends on.
     pub fn clear(&mut self) {
         self.cache.clear();
     }
@@ -40,3 +99,113 @@ impl DiffCache {
         self.cache.is_empty()
     }
 }
+
+/// One file per diff, named by a hash of its key. The key is stored on the first line so a
+/// hash collision reads as a miss rather than the wrong diff.
+struct DiskCache {
+    dir: PathBuf,
+    max_entries: usize,
+    ttl: Duration,
+    /// Entries on disk as of the last prune plus the ones written since
+    entries: usize,
+}
+
+impl DiskCache {
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("{:016x}.diff", fnv1a(key.as_bytes())))
+    }
+
+    fn read(&self, key: &str) -> Option<String> {
+        let path = self.entry_path(key);
+        if self.is_expired(&path) {
+            return None;
+        }
+        let contents = fs::read_to_string(path).ok()?;
+        let (stored_key, diff) = contents.split_once('\n')?;
+        (stored_key == key).then(|| diff.to_string())
+    }
+
+    /// Failures only cost a future cache miss, so they are logged and otherwise ignored.
+    /// Going over `max_entries` prunes the directory again.
+    fn write(&mut self, key: &str, value: &str) {
+        // Keys are built from paths and hashes, but a newline would break the header line
+        if key.contains('\n') {
+            return;
+        }
+        let path = self.entry_path(key);
+        let is_new = !path.exists();
+        // A temp file of its own per write, so concurrent instances never share one
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|_| tempfile::NamedTempFile::new_in(&self.dir))
+            .and_then(|mut temp| {
+                write!(temp, "{}\n{}", key, value)?;
+                temp.persist(&path).map_err(|e| e.error)
+            });
+        match result {
+            Ok(_) if is_new => {
+                self.entries += 1;
+                if self.entries > self.max_entries {
+                    self.prune();
+                }
+            }
+            Ok(_) => {}
+            Err(e) => log::debug!("Failed to write diff cache entry {}: {}", path.display(), e),
+        }
+    }
+
+    fn is_expired(&self, path: &Path) -> bool {
+        modified_age(path).is_some_and(|age| age > self.ttl)
+    }
+
+    /// Remove expired files, then the oldest entries beyond `max_entries`. Temp files of
+    /// writes in progress are left alone until they expire.
+    fn prune(&mut self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            self.entries = 0;
+            return;
+        };
+        let mut kept: Vec<(Duration, PathBuf)> = Vec::new();
+        for path in entries.flatten().map(|entry| entry.path()) {
+            match modified_age(&path) {
+                Some(age) if age <= self.ttl => {
+                    if path.extension().is_some_and(|ext| ext == "diff") {
+                        kept.push((age, path));
+                    }
+                }
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        if kept.len() > self.max_entries {
+            kept.sort_by_key(|(age, _)| *age);
+            for (_, path) in kept.drain(self.max_entries..) {
+                let _ = fs::remove_file(path);
+            }
+        }
+        self.entries = kept.len();
+    }
+}
+
+fn modified_age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    Some(
+        SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Cache directory for one repository: `<cache dir>/geschichte/<hash of the repo root>`
+pub fn disk_cache_dir(repo_root: &Path) -> Option<PathBuf> {
+    let repo_key = fnv1a(repo_root.to_string_lossy().as_bytes());
+    dirs::cache_dir().map(|dir| dir.join("geschichte").join(format!("{:016x}", repo_key)))
+}
+
+/// 64-bit FNV-1a, which unlike std's hasher is stable across Rust releases
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
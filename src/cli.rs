@@ -1,7 +1,9 @@
 use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum LayoutMode {
     /// Traditional unified diff view (two panels)
     Unified,
@@ -11,6 +13,24 @@ pub enum LayoutMode {
     Auto,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum InitialFocus {
+    /// Start with the commits panel focused
+    Commits,
+    /// Start with the diff panel focused
+    Diff,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ClipboardMode {
+    /// OSC 52 in SSH sessions, the system clipboard otherwise
+    Auto,
+    /// The system clipboard
+    Native,
+    /// OSC 52 escape sequences, handled by the terminal (works over SSH and in tmux)
+    Osc52,
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "geschichte",
@@ -19,7 +39,7 @@ pub enum LayoutMode {
     long_about = None
 )]
 pub struct Args {
-    /// Path to the file to view history for (optional - opens file picker if not provided)
+    /// Path to the file (or directory) to view history for (optional - opens file picker if not provided)
     #[arg(value_name = "FILE")]
     pub file_path: Option<PathBuf>,
 
@@ -39,10 +59,14 @@ pub struct Args {
     #[arg(long = "first-parent")]
     pub first_parent: bool,
 
-    /// Disable rename tracking
+    /// Disable rename tracking (toggle with `r`)
     #[arg(long = "no-follow")]
     pub no_follow: bool,
 
+    /// When FILE is a tracked symlink, view the history of its target instead of the link
+    #[arg(long = "follow-symlinks")]
+    pub follow_symlinks: bool,
+
     /// Enable debug logging
     #[arg(long = "debug")]
     pub debug: bool,
@@ -51,9 +75,128 @@ pub struct Args {
     #[arg(short = 's', long = "side-by-side")]
     pub side_by_side: bool,
 
-    /// Layout mode for the UI
-    #[arg(long = "layout", value_enum, default_value = "unified")]
-    pub layout: LayoutMode,
+    /// Layout mode for the UI [default: the last session's, or unified]
+    #[arg(long = "layout", value_enum)]
+    pub layout: Option<LayoutMode>,
+
+    /// With `--layout auto`, go side by side from this many terminal columns [default: 120]
+    #[arg(long = "auto-width", value_name = "COLS")]
+    pub auto_width: Option<u16>,
+
+    /// In the unified layout, show the commit list as a strip below the diff
+    #[arg(long = "commits-bottom")]
+    pub commits_bottom: bool,
+
+    /// Render spaces as `·` and tabs as `→` in diffs (toggle with `.`)
+    #[arg(long = "show-whitespace")]
+    pub show_whitespace: bool,
+
+    /// Leave whitespace-only changes out of diffs, like `git diff -w` (toggle with `W`)
+    #[arg(long = "ignore-whitespace")]
+    pub ignore_whitespace: bool,
+
+    /// Poll the file's modification time every MS milliseconds and refresh the working directory entry when it changes
+    #[arg(long = "watch-interval", value_name = "MS")]
+    pub watch_interval: Option<u64>,
+
+    /// Draw code in plain diff colors instead of syntax highlighting it (toggle with `H`)
+    #[arg(long = "no-highlight")]
+    pub no_highlight: bool,
+
+    /// Syntax highlighting theme (a bundled syntect theme or a bat theme name)
+    #[arg(long = "theme", value_name = "NAME")]
+    pub theme: Option<String>,
+
+    /// Highlight code as this language (a syntax name like Python, or an extension like py)
+    #[arg(long = "syntax", value_name = "LANG")]
+    pub syntax: Option<String>,
+
+    /// Draw without colors, as the NO_COLOR environment variable also asks
+    #[arg(long = "no-color")]
+    pub no_color: bool,
+
+    /// Built-in diff colors that `~/.config/geschichte/theme.toml` starts from
+    #[arg(long = "palette", value_name = "NAME")]
+    pub palette: Option<crate::config::theme::Palette>,
+
+    /// Use the syntax theme from the BAT_THEME environment variable when it is recognised
+    #[arg(long = "theme-from-bat")]
+    pub theme_from_bat: bool,
+
+    /// Commit date format: a git date style (relative, short, iso, ...) or a strftime format
+    #[arg(long = "date-format", value_name = "FORMAT")]
+    pub date_format: Option<String>,
+
+    /// Decode file contents in diffs with this encoding (e.g. latin1, shift_jis)
+    #[arg(long = "encoding", value_name = "NAME")]
+    pub encoding: Option<String>,
+
+    /// List the oldest commits first
+    #[arg(long = "reverse")]
+    pub reverse: bool,
+
+    /// List at most N commits, the newest ones (`git log --max-count`)
+    #[arg(short = 'n', long = "max-count", value_name = "N")]
+    pub max_count: Option<usize>,
+
+    /// List only commits more recent than DATE (`git log --since`, e.g. "2 weeks ago")
+    #[arg(long = "since", value_name = "DATE")]
+    pub since: Option<String>,
+
+    /// List only commits older than DATE (`git log --until`, e.g. 2024-01-31)
+    #[arg(long = "until", value_name = "DATE")]
+    pub until: Option<String>,
+
+    /// Don't read or write the on-disk diff cache
+    #[arg(long = "no-cache")]
+    pub no_cache: bool,
+
+    /// Show each commit's insertions and deletions in the commits panel (costs an extra git call)
+    #[arg(long = "stats")]
+    pub stats: bool,
+
+    /// Directory the save-patch key (Alt+p) writes `NNNN-subject.patch` files to [default: current directory]
+    #[arg(long = "patch-dir", value_name = "DIR")]
+    pub patch_dir: Option<PathBuf>,
+
+    /// Print the diff on screen to stdout when quitting, as `Q` does
+    #[arg(long = "print-on-exit")]
+    pub print_on_exit: bool,
+
+    /// Don't restore or save the splits, layout and last file of the previous session
+    #[arg(long = "no-session")]
+    pub no_session: bool,
+
+    /// Without FILE, reopen the file viewed last in this repository instead of the file picker
+    #[arg(long = "last", conflicts_with = "no_session")]
+    pub last: bool,
+
+    /// Open FILE's diff between two refs (branches, tags or commits) instead of its history
+    #[arg(
+        long = "compare",
+        num_args = 2,
+        value_names = ["REF_A", "REF_B"],
+        requires = "file_path",
+        conflicts_with = "json"
+    )]
+    pub compare: Option<Vec<String>>,
+
+    /// Print the file's commits as a JSON array instead of opening the TUI; dates follow
+    /// --date-format
+    #[arg(long = "json", requires = "file_path")]
+    pub json: bool,
+
+    /// Include each commit's changed files, insertions and deletions in the --json output
+    #[arg(long = "with-stats", requires = "json")]
+    pub with_stats: bool,
+
+    /// Where copied text goes: auto, native or osc52
+    #[arg(long = "clipboard", value_enum, default_value = "auto")]
+    pub clipboard: ClipboardMode,
+
+    /// Panel that has focus when the history view opens
+    #[arg(long = "focus", value_enum, default_value = "commits")]
+    pub focus: InitialFocus,
 }
 
 impl Args {
@@ -62,6 +205,52 @@ impl Args {
             return Err("Context lines must be between 0 and 100".to_string());
         }
 
+        if self.auto_width == Some(0) {
+            return Err("Auto width must be greater than 0".to_string());
+        }
+
+        if self.watch_interval == Some(0) {
+            return Err("Watch interval must be greater than 0".to_string());
+        }
+
+        if let Some(ref theme) = self.theme {
+            if crate::diff::syntax::find_theme(theme).is_none() {
+                return Err(format!(
+                    "Unknown theme: {} (available: {})",
+                    theme,
+                    crate::diff::syntax::available_themes().join(", ")
+                ));
+            }
+        }
+
+        if let Some(ref language) = self.syntax {
+            if crate::diff::syntax::find_syntax(language).is_none() {
+                return Err(format!("Unknown syntax: {}", language));
+            }
+        }
+
+        if let Some(ref format) = self.date_format {
+            if crate::git::history::date_style(format).is_none() {
+                return Err(format!(
+                    "Unknown date format: {} (use a git date style like relative or short, or a strftime format like %d.%m.%Y)",
+                    format
+                ));
+            }
+        }
+
+        // git judges the dates themselves; an empty one would just be ignored
+        for (flag, date) in [("--since", &self.since), ("--until", &self.until)] {
+            if date.as_deref().is_some_and(|date| date.trim().is_empty()) {
+                return Err(format!("{} needs a date", flag));
+            }
+        }
+
+        if let Some(ref label) = self.encoding {
+            if crate::git::encoding::encoding_for_label(label).is_none() {
+                return Err(format!("Unknown encoding: {}", label));
+            }
+        }
+
         Ok(())
     }
 
@@ -81,7 +270,12 @@ impl Args {
         if self.side_by_side {
             LayoutMode::SideBySide
         } else {
-            self.layout
+            self.layout.unwrap_or(LayoutMode::Unified)
         }
     }
+
+    /// Whether the layout was chosen on the command line, overriding the saved session's
+    pub fn layout_given(&self) -> bool {
+        self.side_by_side || self.layout.is_some()
+    }
 }
@@ -1,22 +1,40 @@
+use serde::Serialize;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone)]
+/// A commit of the file's history. Serializes to the fields printed by `--json`.
+#[derive(Debug, Clone, Serialize)]
 pub struct Commit {
     pub hash: String,
     pub short_hash: String,
+    #[serde(skip)]
     pub date: String,
+    #[serde(rename = "author")]
     pub author_name: String,
     pub author_email: String,
+    #[serde(skip)]
     pub committer_name: String,
+    #[serde(skip)]
     pub committer_email: String,
     pub author_date: String,
+    #[serde(skip)]
     pub committer_date: String,
     pub subject: String,
+    #[serde(skip)]
     pub body: String,
+    #[serde(skip)]
     pub refs: Vec<String>,
+    #[serde(skip)]
     pub pr_info: Option<PullRequestInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stats: Option<CommitStats>,
+    /// `Some(None)` once the line counts failed to load, so they aren't asked for again
+    #[serde(skip)]
+    pub line_counts: Option<Option<FileLineCounts>>,
+    #[serde(skip)]
+    pub signature: Option<SignatureInfo>,
+    #[serde(skip)]
     pub _rename_info: Option<RenameInfo>,
+    #[serde(skip)]
     pub is_working_directory: bool,
 }
 
@@ -25,12 +43,10 @@ pub struct PullRequestInfo {
     pub number: u32,
     pub title: String,
     pub url: String,
-    #[allow(dead_code)]
     pub status: PRStatus,
 }
 
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum PRStatus {
     Open,
     Closed,
@@ -38,13 +54,212 @@ pub enum PRStatus {
     Unknown,
 }
 
-#[derive(Debug, Clone)]
+impl PRStatus {
+    /// How the commit info popup names the state; nothing when it isn't known
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            PRStatus::Open => Some("open"),
+            PRStatus::Closed => Some("closed"),
+            PRStatus::Merged => Some("merged"),
+            PRStatus::Unknown => None,
+        }
+    }
+}
+
+/// How git judged a commit's signature, from the `%G?` placeholder
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SignatureStatus {
+    /// `G`: a good, trusted signature
+    Good,
+    /// `B`: the signature doesn't match the commit
+    Bad,
+    /// `U`: a good signature from a key of unknown validity
+    UnknownValidity,
+    /// `X`/`Y`: a good signature that has expired, or made by an expired key
+    Expired,
+    /// `R`: a good signature made by a revoked key
+    RevokedKey,
+    /// `E`: the signature can't be checked, e.g. the key is missing
+    CannotCheck,
+    /// `N`: no signature
+    Unsigned,
+}
+
+impl SignatureStatus {
+    pub fn from_code(code: char) -> Option<Self> {
+        Some(match code {
+            'G' => SignatureStatus::Good,
+            'B' => SignatureStatus::Bad,
+            'U' => SignatureStatus::UnknownValidity,
+            'X' | 'Y' => SignatureStatus::Expired,
+            'R' => SignatureStatus::RevokedKey,
+            'E' => SignatureStatus::CannotCheck,
+            'N' => SignatureStatus::Unsigned,
+            _ => return None,
+        })
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignatureStatus::Good => "good signature",
+            SignatureStatus::Bad => "BAD signature",
+            SignatureStatus::UnknownValidity => "good signature, unknown validity",
+            SignatureStatus::Expired => "expired signature",
+            SignatureStatus::RevokedKey => "signed with a revoked key",
+            SignatureStatus::CannotCheck => "signature can't be checked",
+            SignatureStatus::Unsigned => "unsigned",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureInfo {
+    pub status: SignatureStatus,
+    /// The signer (`%GS`); empty when unsigned or unknown
+    pub signer: String,
+}
+
+impl SignatureInfo {
+    /// Parse `git log --format="%G? %GS"` output, e.g. "G Jane Doe <jane@example.com>"
+    pub fn parse(output: &str) -> Option<Self> {
+        let output = output.trim();
+        let status = SignatureStatus::from_code(output.chars().next()?)?;
+        let signer = output[1..].trim().to_string();
+        Some(Self { status, signer })
+    }
+
+    /// E.g. "good signature by Jane Doe <jane@example.com>"
+    pub fn describe(&self) -> String {
+        if self.signer.is_empty() {
+            self.status.label().to_string()
+        } else {
+            format!("{} by {}", self.status.label(), self.signer)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
 pub struct CommitStats {
     pub files_changed: u32,
     pub insertions: u32,
     pub deletions: u32,
 }
 
+/// Line count of the viewed file before (in the parent) and after the commit.
+/// `None` means the file does not exist on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileLineCounts {
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+}
+
+impl FileLineCounts {
+    /// Format as e.g. "248 → 263 lines (+15)"
+    pub fn describe(&self) -> String {
+        match (self.before, self.after) {
+            (Some(before), Some(after)) => format!(
+                "{} → {} lines ({:+})",
+                before,
+                after,
+                after as i64 - before as i64
+            ),
+            (None, Some(after)) => format!("new file, {} lines", after),
+            (Some(before), None) => format!("{} lines, file removed", before),
+            (None, None) => "file not present".to_string(),
+        }
+    }
+}
+
+/// Summary of a file across all loaded commits, built from the per-commit stats
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileHistoryStats {
+    pub total_commits: usize,
+    pub insertions: u64,
+    pub deletions: u64,
+    /// Authors ordered by number of commits, most active first
+    pub top_contributors: Vec<ContributorStats>,
+    pub first_change: Option<String>,
+    pub last_change: Option<String>,
+    pub largest_commit: Option<LargestCommit>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContributorStats {
+    pub name: String,
+    pub commits: usize,
+    pub lines_changed: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargestCommit {
+    pub short_hash: String,
+    pub subject: String,
+    pub insertions: u32,
+    pub deletions: u32,
+}
+
+impl FileHistoryStats {
+    /// Number of contributors kept in `top_contributors`
+    pub const TOP_CONTRIBUTORS: usize = 5;
+
+    /// Aggregate the commits (newest first, as loaded); the working directory entry is skipped
+    /// and commits whose stats have not been loaded only count towards commits and dates
+    pub fn from_commits(commits: &[Commit]) -> Self {
+        let mut stats = Self::default();
+        let mut contributors: Vec<ContributorStats> = Vec::new();
+        let mut largest_lines = 0;
+
+        for commit in commits.iter().filter(|c| !c.is_working_directory) {
+            stats.total_commits += 1;
+            if stats.last_change.is_none() {
+                stats.last_change = Some(commit.date.clone());
+            }
+            stats.first_change = Some(commit.date.clone());
+
+            let lines_changed = commit
+                .stats
+                .as_ref()
+                .map_or(0, |s| s.insertions as u64 + s.deletions as u64);
+            if let Some(ref commit_stats) = commit.stats {
+                stats.insertions += commit_stats.insertions as u64;
+                stats.deletions += commit_stats.deletions as u64;
+
+                if lines_changed > largest_lines {
+                    largest_lines = lines_changed;
+                    stats.largest_commit = Some(LargestCommit {
+                        short_hash: commit.short_hash.clone(),
+                        subject: commit.subject.clone(),
+                        insertions: commit_stats.insertions,
+                        deletions: commit_stats.deletions,
+                    });
+                }
+            }
+
+            match contributors
+                .iter_mut()
+                .find(|c| c.name == commit.author_name)
+            {
+                Some(contributor) => {
+                    contributor.commits += 1;
+                    contributor.lines_changed += lines_changed;
+                }
+                None => contributors.push(ContributorStats {
+                    name: commit.author_name.clone(),
+                    commits: 1,
+                    lines_changed,
+                }),
+            }
+        }
+
+        // Stable sort keeps the most recent contributor first on ties
+        contributors.sort_by_key(|c| std::cmp::Reverse(c.commits));
+        contributors.truncate(Self::TOP_CONTRIBUTORS);
+        stats.top_contributors = contributors;
+
+        stats
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RenameInfo {
     pub _old_path: PathBuf,
@@ -78,6 +293,8 @@ impl Commit {
             refs: Vec::new(),
             pr_info: None,
             stats: None,
+            line_counts: None,
+            signature: None,
             _rename_info: None,
             is_working_directory: false,
         }
@@ -111,6 +328,8 @@ impl Commit {
             refs: Vec::new(),
             pr_info: None,
             stats: None,
+            line_counts: None,
+            signature: None,
             _rename_info: None,
             is_working_directory: false,
         }
@@ -132,6 +351,8 @@ impl Commit {
             refs: Vec::new(),
             pr_info: None,
             stats: None,
+            line_counts: None,
+            signature: None,
             _rename_info: None,
             is_working_directory: true,
         }
@@ -0,0 +1,478 @@
+use crate::error::{GeschichteError, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Something a key can be bound to in the history view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    SwitchFocus,
+    JumpToTop,
+    JumpToBottom,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    ScrollLineUp,
+    ScrollLineDown,
+    ScrollLeft,
+    ScrollRight,
+    NextChange,
+    PreviousChange,
+    NextFile,
+    PreviousFile,
+    Quit,
+    QuitAndPrint,
+    ShrinkSplit,
+    GrowSplit,
+    FilePicker,
+    FilePickerInDirectory,
+    Search,
+    SearchHistory,
+    GotoLine,
+    GotoCommit,
+    SetMark,
+    JumpToMark,
+    Pickaxe,
+    AuthorFilter,
+    FollowRenames,
+    DiffRange,
+    ParentCommit,
+    ChildCommit,
+    Help,
+    CommitInfo,
+    Select,
+    FileStats,
+    CompareClipboard,
+    InvertDiff,
+    ToggleBlame,
+    CycleAuthor,
+    WordDiff,
+    ShowWhitespace,
+    WordWrap,
+    ToggleHighlight,
+    IgnoreWhitespace,
+    CombinedDiff,
+    MoreContext,
+    LessContext,
+    ToggleLayout,
+    FullFile,
+    HeatMap,
+    WholeCommit,
+    ToggleHunk,
+    VisualSelect,
+    StageFile,
+    UnstageFile,
+    OpenEditor,
+    OpenInBrowser,
+    SavePatch,
+    CopyMode,
+    CopyShortSha,
+    CopySha,
+}
+
+impl Action {
+    /// Every action, in the order conflicts are reported
+    pub const ALL: &'static [Action] = &[
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::SwitchFocus,
+        Action::JumpToTop,
+        Action::JumpToBottom,
+        Action::PageUp,
+        Action::PageDown,
+        Action::HalfPageUp,
+        Action::HalfPageDown,
+        Action::ScrollLineUp,
+        Action::ScrollLineDown,
+        Action::ScrollLeft,
+        Action::ScrollRight,
+        Action::NextChange,
+        Action::PreviousChange,
+        Action::NextFile,
+        Action::PreviousFile,
+        Action::Quit,
+        Action::QuitAndPrint,
+        Action::ShrinkSplit,
+        Action::GrowSplit,
+        Action::FilePicker,
+        Action::FilePickerInDirectory,
+        Action::Search,
+        Action::SearchHistory,
+        Action::GotoLine,
+        Action::GotoCommit,
+        Action::SetMark,
+        Action::JumpToMark,
+        Action::Pickaxe,
+        Action::AuthorFilter,
+        Action::FollowRenames,
+        Action::DiffRange,
+        Action::ParentCommit,
+        Action::ChildCommit,
+        Action::Help,
+        Action::CommitInfo,
+        Action::Select,
+        Action::FileStats,
+        Action::CompareClipboard,
+        Action::InvertDiff,
+        Action::ToggleBlame,
+        Action::CycleAuthor,
+        Action::WordDiff,
+        Action::ShowWhitespace,
+        Action::WordWrap,
+        Action::ToggleHighlight,
+        Action::IgnoreWhitespace,
+        Action::CombinedDiff,
+        Action::MoreContext,
+        Action::LessContext,
+        Action::ToggleLayout,
+        Action::FullFile,
+        Action::HeatMap,
+        Action::WholeCommit,
+        Action::ToggleHunk,
+        Action::VisualSelect,
+        Action::StageFile,
+        Action::UnstageFile,
+        Action::OpenEditor,
+        Action::OpenInBrowser,
+        Action::SavePatch,
+        Action::CopyMode,
+        Action::CopyShortSha,
+        Action::CopySha,
+    ];
+
+    /// Name used for the action in `keys.toml`
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::SwitchFocus => "switch_focus",
+            Action::JumpToTop => "jump_to_top",
+            Action::JumpToBottom => "jump_to_bottom",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::HalfPageDown => "half_page_down",
+            Action::ScrollLineUp => "scroll_line_up",
+            Action::ScrollLineDown => "scroll_line_down",
+            Action::ScrollLeft => "scroll_left",
+            Action::ScrollRight => "scroll_right",
+            Action::NextChange => "next_change",
+            Action::PreviousChange => "previous_change",
+            Action::NextFile => "next_file",
+            Action::PreviousFile => "previous_file",
+            Action::Quit => "quit",
+            Action::QuitAndPrint => "quit_and_print",
+            Action::ShrinkSplit => "shrink_split",
+            Action::GrowSplit => "grow_split",
+            Action::FilePicker => "file_picker",
+            Action::FilePickerInDirectory => "file_picker_in_directory",
+            Action::Search => "search",
+            Action::SearchHistory => "search_history",
+            Action::GotoLine => "goto_line",
+            Action::GotoCommit => "goto_commit",
+            Action::SetMark => "set_mark",
+            Action::JumpToMark => "jump_to_mark",
+            Action::Pickaxe => "pickaxe",
+            Action::AuthorFilter => "author_filter",
+            Action::FollowRenames => "follow_renames",
+            Action::DiffRange => "diff_range",
+            Action::ParentCommit => "parent_commit",
+            Action::ChildCommit => "child_commit",
+            Action::Help => "help",
+            Action::CommitInfo => "commit_info",
+            Action::Select => "select",
+            Action::FileStats => "file_stats",
+            Action::CompareClipboard => "compare_clipboard",
+            Action::InvertDiff => "invert_diff",
+            Action::ToggleBlame => "toggle_blame",
+            Action::CycleAuthor => "cycle_author",
+            Action::WordDiff => "word_diff",
+            Action::ShowWhitespace => "show_whitespace",
+            Action::WordWrap => "word_wrap",
+            Action::ToggleHighlight => "toggle_highlight",
+            Action::IgnoreWhitespace => "ignore_whitespace",
+            Action::CombinedDiff => "combined_diff",
+            Action::MoreContext => "more_context",
+            Action::LessContext => "less_context",
+            Action::ToggleLayout => "toggle_layout",
+            Action::FullFile => "full_file",
+            Action::HeatMap => "heat_map",
+            Action::WholeCommit => "whole_commit",
+            Action::ToggleHunk => "toggle_hunk",
+            Action::VisualSelect => "visual_select",
+            Action::StageFile => "stage_file",
+            Action::UnstageFile => "unstage_file",
+            Action::OpenEditor => "open_editor",
+            Action::OpenInBrowser => "open_in_browser",
+            Action::SavePatch => "save_patch",
+            Action::CopyMode => "copy_mode",
+            Action::CopyShortSha => "copy_short_sha",
+            Action::CopySha => "copy_sha",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|action| action.name() == name)
+    }
+
+    /// Keys bound to the action when `keys.toml` doesn't mention it
+    pub fn default_keys(self) -> &'static [&'static str] {
+        match self {
+            Action::MoveUp => &["up", "k"],
+            Action::MoveDown => &["down", "j"],
+            Action::SwitchFocus => &["tab"],
+            Action::JumpToTop => &["g"],
+            Action::JumpToBottom => &["G"],
+            Action::PageUp => &["pageup", "ctrl+b"],
+            Action::PageDown => &["pagedown", "ctrl+f"],
+            Action::HalfPageUp => &["ctrl+u"],
+            Action::HalfPageDown => &["ctrl+d"],
+            Action::ScrollLineUp => &["ctrl+y"],
+            Action::ScrollLineDown => &["ctrl+e"],
+            Action::ScrollLeft => &["a"],
+            Action::ScrollRight => &["s"],
+            Action::NextChange => &["n"],
+            Action::PreviousChange => &["N"],
+            Action::NextFile => &["}"],
+            Action::PreviousFile => &["{"],
+            Action::Quit => &["q"],
+            Action::QuitAndPrint => &["Q"],
+            Action::ShrinkSplit => &["h"],
+            Action::GrowSplit => &["l"],
+            Action::FilePicker => &["f"],
+            Action::FilePickerInDirectory => &["F"],
+            Action::Search => &["/"],
+            Action::SearchHistory => &["ctrl+g", "alt+/"],
+            Action::GotoLine => &[":"],
+            Action::GotoCommit => &["#"],
+            Action::SetMark => &["m"],
+            Action::JumpToMark => &["'"],
+            Action::Pickaxe => &["S"],
+            Action::AuthorFilter => &["A"],
+            Action::FollowRenames => &["r"],
+            Action::DiffRange => &["d"],
+            Action::ParentCommit => &["["],
+            Action::ChildCommit => &["]"],
+            Action::Help => &["?"],
+            Action::CommitInfo => &["i"],
+            Action::Select => &["enter"],
+            Action::FileStats => &["T"],
+            Action::CompareClipboard => &["C"],
+            Action::InvertDiff => &["I"],
+            Action::ToggleBlame => &["b"],
+            Action::CycleAuthor => &["@"],
+            Action::WordDiff => &["w"],
+            Action::ShowWhitespace => &["."],
+            Action::WordWrap => &["z"],
+            Action::ToggleHighlight => &["H"],
+            Action::IgnoreWhitespace => &["W"],
+            Action::CombinedDiff => &["M"],
+            Action::MoreContext => &["+"],
+            Action::LessContext => &["-"],
+            Action::ToggleLayout => &["v"],
+            Action::FullFile => &["o"],
+            Action::HeatMap => &["R"],
+            Action::WholeCommit => &["X"],
+            Action::ToggleHunk => &["space"],
+            Action::VisualSelect => &["V"],
+            Action::StageFile => &["alt+s"],
+            Action::UnstageFile => &["alt+u"],
+            Action::OpenEditor => &["e"],
+            Action::OpenInBrowser => &["O"],
+            Action::SavePatch => &["alt+p"],
+            Action::CopyMode => &["y"],
+            Action::CopyShortSha => &["Y"],
+            Action::CopySha => &["c"],
+        }
+    }
+}
+
+/// A single key with its modifiers, parsed from specs like `"j"`, `"ctrl+n"` or `"pagedown"`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeySpec {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeySpec {
+    pub fn parse(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| {
+            GeschichteError::ConfigError(format!("invalid key \"{}\": {}", spec, reason))
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec.trim();
+        // A lone "+" is the plus key, not an empty modifier list
+        while let Some((modifier, key)) = rest.split_once('+').filter(|(_, key)| !key.is_empty()) {
+            modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid(&format!("unknown modifier \"{}\"", modifier))),
+            };
+            rest = key;
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "tab" => KeyCode::Tab,
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=12) => KeyCode::F(n),
+                    _ => return Err(invalid("unknown key name")),
+                },
+            },
+        };
+
+        Ok(Self::normalized(code, modifiers))
+    }
+
+    /// Whether the key event is this key
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        *self == Self::normalized(key.code, key.modifiers)
+    }
+
+    /// Terminals differ in whether they report Shift alongside the character it produced, so
+    /// characters carry their case alone: `"shift+t"` and `"T"` are the same key
+    fn normalized(code: KeyCode, mut modifiers: KeyModifiers) -> Self {
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                KeyCode::Char(c.to_uppercase().next().unwrap_or(c))
+            }
+            other => other,
+        };
+        Self { code, modifiers }
+    }
+}
+
+/// One key or a list of keys for an action
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Keys for each action, read from `~/.config/geschichte/keys.toml`. Actions the file leaves
+/// out keep their default keys.
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: Vec<(KeySpec, Action)>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .iter()
+            .flat_map(|&action| {
+                action.default_keys().iter().map(move |spec| {
+                    let key = KeySpec::parse(spec).expect("default key specs are valid");
+                    (key, action)
+                })
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl KeyMap {
+    /// Location of the key bindings file, if a config directory is known
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("geschichte").join("keys.toml"))
+    }
+
+    /// Load the user's key bindings, or the defaults when there is no bindings file
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::from_file(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&contents).map_err(|e| match e {
+            GeschichteError::ConfigError(message) => {
+                GeschichteError::ConfigError(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })
+    }
+
+    /// Apply the bindings in `contents` over the defaults. Unknown actions, malformed keys and
+    /// keys bound to two actions are errors.
+    pub fn from_toml_str(contents: &str) -> Result<Self> {
+        let overrides: HashMap<String, KeyBinding> =
+            toml::from_str(contents).map_err(|e| GeschichteError::ConfigError(e.to_string()))?;
+
+        let mut specs_by_action: HashMap<Action, Vec<String>> = HashMap::new();
+        for (name, binding) in overrides {
+            let action = Action::from_name(&name).ok_or_else(|| {
+                GeschichteError::ConfigError(format!("unknown action \"{}\" in key bindings", name))
+            })?;
+            let specs = match binding {
+                KeyBinding::One(spec) => vec![spec],
+                KeyBinding::Many(specs) => specs,
+            };
+            specs_by_action.insert(action, specs);
+        }
+
+        let mut bindings: Vec<(KeySpec, Action)> = Vec::new();
+        for &action in Action::ALL {
+            let specs = specs_by_action.remove(&action).unwrap_or_else(|| {
+                action
+                    .default_keys()
+                    .iter()
+                    .map(|spec| spec.to_string())
+                    .collect()
+            });
+            for spec in specs {
+                let key = KeySpec::parse(&spec)?;
+                match bindings.iter().find(|(bound, _)| *bound == key) {
+                    Some((_, existing)) if *existing == action => {}
+                    Some((_, existing)) => {
+                        return Err(GeschichteError::ConfigError(format!(
+                            "key \"{}\" is bound to both \"{}\" and \"{}\"; rebind one of them",
+                            spec,
+                            existing.name(),
+                            action.name()
+                        )));
+                    }
+                    None => bindings.push((key, action)),
+                }
+            }
+        }
+
+        Ok(Self { bindings })
+    }
+
+    /// The action bound to a key event, if any
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(spec, _)| spec.matches(key))
+            .map(|&(_, action)| action)
+    }
+}
@@ -0,0 +1,119 @@
+pub mod keys;
+pub mod theme;
+
+use crate::error::{GeschichteError, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// What Enter does in the commits panel
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnterAction {
+    /// Show the commit info popup
+    #[default]
+    Info,
+    /// Move focus to the diff panel
+    FocusDiff,
+    /// Open the file in $EDITOR
+    OpenEditor,
+    /// Do nothing
+    None,
+}
+
+/// User configuration, read from `~/.config/geschichte/config.toml` and overridden
+/// field-by-field by a `.geschichte.toml` at the repository root
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub enter_action: EnterAction,
+    /// Globs for paths hidden from the file picker, added to the repo's `.geschichteignore`
+    pub exclude: Vec<String>,
+    /// Commit date format, as `--date-format` takes it
+    pub date_format: Option<String>,
+    /// Languages forced for file names and extensions (`"*.inc" = "php"`), added to by
+    /// each layer
+    pub syntax_overrides: HashMap<String, String>,
+    /// Terminal width from which the auto layout goes side by side, as `--auto-width` takes it
+    pub auto_width: Option<u16>,
+}
+
+/// Settings from a single config file; fields it leaves out fall through to earlier layers
+#[derive(Debug, Default, Deserialize)]
+struct ConfigLayer {
+    enter_action: Option<EnterAction>,
+    exclude: Option<Vec<String>>,
+    date_format: Option<String>,
+    syntax_overrides: Option<HashMap<String, String>>,
+    auto_width: Option<u16>,
+}
+
+impl Config {
+    /// Name of the per-repository config file
+    pub const REPO_FILE: &'static str = ".geschichte.toml";
+
+    /// Location of the user config file, if a config directory is known
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("geschichte").join("config.toml"))
+    }
+
+    /// Load the user config and then the repository's, falling back to defaults for
+    /// anything neither file sets
+    pub fn load(repo_root: &Path) -> Result<Self> {
+        Self::load_layers(Self::default_path(), repo_root)
+    }
+
+    /// Load `user_path` (if given) and then the repository's config
+    pub fn load_layers(user_path: Option<PathBuf>, repo_root: &Path) -> Result<Self> {
+        let mut config = Self::default();
+        let layers = [user_path, Some(repo_root.join(Self::REPO_FILE))];
+
+        for path in layers.into_iter().flatten() {
+            if path.exists() {
+                config.merge_from(&path)?;
+            }
+        }
+
+        Ok(config)
+    }
+
+    pub fn from_toml_str(contents: &str) -> Result<Self> {
+        let mut config = Self::default();
+        config.merge_toml_str(contents)?;
+        Ok(config)
+    }
+
+    /// Override the fields set in `path`, keeping the current values for the rest
+    fn merge_from(&mut self, path: &Path) -> Result<()> {
+        let contents = std::fs::read_to_string(path)?;
+        let layer: ConfigLayer = toml::from_str(&contents)
+            .map_err(|e| GeschichteError::ConfigError(format!("{}: {}", path.display(), e)))?;
+        self.apply(layer);
+        Ok(())
+    }
+
+    /// Override the fields set in `contents`, keeping the current values for the rest
+    pub fn merge_toml_str(&mut self, contents: &str) -> Result<()> {
+        let layer: ConfigLayer =
+            toml::from_str(contents).map_err(|e| GeschichteError::ConfigError(e.to_string()))?;
+        self.apply(layer);
+        Ok(())
+    }
+
+    fn apply(&mut self, layer: ConfigLayer) {
+        if let Some(enter_action) = layer.enter_action {
+            self.enter_action = enter_action;
+        }
+        if let Some(exclude) = layer.exclude {
+            self.exclude = exclude;
+        }
+        if let Some(date_format) = layer.date_format {
+            self.date_format = Some(date_format);
+        }
+        if let Some(syntax_overrides) = layer.syntax_overrides {
+            self.syntax_overrides.extend(syntax_overrides);
+        }
+        if let Some(auto_width) = layer.auto_width {
+            self.auto_width = Some(auto_width);
+        }
+    }
+}
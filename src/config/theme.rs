@@ -0,0 +1,242 @@
+use crate::diff::DiffLineType;
+use crate::error::{GeschichteError, Result};
+use clap::ValueEnum;
+use once_cell::sync::OnceCell;
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The built-in color palettes a theme file starts from
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    /// Light green and red line backgrounds
+    #[default]
+    Default,
+    /// Saturated backgrounds and bold match colors for low-contrast terminals
+    HighContrast,
+}
+
+/// Colors of the diff: line backgrounds, word emphasis, the cursor and search matches
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// Added code drawn without syntax highlighting, and the `+` marker
+    pub addition_fg: Color,
+    /// Removed code drawn without syntax highlighting, and the `-` marker
+    pub deletion_fg: Color,
+    /// Unchanged code drawn without syntax highlighting
+    pub context_fg: Color,
+    pub addition_bg: Color,
+    pub deletion_bg: Color,
+    /// Changed words within an added line (word diff)
+    pub addition_emphasis_bg: Color,
+    /// Changed words within a removed line (word diff)
+    pub deletion_emphasis_bg: Color,
+    pub cursor_bg: Color,
+    /// Lines of a visual selection (`V`)
+    pub selection_bg: Color,
+    /// Search matches on unchanged lines and in popups
+    pub match_fg: Color,
+    pub match_bg: Color,
+    /// Search matches on added and removed lines, which have their own background
+    pub match_on_change_fg: Color,
+    pub match_on_addition_bg: Color,
+    pub match_on_deletion_bg: Color,
+    /// The search match the cursor is on
+    pub current_match_fg: Color,
+    pub current_match_bg: Color,
+    pub current_match_on_change_fg: Color,
+    pub current_match_on_addition_bg: Color,
+    pub current_match_on_deletion_bg: Color,
+    /// Draw without any colors (`NO_COLOR`, `--no-color`); the cursor, search matches and
+    /// changed words are told apart by bold, underline and reverse video instead
+    pub no_color: bool,
+}
+
+impl Theme {
+    pub const DEFAULT: Theme = Theme {
+        addition_fg: Color::Green,
+        deletion_fg: Color::Red,
+        context_fg: Color::Gray,
+        addition_bg: Color::Rgb(180, 235, 180),
+        deletion_bg: Color::Rgb(235, 180, 180),
+        addition_emphasis_bg: crate::diff::words::ADDITION_EMPHASIS_BG,
+        deletion_emphasis_bg: crate::diff::words::DELETION_EMPHASIS_BG,
+        cursor_bg: Color::Rgb(60, 80, 120),
+        selection_bg: Color::Rgb(90, 90, 130),
+        match_fg: Color::White,
+        match_bg: Color::Rgb(70, 130, 180),
+        match_on_change_fg: Color::Rgb(200, 200, 200),
+        match_on_addition_bg: Color::Rgb(25, 25, 112),
+        match_on_deletion_bg: Color::Rgb(0, 100, 0),
+        current_match_fg: Color::Black,
+        current_match_bg: Color::Rgb(255, 215, 0),
+        current_match_on_change_fg: Color::Rgb(255, 255, 255),
+        current_match_on_addition_bg: Color::Rgb(75, 0, 130),
+        current_match_on_deletion_bg: Color::Rgb(0, 0, 139),
+        no_color: false,
+    };
+
+    pub const HIGH_CONTRAST: Theme = Theme {
+        addition_fg: Color::LightGreen,
+        deletion_fg: Color::LightRed,
+        context_fg: Color::White,
+        addition_bg: Color::Rgb(0, 70, 0),
+        deletion_bg: Color::Rgb(100, 0, 0),
+        addition_emphasis_bg: Color::Rgb(0, 140, 0),
+        deletion_emphasis_bg: Color::Rgb(180, 0, 0),
+        cursor_bg: Color::Rgb(0, 0, 175),
+        selection_bg: Color::Rgb(95, 0, 135),
+        match_fg: Color::Black,
+        match_bg: Color::Cyan,
+        match_on_change_fg: Color::Black,
+        match_on_addition_bg: Color::Cyan,
+        match_on_deletion_bg: Color::Cyan,
+        current_match_fg: Color::Black,
+        current_match_bg: Color::Yellow,
+        current_match_on_change_fg: Color::Black,
+        current_match_on_addition_bg: Color::Yellow,
+        current_match_on_deletion_bg: Color::Yellow,
+        no_color: false,
+    };
+
+    pub fn builtin(palette: Palette) -> Self {
+        match palette {
+            Palette::Default => Self::DEFAULT,
+            Palette::HighContrast => Self::HIGH_CONTRAST,
+        }
+    }
+
+    /// This theme for a monochrome terminal
+    pub fn without_color(self) -> Self {
+        Self {
+            no_color: true,
+            ..self
+        }
+    }
+
+    /// Style of a search match. Matches on added and removed lines get their own colors so
+    /// they stand out against the line background.
+    pub fn search_match_style(&self, is_current_match: bool, line_type: DiffLineType) -> Style {
+        let (fg, bg) = match (is_current_match, line_type) {
+            (true, DiffLineType::Addition) => (
+                self.current_match_on_change_fg,
+                self.current_match_on_addition_bg,
+            ),
+            (true, DiffLineType::Deletion) => (
+                self.current_match_on_change_fg,
+                self.current_match_on_deletion_bg,
+            ),
+            (true, _) => (self.current_match_fg, self.current_match_bg),
+            (false, DiffLineType::Addition) => (self.match_on_change_fg, self.match_on_addition_bg),
+            (false, DiffLineType::Deletion) => (self.match_on_change_fg, self.match_on_deletion_bg),
+            (false, _) => (self.match_fg, self.match_bg),
+        };
+        let style = Style::default().fg(fg).bg(bg).add_modifier(Modifier::BOLD);
+        match (self.no_color, is_current_match) {
+            (false, _) => style,
+            (true, false) => style.add_modifier(Modifier::UNDERLINED),
+            (true, true) => style.add_modifier(Modifier::UNDERLINED | Modifier::REVERSED),
+        }
+    }
+
+    /// Location of the user's theme file, if a config directory is known
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("geschichte").join("theme.toml"))
+    }
+
+    /// Load the user's theme file over its palette, or just the palette when there is no
+    /// file. `palette` (from `--palette`) wins over the one the file names.
+    pub fn load(palette: Option<Palette>) -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::from_file(&path, palette),
+            _ => Ok(Self::builtin(palette.unwrap_or_default())),
+        }
+    }
+
+    fn from_file(path: &Path, palette: Option<Palette>) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&contents, palette).map_err(|e| match e {
+            GeschichteError::ConfigError(message) => {
+                GeschichteError::ConfigError(format!("{}: {}", path.display(), message))
+            }
+            other => other,
+        })
+    }
+
+    /// Apply the colors in `contents` over a built-in palette: `palette` if given, otherwise
+    /// the file's `palette` key, otherwise the default. Colors are names (`blue`,
+    /// `lightred`), `#rrggbb` or a 256-color index; unknown fields are errors.
+    pub fn from_toml_str(contents: &str, palette: Option<Palette>) -> Result<Self> {
+        let mut table: toml::Table =
+            toml::from_str(contents).map_err(|e| GeschichteError::ConfigError(e.to_string()))?;
+
+        let file_palette = match table.remove("palette") {
+            Some(value) => Some(
+                Palette::deserialize(value)
+                    .map_err(|e| GeschichteError::ConfigError(format!("palette: {}", e)))?,
+            ),
+            None => None,
+        };
+        let mut theme = Self::builtin(palette.or(file_palette).unwrap_or_default());
+
+        for (name, value) in table {
+            let spec = value.as_str().ok_or_else(|| {
+                GeschichteError::ConfigError(format!("{}: expected a color string", name))
+            })?;
+            let color = Color::from_str(spec).map_err(|_| {
+                GeschichteError::ConfigError(format!("{}: invalid color \"{}\"", name, spec))
+            })?;
+            *theme.color_mut(&name).ok_or_else(|| {
+                GeschichteError::ConfigError(format!("unknown theme color \"{}\"", name))
+            })? = color;
+        }
+
+        Ok(theme)
+    }
+
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "addition_fg" => &mut self.addition_fg,
+            "deletion_fg" => &mut self.deletion_fg,
+            "context_fg" => &mut self.context_fg,
+            "addition_bg" => &mut self.addition_bg,
+            "deletion_bg" => &mut self.deletion_bg,
+            "addition_emphasis_bg" => &mut self.addition_emphasis_bg,
+            "deletion_emphasis_bg" => &mut self.deletion_emphasis_bg,
+            "cursor_bg" => &mut self.cursor_bg,
+            "selection_bg" => &mut self.selection_bg,
+            "match_fg" => &mut self.match_fg,
+            "match_bg" => &mut self.match_bg,
+            "match_on_change_fg" => &mut self.match_on_change_fg,
+            "match_on_addition_bg" => &mut self.match_on_addition_bg,
+            "match_on_deletion_bg" => &mut self.match_on_deletion_bg,
+            "current_match_fg" => &mut self.current_match_fg,
+            "current_match_bg" => &mut self.current_match_bg,
+            "current_match_on_change_fg" => &mut self.current_match_on_change_fg,
+            "current_match_on_addition_bg" => &mut self.current_match_on_addition_bg,
+            "current_match_on_deletion_bg" => &mut self.current_match_on_deletion_bg,
+            _ => return None,
+        })
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Theme chosen at startup; drawing uses `Theme::DEFAULT` until one is set
+static SELECTED_THEME: OnceCell<Theme> = OnceCell::new();
+
+/// Set the theme used for all drawing. Only the first call has an effect.
+pub fn set_theme(theme: Theme) {
+    let _ = SELECTED_THEME.set(theme);
+}
+
+/// The theme to draw with
+pub fn current() -> &'static Theme {
+    SELECTED_THEME.get().unwrap_or(&Theme::DEFAULT)
+}
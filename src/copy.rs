@@ -1,7 +1,14 @@
+use crate::cli::ClipboardMode;
 use crate::commit::Commit;
 use crate::error;
+use crate::git::remote::RemoteRepo;
+use crate::git::show::PatchSource;
 use arboard::Clipboard;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use std::fmt;
+use std::io::Write;
+use std::path::Path;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CopyMode {
@@ -18,6 +25,8 @@ pub enum CopyFormat {
     Author,
     Date,
     GitHubUrl,
+    /// `git format-patch` output (or `git diff` for uncommitted changes)
+    Patch,
 }
 
 impl fmt::Display for CopyFormat {
@@ -30,23 +39,118 @@ impl fmt::Display for CopyFormat {
             CopyFormat::Author => write!(f, "Author"),
             CopyFormat::Date => write!(f, "Date"),
             CopyFormat::GitHubUrl => write!(f, "GitHub URL"),
+            CopyFormat::Patch => write!(f, "Patch"),
         }
     }
 }
 
+/// Where copied text goes
+enum ClipboardBackend {
+    /// The system clipboard, opened on first use
+    Native(Option<Clipboard>),
+    /// OSC 52 escape sequences, which the terminal puts on its own clipboard (even over SSH)
+    Osc52,
+}
+
+/// Terminal multiplexers need OSC 52 wrapped to pass it on to the outer terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    pub fn detect() -> Self {
+        if std::env::var_os("TMUX").is_some() {
+            Multiplexer::Tmux
+        } else if std::env::var("TERM").is_ok_and(|term| term.starts_with("screen")) {
+            Multiplexer::Screen
+        } else {
+            Multiplexer::None
+        }
+    }
+}
+
+/// Largest base64 payload sent over OSC 52; terminals drop or truncate longer ones
+pub const OSC52_MAX_PAYLOAD: usize = 100_000;
+
+/// Base64 bytes per write to the terminal
+const OSC52_CHUNK: usize = 4096;
+
+/// GNU screen passes on at most this much of a device control string at once
+const SCREEN_CHUNK: usize = 76;
+
+/// The writes that put `text` on the terminal's clipboard with OSC 52. The base64 payload is
+/// split into chunks so no single write (or, under screen, control string) is too long.
+pub fn osc52_writes(text: &str, multiplexer: Multiplexer) -> Result<Vec<String>, String> {
+    let payload = STANDARD.encode(text);
+    if payload.len() > OSC52_MAX_PAYLOAD {
+        return Err(format!(
+            "Too large to copy through the terminal ({} KiB)",
+            text.len() / 1024
+        ));
+    }
+
+    let writes = match multiplexer {
+        Multiplexer::None | Multiplexer::Tmux => {
+            let (start, end) = if multiplexer == Multiplexer::Tmux {
+                ("\x1bPtmux;\x1b\x1b]52;c;", "\x07\x1b\\")
+            } else {
+                ("\x1b]52;c;", "\x07")
+            };
+            std::iter::once(start.to_string())
+                .chain(chunks(&payload, OSC52_CHUNK))
+                .chain(std::iter::once(end.to_string()))
+                .collect()
+        }
+        // Screen joins consecutive control strings, so each carries a piece of the sequence
+        Multiplexer::Screen => chunks(&format!("\x1b]52;c;{}\x07", payload), SCREEN_CHUNK)
+            .map(|piece| format!("\x1bP{}\x1b\\", piece))
+            .collect(),
+    };
+    Ok(writes)
+}
+
+/// Split ASCII `text` into strings of at most `size` bytes
+fn chunks(text: &str, size: usize) -> impl Iterator<Item = String> + '_ {
+    text.as_bytes()
+        .chunks(size)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+}
+
 pub struct CommitCopier {
-    clipboard: Option<Clipboard>,
+    clipboard: ClipboardBackend,
+    /// Where commit URLs point; without one there is no URL to copy
+    remote: Option<RemoteRepo>,
 }
 
 impl CommitCopier {
     pub fn new() -> Self {
-        let clipboard = if error::is_ci_environment() {
-            // In CI environments, create a dummy clipboard that always succeeds
-            None
-        } else {
-            Clipboard::new().ok()
+        Self::with_mode(ClipboardMode::Auto)
+    }
+
+    /// A copier using the clipboard `mode` picks. `Auto` uses OSC 52 in SSH sessions, where
+    /// the system clipboard is on the wrong machine (or missing).
+    pub fn with_mode(mode: ClipboardMode) -> Self {
+        let clipboard = match mode {
+            ClipboardMode::Native => ClipboardBackend::Native(None),
+            ClipboardMode::Osc52 => ClipboardBackend::Osc52,
+            ClipboardMode::Auto if std::env::var_os("SSH_TTY").is_some() => ClipboardBackend::Osc52,
+            ClipboardMode::Auto => ClipboardBackend::Native(None),
         };
-        Self { clipboard }
+        Self {
+            clipboard,
+            remote: None,
+        }
+    }
+
+    pub fn set_remote(&mut self, remote: Option<RemoteRepo>) {
+        self.remote = remote;
+    }
+
+    pub fn remote(&self) -> Option<&RemoteRepo> {
+        self.remote.as_ref()
     }
 
     pub fn copy_commit_info(
@@ -67,32 +171,89 @@ impl CommitCopier {
             }
             CopyFormat::Author => commit.author(),
             CopyFormat::Date => commit.author_date.clone(),
-            CopyFormat::GitHubUrl => {
-                // This would need actual remote detection in real implementation
-                if let Some(ref pr_info) = commit.pr_info {
-                    pr_info.url.clone()
-                } else {
-                    format!("https://github.com/repo/commit/{}", commit.hash)
+            CopyFormat::GitHubUrl => match (&commit.pr_info, &self.remote) {
+                (Some(pr_info), _) if !pr_info.url.is_empty() => pr_info.url.clone(),
+                (_, Some(remote)) => remote.commit_url(&commit.hash),
+                (_, None) => {
+                    return Err("No 'origin' remote to build a URL from".to_string());
                 }
-            }
+            },
+            // Git builds patches, in the repository
+            CopyFormat::Patch => return Err("Copy patches with copy_patch".to_string()),
         };
 
+        self.copy_text(&content)?;
+        Ok(content)
+    }
+
+    /// Copy the patch of `source`, built by git in `repo_root`
+    pub fn copy_patch(&mut self, repo_root: &Path, source: &PatchSource) -> Result<String, String> {
+        let patch = crate::git::show::fetch_patch(repo_root, source).map_err(|e| e.to_string())?;
+        if patch.is_empty() {
+            return Err("Nothing to put in a patch".to_string());
+        }
+        self.copy_text(&patch)?;
+        Ok(patch)
+    }
+
+    /// Copy arbitrary text to the clipboard
+    pub fn copy_text(&mut self, content: &str) -> Result<(), String> {
         if error::is_ci_environment() {
             // In CI environments, simulate successful clipboard operation
-            Ok(content)
-        } else if let Some(ref mut clipboard) = self.clipboard {
-            clipboard
-                .set_text(&content)
-                .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
-            Ok(content)
-        } else {
-            Err("Clipboard not available".to_string())
+            return Ok(());
+        }
+
+        match self.clipboard {
+            ClipboardBackend::Native(_) => self
+                .native_clipboard()?
+                .set_text(content)
+                .map_err(|e| format!("Failed to copy to clipboard: {}", e)),
+            ClipboardBackend::Osc52 => {
+                let writes = osc52_writes(content, Multiplexer::detect())?;
+                let mut stdout = std::io::stdout().lock();
+                writes
+                    .iter()
+                    .try_for_each(|write| {
+                        stdout.write_all(write.as_bytes())?;
+                        stdout.flush()
+                    })
+                    .map_err(|e| format!("Failed to copy through the terminal: {}", e))
+            }
+        }
+    }
+
+    /// Read text from the clipboard
+    pub fn paste_text(&mut self) -> Result<String, String> {
+        match self.clipboard {
+            ClipboardBackend::Native(_) => self
+                .native_clipboard()?
+                .get_text()
+                .map_err(|e| format!("Failed to read clipboard: {}", e)),
+            ClipboardBackend::Osc52 => {
+                Err("Reading the clipboard needs --clipboard native".to_string())
+            }
         }
     }
 
     #[allow(dead_code)]
-    pub fn is_available(&self) -> bool {
-        self.clipboard.is_some()
+    pub fn is_available(&mut self) -> bool {
+        match self.clipboard {
+            ClipboardBackend::Native(_) => self.native_clipboard().is_ok(),
+            ClipboardBackend::Osc52 => true,
+        }
+    }
+
+    /// The system clipboard, opened the first time it's needed
+    fn native_clipboard(&mut self) -> Result<&mut Clipboard, String> {
+        let ClipboardBackend::Native(ref mut clipboard) = self.clipboard else {
+            return Err("Clipboard not available".to_string());
+        };
+        if clipboard.is_none() {
+            *clipboard = Some(Clipboard::new().map_err(|_| "Clipboard not available".to_string())?);
+        }
+        clipboard
+            .as_mut()
+            .ok_or_else(|| "Clipboard not available".to_string())
     }
 }
 
@@ -1,10 +1,12 @@
 pub mod parser;
 pub mod side_by_side;
 pub mod syntax;
+pub mod words;
 
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DiffLineType {
@@ -26,15 +28,55 @@ pub struct DiffLine {
 /// Enhanced diff with syntax highlighting
 pub struct HighlightedDiff {
     pub lines: Vec<DiffLine>,
-    file_path: Option<std::path::PathBuf>,
+    file_path: Option<PathBuf>,
+    /// Where each file of a multi-file diff starts, so its lines highlight by their own path
+    file_sections: Vec<(usize, PathBuf)>,
+    /// Language forced over the one the path suggests (`--syntax` or a shebang)
+    language: Option<String>,
+    hunk_counts: HashMap<usize, (usize, usize)>,
+    show_whitespace: bool,
+    word_highlights: Option<words::WordHighlights>,
 }
 
 impl HighlightedDiff {
     pub fn new(diff_text: &str, file_path: Option<&Path>) -> Self {
         let lines = parse_diff(diff_text);
         let file_path = file_path.map(|p| p.to_path_buf());
+        let hunk_counts = count_hunk_changes(&lines);
+        let file_sections = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.line_type == DiffLineType::Header)
+            .filter_map(|(i, line)| diff_header_path(&line.content).map(|path| (i, path)))
+            .collect();
+
+        Self {
+            lines,
+            file_path,
+            file_sections,
+            language: None,
+            hunk_counts,
+            show_whitespace: false,
+            word_highlights: None,
+        }
+    }
+
+    /// Render spaces and tabs in code content as visible glyphs
+    pub fn with_whitespace_markers(mut self, show_whitespace: bool) -> Self {
+        self.show_whitespace = show_whitespace;
+        self
+    }
+
+    /// Highlight code as `language` rather than by its path
+    pub fn with_language(mut self, language: Option<&str>) -> Self {
+        self.language = language.map(str::to_string);
+        self
+    }
 
-        Self { lines, file_path }
+    /// Emphasize the changed words between similar deleted and added lines
+    pub fn with_word_diff(mut self, word_diff: bool) -> Self {
+        self.word_highlights = word_diff.then(|| words::WordHighlights::from_lines(&self.lines));
+        self
     }
 
     pub fn to_styled_lines_with_search(
@@ -61,6 +103,23 @@ impl HighlightedDiff {
             .collect()
     }
 
+    /// The path to highlight line `index` as: the file whose section it falls in, or the
+    /// diff's own path. `None` when highlighting is off.
+    fn highlight_path_for_line(&self, index: usize) -> Option<&Path> {
+        let file_path = self.file_path.as_deref()?;
+        Some(self.section_path(index).unwrap_or(file_path))
+    }
+
+    /// Path from the `diff --git` header of the file section line `index` falls in, if any
+    fn section_path(&self, index: usize) -> Option<&Path> {
+        let section = self
+            .file_sections
+            .partition_point(|(start, _)| *start <= index);
+        section
+            .checked_sub(1)
+            .map(|n| self.file_sections[n].1.as_path())
+    }
+
     fn style_diff_line(
         &self,
         line: &DiffLine,
@@ -82,10 +141,20 @@ impl HighlightedDiff {
             }
             DiffLineType::HunkHeader => {
                 // Hunk headers in cyan - no line numbers
-                Line::from(vec![
+                let mut spans = vec![
                     Span::styled("         ".to_string(), Style::default()), // Space for line numbers (4+1+4+1=10 chars)
                     Span::styled(line.content.clone(), Style::default().fg(Color::Cyan)),
-                ])
+                ];
+
+                // Summarize the size of the hunk with a (+N -M) badge
+                if let Some((additions, deletions)) = self.hunk_counts.get(&line_index) {
+                    spans.push(Span::styled(
+                        format!(" (+{} -{})", additions, deletions),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+
+                Line::from(spans)
             }
             DiffLineType::Addition | DiffLineType::Deletion | DiffLineType::Context => {
                 // Apply syntax highlighting to code content
@@ -107,10 +176,11 @@ impl HighlightedDiff {
                 ));
 
                 // Add the diff marker with appropriate color
+                let theme = crate::config::theme::current();
                 let (marker, marker_color, bg_color) = match line.line_type {
-                    DiffLineType::Addition => ("+", Color::Green, Some(Color::Rgb(180, 235, 180))), // Medium light green
-                    DiffLineType::Deletion => ("-", Color::Red, Some(Color::Rgb(235, 180, 180))), // Medium light red
-                    DiffLineType::Context => (" ", Color::Gray, None),
+                    DiffLineType::Addition => ("+", theme.addition_fg, Some(theme.addition_bg)),
+                    DiffLineType::Deletion => ("-", theme.deletion_fg, Some(theme.deletion_bg)),
+                    DiffLineType::Context => (" ", theme.context_fg, None),
                     _ => unreachable!(),
                 };
 
@@ -129,8 +199,12 @@ impl HighlightedDiff {
                 };
 
                 // Apply syntax highlighting if available
-                if let Some(ref file_path) = self.file_path {
-                    let highlighted_spans = self::syntax::highlight_line(&code_content, file_path);
+                if let Some(file_path) = self.highlight_path_for_line(line_index) {
+                    let highlighted_spans = self::syntax::highlight_line(
+                        &code_content,
+                        file_path,
+                        self.language.as_deref(),
+                    );
 
                     // Apply background color for additions/deletions
                     for span in highlighted_spans {
@@ -143,9 +217,9 @@ impl HighlightedDiff {
                 } else {
                     // No syntax highlighting, just use basic colors
                     let style = Style::default().fg(match line.line_type {
-                        DiffLineType::Addition => Color::Green,
-                        DiffLineType::Deletion => Color::Red,
-                        DiffLineType::Context => Color::Gray,
+                        DiffLineType::Addition => theme.addition_fg,
+                        DiffLineType::Deletion => theme.deletion_fg,
+                        DiffLineType::Context => theme.context_fg,
                         _ => Color::White,
                     });
 
@@ -159,6 +233,13 @@ impl HighlightedDiff {
 
                 let mut styled_line = Line::from(spans);
 
+                if let Some(ranges) = self.word_highlights.as_ref().and_then(|highlights| {
+                    highlights.ranges_for(self.section_path(line_index), line)
+                }) {
+                    // Skip the line number and diff marker spans
+                    styled_line = words::emphasize_ranges(styled_line, 2, ranges, line.line_type);
+                }
+
                 // Apply search highlighting if active - only for code lines
                 if let Some(search_state) = search_state {
                     styled_line = apply_search_highlighting_to_code_content(
@@ -169,12 +250,52 @@ impl HighlightedDiff {
                     );
                 }
 
+                if self.show_whitespace {
+                    // Skip the line number and diff marker spans
+                    styled_line = render_whitespace(styled_line, 2);
+                }
+
                 styled_line
             }
         }
     }
 }
 
+/// Replace spaces with `·` and tabs with `→` (dimmed) in the spans from `code_start` on.
+/// Each glyph is a single character, so horizontal scroll math is unaffected.
+pub fn render_whitespace(line: Line<'static>, code_start: usize) -> Line<'static> {
+    let mut spans = Vec::with_capacity(line.spans.len());
+
+    for (index, span) in line.spans.into_iter().enumerate() {
+        if index < code_start || !span.content.contains([' ', '\t']) {
+            spans.push(span);
+            continue;
+        }
+
+        let whitespace_style = span.style.fg(Color::DarkGray).add_modifier(Modifier::DIM);
+        let mut text = String::new();
+        for ch in span.content.chars() {
+            let glyph = match ch {
+                ' ' => '·',
+                '\t' => '→',
+                _ => {
+                    text.push(ch);
+                    continue;
+                }
+            };
+            if !text.is_empty() {
+                spans.push(Span::styled(std::mem::take(&mut text), span.style));
+            }
+            spans.push(Span::styled(glyph.to_string(), whitespace_style));
+        }
+        if !text.is_empty() {
+            spans.push(Span::styled(text, span.style));
+        }
+    }
+
+    Line::from(spans)
+}
+
 /// Apply search highlighting specifically to code content, respecting the line structure
 fn apply_search_highlighting_to_code_content(
     styled_line: Line<'static>,
@@ -217,8 +338,9 @@ fn apply_search_highlighting_to_code_content(
             .map(|s| s.content.as_ref())
             .collect();
 
-        let span_start_in_code = code_content_before.len();
-        let span_end_in_code = span_start_in_code + span_content.len();
+        // Match positions count chars, so spans are measured in chars too
+        let span_start_in_code = code_content_before.chars().count();
+        let span_end_in_code = span_start_in_code + span_content.chars().count();
 
         // Find matches that overlap with this span's content
         let overlapping_matches: Vec<&crate::app::SearchMatch> = line_matches
@@ -263,6 +385,7 @@ fn apply_highlighting_to_span(
     result_spans: &mut Vec<Span<'static>>,
 ) {
     let span_content = span.content.to_string();
+    let span_len = span_content.chars().count();
     let mut processed_chars = 0;
 
     for &search_match in overlapping_matches {
@@ -273,10 +396,10 @@ fn apply_highlighting_to_span(
         // Calculate positions within this specific span
         let match_start_in_span = match_start_in_code.saturating_sub(span_start_in_code);
         let match_end_in_span =
-            (match_end_in_code.saturating_sub(span_start_in_code)).min(span_content.len());
+            (match_end_in_code.saturating_sub(span_start_in_code)).min(span_len);
 
         // Skip if match doesn't actually overlap this span
-        if match_start_in_span >= span_content.len() || match_end_in_span == 0 {
+        if match_start_in_span >= span_len || match_end_in_span == 0 {
             continue;
         }
 
@@ -315,7 +438,7 @@ fn apply_highlighting_to_span(
     }
 
     // Add remaining text after all matches
-    if processed_chars < span_content.len() {
+    if processed_chars < span_len {
         let remaining_text = span_content
             .chars()
             .skip(processed_chars)
@@ -329,72 +452,82 @@ fn apply_highlighting_to_span(
 
 /// Get search highlight style based on match type and diff line context
 pub fn get_search_highlight_style(is_current_match: bool, line_type: DiffLineType) -> Style {
-    match (is_current_match, line_type) {
-        // Current match styles - need to be highly visible against any background
-        (true, DiffLineType::Addition) => {
-            // Against light green background: Use dark purple for maximum contrast
-            Style::default()
-                .bg(Color::Rgb(75, 0, 130)) // Indigo
-                .fg(Color::Rgb(255, 255, 255)) // Bright white
-                .add_modifier(Modifier::BOLD)
-        }
-        (true, DiffLineType::Deletion) => {
-            // Against light red background: Use dark blue for maximum contrast
-            Style::default()
-                .bg(Color::Rgb(0, 0, 139)) // Dark blue
-                .fg(Color::Rgb(255, 255, 255)) // Bright white
-                .add_modifier(Modifier::BOLD)
-        }
-        (true, _) => {
-            // Against no background (context): Use bright yellow
-            Style::default()
-                .bg(Color::Rgb(255, 215, 0)) // Gold
-                .fg(Color::Black)
-                .add_modifier(Modifier::BOLD)
-        }
+    crate::config::theme::current().search_match_style(is_current_match, line_type)
+}
 
-        // Non-current match styles - should be visible but less prominent
-        (false, DiffLineType::Addition) => {
-            // Against light green background: Use dark navy
-            Style::default()
-                .bg(Color::Rgb(25, 25, 112)) // Midnight blue
-                .fg(Color::Rgb(200, 200, 200)) // Light gray
-                .add_modifier(Modifier::BOLD)
-        }
-        (false, DiffLineType::Deletion) => {
-            // Against light red background: Use dark green
-            Style::default()
-                .bg(Color::Rgb(0, 100, 0)) // Dark green
-                .fg(Color::Rgb(200, 200, 200)) // Light gray
-                .add_modifier(Modifier::BOLD)
-        }
-        (false, _) => {
-            // Against no background (context): Use medium blue
-            Style::default()
-                .bg(Color::Rgb(70, 130, 180)) // Steel blue
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD)
-        }
-    }
+/// Extended header lines git writes between `diff --git` and the first hunk
+const EXTENDED_HEADERS: &[&str] = &[
+    "old mode ",
+    "new mode ",
+    "deleted file mode ",
+    "new file mode ",
+    "similarity index ",
+    "dissimilarity index ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+];
+
+/// The new-side path named by a `diff --git a/<old> b/<new>` line
+pub fn diff_header_path(line: &str) -> Option<PathBuf> {
+    let paths = line.strip_prefix("diff --git a/")?;
+    let (_, new_path) = paths.rsplit_once(" b/")?;
+    Some(PathBuf::from(new_path))
 }
 
 pub fn parse_diff(diff_text: &str) -> Vec<DiffLine> {
     let mut result = Vec::new();
     let mut old_line_num = 0;
     let mut new_line_num = 0;
+    // Status columns before each line's content: one per parent in a combined (`--cc`) diff
+    let mut columns = 1;
 
     for line in diff_text.lines() {
+        if line.starts_with("diff ") {
+            columns = 1;
+        }
+        if columns > 1 && !line.starts_with("@@") {
+            if let Some(combined) =
+                parse_combined_line(line, columns, &mut old_line_num, &mut new_line_num)
+            {
+                result.push(combined);
+                continue;
+            }
+        }
+
+        // "\ No newline at end of file" describes the line before it and isn't a file line
+        if line.starts_with('\\') {
+            result.push(DiffLine {
+                line_type: DiffLineType::Context,
+                content: line.to_string(),
+                old_line_num: None,
+                new_line_num: None,
+            });
+            continue;
+        }
+
         let line_type = if line.starts_with("diff --git")
+            || line.starts_with("diff --cc")
+            || line.starts_with("diff --combined")
             || line.starts_with("index ")
             || line.starts_with("---")
             || line.starts_with("+++")
+            // git's binary marker, or the placeholder it is replaced with
+            || line.starts_with("Binary file")
+            || EXTENDED_HEADERS
+                .iter()
+                .any(|header| line.starts_with(header))
         {
             DiffLineType::Header
         } else if line.starts_with("@@") {
+            // A combined diff's hunk header has one `@` per parent plus one
+            columns = line.chars().take_while(|&c| c == '@').count() - 1;
             // Parse hunk header to get line numbers
+            // Counters advance before each line is numbered, so start one line before the hunk
             if let Some((old_start, new_start)) = parse_hunk_header(line) {
-                old_line_num = old_start;
-                new_line_num = new_start;
+                old_line_num = old_start.saturating_sub(1);
+                new_line_num = new_start.saturating_sub(1);
             }
             DiffLineType::HunkHeader
         } else if line.starts_with('+') && !line.starts_with("+++") {
@@ -429,11 +562,92 @@ pub fn parse_diff(diff_text: &str) -> Vec<DiffLine> {
     result
 }
 
+/// Type and number a line of a combined diff hunk, whose first `columns` characters mark it
+/// as added (`+`) or removed (`-`) relative to each parent. Old line numbers follow the first
+/// parent. The content keeps a single marker, like a normal diff line, so it renders the
+/// same way. Returns `None` for lines that aren't part of the hunk body.
+fn parse_combined_line(
+    line: &str,
+    columns: usize,
+    old_line_num: &mut usize,
+    new_line_num: &mut usize,
+) -> Option<DiffLine> {
+    let markers = line.get(..columns)?;
+    if !markers.chars().all(|c| matches!(c, ' ' | '+' | '-')) {
+        return None;
+    }
+
+    let line_type = if markers.contains('-') {
+        DiffLineType::Deletion
+    } else if markers.contains('+') {
+        DiffLineType::Addition
+    } else {
+        DiffLineType::Context
+    };
+
+    // A removed line was in each parent marked `-`; any other line is in each parent
+    // not marked `+`
+    let in_first_parent = if line_type == DiffLineType::Deletion {
+        markers.starts_with('-')
+    } else {
+        markers.starts_with(' ')
+    };
+    if in_first_parent {
+        *old_line_num += 1;
+    }
+    if line_type != DiffLineType::Deletion {
+        *new_line_num += 1;
+    }
+
+    let marker = match line_type {
+        DiffLineType::Deletion => '-',
+        DiffLineType::Addition => '+',
+        _ => ' ',
+    };
+    Some(DiffLine {
+        line_type,
+        content: format!("{}{}", marker, &line[columns..]),
+        old_line_num: in_first_parent.then_some(*old_line_num),
+        new_line_num: (line_type != DiffLineType::Deletion).then_some(*new_line_num),
+    })
+}
+
+/// Count additions and deletions in each hunk, keyed by the index of its hunk header line
+pub fn count_hunk_changes(lines: &[DiffLine]) -> HashMap<usize, (usize, usize)> {
+    let mut counts = HashMap::new();
+    let mut current_hunk: Option<usize> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        match line.line_type {
+            DiffLineType::HunkHeader => {
+                current_hunk = Some(index);
+                counts.insert(index, (0, 0));
+            }
+            DiffLineType::Header => current_hunk = None,
+            DiffLineType::Addition | DiffLineType::Deletion => {
+                if let Some((additions, deletions)) =
+                    current_hunk.and_then(|hunk| counts.get_mut(&hunk))
+                {
+                    if line.line_type == DiffLineType::Addition {
+                        *additions += 1;
+                    } else {
+                        *deletions += 1;
+                    }
+                }
+            }
+            DiffLineType::Context => {}
+        }
+    }
+
+    counts
+}
+
 /// Parse a hunk header like "@@ -24,6 +24,7 @@" to extract starting line numbers
 fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
     use regex::Regex;
-    static HUNK_REGEX: once_cell::sync::Lazy<Regex> =
-        once_cell::sync::Lazy::new(|| Regex::new(r"@@ -(\d+),?\d* \+(\d+),?\d* @@").unwrap());
+    static HUNK_REGEX: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(r"@@+ -(\d+),?\d*(?: -\d+,?\d*)* \+(\d+),?\d* @@").unwrap()
+    });
 
     HUNK_REGEX.captures(line).and_then(|caps| {
         let old_start = caps.get(1)?.as_str().parse::<usize>().ok()?;
@@ -1,2 +1,106 @@
 // Additional diff parsing utilities can be added here
 // For now, the main parsing logic is in mod.rs
+
+/// Present a whole file as a diff of one hunk of context lines, so it can be shown, scrolled
+/// and searched like any other diff
+pub fn file_as_diff(content: &str) -> String {
+    let line_count = content.lines().count();
+    let mut diff = format!("@@ -1,{0} +1,{0} @@\n", line_count);
+    for line in content.lines() {
+        diff.push(' ');
+        diff.push_str(line);
+        diff.push('\n');
+    }
+    diff
+}
+
+/// Invert a unified diff so the new side becomes the base (like `git diff -R`).
+///
+/// File headers and hunk ranges are swapped, and within each run of changes the
+/// former additions are emitted first as deletions, followed by the former
+/// deletions as additions, so the result reads like a diff git would produce.
+pub fn invert_diff(diff_text: &str) -> String {
+    let mut output: Vec<String> = Vec::new();
+    let mut removed: Vec<&str> = Vec::new(); // Original '-' lines, become '+'
+    let mut added: Vec<&str> = Vec::new(); // Original '+' lines, become '-'
+    let mut pending_old_header: Option<&str> = None;
+    let mut in_hunk = false;
+
+    for line in diff_text.lines() {
+        if in_hunk {
+            if line.starts_with('+') {
+                added.push(line);
+                continue;
+            }
+            if line.starts_with('-') {
+                removed.push(line);
+                continue;
+            }
+        }
+
+        flush_change_run(&mut output, &mut added, &mut removed);
+
+        if line.starts_with("@@") {
+            in_hunk = true;
+            output.push(invert_hunk_header(line));
+        } else if line.starts_with("diff ") {
+            in_hunk = false;
+            output.push(line.to_string());
+        } else if in_hunk {
+            // Context lines and "\ No newline at end of file" markers
+            output.push(line.to_string());
+        } else if let Some(old_path) = line.strip_prefix("--- ") {
+            pending_old_header = Some(old_path);
+        } else if let Some(new_path) = line.strip_prefix("+++ ") {
+            output.push(format!("--- {}", new_path));
+            if let Some(old_path) = pending_old_header.take() {
+                output.push(format!("+++ {}", old_path));
+            }
+        } else if let Some(mode) = line.strip_prefix("new file mode ") {
+            output.push(format!("deleted file mode {}", mode));
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            output.push(format!("new file mode {}", mode));
+        } else {
+            output.push(line.to_string());
+        }
+    }
+
+    flush_change_run(&mut output, &mut added, &mut removed);
+    if let Some(old_path) = pending_old_header {
+        output.push(format!("+++ {}", old_path));
+    }
+
+    let mut result = output.join("\n");
+    if diff_text.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Emit a buffered run of changes with the roles of additions and deletions swapped
+fn flush_change_run(output: &mut Vec<String>, added: &mut Vec<&str>, removed: &mut Vec<&str>) {
+    for line in added.drain(..) {
+        output.push(format!("-{}", &line[1..]));
+    }
+    for line in removed.drain(..) {
+        output.push(format!("+{}", &line[1..]));
+    }
+}
+
+/// Swap the old and new ranges of a hunk header, keeping any trailing context
+fn invert_hunk_header(line: &str) -> String {
+    let Some(rest) = line.strip_prefix("@@ ") else {
+        return line.to_string();
+    };
+    let Some((ranges, tail)) = rest.split_once(" @@") else {
+        return line.to_string();
+    };
+
+    let mut parts = ranges.split_whitespace();
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(old), Some(new), None) if old.starts_with('-') && new.starts_with('+') => {
+            format!("@@ -{} +{} @@{}", &new[1..], &old[1..], tail)
+        }
+        _ => line.to_string(),
+    }
+}
@@ -4,52 +4,74 @@ use super::{DiffLine, DiffLineType};
 pub struct SideBySideDiff {
     pub old_lines: Vec<Option<DiffLine>>,
     pub new_lines: Vec<Option<DiffLine>>,
+    line_rows: Vec<usize>, // Row each unified diff line ends up on, by unified line index
 }
 
 impl SideBySideDiff {
     /// Convert a unified diff into side-by-side view
     pub fn from_unified(diff_lines: &[DiffLine]) -> Self {
-        let mut old_lines = Vec::new();
-        let mut new_lines = Vec::new();
+        // Lay out indices into `diff_lines` first so rows can be traced back to unified lines
+        let mut old_rows = Vec::new();
+        let mut new_rows = Vec::new();
 
-        for line in diff_lines {
+        for (index, line) in diff_lines.iter().enumerate() {
             match line.line_type {
                 DiffLineType::Header | DiffLineType::HunkHeader => {
                     // Headers appear in both sides
-                    old_lines.push(Some(line.clone()));
-                    new_lines.push(Some(line.clone()));
+                    old_rows.push(Some(index));
+                    new_rows.push(Some(index));
                 }
                 DiffLineType::Context => {
                     // Context lines appear in both sides
-                    old_lines.push(Some(line.clone()));
-                    new_lines.push(Some(line.clone()));
+                    old_rows.push(Some(index));
+                    new_rows.push(Some(index));
                 }
                 DiffLineType::Deletion => {
                     // Deletion only appears in old file
-                    old_lines.push(Some(line.clone()));
-                    new_lines.push(None); // Placeholder for alignment
+                    old_rows.push(Some(index));
+                    new_rows.push(None); // Placeholder for alignment
                 }
                 DiffLineType::Addition => {
                     // Addition only appears in new file
-                    old_lines.push(None); // Placeholder for alignment
-                    new_lines.push(Some(line.clone()));
+                    old_rows.push(None); // Placeholder for alignment
+                    new_rows.push(Some(index));
                 }
             }
         }
 
         // Compact consecutive additions and deletions for better visual alignment
-        Self::compact_changes(&mut old_lines, &mut new_lines);
+        Self::compact_changes(diff_lines, &mut old_rows, &mut new_rows);
+
+        let mut line_rows = vec![0; diff_lines.len()];
+        for (row, (old, new)) in old_rows.iter().zip(&new_rows).enumerate() {
+            for index in old.iter().chain(new.iter()) {
+                line_rows[*index] = row;
+            }
+        }
+
+        let lines = |rows: Vec<Option<usize>>| -> Vec<Option<DiffLine>> {
+            rows.into_iter()
+                .map(|index| index.map(|index| diff_lines[index].clone()))
+                .collect()
+        };
 
         Self {
-            old_lines,
-            new_lines,
+            old_lines: lines(old_rows),
+            new_lines: lines(new_rows),
+            line_rows,
         }
     }
 
+    /// The side-by-side row showing the unified diff line at `line_index`
+    pub fn row_for_line(&self, line_index: usize) -> Option<usize> {
+        self.line_rows.get(line_index).copied()
+    }
+
     /// Compact consecutive additions and deletions to align them side by side
     fn compact_changes(
-        old_lines: &mut Vec<Option<DiffLine>>,
-        new_lines: &mut Vec<Option<DiffLine>>,
+        diff_lines: &[DiffLine],
+        old_lines: &mut Vec<Option<usize>>,
+        new_lines: &mut Vec<Option<usize>>,
     ) {
         // This is a simplified version - a more sophisticated algorithm would
         // better align changes based on content similarity
@@ -58,16 +80,16 @@ impl SideBySideDiff {
         while i < old_lines.len() {
             // Find a deletion followed by additions
             if old_lines[i].is_some() && new_lines[i].is_none() {
-                if let Some(ref line) = old_lines[i] {
-                    if line.line_type == DiffLineType::Deletion {
+                if let Some(line) = old_lines[i] {
+                    if diff_lines[line].line_type == DiffLineType::Deletion {
                         // Look for following additions
                         let mut j = i + 1;
                         while j < old_lines.len()
                             && old_lines[j].is_none()
                             && new_lines[j].is_some()
                         {
-                            if let Some(ref new_line) = new_lines[j] {
-                                if new_line.line_type != DiffLineType::Addition {
+                            if let Some(new_line) = new_lines[j] {
+                                if diff_lines[new_line].line_type != DiffLineType::Addition {
                                     break;
                                 }
                             }
@@ -1,6 +1,7 @@
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 use ratatui::style::{Color as RatatuiColor, Modifier};
 use ratatui::text::Span;
+use std::collections::HashMap;
 use std::path::Path;
 use syntect::easy::HighlightLines;
 use syntect::highlighting::ThemeSet;
@@ -12,12 +13,138 @@ static SYNTAX_SET: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_newlines
 /// Lazy-loaded themes
 static THEME_SET: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
 
-/// Highlight a line of code for a given file path
-pub fn highlight_line(line: &str, file_path: &Path) -> Vec<Span<'static>> {
-    let syntax = detect_syntax(file_path);
+/// Theme used when none is configured - a light theme designed for GitHub
+pub const DEFAULT_THEME: &str = "InspiredGitHub";
+
+/// Theme chosen at startup; highlighting uses `DEFAULT_THEME` until one is set
+static SELECTED_THEME: OnceCell<&'static str> = OnceCell::new();
+
+/// Languages forced for file names and extensions (`syntax_overrides` in the config)
+static SYNTAX_OVERRIDES: OnceCell<HashMap<String, String>> = OnceCell::new();
+
+/// Files known by name that have no syntax of their own, mapped to the closest bundled one
+/// (matched with any suffix, e.g. `Dockerfile.dev`)
+const FILE_NAME_SYNTAXES: &[(&str, &str)] = &[
+    ("Dockerfile", "bash"),
+    ("Containerfile", "bash"),
+    ("Jenkinsfile", "groovy"),
+    ("Vagrantfile", "ruby"),
+];
+
+/// bat/delta theme names mapped to the closest bundled syntect theme
+const BAT_THEME_ALIASES: &[(&str, &str)] = &[
+    ("GitHub", "InspiredGitHub"),
+    ("Monokai Extended", "base16-mocha.dark"),
+    ("Monokai Extended Bright", "base16-mocha.dark"),
+    ("Monokai Extended Light", "InspiredGitHub"),
+    ("Monokai Extended Origin", "base16-mocha.dark"),
+    ("OneHalfDark", "base16-ocean.dark"),
+    ("OneHalfLight", "base16-ocean.light"),
+    ("Nord", "base16-ocean.dark"),
+    ("TwoDark", "base16-ocean.dark"),
+    ("Dracula", "base16-eighties.dark"),
+    ("zenburn", "base16-eighties.dark"),
+    ("base16", "base16-ocean.dark"),
+    ("base16-256", "base16-ocean.dark"),
+    ("Coldark-Cold", "base16-ocean.light"),
+    ("Coldark-Dark", "base16-ocean.dark"),
+    ("gruvbox-dark", "base16-eighties.dark"),
+    ("gruvbox-light", "Solarized (light)"),
+    ("Sublime Snazzy", "base16-eighties.dark"),
+];
+
+/// Names of the bundled syntax themes
+pub fn available_themes() -> Vec<&'static str> {
+    THEME_SET.themes.keys().map(|name| name.as_str()).collect()
+}
+
+/// Find a bundled theme by its name, or by a bat theme name that maps to one
+pub fn find_theme(name: &str) -> Option<&'static str> {
+    if let Some((key, _)) = THEME_SET.themes.get_key_value(name) {
+        return Some(key.as_str());
+    }
+
+    BAT_THEME_ALIASES
+        .iter()
+        .find(|(bat_name, _)| bat_name.eq_ignore_ascii_case(name))
+        .and_then(|(_, theme)| find_theme(theme))
+}
+
+/// Pick the syntax theme: a recognised `BAT_THEME` first (when enabled), then `--theme`,
+/// then the default
+pub fn resolve_theme(cli_theme: Option<&str>, bat_theme: Option<&str>) -> &'static str {
+    bat_theme
+        .and_then(find_theme)
+        .or_else(|| cli_theme.and_then(find_theme))
+        .unwrap_or(DEFAULT_THEME)
+}
+
+/// Set the theme used for all highlighting. Only the first call has an effect.
+pub fn set_theme(name: &'static str) {
+    let _ = SELECTED_THEME.set(name);
+}
+
+/// Find a bundled syntax by its name (`Python`, case-insensitively) or an extension (`py`)
+pub fn find_syntax(language: &str) -> Option<&'static SyntaxReference> {
+    SYNTAX_SET.find_syntax_by_token(language)
+}
+
+/// Set the languages forced for file names and extensions (`*.inc` or `inc`). Only the
+/// first call has an effect.
+pub fn set_overrides(overrides: HashMap<String, String>) {
+    let overrides = overrides
+        .into_iter()
+        .map(|(pattern, language)| {
+            let pattern = pattern.strip_prefix("*.").unwrap_or(&pattern).to_string();
+            (pattern, language)
+        })
+        .collect();
+    let _ = SYNTAX_OVERRIDES.set(overrides);
+}
+
+/// The language a file's own name doesn't tell: `forced` (`--syntax`) when given, otherwise
+/// the interpreter named by the shebang of an unrecognised file in the working tree
+pub fn file_language(repo_root: &Path, file_path: &Path, forced: Option<&str>) -> Option<String> {
+    if let Some(language) = forced {
+        return Some(language.to_string());
+    }
+    if syntax_for_path(file_path).is_some() {
+        return None;
+    }
+
+    let contents = std::fs::read(repo_root.join(file_path)).ok()?;
+    let first_line = contents.split(|&b| b == b'\n').next()?;
+    let first_line = std::str::from_utf8(first_line).ok()?;
+    shebang_syntax(first_line).map(|syntax| syntax.name.clone())
+}
+
+/// The syntax of a script from its `#!` line
+fn shebang_syntax(first_line: &str) -> Option<&'static SyntaxReference> {
+    if !first_line.starts_with("#!") {
+        return None;
+    }
+    SYNTAX_SET.find_syntax_by_first_line(first_line)
+}
+
+/// Name of the syntax a file is highlighted with, `language` (from `file_language`) first
+#[allow(dead_code)] // Used in tests
+pub fn syntax_name(file_path: &Path, language: Option<&str>) -> Option<&'static str> {
+    choose_syntax(file_path, language).map(|syntax| syntax.name.as_str())
+}
+
+fn choose_syntax(file_path: &Path, language: Option<&str>) -> Option<&'static SyntaxReference> {
+    language
+        .and_then(find_syntax)
+        .or_else(|| syntax_for_path(file_path))
+}
+
+/// Highlight a line of code for a given file path, as `language` when given
+pub fn highlight_line(line: &str, file_path: &Path, language: Option<&str>) -> Vec<Span<'static>> {
+    let syntax = choose_syntax(file_path, language);
 
     if let Some(syntax) = syntax {
-        let theme = &THEME_SET.themes["InspiredGitHub"]; // Try a light theme designed for GitHub
+        let theme_name = SELECTED_THEME.get().copied().unwrap_or(DEFAULT_THEME);
+        let theme = &THEME_SET.themes[theme_name];
         let mut highlighter = HighlightLines::new(syntax, theme);
 
         match highlighter.highlight_line(line, &SYNTAX_SET) {
@@ -63,6 +190,39 @@ pub fn highlight_line(line: &str, file_path: &Path) -> Vec<Span<'static>> {
     }
 }
 
+/// The syntax for a path: configured overrides by file name, then by extension, then the
+/// names syntect knows without an extension (`Makefile`, `.bashrc`), then the extension
+fn syntax_for_path(file_path: &Path) -> Option<&'static SyntaxReference> {
+    let file_name = file_path.file_name()?.to_str()?;
+    let extension = file_path
+        .extension()
+        .and_then(|extension| extension.to_str());
+
+    if let Some(overrides) = SYNTAX_OVERRIDES.get() {
+        let configured = overrides
+            .get(file_name)
+            .or_else(|| extension.and_then(|extension| overrides.get(extension)));
+        if let Some(syntax) = configured.and_then(|language| find_syntax(language)) {
+            return Some(syntax);
+        }
+    }
+
+    SYNTAX_SET
+        .find_syntax_by_extension(file_name)
+        .or_else(|| {
+            FILE_NAME_SYNTAXES
+                .iter()
+                .find(|(name, _)| {
+                    file_name == *name
+                        || file_name
+                            .strip_prefix(name)
+                            .is_some_and(|rest| rest.starts_with('.'))
+                })
+                .and_then(|(_, language)| find_syntax(language))
+        })
+        .or_else(|| detect_syntax(file_path))
+}
+
 /// Detect syntax from file extension
 fn detect_syntax(file_path: &Path) -> Option<&'static SyntaxReference> {
     let extension = file_path.extension()?.to_str()?;
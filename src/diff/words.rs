@@ -0,0 +1,250 @@
+use super::{diff_header_path, DiffLine, DiffLineType};
+use ratatui::style::{Color, Modifier};
+use ratatui::text::{Line, Span};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Default background for the changed words of an added line, stronger than the line
+/// background
+pub const ADDITION_EMPHASIS_BG: Color = Color::Rgb(120, 215, 120);
+/// Default background for the changed words of a deleted line, stronger than the line
+/// background
+pub const DELETION_EMPHASIS_BG: Color = Color::Rgb(230, 120, 120);
+
+/// Share of a line pair that must be unchanged for word highlighting to be worthwhile;
+/// below this the lines are rewrites and highlighting every word only adds noise
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// Token-grid size above which pairs are skipped to keep rendering responsive
+const MAX_COMPARISON_CELLS: usize = 250_000;
+
+/// Changed byte ranges within the code (after the diff marker) of paired deleted and added
+/// lines, keyed by the file they belong to (its `diff --git` path, `None` before any such
+/// header) and their line number on each side. The path keeps the files of a whole-commit
+/// diff apart, whose line numbers restart with every file.
+#[derive(Debug, Default)]
+pub struct WordHighlights {
+    deletions: HashMap<(Option<PathBuf>, usize), Vec<Range<usize>>>,
+    additions: HashMap<(Option<PathBuf>, usize), Vec<Range<usize>>>,
+}
+
+impl WordHighlights {
+    /// Pair each run of deletions with the run of additions right after it, line by line,
+    /// and record the words that differ within similar pairs
+    pub fn from_lines(lines: &[DiffLine]) -> Self {
+        let mut highlights = Self::default();
+        let mut file: Option<PathBuf> = None;
+        let mut index = 0;
+
+        while index < lines.len() {
+            if let Some(path) = diff_header_path(&lines[index].content) {
+                file = Some(path);
+            }
+
+            let deletions_start = index;
+            while index < lines.len() && lines[index].line_type == DiffLineType::Deletion {
+                index += 1;
+            }
+            let additions_start = index;
+            while index < lines.len() && lines[index].line_type == DiffLineType::Addition {
+                index += 1;
+            }
+
+            if deletions_start == additions_start {
+                // Not a deletion run; additions without deletions have nothing to compare
+                index = index.max(deletions_start + 1);
+                continue;
+            }
+
+            let deleted = &lines[deletions_start..additions_start];
+            let added = &lines[additions_start..index];
+            for (old, new) in deleted.iter().zip(added) {
+                let (Some(old_num), Some(new_num)) = (old.old_line_num, new.new_line_num) else {
+                    continue;
+                };
+                if let Some((old_ranges, new_ranges)) =
+                    changed_word_ranges(code_of(old), code_of(new))
+                {
+                    highlights
+                        .deletions
+                        .insert((file.clone(), old_num), old_ranges);
+                    highlights
+                        .additions
+                        .insert((file.clone(), new_num), new_ranges);
+                }
+            }
+        }
+
+        highlights
+    }
+
+    /// Changed ranges for a line of `file`, if it was paired with a similar line on the
+    /// other side
+    pub fn ranges_for(&self, file: Option<&Path>, line: &DiffLine) -> Option<&[Range<usize>]> {
+        let file = file.map(Path::to_path_buf);
+        match line.line_type {
+            DiffLineType::Deletion => self.deletions.get(&(file, line.old_line_num?)),
+            DiffLineType::Addition => self.additions.get(&(file, line.new_line_num?)),
+            _ => None,
+        }
+        .map(Vec::as_slice)
+    }
+}
+
+/// The line's code without its leading diff marker
+fn code_of(line: &DiffLine) -> &str {
+    line.content.get(1..).unwrap_or("")
+}
+
+/// Split text into words, runs of whitespace and single punctuation characters
+fn tokenize(text: &str) -> Vec<Range<usize>> {
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            1
+        } else if c.is_whitespace() {
+            2
+        } else {
+            0
+        }
+    };
+
+    let mut previous_class = None;
+    for (start, c) in text.char_indices() {
+        let end = start + c.len_utf8();
+        let current = class(c);
+        match tokens.last_mut() {
+            // Words and whitespace extend the current token; punctuation stands alone
+            Some(last) if current != 0 && previous_class == Some(current) => last.end = end,
+            _ => tokens.push(start..end),
+        }
+        previous_class = Some(current);
+    }
+
+    tokens
+}
+
+/// Changed byte ranges in the old line and in the new line
+pub type WordRanges = (Vec<Range<usize>>, Vec<Range<usize>>);
+
+/// Byte ranges of the tokens that differ between two similar lines, or `None` when the lines
+/// have too little in common to be worth comparing word by word
+pub fn changed_word_ranges(old: &str, new: &str) -> Option<WordRanges> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    if old_tokens.len().saturating_mul(new_tokens.len()) > MAX_COMPARISON_CELLS {
+        return None;
+    }
+
+    // Longest common subsequence of tokens, filled from the end
+    let (n, m) = (old_tokens.len(), new_tokens.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut old_changed = Vec::new();
+    let mut new_changed = Vec::new();
+    let mut common_bytes = 0;
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+            common_bytes += old_tokens[i].len();
+            i += 1;
+            j += 1;
+        } else if j >= m || (i < n && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            push_merged(&mut old_changed, old_tokens[i].clone());
+            i += 1;
+        } else {
+            push_merged(&mut new_changed, new_tokens[j].clone());
+            j += 1;
+        }
+    }
+
+    let total_bytes = old.len() + new.len();
+    if total_bytes == 0 || (2 * common_bytes) as f64 / (total_bytes as f64) < MIN_SIMILARITY {
+        return None;
+    }
+
+    Some((old_changed, new_changed))
+}
+
+/// Append a range, joining it to the previous one when they touch
+fn push_merged(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+/// Give the code within `ranges` the emphasis background. `ranges` are byte offsets into the
+/// text of the spans from `code_start` on.
+pub fn emphasize_ranges(
+    line: Line<'static>,
+    code_start: usize,
+    ranges: &[Range<usize>],
+    line_type: DiffLineType,
+) -> Line<'static> {
+    let theme = crate::config::theme::current();
+    let emphasis = match line_type {
+        DiffLineType::Addition => theme.addition_emphasis_bg,
+        DiffLineType::Deletion => theme.deletion_emphasis_bg,
+        _ => return line,
+    };
+    // Without colors the changed words are bold instead
+    let emphasis_modifier = if theme.no_color {
+        Modifier::BOLD
+    } else {
+        Modifier::empty()
+    };
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+
+    for (index, span) in line.spans.into_iter().enumerate() {
+        if index < code_start {
+            spans.push(span);
+            continue;
+        }
+
+        let content = span.content.as_ref();
+        let span_range = offset..offset + content.len();
+        offset = span_range.end;
+
+        // Cut the span at every range boundary that falls inside it
+        let mut cursor = span_range.start;
+        for range in ranges {
+            let start = range.start.clamp(span_range.start, span_range.end);
+            let end = range.end.clamp(span_range.start, span_range.end);
+            if start >= end {
+                continue;
+            }
+            if start > cursor {
+                let text = &content[cursor - span_range.start..start - span_range.start];
+                spans.push(Span::styled(text.to_string(), span.style));
+            }
+            let text = &content[start - span_range.start..end - span_range.start];
+            spans.push(Span::styled(
+                text.to_string(),
+                span.style.bg(emphasis).add_modifier(emphasis_modifier),
+            ));
+            cursor = end;
+        }
+        if cursor < span_range.end {
+            let text = &content[cursor - span_range.start..];
+            spans.push(Span::styled(text.to_string(), span.style));
+        }
+    }
+
+    Line::from(spans)
+}
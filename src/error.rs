@@ -31,7 +31,6 @@ pub enum GeschichteError {
     StateError(String),
 
     #[error("Configuration error: {0}")]
-    #[allow(dead_code)]
     ConfigError(String),
 
     #[error("Cache error: {0}")]
@@ -0,0 +1,66 @@
+//! Non-interactive output of a file's history for scripts and pipes. Nothing here touches the
+//! terminal, so it works without a TTY (in CI or with stdout redirected).
+
+use crate::error::Result;
+use std::io::Write;
+use std::path::Path;
+
+/// Write the file's commits, newest first and at most `max_count` of them, as a JSON array.
+/// With `with_stats` each commit also carries its `git show --stat` summary.
+pub fn write_history_json(
+    out: &mut impl Write,
+    repo_root: &Path,
+    file_path: &Path,
+    follow_renames: bool,
+    first_parent: bool,
+    with_stats: bool,
+    max_count: Option<usize>,
+) -> Result<()> {
+    let mut commits = crate::git::history::fetch_commit_history(
+        repo_root,
+        file_path,
+        follow_renames,
+        first_parent,
+        0,
+        max_count,
+    )?;
+
+    if with_stats {
+        for commit in &mut commits {
+            commit.stats = crate::git::history::fetch_commit_stats(repo_root, &commit.hash)?;
+        }
+    }
+
+    serde_json::to_writer_pretty(&mut *out, &commits).map_err(std::io::Error::from)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Write a diff as text, colored with ANSI escapes like `git diff --color` when `color`
+pub fn write_diff(out: &mut impl Write, diff: &str, color: bool) -> Result<()> {
+    for line in diff.lines() {
+        let style = if !color {
+            None
+        } else if line.starts_with("diff --git")
+            || line.starts_with("index ")
+            || line.starts_with("--- ")
+            || line.starts_with("+++ ")
+        {
+            Some("\x1b[1m")
+        } else if line.starts_with("@@") {
+            Some("\x1b[36m")
+        } else if line.starts_with('+') {
+            Some("\x1b[32m")
+        } else if line.starts_with('-') {
+            Some("\x1b[31m")
+        } else {
+            None
+        };
+
+        match style {
+            Some(style) => writeln!(out, "{}{}\x1b[0m", style, line)?,
+            None => writeln!(out, "{}", line)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,247 @@
+use crate::error::{GeschichteError, Result};
+use crate::git::encoding::{self, TextEncoding};
+use chrono::{DateTime, FixedOffset};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Author shown for lines that only exist in the working tree
+pub const NOT_COMMITTED_AUTHOR: &str = "Not Committed Yet";
+
+/// One line of `git blame` output
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub date: String,
+    /// Author time as a unix timestamp
+    pub time: i64,
+    pub content: String,
+}
+
+/// How long ago a line last changed, for coloring the full file view as a heat map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineAge {
+    Day,
+    Week,
+    Month,
+    Older,
+}
+
+impl LineAge {
+    /// The age of a line authored at `time`, as seen at `now` (both unix timestamps)
+    pub fn of(time: i64, now: i64) -> Self {
+        const DAY: i64 = 86_400;
+        match now - time {
+            elapsed if elapsed < DAY => LineAge::Day,
+            elapsed if elapsed < 7 * DAY => LineAge::Week,
+            elapsed if elapsed < 30 * DAY => LineAge::Month,
+            _ => LineAge::Older,
+        }
+    }
+}
+
+impl BlameLine {
+    /// Whether the line has no commit yet (it was changed in the working tree)
+    pub fn is_uncommitted(&self) -> bool {
+        self.hash.bytes().all(|b| b == b'0')
+    }
+
+    /// How long ago the line changed; uncommitted lines are the newest there are
+    pub fn age(&self, now: i64) -> LineAge {
+        if self.is_uncommitted() {
+            LineAge::Day
+        } else {
+            LineAge::of(self.time, now)
+        }
+    }
+}
+
+/// Per-commit details that `--porcelain` only prints the first time a commit appears
+#[derive(Default, Clone)]
+struct BlameCommitInfo {
+    author: String,
+    time: i64,
+    tz: String,
+}
+
+/// Blames every line of a file as of a commit, or the working tree when `commit_hash`
+/// is `None`
+pub fn fetch_blame(
+    repo_root: &Path,
+    file_path: &Path,
+    commit_hash: Option<&str>,
+    text_encoding: Option<TextEncoding>,
+) -> Result<Vec<BlameLine>> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root).arg("blame").arg("--porcelain");
+    if let Some(hash) = commit_hash {
+        cmd.arg(hash);
+    }
+    cmd.arg("--").arg(file_path);
+
+    let command = format!(
+        "git blame {} -- {}",
+        commit_hash.unwrap_or("(working tree)"),
+        file_path.display()
+    );
+    let output = cmd
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: command.clone(),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(parse_porcelain(&output.stdout, text_encoding))
+}
+
+/// Reads `git blame --porcelain` output into one entry per line
+pub fn parse_porcelain(stdout: &[u8], text_encoding: Option<TextEncoding>) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut commits: HashMap<String, BlameCommitInfo> = HashMap::new();
+    let mut current_hash = String::new();
+
+    for raw_line in stdout.split(|&b| b == b'\n') {
+        // Line content is prefixed with a tab and may be in the file's own encoding
+        if let Some(content) = raw_line.strip_prefix(b"\t") {
+            let info = commits.get(&current_hash).cloned().unwrap_or_default();
+            let mut line = BlameLine {
+                short_hash: current_hash.chars().take(7).collect(),
+                hash: current_hash.clone(),
+                author: info.author,
+                date: format_blame_date(info.time, &info.tz),
+                time: info.time,
+                content: encoding::decode(content, text_encoding),
+            };
+            if line.is_uncommitted() {
+                line.author = NOT_COMMITTED_AUTHOR.to_string();
+            }
+            lines.push(line);
+            continue;
+        }
+
+        let line = String::from_utf8_lossy(raw_line);
+        let (key, value) = line.split_once(' ').unwrap_or((&line, ""));
+
+        // A group header: "<hash> <source line> <result line> [<lines in group>]", with
+        // SHA-1 or SHA-256 hashes
+        if matches!(key.len(), 40 | 64) && key.chars().all(|c| c.is_ascii_hexdigit()) {
+            current_hash = key.to_string();
+            commits.entry(current_hash.clone()).or_default();
+            continue;
+        }
+
+        if let Some(info) = commits.get_mut(&current_hash) {
+            match key {
+                "author" => info.author = value.to_string(),
+                "author-time" => info.time = value.parse().unwrap_or(0),
+                "author-tz" => info.tz = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+
+    lines
+}
+
+/// Formats a unix timestamp as a `YYYY-MM-DD` date in the author's timezone, such as
+/// "+0530" or "-0800"; an unreadable timezone counts as UTC
+fn format_blame_date(timestamp: i64, tz: &str) -> String {
+    let offset = tz
+        .parse::<FixedOffset>()
+        .unwrap_or_else(|_| FixedOffset::east_opt(0).expect("UTC is a valid offset"));
+    DateTime::from_timestamp(timestamp, 0)
+        .map(|time| time.with_timezone(&offset).format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Where a line of a file came from: the commit that introduced it, with the file's path and
+/// the line's number in that commit. Blame follows renames, so the path may be an older one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineOrigin {
+    pub hash: String,
+    pub path: PathBuf,
+    pub line: usize,
+}
+
+impl LineOrigin {
+    /// Whether the line has no commit yet (it was changed in the working tree)
+    pub fn is_uncommitted(&self) -> bool {
+        self.hash.bytes().all(|b| b == b'0')
+    }
+}
+
+/// Blames line `line` (1-based) of a file as of a commit, or the working tree when
+/// `commit_hash` is `None`
+pub fn fetch_line_origin(
+    repo_root: &Path,
+    file_path: &Path,
+    commit_hash: Option<&str>,
+    line: usize,
+) -> Result<LineOrigin> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
+        .arg("blame")
+        .arg("--porcelain")
+        .arg("-L")
+        .arg(format!("{},{}", line, line));
+    if let Some(hash) = commit_hash {
+        cmd.arg(hash);
+    }
+    cmd.arg("--").arg(file_path);
+
+    let command = format!(
+        "git blame -L {},{} {} -- {}",
+        line,
+        line,
+        commit_hash.unwrap_or("(working tree)"),
+        file_path.display()
+    );
+    let output = cmd
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: command.clone(),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    parse_line_origin(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| {
+        GeschichteError::GitCommandFailed {
+            command,
+            output: "unexpected blame output".to_string(),
+        }
+    })
+}
+
+/// Reads the commit, source line and filename of the first line in `--porcelain` output
+pub fn parse_line_origin(porcelain: &str) -> Option<LineOrigin> {
+    let mut lines = porcelain.lines();
+    // The group header: "<hash> <source line> <result line> [<lines in group>]"
+    let mut header = lines.next()?.split(' ');
+    let hash = header.next()?.to_string();
+    let line = header.next()?.parse().ok()?;
+    // Details follow up to the line's content, which is prefixed with a tab
+    let path = lines
+        .take_while(|line| !line.starts_with('\t'))
+        .find_map(|line| line.strip_prefix("filename "))?;
+
+    Some(LineOrigin {
+        hash,
+        path: PathBuf::from(path),
+        line,
+    })
+}
@@ -1,4 +1,5 @@
 use crate::error::{GeschichteError, Result};
+use crate::git::encoding::{self, TextEncoding};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -9,6 +10,8 @@ pub fn fetch_diff(
     parent_hash: Option<&str>,
     file_path: &Path,
     context_lines: u32,
+    ignore_whitespace: bool,
+    text_encoding: Option<TextEncoding>,
 ) -> Result<String> {
     let mut cmd = Command::new("git");
     cmd.current_dir(repo_root);
@@ -17,19 +20,20 @@ pub fn fetch_diff(
         // Normal commit with parent
         cmd.arg("diff")
             .arg(format!("--unified={}", context_lines))
-            .arg("--find-renames")
-            .arg(parent)
-            .arg(commit_hash)
-            .arg("--")
-            .arg(file_path);
+            .arg("--find-renames");
+        if ignore_whitespace {
+            cmd.arg("-w");
+        }
+        cmd.arg(parent).arg(commit_hash).arg("--").arg(file_path);
     } else {
         // Root commit (no parent)
         cmd.arg("show")
             .arg("--patch")
-            .arg(format!("--unified={}", context_lines))
-            .arg(commit_hash)
-            .arg("--")
-            .arg(file_path);
+            .arg(format!("--unified={}", context_lines));
+        if ignore_whitespace {
+            cmd.arg("-w");
+        }
+        cmd.arg(commit_hash).arg("--").arg(file_path);
     }
 
     let output = cmd
@@ -51,7 +55,109 @@ pub fn fetch_diff(
         });
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    Ok(describe_binary_changes(
+        repo_root,
+        parent_hash,
+        Some(commit_hash),
+        encoding::decode(&output.stdout, text_encoding),
+    ))
+}
+
+/// Fetches the combined diff (`git show --cc`) of a merge commit, which shows how the merge
+/// result differs from each parent and so the changes made while resolving it
+pub fn fetch_combined_diff(
+    repo_root: &Path,
+    commit_hash: &str,
+    file_path: &Path,
+    context_lines: u32,
+    ignore_whitespace: bool,
+    text_encoding: Option<TextEncoding>,
+) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
+        .arg("show")
+        .arg("--cc")
+        .arg("--format=")
+        .arg(format!("--unified={}", context_lines));
+    if ignore_whitespace {
+        cmd.arg("-w");
+    }
+    cmd.arg(commit_hash).arg("--").arg(file_path);
+
+    let output = cmd
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git show --cc {}", commit_hash),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!("git show --cc {}", commit_hash),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    let diff_output = encoding::decode(&output.stdout, text_encoding);
+    if diff_output.trim().is_empty() {
+        // The merge took the file as it was on one side
+        return Ok(String::from(
+            "The merge kept this file from one parent; no combined diff\n",
+        ));
+    }
+
+    Ok(diff_output)
+}
+
+/// Fetches the diff of every file a commit touched. Merges show their changes against the
+/// first parent, as the single-file diff does.
+pub fn fetch_commit_files_diff(
+    repo_root: &Path,
+    commit_hash: &str,
+    context_lines: u32,
+    ignore_whitespace: bool,
+    text_encoding: Option<TextEncoding>,
+) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
+        .arg("show")
+        .arg("--patch")
+        .arg("--format=")
+        .arg("-m")
+        .arg("--first-parent")
+        .arg("--find-renames")
+        .arg(format!("--unified={}", context_lines));
+    if ignore_whitespace {
+        cmd.arg("-w");
+    }
+    cmd.arg(commit_hash);
+
+    let output = cmd
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git show {}", commit_hash),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!("git show {}", commit_hash),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    let diff_output = encoding::decode(&output.stdout, text_encoding);
+    if diff_output.trim().is_empty() {
+        return Ok(String::from("This commit changes no files\n"));
+    }
+
+    let first_parent = format!("{}^", commit_hash);
+    Ok(describe_binary_changes(
+        repo_root,
+        Some(&first_parent),
+        Some(commit_hash),
+        diff_output,
+    ))
 }
 
 /// Fetches the diff between two commits for a specific file
@@ -61,13 +167,18 @@ pub fn get_diff_between_commits(
     end_commit_hash: &str,
     file_path: &Path,
     context_lines: u32,
+    ignore_whitespace: bool,
+    text_encoding: Option<TextEncoding>,
 ) -> Result<String> {
     let mut cmd = Command::new("git");
     cmd.current_dir(repo_root)
         .arg("diff")
         .arg(format!("--unified={}", context_lines))
-        .arg("--find-renames")
-        .arg(format!("{}..{}", start_commit_hash, end_commit_hash))
+        .arg("--find-renames");
+    if ignore_whitespace {
+        cmd.arg("-w");
+    }
+    cmd.arg(format!("{}..{}", start_commit_hash, end_commit_hash))
         .arg("--")
         .arg(file_path);
 
@@ -90,14 +201,176 @@ pub fn get_diff_between_commits(
         });
     }
 
-    let diff_output = String::from_utf8_lossy(&output.stdout).to_string();
+    let diff_output = encoding::decode(&output.stdout, text_encoding);
     if diff_output.trim().is_empty() {
         return Ok(String::from(
             "No differences between the selected commits\n",
         ));
     }
 
-    Ok(diff_output)
+    Ok(describe_binary_changes(
+        repo_root,
+        Some(start_commit_hash),
+        Some(end_commit_hash),
+        diff_output,
+    ))
+}
+
+/// The paths of a "Binary files a/x and b/x differ" line, which git writes instead of hunks
+fn binary_marker_paths(line: &str) -> Option<(&str, &str)> {
+    line.strip_prefix("Binary files ")?
+        .strip_suffix(" differ")?
+        .split_once(" and ")
+}
+
+/// Replace git's "Binary files ... differ" lines with the file's size before and after.
+/// `old_rev` and `new_rev` are the revisions on each side; `None` reads the working tree.
+pub fn describe_binary_changes(
+    repo_root: &Path,
+    old_rev: Option<&str>,
+    new_rev: Option<&str>,
+    diff: String,
+) -> String {
+    if !diff.lines().any(|line| binary_marker_paths(line).is_some()) {
+        return diff;
+    }
+
+    let mut described = String::with_capacity(diff.len());
+    for line in diff.lines() {
+        match binary_marker_paths(line) {
+            Some((old_path, new_path)) => {
+                let old_size = old_path
+                    .strip_prefix("a/")
+                    .and_then(|path| file_size(repo_root, old_rev, path));
+                let new_size = new_path
+                    .strip_prefix("b/")
+                    .and_then(|path| file_size(repo_root, new_rev, path));
+                described.push_str(&binary_change_summary(old_size, new_size));
+            }
+            None => described.push_str(line),
+        }
+        described.push('\n');
+    }
+    described
+}
+
+/// The placeholder shown for a binary file; `None` is a side the file doesn't exist on
+pub fn binary_change_summary(old_size: Option<u64>, new_size: Option<u64>) -> String {
+    match (old_size, new_size) {
+        (None, Some(new)) => format!("Binary file added — {} bytes", new),
+        (Some(old), None) => format!("Binary file deleted — {} bytes", old),
+        (Some(old), Some(new)) => format!(
+            "Binary file — {} → {} bytes ({:+} bytes changed)",
+            old,
+            new,
+            new as i64 - old as i64
+        ),
+        (None, None) => "Binary file — contents differ".to_string(),
+    }
+}
+
+/// Size of `path` at `rev` (`git cat-file -s`), or in the working tree
+fn file_size(repo_root: &Path, rev: Option<&str>, path: &str) -> Option<u64> {
+    let Some(rev) = rev else {
+        return std::fs::metadata(repo_root.join(path))
+            .ok()
+            .map(|metadata| metadata.len());
+    };
+
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .arg("cat-file")
+        .arg("-s")
+        .arg(format!("{}:{}", rev, path))
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Diffs `text` (e.g. clipboard contents) against the file as of a commit, or against the
+/// working tree copy when `commit_hash` is `None`, using `git diff --no-index` on temp files
+pub fn diff_text_against_file(
+    repo_root: &Path,
+    text: &str,
+    commit_hash: Option<&str>,
+    file_path: &Path,
+    context_lines: u32,
+    text_encoding: Option<TextEncoding>,
+) -> Result<String> {
+    let file_bytes = match commit_hash {
+        Some(hash) => crate::git::show::fetch_file_at_commit(repo_root, hash, file_path)?,
+        None => {
+            std::fs::read(repo_root.join(file_path)).map_err(|_| GeschichteError::FileNotFound {
+                path: file_path.to_path_buf(),
+            })?
+        }
+    };
+    let file_text = encoding::decode(&file_bytes, text_encoding);
+
+    // Run inside a scratch directory so the diff headers read "a/clipboard" and "b/<file name>"
+    let file_name = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "file".to_string());
+    let text_name = if file_name == "clipboard" {
+        "clipboard.txt"
+    } else {
+        "clipboard"
+    };
+    // A fresh directory per call, removed when dropped, so concurrent runs can't collide
+    let scratch_dir = tempfile::Builder::new()
+        .prefix("geschichte-compare-")
+        .tempdir()?;
+
+    run_no_index_diff(
+        scratch_dir.path(),
+        text_name,
+        text,
+        &file_name,
+        &file_text,
+        context_lines,
+    )
+}
+
+/// Write both inputs into `scratch_dir` and diff them there
+fn run_no_index_diff(
+    scratch_dir: &Path,
+    old_name: &str,
+    old_text: &str,
+    new_name: &str,
+    new_text: &str,
+    context_lines: u32,
+) -> Result<String> {
+    std::fs::write(scratch_dir.join(old_name), old_text)?;
+    std::fs::write(scratch_dir.join(new_name), new_text)?;
+
+    let output = Command::new("git")
+        .current_dir(scratch_dir)
+        .arg("diff")
+        .arg("--no-index")
+        .arg("--no-color")
+        .arg(format!("--unified={}", context_lines))
+        .arg("--")
+        .arg(old_name)
+        .arg(new_name)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: "git diff --no-index".to_string(),
+            output: e.to_string(),
+        })?;
+
+    // Exit code 1 just means the inputs differ
+    match output.status.code() {
+        Some(0) => Ok(String::from("No differences from the clipboard\n")),
+        Some(1) => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        _ => Err(GeschichteError::GitCommandFailed {
+            command: "git diff --no-index".to_string(),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        }),
+    }
 }
 
 /// Resolves the path of a file at a specific commit
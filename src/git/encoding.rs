@@ -0,0 +1,70 @@
+use encoding_rs::Encoding;
+use std::path::Path;
+use std::process::Command;
+
+/// How to decode git output for a file whose content may not be UTF-8
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextEncoding {
+    /// Always decode with this encoding (from `--encoding`)
+    Forced(&'static Encoding),
+    /// Decode with this encoding only when the output is not valid UTF-8. Used for the
+    /// `working-tree-encoding` attribute: git converts such files to UTF-8 itself, but
+    /// blobs committed before the attribute was added keep their legacy encoding.
+    Fallback(&'static Encoding),
+}
+
+/// Decode raw git output, defaulting to lossy UTF-8
+pub fn decode(bytes: &[u8], encoding: Option<TextEncoding>) -> String {
+    match encoding {
+        Some(TextEncoding::Forced(encoding)) => {
+            encoding.decode_without_bom_handling(bytes).0.into_owned()
+        }
+        Some(TextEncoding::Fallback(encoding)) => match std::str::from_utf8(bytes) {
+            Ok(text) => text.to_string(),
+            Err(_) => encoding.decode_without_bom_handling(bytes).0.into_owned(),
+        },
+        None => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+/// Look up an encoding by a label such as "latin1", "ISO-8859-1" or "Shift_JIS"
+pub fn encoding_for_label(label: &str) -> Option<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+}
+
+/// Reads the `working-tree-encoding` attribute for a file from `.gitattributes`
+pub fn working_tree_encoding(repo_root: &Path, file_path: &Path) -> Option<&'static Encoding> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .arg("check-attr")
+        .arg("working-tree-encoding")
+        .arg("--")
+        .arg(file_path)
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    // Output looks like "path: working-tree-encoding: ISO-8859-1"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = stdout.trim().rsplit(": ").next()?;
+    match value {
+        "unspecified" | "unset" | "set" => None,
+        label => encoding_for_label(label),
+    }
+}
+
+/// Choose how to decode a file's diffs: an explicit encoding wins, otherwise the
+/// file's `working-tree-encoding` attribute is used as a fallback
+pub fn resolve_encoding(
+    repo_root: &Path,
+    file_path: &Path,
+    forced: Option<&'static Encoding>,
+) -> Option<TextEncoding> {
+    match forced {
+        Some(encoding) => Some(TextEncoding::Forced(encoding)),
+        None => working_tree_encoding(repo_root, file_path).map(TextEncoding::Fallback),
+    }
+}
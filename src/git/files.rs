@@ -1,6 +1,7 @@
 use crate::error::{GeschichteError, Result as GeschichteResult};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
+use globset::{GlobBuilder, GlobMatcher};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::SystemTime;
@@ -46,8 +47,110 @@ impl FileStatus {
     }
 }
 
-/// Get all files in the repository (tracked + untracked, excluding ignored)
-pub fn get_git_files(repo_root: &Path) -> Result<Vec<GitFile>> {
+/// Glob patterns for generated or vendored paths hidden from the file picker, taken from the
+/// config's `exclude` list and the repository's `.geschichteignore`
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeFilter {
+    rules: Vec<ExcludeRule>,
+}
+
+/// One gitignore-style line of an [`ExcludeFilter`]
+#[derive(Debug, Clone)]
+struct ExcludeRule {
+    matcher: GlobMatcher,
+    /// A `!` pattern, re-including what earlier patterns excluded
+    negated: bool,
+    /// A pattern with a trailing `/`, which only matches directories
+    directory_only: bool,
+}
+
+impl ExcludeFilter {
+    pub const IGNORE_FILE: &'static str = ".geschichteignore";
+
+    /// Build from gitignore-style globs; blank lines and `#` comments are skipped. Invalid
+    /// globs are skipped too.
+    pub fn new<S: AsRef<str>>(globs: &[S]) -> Self {
+        let rules = globs
+            .iter()
+            .map(|glob| glob.as_ref().trim())
+            .filter(|glob| !glob.is_empty() && !glob.starts_with('#'))
+            .filter_map(ExcludeRule::parse)
+            .collect();
+
+        Self { rules }
+    }
+
+    /// Combine the configured globs with those in the repository's ignore file, if any
+    pub fn load(repo_root: &Path, config_globs: &[String]) -> Self {
+        let mut globs = config_globs.to_vec();
+        if let Ok(contents) = std::fs::read_to_string(repo_root.join(Self::IGNORE_FILE)) {
+            globs.extend(contents.lines().map(str::to_string));
+        }
+
+        Self::new(&globs)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether a repository-relative path (with `/` separators) is excluded. As in
+    /// `.gitignore`, the last pattern matching the path or one of its directories decides.
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path))
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+impl ExcludeRule {
+    /// Patterns without a `/` match at any depth, a leading `/` anchors to the repository
+    /// root, `**/` spans any number of directories and `*`/`?` stay within one segment
+    fn parse(glob: &str) -> Option<Self> {
+        let (negated, glob) = match glob.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, glob),
+        };
+        let directory_only = glob.ends_with('/');
+        let glob = glob.trim_end_matches('/');
+        let anchored = glob.contains('/');
+        let glob = glob.trim_start_matches('/');
+        if glob.is_empty() {
+            return None;
+        }
+
+        let glob = if anchored {
+            glob.to_string()
+        } else {
+            format!("**/{}", glob)
+        };
+        let matcher = GlobBuilder::new(&glob)
+            .literal_separator(true)
+            .build()
+            .ok()?
+            .compile_matcher();
+
+        Some(Self {
+            matcher,
+            negated,
+            directory_only,
+        })
+    }
+
+    /// A match on a directory also covers everything below it
+    fn matches(&self, path: &str) -> bool {
+        let directories = path.match_indices('/').map(|(index, _)| &path[..index]);
+        let file = (!self.directory_only).then_some(path);
+        directories
+            .chain(file)
+            .any(|candidate| self.matcher.is_match(candidate))
+    }
+}
+
+/// Get all files in the repository (tracked + untracked, excluding ignored and `excludes`)
+pub fn get_git_files(repo_root: &Path, excludes: &ExcludeFilter) -> Result<Vec<GitFile>> {
     let mut files = Vec::new();
 
     // Get all files: tracked + untracked (excluding ignored)
@@ -77,6 +180,10 @@ pub fn get_git_files(repo_root: &Path) -> Result<Vec<GitFile>> {
     let status_map = get_file_status_map(repo_root)?;
 
     for file_path in file_paths {
+        if excludes.is_excluded(file_path) {
+            continue;
+        }
+
         let path = repo_root.join(file_path);
         let display_path = file_path.to_string();
 
@@ -212,18 +319,94 @@ pub fn format_modified_time(modified: Option<SystemTime>) -> String {
     }
 }
 
-/// Verifies that a file exists in the git repository
-pub fn verify_file_in_repo(repo_root: &Path, file_path: &Path) -> GeschichteResult<PathBuf> {
+/// Verifies that a file exists in the git repository. A directory holding tracked files
+/// passes too, for viewing the history of everything under it.
+///
+/// Symlinks are resolved against what git actually tracks: a tracked symlink is used as-is
+/// unless `follow_symlinks` is set, in which case its target is preferred when that is
+/// tracked too. An untracked symlink pointing at a tracked file resolves to the target.
+pub fn verify_file_in_repo(
+    repo_root: &Path,
+    file_path: &Path,
+    follow_symlinks: bool,
+) -> GeschichteResult<PathBuf> {
+    let not_found = || GeschichteError::FileNotFound {
+        path: file_path.to_path_buf(),
+    };
+
     let relative_path = if file_path.is_absolute() {
-        file_path
-            .strip_prefix(repo_root)
-            .map_err(|_| GeschichteError::FileNotFound {
-                path: file_path.to_path_buf(),
-            })?
+        repo_relative_path(repo_root, file_path).ok_or_else(not_found)?
+    } else {
+        file_path.to_path_buf()
+    };
+    // The repository root itself, which git only accepts as `.`
+    let relative_path = if relative_path.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        relative_path
+    };
+
+    let is_symlink = std::fs::symlink_metadata(repo_root.join(&relative_path))
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    // Resolve the symlink target, but only if it stays inside the repository
+    let target_path = if is_symlink {
+        std::fs::canonicalize(repo_root.join(&relative_path))
+            .ok()
+            .and_then(|target| repo_relative_path(repo_root, &target))
     } else {
-        file_path
+        None
     };
 
+    if follow_symlinks {
+        if let Some(ref target) = target_path {
+            if is_tracked(repo_root, target)? {
+                return Ok(target.clone());
+            }
+        }
+    }
+
+    if is_tracked(repo_root, &relative_path)? {
+        return Ok(relative_path);
+    }
+
+    if let Some(target) = target_path {
+        if is_tracked(repo_root, &target)? {
+            return Ok(target);
+        }
+    }
+
+    // A new file has no history yet, but its working directory entry shows its content
+    if crate::git::working::is_untracked(repo_root, &relative_path)? {
+        return Ok(relative_path);
+    }
+
+    Err(not_found())
+}
+
+/// Strips the repository root from an absolute path.
+///
+/// Tries the path as given first and then with its parent directory canonicalized, so paths
+/// reached through symlinked directories (e.g. `/tmp` vs `/private/tmp`) still resolve. The
+/// file name itself is never resolved, which keeps a symlinked file distinct from its target.
+fn repo_relative_path(repo_root: &Path, path: &Path) -> Option<PathBuf> {
+    let canonical_root =
+        std::fs::canonicalize(repo_root).unwrap_or_else(|_| repo_root.to_path_buf());
+
+    for root in [repo_root, canonical_root.as_path()] {
+        if let Ok(relative) = path.strip_prefix(root) {
+            return Some(relative.to_path_buf());
+        }
+    }
+
+    let parent = std::fs::canonicalize(path.parent()?).ok()?;
+    let relative_parent = parent.strip_prefix(&canonical_root).ok()?;
+    Some(relative_parent.join(path.file_name()?))
+}
+
+/// Checks whether git tracks the given repository-relative path
+fn is_tracked(repo_root: &Path, relative_path: &Path) -> GeschichteResult<bool> {
     let output = Command::new("git")
         .arg("ls-files")
         .arg("--error-unmatch")
@@ -235,13 +418,7 @@ pub fn verify_file_in_repo(repo_root: &Path, file_path: &Path) -> GeschichteResu
             output: e.to_string(),
         })?;
 
-    if !output.status.success() {
-        return Err(GeschichteError::FileNotFound {
-            path: file_path.to_path_buf(),
-        });
-    }
-
-    Ok(relative_path.to_path_buf())
+    Ok(output.status.success())
 }
 
 #[cfg(test)]
@@ -1,20 +1,26 @@
-use crate::commit::Commit;
+use crate::commit::{Commit, SignatureInfo};
 use crate::error::{GeschichteError, Result};
+use crate::git::remote::RemoteRepo;
+use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-/// Fetches the commit history for a file with rename tracking
+/// Fetches the commit history for a file with rename tracking. `skip` and `max_count` page
+/// through it, newest first; `None` fetches everything after the skipped commits.
 pub fn fetch_commit_history(
     repo_root: &Path,
     file_path: &Path,
     follow_renames: bool,
     first_parent: bool,
+    skip: usize,
+    max_count: Option<usize>,
 ) -> Result<Vec<Commit>> {
     let mut cmd = Command::new("git");
     cmd.current_dir(repo_root).arg("log");
 
-    if follow_renames {
+    // `--follow` only tracks a single file; a directory lists every commit under it
+    if follow_renames && !repo_root.join(file_path).is_dir() {
         cmd.arg("--follow");
     }
 
@@ -22,50 +28,207 @@ pub fn fetch_commit_history(
         cmd.arg("--first-parent");
     }
 
-    cmd.arg("--format=%H%x00%h%x00%ad%x00%an%x00%ae%x00%cn%x00%ce%x00%cd%x00%s%x00%B")
-        .arg("--date=format:%Y-%m-%d %H:%M:%S")
-        .arg("--")
-        .arg(file_path);
+    add_date_range_args(&mut cmd);
+    add_page_args(&mut cmd, skip, max_count);
+
+    run_log(
+        cmd,
+        Some(file_path),
+        format!("git log --follow {}", file_path.display()),
+    )
+}
+
+/// Fetches the commits of a file's history that change the number of occurrences of
+/// `pattern` (`git log -S`), or with `is_regex` that add or remove lines matching it (`-G`)
+pub fn fetch_commit_history_pickaxe(
+    repo_root: &Path,
+    file_path: &Path,
+    pattern: &str,
+    is_regex: bool,
+) -> Result<Vec<Commit>> {
+    let flag = if is_regex { "-G" } else { "-S" };
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
+        .arg("log")
+        .arg("--follow")
+        .arg(format!("{}{}", flag, pattern));
+    add_date_range_args(&mut cmd);
+
+    run_log(
+        cmd,
+        Some(file_path),
+        format!("git log {}{} {}", flag, pattern, file_path.display()),
+    )
+}
+
+/// The commit `rev` (a branch, tag or hash) points at
+pub fn fetch_ref_commit(repo_root: &Path, rev: &str) -> Result<Commit> {
+    let command = format!("git log -1 {}", rev);
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root).arg("log").arg("-1").arg(rev);
+
+    run_log(cmd, None, command.clone())?
+        .into_iter()
+        .next()
+        .ok_or_else(|| GeschichteError::GitCommandFailed {
+            command,
+            output: format!("{} names no commit", rev),
+        })
+}
+
+/// `--date=` style of commit lists when none is configured
+pub const DEFAULT_DATE_STYLE: &str = "format:%Y-%m-%d %H:%M:%S";
+
+/// git's own date styles, usable as a date format by name
+const NAMED_DATE_STYLES: &[&str] = &[
+    "relative",
+    "local",
+    "iso",
+    "iso8601",
+    "iso-strict",
+    "iso8601-strict",
+    "rfc",
+    "rfc2822",
+    "short",
+    "raw",
+    "human",
+    "unix",
+    "default",
+];
+
+/// Date style chosen at startup; commit lists use `DEFAULT_DATE_STYLE` until one is set
+static SELECTED_DATE_STYLE: OnceCell<String> = OnceCell::new();
+
+/// The `--date=` style for a date format: one of git's styles such as `relative` or
+/// `short`, or a strftime format like `%d.%m.%Y %H:%M`. Empty means the default; anything
+/// else is not a date format.
+pub fn date_style(format: &str) -> Option<String> {
+    let format = format.trim();
+    if format.is_empty() {
+        Some(DEFAULT_DATE_STYLE.to_string())
+    } else if NAMED_DATE_STYLES.contains(&format) {
+        Some(format.to_string())
+    } else if format.contains('%') {
+        Some(format!("format:{}", format))
+    } else {
+        None
+    }
+}
+
+/// Set the date style of commit lists. Only the first call has an effect.
+pub fn set_date_style(style: String) {
+    let _ = SELECTED_DATE_STYLE.set(style);
+}
+
+/// `git log` arguments bounding histories by date, chosen at startup
+static SELECTED_DATE_RANGE: OnceCell<Vec<String>> = OnceCell::new();
+
+/// The `git log` arguments keeping only commits after `since` and before `until`, dates in
+/// any form git reads ("2 weeks ago", "2024-01-31", ...)
+pub fn date_range_args(since: Option<&str>, until: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(since) = since {
+        args.push(format!("--since={}", since));
+    }
+    if let Some(until) = until {
+        args.push(format!("--until={}", until));
+    }
+    args
+}
+
+/// Bound every file history by date. Only the first call has an effect.
+pub fn set_date_range(since: Option<&str>, until: Option<&str>) {
+    let _ = SELECTED_DATE_RANGE.set(date_range_args(since, until));
+}
+
+/// Limits a `git log` command to the commits in the selected date range
+fn add_date_range_args(cmd: &mut Command) {
+    if let Some(args) = SELECTED_DATE_RANGE.get() {
+        cmd.args(args);
+    }
+}
+
+/// Limits a `git log` command to one page of its commits
+fn add_page_args(cmd: &mut Command, skip: usize, max_count: Option<usize>) {
+    if skip > 0 {
+        cmd.arg(format!("--skip={}", skip));
+    }
+    if let Some(max_count) = max_count {
+        cmd.arg(format!("--max-count={}", max_count));
+    }
+}
+
+/// Runs a prepared `git log` command, limited to `file_path` if given, with the format
+/// `parse_log_records` reads, returning its commits
+fn run_log(mut cmd: Command, file_path: Option<&Path>, command: String) -> Result<Vec<Commit>> {
+    // Each record starts with RECORD_SEPARATOR (%x1e) since bodies span multiple lines
+    cmd.arg("--format=%x1e%H%x00%h%x00%ad%x00%an%x00%ae%x00%cn%x00%ce%x00%cd%x00%s%x00%D%x00%b")
+        .arg(format!(
+            "--date={}",
+            SELECTED_DATE_STYLE
+                .get()
+                .map(String::as_str)
+                .unwrap_or(DEFAULT_DATE_STYLE)
+        ))
+        .arg("--");
+    if let Some(file_path) = file_path {
+        cmd.arg(file_path);
+    }
 
     let output = cmd
         .output()
         .map_err(|e| GeschichteError::GitCommandFailed {
-            command: format!("git log --follow {}", file_path.display()),
+            command: command.clone(),
             output: e.to_string(),
         })?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(GeschichteError::GitCommandFailed {
-            command: format!("git log --follow {}", file_path.display()),
+            command,
             output: stderr.to_string(),
         });
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_log_records(&stdout))
+}
+
+/// Marks the start of each `git log` record. Commit bodies span multiple lines, so records
+/// cannot be split on newlines; an ASCII record separator never appears in commit text.
+const RECORD_SEPARATOR: char = '\x1e';
+
+/// Parses `git log` output where each record starts with `RECORD_SEPARATOR` and holds
+/// NUL-separated fields, the last of which is the (possibly multi-line) body
+fn parse_log_records(stdout: &str) -> Vec<Commit> {
     let mut commits = Vec::new();
 
-    for line in stdout.lines() {
-        if line.is_empty() {
+    for record in stdout.split(RECORD_SEPARATOR) {
+        // Drop the newline git appends after each record (and after the body)
+        let record = record.trim_end_matches('\n');
+        if record.is_empty() {
             continue;
         }
 
-        let parts: Vec<&str> = line.split('\0').collect();
-        if parts.len() >= 10 {
+        // Only split off the fixed fields so the body is kept intact
+        let parts: Vec<&str> = record.splitn(11, '\0').collect();
+        if parts.len() >= 11 {
             // New enhanced format: hash, short_hash, author_date, author_name, author_email,
-            // committer_name, committer_email, committer_date, subject, body
-            commits.push(Commit::new_enhanced(
-                parts[0].to_string(), // hash
-                parts[1].to_string(), // short_hash
-                parts[3].to_string(), // author_name
-                parts[4].to_string(), // author_email
-                parts[2].to_string(), // author_date
-                parts[5].to_string(), // committer_name
-                parts[6].to_string(), // committer_email
-                parts[7].to_string(), // committer_date
-                parts[8].to_string(), // subject
-                parts[9].to_string(), // body
-            ));
+            // committer_name, committer_email, committer_date, subject, decorations, body
+            let mut commit = Commit::new_enhanced(
+                parts[0].to_string(),             // hash
+                parts[1].to_string(),             // short_hash
+                parts[3].to_string(),             // author_name
+                parts[4].to_string(),             // author_email
+                parts[2].to_string(),             // author_date
+                parts[5].to_string(),             // committer_name
+                parts[6].to_string(),             // committer_email
+                parts[7].to_string(),             // committer_date
+                parts[8].to_string(),             // subject
+                parts[10].trim_end().to_string(), // body
+            );
+            commit.refs = parse_decorations(parts[9]);
+            commits.push(commit);
         } else if parts.len() >= 5 {
             // Fallback to old format for compatibility
             commits.push(Commit::new(
@@ -78,111 +241,271 @@ pub fn fetch_commit_history(
         }
     }
 
-    Ok(commits)
+    commits
 }
 
-/// Builds a map of commit hashes to file paths for rename tracking
-pub fn build_rename_map(repo_root: &Path, file_path: &Path) -> Result<HashMap<String, PathBuf>> {
+/// Builds a map of commit hashes to file paths for rename tracking. `first_parent`, `skip`
+/// and `max_count` select the same page of commits as `fetch_commit_history`; each commit maps
+/// to the path the file had at that commit, so the maps of several pages can be merged.
+pub fn build_rename_map(
+    repo_root: &Path,
+    file_path: &Path,
+    first_parent: bool,
+    skip: usize,
+    max_count: Option<usize>,
+) -> Result<HashMap<String, PathBuf>> {
     let mut rename_map = HashMap::new();
 
-    let output = Command::new("git")
-        .current_dir(repo_root)
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
         .arg("log")
         .arg("--follow")
         .arg("--name-status")
-        .arg("--format=%H")
-        .arg("--")
-        .arg(file_path)
-        .output()
-        .map_err(|e| GeschichteError::GitCommandFailed {
-            command: format!("git log --follow --name-status {}", file_path.display()),
-            output: e.to_string(),
-        })?;
+        .arg("--format=%H");
+    if first_parent {
+        cmd.arg("--first-parent");
+    }
+    add_date_range_args(&mut cmd);
+    // The name a page starts with depends on the renames in every newer commit, so read
+    // from the newest commit and keep only this page's entries
+    add_page_args(&mut cmd, 0, max_count.map(|max_count| skip + max_count));
+    let output =
+        cmd.arg("--")
+            .arg(file_path)
+            .output()
+            .map_err(|e| GeschichteError::GitCommandFailed {
+                command: format!("git log --follow --name-status {}", file_path.display()),
+                output: e.to_string(),
+            })?;
 
     if !output.status.success() {
         return Ok(rename_map); // Return empty map on failure
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    let mut current_hash = String::new();
-    let mut current_path = file_path.to_path_buf();
 
-    for line in stdout.lines() {
-        if line.is_empty() {
-            continue;
+    // `--format=%H` puts each hash on its own line, followed by the file's status line.
+    // Pair them up first, then walk from newest to oldest knowing the name at each commit.
+    let mut statuses: Vec<(&str, Option<&str>)> = Vec::new();
+    for line in stdout.lines().filter(|line| !line.is_empty()) {
+        if line.len() == 40 && line.chars().all(|c| c.is_ascii_hexdigit()) {
+            statuses.push((line, None));
+        } else if let Some((_, status @ None)) = statuses.last_mut() {
+            *status = Some(line);
         }
+    }
 
-        // Check if this is a commit hash (40 chars)
-        if line.len() == 40 && line.chars().all(|c| c.is_ascii_hexdigit()) {
-            current_hash = line.to_string();
-            rename_map.insert(current_hash.clone(), current_path.clone());
-        } else if line.starts_with('R') {
-            // Parse rename: R100	old_path	new_path
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() == 3 {
-                let old_path = PathBuf::from(parts[1]);
-                let new_path = PathBuf::from(parts[2]);
-                current_path = old_path; // Track the old name for previous commits
-
-                // Update the current commit's path
-                if !current_hash.is_empty() {
-                    rename_map.insert(current_hash.clone(), new_path);
-                }
-            }
-        } else if line.starts_with('A') || line.starts_with('M') || line.starts_with('D') {
-            // Parse regular status: A	path or M	path or D	path
-            let parts: Vec<&str> = line.split('\t').collect();
-            if parts.len() == 2 {
-                current_path = PathBuf::from(parts[1]);
-                if !current_hash.is_empty() {
-                    rename_map.insert(current_hash.clone(), current_path.clone());
-                }
-            }
+    let mut current_path = file_path.to_path_buf();
+    for (index, (hash, status)) in statuses.into_iter().enumerate() {
+        let (path, earlier_path) = match status.and_then(parse_name_status) {
+            Some((path, earlier_path)) => (path, earlier_path),
+            None => (current_path.clone(), None),
+        };
+        // Earlier pages were only read to learn the name this page starts with
+        if index >= skip {
+            rename_map.insert(hash.to_string(), path.clone());
         }
+        current_path = earlier_path.unwrap_or(path);
     }
 
     Ok(rename_map)
 }
 
-/// Fetches additional metadata for a commit (refs, stats, etc.)
-pub fn fetch_commit_refs(repo_root: &Path, commit_hash: &str) -> Result<Vec<String>> {
-    let mut refs = Vec::new();
+/// Read a `--name-status` line into the file's path at that commit and, for a rename or copy,
+/// the path it had before
+fn parse_name_status(line: &str) -> Option<(PathBuf, Option<PathBuf>)> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    match (parts[0].chars().next()?, parts.as_slice()) {
+        ('R' | 'C', [_, old_path, new_path]) => {
+            Some((PathBuf::from(new_path), Some(PathBuf::from(old_path))))
+        }
+        ('A' | 'M' | 'D' | 'T', [_, path]) => Some((PathBuf::from(path), None)),
+        _ => None,
+    }
+}
+
+/// The refs pointing at a commit, from its `%D` decorations ("HEAD -> main, tag: v1.0"), as
+/// `branch:NAME` and `tag:NAME`. HEAD itself is left out; the branch it points at is kept.
+pub fn parse_decorations(decorations: &str) -> Vec<String> {
+    decorations
+        .split(", ")
+        .map(|decoration| decoration.trim_start_matches("HEAD -> "))
+        .filter(|decoration| {
+            !decoration.is_empty() && *decoration != "HEAD" && !decoration.ends_with("/HEAD")
+        })
+        .map(|decoration| match decoration.strip_prefix("tag: ") {
+            Some(tag) => format!("tag:{}", tag),
+            None => format!("branch:{}", decoration),
+        })
+        .collect()
+}
 
-    // Get branches containing this commit
-    if let Ok(output) = Command::new("git")
-        .args(["branch", "--contains", commit_hash])
+/// The verification status and signer of a commit's signature. `None` if git can't tell.
+pub fn fetch_commit_signature(
+    repo_root: &Path,
+    commit_hash: &str,
+) -> Result<Option<SignatureInfo>> {
+    let output = Command::new("git")
         .current_dir(repo_root)
+        .arg("log")
+        .arg("-1")
+        .arg("--format=%G? %GS")
+        .arg(commit_hash)
         .output()
-    {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                let branch = line.trim().trim_start_matches("* ").trim();
-                if !branch.is_empty() && !branch.starts_with("(HEAD detached") {
-                    refs.push(format!("branch:{}", branch));
-                }
-            }
-        }
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git log -1 --format=%G? {}", commit_hash),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!("git log -1 --format=%G? {}", commit_hash),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
     }
 
-    // Get tags at this commit
-    if let Ok(output) = Command::new("git")
-        .args(["tag", "--points-at", commit_hash])
-        .current_dir(repo_root)
+    Ok(SignatureInfo::parse(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Counts the lines of a file as of a commit, or `None` if the file does not exist there
+pub fn fetch_file_line_count(
+    repo_root: &Path,
+    commit_hash: &str,
+    file_path: &Path,
+) -> Result<Option<usize>> {
+    let content = crate::git::show::fetch_blob(repo_root, commit_hash, file_path)?;
+    Ok(content.map(|content| {
+        let newlines = content.iter().filter(|&&b| b == b'\n').count();
+        let unterminated_last_line = !content.is_empty() && !content.ends_with(b"\n");
+        newlines + usize::from(unterminated_last_line)
+    }))
+}
+
+/// Counts the commits in a file's history without listing them. `git rev-list --count`
+/// can't follow renames, so a followed file's commits are counted from a bare `git log`.
+pub fn count_commits(
+    repo_root: &Path,
+    file_path: &Path,
+    follow_renames: bool,
+    first_parent: bool,
+) -> Result<usize> {
+    let follow = follow_renames && !repo_root.join(file_path).is_dir();
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root);
+    if follow {
+        cmd.args(["log", "--follow", "--format=%h"]);
+    } else {
+        cmd.args(["rev-list", "--count", "HEAD"]);
+    }
+    if first_parent {
+        cmd.arg("--first-parent");
+    }
+    add_date_range_args(&mut cmd);
+    let command = format!("git rev-list --count {}", file_path.display());
+    let output =
+        cmd.arg("--")
+            .arg(file_path)
+            .output()
+            .map_err(|e| GeschichteError::GitCommandFailed {
+                command: command.clone(),
+                output: e.to_string(),
+            })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if follow {
+        Ok(stdout.lines().count())
+    } else {
+        stdout
+            .trim()
+            .parse()
+            .map_err(|_| GeschichteError::GitCommandFailed {
+                command,
+                output: stdout.to_string(),
+            })
+    }
+}
+
+/// Fetches the statistics of every commit in a file's history in one `git log --numstat`
+/// pass, keyed by commit hash. `--full-diff` counts all the files each commit changed, like
+/// `fetch_commit_stats` does. Commits without a diff (such as merges) are left out.
+pub fn fetch_all_commit_stats(
+    repo_root: &Path,
+    file_path: &Path,
+    follow_renames: bool,
+    first_parent: bool,
+) -> Result<HashMap<String, crate::commit::CommitStats>> {
+    // git refuses `--follow` with `--full-diff`, so look for every name the file had instead
+    let mut paths = vec![file_path.to_path_buf()];
+    if follow_renames && !repo_root.join(file_path).is_dir() {
+        let rename_map = build_rename_map(repo_root, file_path, first_parent, 0, None)?;
+        paths.extend(rename_map.into_values());
+        paths.sort();
+        paths.dedup();
+    }
+
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root).arg("log");
+    if first_parent {
+        cmd.arg("--first-parent");
+    }
+    add_date_range_args(&mut cmd);
+    let output = cmd
+        .args(["--full-diff", "--numstat", "--format=%x1e%H", "--"])
+        .args(&paths)
         .output()
-    {
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            for line in stdout.lines() {
-                let tag = line.trim();
-                if !tag.is_empty() {
-                    refs.push(format!("tag:{}", tag));
-                }
-            }
-        }
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git log --numstat {}", file_path.display()),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!("git log --numstat {}", file_path.display()),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
     }
 
-    Ok(refs)
+    Ok(parse_numstat_records(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Sums the `--numstat` lines of each record into per-commit statistics. Binary files count
+/// as changed files with no line counts.
+fn parse_numstat_records(output: &str) -> HashMap<String, crate::commit::CommitStats> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let hash = lines.next()?.trim();
+            let mut stats = crate::commit::CommitStats {
+                files_changed: 0,
+                insertions: 0,
+                deletions: 0,
+            };
+            for line in lines {
+                let mut fields = line.split('\t');
+                let (Some(added), Some(removed), Some(_path)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                stats.files_changed += 1;
+                stats.insertions += added.parse().unwrap_or(0);
+                stats.deletions += removed.parse().unwrap_or(0);
+            }
+            (!hash.is_empty() && stats.files_changed > 0).then(|| (hash.to_string(), stats))
+        })
+        .collect()
 }
 
 /// Fetches commit statistics (files changed, insertions, deletions)
@@ -246,15 +569,18 @@ fn parse_stat_line(line: &str) -> Option<crate::commit::CommitStats> {
     })
 }
 
-/// Detects PR information from commit message
-pub fn detect_pr_info(commit: &crate::commit::Commit) -> Option<crate::commit::PullRequestInfo> {
+/// Detects PR information from commit message. Without a remote the PR keeps an empty URL.
+pub fn detect_pr_info(
+    commit: &crate::commit::Commit,
+    remote: Option<&RemoteRepo>,
+) -> Option<crate::commit::PullRequestInfo> {
     // Method 1: Check for merge commit patterns first (more specific)
     if commit.subject.starts_with("Merge pull request #") {
         if let Some(pr_num) = extract_pr_number(&commit.subject) {
             return Some(crate::commit::PullRequestInfo {
                 number: pr_num,
                 title: commit.subject.clone(),
-                url: build_pr_url(pr_num),
+                url: build_pr_url(remote, pr_num),
                 status: crate::commit::PRStatus::Merged,
             });
         }
@@ -265,7 +591,7 @@ pub fn detect_pr_info(commit: &crate::commit::Commit) -> Option<crate::commit::P
         return Some(crate::commit::PullRequestInfo {
             number: pr_num,
             title: extract_pr_title(&commit.subject),
-            url: build_pr_url(pr_num),
+            url: build_pr_url(remote, pr_num),
             status: crate::commit::PRStatus::Unknown,
         });
     }
@@ -305,10 +631,71 @@ fn extract_pr_title(message: &str) -> String {
     }
 }
 
-fn build_pr_url(pr_number: u32) -> String {
-    // This would ideally detect the remote origin and build appropriate URL
-    // For now, return a placeholder
-    format!("https://github.com/repo/pull/{}", pr_number)
+fn build_pr_url(remote: Option<&RemoteRepo>, pr_number: u32) -> String {
+    remote
+        .map(|remote| remote.pull_request_url(pr_number))
+        .unwrap_or_default()
+}
+
+/// The newest commit at or before `commit_hash` that changed `file_path`, if any. A parent
+/// that didn't touch the file leads to the commit the file's history shows next.
+pub fn find_file_commit(
+    repo_root: &Path,
+    commit_hash: &str,
+    file_path: &Path,
+) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .arg("rev-list")
+        .arg("-n1")
+        .arg(commit_hash)
+        .arg("--")
+        .arg(file_path)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!(
+                "git rev-list -n1 {} -- {}",
+                commit_hash,
+                file_path.display()
+            ),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!(
+                "git rev-list -n1 {} -- {}",
+                commit_hash,
+                file_path.display()
+            ),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!hash.is_empty()).then_some(hash))
+}
+
+/// The full hash of the commit `rev` names (a hash prefix, branch or tag), if it names one
+pub fn resolve_commit(repo_root: &Path, rev: &str) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .arg("rev-parse")
+        .arg("--verify")
+        .arg("--quiet")
+        .arg(format!("{}^{{commit}}", rev))
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git rev-parse --verify {}", rev),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok((!hash.is_empty()).then_some(hash))
 }
 
 /// Gets the parent commits for a given commit
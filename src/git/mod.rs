@@ -1,7 +1,12 @@
+pub mod blame;
 pub mod commands;
 pub mod diff;
+pub mod encoding;
 pub mod files;
 pub mod history;
+pub mod pr;
+pub mod remote;
+pub mod show;
 pub mod working;
 
 use crate::error::{GeschichteError, Result};
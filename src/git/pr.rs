@@ -0,0 +1,70 @@
+use crate::commit::{PRStatus, PullRequestInfo};
+use crate::error::{GeschichteError, Result};
+use serde::Deserialize;
+use std::io::ErrorKind;
+use std::process::Command;
+
+/// The fields asked of `gh pr view --json`
+#[derive(Deserialize)]
+struct PrView {
+    state: String,
+    title: String,
+    url: String,
+}
+
+/// A pull request's state, title and URL as GitHub reports them, through the `gh` CLI.
+/// `Ok(None)` when `gh` isn't installed; an error when it fails, e.g. when not signed in or
+/// the number is an issue rather than a pull request.
+pub fn fetch_pr_status(owner: &str, repo: &str, number: u32) -> Result<Option<PullRequestInfo>> {
+    let command = format!("gh pr view {} --repo {}/{}", number, owner, repo);
+    let output = match Command::new("gh")
+        .arg("pr")
+        .arg("view")
+        .arg(number.to_string())
+        .arg("--repo")
+        .arg(format!("{}/{}", owner, repo))
+        .arg("--json")
+        .arg("state,title,url")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(GeschichteError::GitCommandFailed {
+                command,
+                output: e.to_string(),
+            })
+        }
+    };
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    parse_pr_view(number, &String::from_utf8_lossy(&output.stdout))
+        .map(Some)
+        .ok_or_else(|| GeschichteError::GitCommandFailed {
+            command,
+            output: "unexpected output".to_string(),
+        })
+}
+
+/// Read the JSON `gh pr view --json state,title,url` prints
+pub fn parse_pr_view(number: u32, json: &str) -> Option<PullRequestInfo> {
+    let view: PrView = serde_json::from_str(json).ok()?;
+    let status = match view.state.as_str() {
+        "OPEN" => PRStatus::Open,
+        "CLOSED" => PRStatus::Closed,
+        "MERGED" => PRStatus::Merged,
+        _ => PRStatus::Unknown,
+    };
+    Some(PullRequestInfo {
+        number,
+        title: view.title,
+        url: view.url,
+        status,
+    })
+}
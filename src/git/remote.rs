@@ -0,0 +1,127 @@
+use crate::error::{GeschichteError, Result};
+use std::path::Path;
+use std::process::Command;
+
+/// Hosting service behind a remote, which decides the shape of its web URLs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteHost {
+    GitHub,
+    GitLab,
+    Bitbucket,
+}
+
+impl RemoteHost {
+    /// Self-hosted GitLab and Bitbucket servers usually carry the product in their name;
+    /// anything else is assumed to be GitHub or GitHub Enterprise
+    fn from_domain(domain: &str) -> Self {
+        let domain = domain.to_ascii_lowercase();
+        if domain.contains("gitlab") {
+            RemoteHost::GitLab
+        } else if domain.contains("bitbucket") {
+            RemoteHost::Bitbucket
+        } else {
+            RemoteHost::GitHub
+        }
+    }
+}
+
+/// A remote repository, reduced from its clone URL to a web host and `owner/repo` path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    pub host: RemoteHost,
+    pub domain: String,
+    /// `owner/repo`, or `group/subgroup/repo` on GitLab
+    pub path: String,
+}
+
+impl RemoteRepo {
+    /// Parse SSH (`git@host:owner/repo.git`, `ssh://git@host/owner/repo`) and HTTP(S) clone
+    /// URLs. Local paths and other unrecognized forms give `None`.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (authority, path) = match url.split_once("://") {
+            Some((_, rest)) => rest.split_once('/')?,
+            // scp-like syntax: [user@]host:path
+            None => url.split_once(':')?,
+        };
+
+        // Drop credentials and any port
+        let domain = authority.rsplit('@').next()?.split(':').next()?;
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+
+        if domain.is_empty() || !path.contains('/') || path.contains('\\') {
+            return None;
+        }
+
+        Some(Self {
+            host: RemoteHost::from_domain(domain),
+            domain: domain.to_string(),
+            path: path.to_string(),
+        })
+    }
+
+    /// Web page of the repository
+    pub fn web_url(&self) -> String {
+        format!("https://{}/{}", self.domain, self.path)
+    }
+
+    /// Web page of a commit
+    pub fn commit_url(&self, hash: &str) -> String {
+        let segment = match self.host {
+            RemoteHost::GitHub => "commit",
+            RemoteHost::GitLab => "-/commit",
+            RemoteHost::Bitbucket => "commits",
+        };
+        format!("{}/{}/{}", self.web_url(), segment, hash)
+    }
+
+    /// Web page of a file as of a commit, scrolled to `line`
+    pub fn blob_url(&self, hash: &str, path: &Path, line: usize) -> String {
+        // Git paths always use '/'
+        let path = path.to_string_lossy().replace('\\', "/");
+        match self.host {
+            RemoteHost::GitHub => format!("{}/blob/{}/{}#L{}", self.web_url(), hash, path, line),
+            RemoteHost::GitLab => format!("{}/-/blob/{}/{}#L{}", self.web_url(), hash, path, line),
+            RemoteHost::Bitbucket => {
+                format!("{}/src/{}/{}#lines-{}", self.web_url(), hash, path, line)
+            }
+        }
+    }
+
+    /// Web page of a pull request (a merge request on GitLab)
+    pub fn pull_request_url(&self, number: u32) -> String {
+        let segment = match self.host {
+            RemoteHost::GitHub => "pull",
+            RemoteHost::GitLab => "-/merge_requests",
+            RemoteHost::Bitbucket => "pull-requests",
+        };
+        format!("{}/{}/{}", self.web_url(), segment, number)
+    }
+}
+
+/// Look up the `origin` remote and reduce it to its web host and repository path
+pub fn get_remote_url(repo_root: &Path) -> Result<RemoteRepo> {
+    let command = "git remote get-url origin";
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: command.to_string(),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: command.to_string(),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    let url = String::from_utf8_lossy(&output.stdout);
+    RemoteRepo::parse(&url).ok_or_else(|| GeschichteError::GitCommandFailed {
+        command: command.to_string(),
+        output: format!("unrecognized remote URL: {}", url.trim()),
+    })
+}
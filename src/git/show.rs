@@ -0,0 +1,152 @@
+use crate::error::{GeschichteError, Result};
+use crate::git::working::WorkingDirectoryStatus;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Fetches the complete `git show` output for a commit (metadata plus the diff of every file)
+pub fn fetch_full_show(repo_root: &Path, hash: &str) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .arg("show")
+        .arg("--no-color")
+        .arg(hash)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git show {}", hash),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command: format!("git show {}", hash),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Fetches the raw contents of a file as of a commit, or `None` if the file does not exist there
+pub fn fetch_blob(repo_root: &Path, hash: &str, file_path: &Path) -> Result<Option<Vec<u8>>> {
+    let object = format!("{}:{}", hash, file_path.to_string_lossy());
+    let output = Command::new("git")
+        .args(["cat-file", "blob", &object])
+        .current_dir(repo_root)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git cat-file blob {}", object),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(Some(output.stdout))
+}
+
+/// Fetches the raw contents of a file as of a commit
+pub fn fetch_file_at_commit(repo_root: &Path, hash: &str, file_path: &Path) -> Result<Vec<u8>> {
+    fetch_blob(repo_root, hash, file_path)?.ok_or_else(|| GeschichteError::FileNotFound {
+        path: file_path.to_path_buf(),
+    })
+}
+
+/// What a patch of the selection is made from
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchSource {
+    /// A single commit, as `git am` takes it
+    Commit(String),
+    /// The commits after `older` up to and including `newer`
+    Range { older: String, newer: String },
+    /// Uncommitted changes to a file: the staged ones when only those exist, the unstaged
+    /// ones when only those exist, and everything since HEAD when there are both
+    WorkingDirectory {
+        file_path: PathBuf,
+        status: WorkingDirectoryStatus,
+    },
+}
+
+impl PatchSource {
+    /// Arguments to git producing the patch: on stdout, or with `output_dir` written there as
+    /// `NNNN-subject.patch` files (`git format-patch` names them; only commits can be saved)
+    pub fn git_args(&self, output_dir: Option<&Path>) -> Vec<String> {
+        let destination = match output_dir {
+            Some(dir) => vec!["-o".to_string(), dir.to_string_lossy().into_owned()],
+            None => vec!["--stdout".to_string()],
+        };
+        match self {
+            PatchSource::Commit(hash) => ["format-patch", "-1"]
+                .iter()
+                .map(|arg| arg.to_string())
+                .chain(destination)
+                .chain([hash.clone()])
+                .collect(),
+            PatchSource::Range { older, newer } => std::iter::once("format-patch".to_string())
+                .chain(destination)
+                .chain([format!("{}..{}", older, newer)])
+                .collect(),
+            PatchSource::WorkingDirectory { file_path, status } => {
+                let mut args = vec!["diff".to_string()];
+                match status {
+                    WorkingDirectoryStatus::Staged => args.push("--staged".to_string()),
+                    WorkingDirectoryStatus::ModifiedAndStaged => args.push("HEAD".to_string()),
+                    WorkingDirectoryStatus::Modified
+                    | WorkingDirectoryStatus::Clean
+                    | WorkingDirectoryStatus::Untracked => {}
+                }
+                args.push("--".to_string());
+                args.push(file_path.to_string_lossy().into_owned());
+                args
+            }
+        }
+    }
+}
+
+/// Fetches the patch for `source`, ready for `git am` (or `git apply` for uncommitted changes)
+pub fn fetch_patch(repo_root: &Path, source: &PatchSource) -> Result<String> {
+    run_git(repo_root, &source.git_args(None))
+}
+
+/// Writes the patches of `source` into `dir` (relative to `repo_root` unless absolute) and
+/// returns the files written
+pub fn save_patch(repo_root: &Path, source: &PatchSource, dir: &Path) -> Result<Vec<PathBuf>> {
+    let dir = repo_root.join(dir);
+    if let PatchSource::WorkingDirectory { .. } = source {
+        // Not a commit, so git has no subject to name the file after
+        let path = dir.join("0001-uncommitted-changes.patch");
+        fs::create_dir_all(dir)?;
+        fs::write(&path, fetch_patch(repo_root, source)?)?;
+        return Ok(vec![path]);
+    }
+
+    // format-patch prints the files it wrote, relative to where it ran
+    let written = run_git(repo_root, &source.git_args(Some(&dir)))?;
+    Ok(written
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| repo_root.join(line))
+        .collect())
+}
+
+fn run_git(repo_root: &Path, args: &[String]) -> Result<String> {
+    let command = format!("git {}", args.join(" "));
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(args)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: command.clone(),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -1,4 +1,5 @@
 use crate::error::{GeschichteError, Result};
+use crate::git::encoding::{self, TextEncoding};
 use std::path::Path;
 use std::process::Command;
 
@@ -8,6 +9,21 @@ pub enum WorkingDirectoryStatus {
     Modified,
     Staged,
     ModifiedAndStaged,
+    /// Not tracked by git yet, so every line of it is new
+    Untracked,
+}
+
+impl WorkingDirectoryStatus {
+    /// How the working directory entry describes the file; nothing when it is clean
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            WorkingDirectoryStatus::Clean => None,
+            WorkingDirectoryStatus::Modified => Some("Modified"),
+            WorkingDirectoryStatus::Staged => Some("Staged"),
+            WorkingDirectoryStatus::ModifiedAndStaged => Some("Modified + Staged"),
+            WorkingDirectoryStatus::Untracked => Some("Untracked"),
+        }
+    }
 }
 
 /// Checks if the working directory has changes for the specified file
@@ -50,20 +66,76 @@ pub fn check_working_directory_status(
         (true, true) => Ok(WorkingDirectoryStatus::ModifiedAndStaged),
         (true, false) => Ok(WorkingDirectoryStatus::Staged),
         (false, true) => Ok(WorkingDirectoryStatus::Modified),
+        (false, false) if is_untracked(repo_root, file_path)? => {
+            Ok(WorkingDirectoryStatus::Untracked)
+        }
         (false, false) => Ok(WorkingDirectoryStatus::Clean),
     }
 }
 
+/// Whether the file is new to git and not ignored (`git status` shows it as `??`)
+pub fn is_untracked(repo_root: &Path, file_path: &Path) -> Result<bool> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["ls-files", "--others", "--exclude-standard", "--"])
+        .arg(file_path)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: format!("git ls-files --others {}", file_path.display()),
+            output: e.to_string(),
+        })?;
+
+    Ok(output.status.success() && !output.stdout.is_empty())
+}
+
+/// Stages the file's working directory changes (`git add`)
+pub fn stage_file(repo_root: &Path, file_path: &Path) -> Result<()> {
+    run_index_command(repo_root, &["add"], file_path)
+}
+
+/// Unstages the file, keeping its changes in the working directory (`git restore --staged`)
+pub fn unstage_file(repo_root: &Path, file_path: &Path) -> Result<()> {
+    run_index_command(repo_root, &["restore", "--staged"], file_path)
+}
+
+fn run_index_command(repo_root: &Path, args: &[&str], file_path: &Path) -> Result<()> {
+    let command = format!("git {} {}", args.join(" "), file_path.display());
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(args)
+        .arg("--")
+        .arg(file_path)
+        .output()
+        .map_err(|e| GeschichteError::GitCommandFailed {
+            command: command.clone(),
+            output: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(GeschichteError::GitCommandFailed {
+            command,
+            output: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
 /// Fetches the working directory diff vs HEAD
 pub fn fetch_working_directory_diff(
     repo_root: &Path,
     file_path: &Path,
     context_lines: u32,
+    ignore_whitespace: bool,
+    text_encoding: Option<TextEncoding>,
 ) -> Result<String> {
-    let output = Command::new("git")
-        .current_dir(repo_root)
+    let mut cmd = Command::new("git");
+    cmd.current_dir(repo_root)
         .arg("diff")
-        .arg(format!("--unified={}", context_lines))
+        .arg(format!("--unified={}", context_lines));
+    if ignore_whitespace {
+        cmd.arg("-w");
+    }
+    let output = cmd
         .arg("HEAD")
         .arg("--")
         .arg(file_path)
@@ -78,7 +150,7 @@ pub fn fetch_working_directory_diff(
         // It's okay if the file doesn't exist in HEAD (new file)
         if stderr.contains("does not exist") || stderr.contains("pathspec") {
             // For new files, show the entire file as additions
-            return fetch_new_file_diff(repo_root, file_path);
+            return fetch_new_file_diff(repo_root, file_path, text_encoding);
         }
         return Err(GeschichteError::GitCommandFailed {
             command: format!("git diff HEAD {}", file_path.display()),
@@ -86,18 +158,30 @@ pub fn fetch_working_directory_diff(
         });
     }
 
-    let diff_output = String::from_utf8_lossy(&output.stdout).to_string();
+    let diff_output = encoding::decode(&output.stdout, text_encoding);
 
-    // If no diff output, the working directory might be clean
-    if diff_output.trim().is_empty() {
+    // git diff leaves untracked files out, so show their whole content as added
+    if diff_output.trim().is_empty() && is_untracked(repo_root, file_path)? {
+        fetch_new_file_diff(repo_root, file_path, text_encoding)
+    } else if diff_output.trim().is_empty() {
+        // If no diff output, the working directory might be clean
         Ok("Working directory is clean - no changes detected".to_string())
     } else {
-        Ok(diff_output)
+        Ok(crate::git::diff::describe_binary_changes(
+            repo_root,
+            Some("HEAD"),
+            None,
+            diff_output,
+        ))
     }
 }
 
 /// Handles new files that don't exist in HEAD
-fn fetch_new_file_diff(repo_root: &Path, file_path: &Path) -> Result<String> {
+fn fetch_new_file_diff(
+    repo_root: &Path,
+    file_path: &Path,
+    text_encoding: Option<TextEncoding>,
+) -> Result<String> {
     let output = Command::new("git")
         .current_dir(repo_root)
         .arg("diff")
@@ -111,7 +195,7 @@ fn fetch_new_file_diff(repo_root: &Path, file_path: &Path) -> Result<String> {
         })?;
 
     // git diff --no-index returns exit code 1 when files differ, which is expected
-    let diff_output = String::from_utf8_lossy(&output.stdout).to_string();
+    let diff_output = encoding::decode(&output.stdout, text_encoding);
 
     if diff_output.trim().is_empty() {
         // Fallback: try to show the file as entirely new
@@ -122,6 +206,11 @@ fn fetch_new_file_diff(repo_root: &Path, file_path: &Path) -> Result<String> {
             file_path.display()
         ))
     } else {
-        Ok(diff_output)
+        Ok(crate::git::diff::describe_binary_changes(
+            repo_root,
+            None,
+            None,
+            diff_output,
+        ))
     }
 }
@@ -2,10 +2,13 @@ pub mod app;
 pub mod cache;
 pub mod cli;
 pub mod commit;
+pub mod config;
 pub mod copy;
 pub mod diff;
 pub mod error;
+pub mod export;
 pub mod git;
+pub mod session;
 pub mod terminal;
 pub mod ui;
 
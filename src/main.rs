@@ -1,20 +1,11 @@
-mod app;
-mod cache;
-mod cli;
-mod commit;
-mod copy;
-mod diff;
-mod error;
-mod git;
-mod terminal;
-mod ui;
-
 use anyhow::Result;
 use clap::Parser;
 use crossterm::event::{
     self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind, MouseButton, MouseEvent,
     MouseEventKind,
 };
+use geschichte::{app, cache, cli, config, copy, diff, error, export, git, session, terminal, ui};
+use std::io::IsTerminal;
 use std::time::Duration;
 
 fn main() -> Result<()> {
@@ -22,9 +13,7 @@ fn main() -> Result<()> {
     let original_panic_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         // Try to restore terminal
-        let _ = crossterm::execute!(std::io::stdout(), DisableMouseCapture);
-        let _ = crossterm::terminal::disable_raw_mode();
-        let _ = crossterm::execute!(std::io::stdout(), crossterm::terminal::LeaveAlternateScreen);
+        terminal::emergency_restore();
 
         // Call the original panic handler
         original_panic_hook(panic_info);
@@ -40,12 +29,32 @@ fn main() -> Result<()> {
         env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
     }
 
+    // Restore the terminal on SIGINT/SIGTERM too, e.g. when interrupted during a blocking
+    // git call. In raw mode Ctrl+C arrives as a key event and is handled by the app instead.
+    if let Err(e) = ctrlc::set_handler(|| {
+        terminal::emergency_restore();
+        std::process::exit(130);
+    }) {
+        log::warn!("Failed to install interrupt handler: {}", e);
+    }
+
     // Validate arguments
     if let Err(e) = args.validate() {
         eprintln!("Error: {}", e);
         std::process::exit(1);
     }
 
+    // Choose the syntax theme before anything is highlighted
+    let bat_theme = if args.theme_from_bat {
+        std::env::var("BAT_THEME").ok()
+    } else {
+        None
+    };
+    diff::syntax::set_theme(diff::syntax::resolve_theme(
+        args.theme.as_deref(),
+        bat_theme.as_deref(),
+    ));
+
     // Run the application
     if let Err(e) = run(args) {
         eprintln!("Error: {}", e);
@@ -71,23 +80,85 @@ fn run(args: cli::Args) -> Result<()> {
     })?;
 
     log::debug!("Found git repository at: {}", repo_root.display());
+    git::history::set_date_range(args.since.as_deref(), args.until.as_deref());
+
+    // Apply user configuration
+    let config = config::Config::load(&repo_root)?;
+    // `--date-format` was checked with the other arguments; the config's is checked here. Dates
+    // in --json follow it too.
+    let date_format = args.date_format.as_ref().or(config.date_format.as_ref());
+    if let Some(format) = date_format {
+        let style = git::history::date_style(format).ok_or_else(|| {
+            error::GeschichteError::ConfigError(format!("unknown date format \"{}\"", format))
+        })?;
+        git::history::set_date_style(style);
+    }
+
+    // Print the history without setting up a terminal, so pipes and CI work. Clap only
+    // accepts --json with a FILE.
+    if let Some(file_path_arg) = args.file_path.as_ref().filter(|_| args.json) {
+        let file_path = absolute_path(file_path_arg)?;
+        let relative_path =
+            git::files::verify_file_in_repo(&repo_root, &file_path, args.follow_symlinks)?;
+        export::write_history_json(
+            &mut std::io::stdout().lock(),
+            &repo_root,
+            &relative_path,
+            !args.no_follow,
+            args.first_parent,
+            args.with_stats,
+            args.max_count,
+        )?;
+        return Ok(());
+    }
 
     // Get the effective layout mode
     let layout_mode = args.effective_layout();
     let effective_context_lines = args.effective_context_lines();
+    let encoding_override = args
+        .encoding
+        .as_deref()
+        .and_then(git::encoding::encoding_for_label);
+
+    let keymap = config::keys::KeyMap::load()?;
+    let theme = config::theme::Theme::load(args.palette)?;
+    let no_color = args.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    config::theme::set_theme(if no_color {
+        theme.without_color()
+    } else {
+        theme
+    });
+    // Unlike `--syntax`, the config's languages can't be checked up front
+    for (pattern, language) in &config.syntax_overrides {
+        if diff::syntax::find_syntax(language).is_none() {
+            return Err(error::GeschichteError::ConfigError(format!(
+                "unknown syntax \"{}\" for {}",
+                language, pattern
+            ))
+            .into());
+        }
+    }
+    diff::syntax::set_overrides(config.syntax_overrides.clone());
+    let exclude_filter = git::files::ExcludeFilter::load(&repo_root, &config.exclude);
+    let disk_cache_dir = if args.no_cache {
+        None
+    } else {
+        cache::disk_cache_dir(&repo_root)
+    };
 
     // Create application state based on whether file argument was provided
-    let mut app = if let Some(file_path_arg) = args.file_path {
+    let mut app = if let Some(ref file_path_arg) = args.file_path {
         // File argument provided - use history mode
-        let file_path = if file_path_arg.is_absolute() {
-            file_path_arg
-        } else {
-            std::env::current_dir()?.join(&file_path_arg)
-        };
+        let file_path = absolute_path(file_path_arg)?;
 
         // Verify file exists in git
-        let relative_path = git::files::verify_file_in_repo(&repo_root, &file_path)?;
-        log::debug!("Viewing history for: {}", relative_path.display());
+        let relative_path =
+            git::files::verify_file_in_repo(&repo_root, &file_path, args.follow_symlinks)?;
+        if repo_root.join(&relative_path).is_dir() {
+            log::debug!("Viewing history for directory: {}", relative_path.display());
+        } else {
+            log::debug!("Viewing history for: {}", relative_path.display());
+        }
 
         let mut app = app::App::new_history(
             repo_root,
@@ -97,9 +168,21 @@ fn run(args: cli::Args) -> Result<()> {
             args.first_parent,
             layout_mode,
         );
+        app.encoding_override = encoding_override;
+        app.syntax_override = args.syntax.clone();
+        app.exclude_filter = exclude_filter;
+        app.reverse = args.reverse;
+        app.max_commits = args.max_count;
+        app.show_commit_stats = args.stats;
+        if let Some(dir) = disk_cache_dir {
+            app.diff_cache.set_disk_dir(dir);
+        }
+        if let Some([ref_a, ref_b]) = args.compare.as_deref() {
+            app.compared_refs = Some((ref_a.clone(), ref_b.clone()));
+        }
 
-        // Load git data
-        if let Err(e) = app.load_git_data() {
+        // Load git data; the history arrives while the UI is already up
+        if let Err(e) = app.load_git_data_in_background() {
             eprintln!("Failed to load git data: {}", e);
             std::process::exit(1);
         }
@@ -113,8 +196,19 @@ fn run(args: cli::Args) -> Result<()> {
             !args.no_follow,
             args.first_parent,
             layout_mode,
+            exclude_filter,
         ) {
-            Ok(app) => app,
+            Ok(mut app) => {
+                app.encoding_override = encoding_override;
+                app.syntax_override = args.syntax.clone();
+                app.reverse = args.reverse;
+                app.max_commits = args.max_count;
+                app.show_commit_stats = args.stats;
+                if let Some(dir) = disk_cache_dir {
+                    app.diff_cache.set_disk_dir(dir);
+                }
+                app
+            }
             Err(e) => {
                 eprintln!("Failed to initialize file picker: {}", e);
                 std::process::exit(1);
@@ -122,6 +216,51 @@ fn run(args: cli::Args) -> Result<()> {
         }
     };
 
+    // Restore the previous session; an explicit --layout wins over the saved one
+    app.session_file = if args.no_session {
+        None
+    } else {
+        session::session_file()
+    };
+    let last_file = app.load_session(!args.layout_given()).filter(|last_file| {
+        app.get_file_path().is_none() && app.repo_root.join(last_file).exists()
+    });
+    if let Some(last_file) = last_file {
+        if args.last {
+            if let Err(e) = app.switch_to_history(last_file, true) {
+                eprintln!("Failed to reopen the last file: {}", e);
+                std::process::exit(1);
+            }
+        } else {
+            // Offer it: Enter in the picker reopens it
+            app.select_in_file_picker(&last_file);
+        }
+    }
+
+    app.show_whitespace = args.show_whitespace;
+    app.syntax_highlighting = !args.no_highlight;
+    app.print_on_exit = args.print_on_exit;
+    app.ignore_whitespace = args.ignore_whitespace;
+    app.ui_state.commits_bottom = args.commits_bottom;
+    app.watch_interval = args.watch_interval.map(Duration::from_millis);
+    if let Some(ref patch_dir) = args.patch_dir {
+        app.patch_dir = absolute_path(patch_dir)?;
+    }
+
+    app.enter_action = config.enter_action;
+    if let Some(auto_width) = args.auto_width.or(config.auto_width) {
+        app.auto_width = auto_width;
+    }
+    app.keymap = keymap;
+    app.copier = copy::CommitCopier::with_mode(args.clipboard);
+    app.copier
+        .set_remote(git::remote::get_remote_url(&app.repo_root).ok());
+
+    app.set_initial_focus(match args.focus {
+        cli::InitialFocus::Commits => app::FocusedPanel::Commits,
+        cli::InitialFocus::Diff => app::FocusedPanel::Diff,
+    });
+
     // Setup terminal
     let mut terminal = terminal::setup_terminal()?;
 
@@ -137,9 +276,32 @@ fn run(args: cli::Args) -> Result<()> {
     // Restore terminal
     terminal::restore_terminal(&mut terminal)?;
 
+    // Only with the terminal back to normal, so the diff lands in the shell (or a pipe)
+    if let Some(ref output) = app.on_exit_output {
+        let stdout = std::io::stdout();
+        let color = stdout.is_terminal() && !config::theme::current().no_color;
+        export::write_diff(&mut stdout.lock(), output, color)?;
+    }
+
+    // Only a clean exit is remembered
+    if result.is_ok() {
+        if let Err(e) = app.save_session() {
+            log::warn!("Failed to save the session: {}", e);
+        }
+    }
+
     result
 }
 
+/// Resolve a path given on the command line against the current directory
+fn absolute_path(path: &std::path::Path) -> Result<std::path::PathBuf> {
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
 fn run_ui(terminal: &mut terminal::AppTerminal, app: &mut app::App) -> Result<()> {
     loop {
         // Draw the UI
@@ -150,6 +312,7 @@ fn run_ui(terminal: &mut terminal::AppTerminal, app: &mut app::App) -> Result<()
         terminal.draw(|frame| {
             // Update terminal dimensions before drawing
             app.handle_resize(frame.area().width, frame.area().height);
+            app.prepare_styled_lines();
             ui::draw(frame, app);
         })?;
 
@@ -186,6 +349,18 @@ fn run_ui(terminal: &mut terminal::AppTerminal, app: &mut app::App) -> Result<()
         // Check for message timeout
         app.check_message_timeout();
 
+        // Show the history once the background load has fetched it
+        app.poll_history_load();
+
+        // Keep the working directory entry live when watching
+        app.poll_working_tree();
+
+        // Continue loading stats for the file statistics popup
+        app.load_pending_file_stats();
+
+        // Continue searching commit diffs for the history search
+        app.load_pending_history_search();
+
         // Check if we should quit
         if app.should_quit {
             terminal.clear()?;
@@ -202,42 +377,32 @@ enum PanelType {
     Diff,
 }
 
-fn get_panel_at_position(app: &app::App, col: u16, _row: u16) -> Option<PanelType> {
-    // Calculate panel boundaries based on split ratio and actual terminal width
-    let split_ratio = app.ui_state.split_ratio;
-    let terminal_width = app.ui_state.terminal_width;
-    let split_point = (terminal_width as f32 * split_ratio) as u16;
+fn get_panel_at_position(app: &app::App, col: u16, row: u16) -> Option<PanelType> {
+    let commits_area = app.commits_panel_area();
+    let in_commits = if app.commits_at_bottom() {
+        row >= commits_area.y
+    } else {
+        col < commits_area.right()
+    };
 
-    if col < split_point {
+    if in_commits {
         Some(PanelType::Commits)
     } else {
         Some(PanelType::Diff)
     }
 }
 
-fn get_commit_at_row(app: &app::App, row: u16) -> Option<usize> {
-    // Calculate which commit corresponds to the clicked row
-    // Account for:
-    // - Panel borders (typically 1 row at top)
-    // - Title row is inside the border
-
-    if row <= 1 {
-        return None; // Clicked on border or title
-    }
-
-    let commit_row = row.saturating_sub(2); // Account for border and title
-    let commit_index = commit_row as usize;
-
-    if commit_index < app.commits.len() {
-        Some(commit_index)
-    } else {
-        None
-    }
-}
-
 fn handle_mouse_event(app: &mut app::App, mouse_event: MouseEvent) -> Result<()> {
-    // Only handle mouse events in history mode
-    if !matches!(app.mode, app::AppMode::History { .. }) {
+    if let app::AppMode::FilePicker { ref mut state, .. } = app.mode {
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => state.move_up(),
+            MouseEventKind::ScrollDown => state.move_down(),
+            MouseEventKind::Down(MouseButton::Left) => app.click_file_picker(mouse_event.row)?,
+            _ => {}
+        }
+        return Ok(());
+    }
+    if app.is_loading() {
         return Ok(());
     }
 
@@ -245,46 +410,42 @@ fn handle_mouse_event(app: &mut app::App, mouse_event: MouseEvent) -> Result<()>
         MouseEventKind::ScrollUp => {
             match get_panel_at_position(app, mouse_event.column, mouse_event.row) {
                 Some(PanelType::Diff) => {
-                    app.ui_state.scroll_diff_up();
+                    app.ui_state.scroll_diff_line_up();
                 }
-                Some(PanelType::Commits) => {
-                    if app.selected_index > 0 {
-                        app.move_selection_up()?;
-                    }
+                Some(PanelType::Commits) if app.selected_index > 0 => {
+                    app.move_selection_up()?;
                 }
-                None => {}
+                _ => {}
             }
         }
         MouseEventKind::ScrollDown => {
             match get_panel_at_position(app, mouse_event.column, mouse_event.row) {
                 Some(PanelType::Diff) => {
                     let max_lines = app.get_diff_line_count();
-                    app.ui_state.scroll_diff_down(max_lines);
+                    app.ui_state.scroll_diff_line_down(max_lines);
                 }
-                Some(PanelType::Commits) => {
-                    if app.selected_index + 1 < app.commits.len() {
-                        app.move_selection_down()?;
-                    }
+                Some(PanelType::Commits) if app.selected_index + 1 < app.commits.len() => {
+                    app.move_selection_down()?;
                 }
-                None => {}
+                _ => {}
             }
         }
         MouseEventKind::ScrollLeft => {
             // Horizontal scrolling (if terminal supports it)
             match get_panel_at_position(app, mouse_event.column, mouse_event.row) {
-                Some(PanelType::Diff) => {
+                Some(PanelType::Diff) if !app.wraps_diff() => {
                     app.ui_state.scroll_diff_left();
                 }
                 Some(PanelType::Commits) => {
                     app.ui_state.scroll_commit_left();
                 }
-                None => {}
+                _ => {}
             }
         }
         MouseEventKind::ScrollRight => {
             // Horizontal scrolling (if terminal supports it)
             match get_panel_at_position(app, mouse_event.column, mouse_event.row) {
-                Some(PanelType::Diff) => {
+                Some(PanelType::Diff) if !app.wraps_diff() => {
                     let max_width = app.calculate_max_diff_line_width();
                     app.ui_state.scroll_diff_right(max_width);
                 }
@@ -292,7 +453,7 @@ fn handle_mouse_event(app: &mut app::App, mouse_event: MouseEvent) -> Result<()>
                     let max_width = app.calculate_max_commit_line_width();
                     app.ui_state.scroll_commit_right(max_width);
                 }
-                None => {}
+                _ => {}
             }
         }
         MouseEventKind::Down(MouseButton::Left) => {
@@ -316,7 +477,7 @@ fn handle_mouse_click(app: &mut app::App, col: u16, row: u16) -> Result<()> {
             }
 
             // Click-to-select commit
-            if let Some(commit_index) = get_commit_at_row(app, row) {
+            if let Some(commit_index) = app.commit_at_row(row) {
                 if commit_index != app.selected_index {
                     app.selected_index = commit_index;
                     app.load_diff_for_selected_commit()?;
@@ -0,0 +1,58 @@
+//! Per-repository view settings remembered between runs: the panel splits, the layout and the
+//! last file viewed. Everything lives in one JSON file keyed by repository root.
+
+use crate::cli::LayoutMode;
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    pub split_ratio: f32,
+    pub vertical_split_ratio: f32,
+    pub layout_mode: LayoutMode,
+    /// Repository-relative path of the file (or directory) viewed last
+    pub last_file: Option<PathBuf>,
+}
+
+/// Where sessions are kept: the XDG state directory, or the local data directory on
+/// platforms without one
+pub fn session_file() -> Option<PathBuf> {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .map(|dir| dir.join("geschichte").join("sessions.json"))
+}
+
+/// The session saved for `repo_root`. A missing or unreadable file just means no session.
+pub fn load(path: &Path, repo_root: &Path) -> Option<Session> {
+    read_all(path).remove(&session_key(repo_root))
+}
+
+/// Save the session for `repo_root`, keeping those of other repositories. The file is
+/// replaced by a rename so an interrupted write never leaves it half written.
+pub fn save(path: &Path, repo_root: &Path, session: Session) -> Result<()> {
+    let mut sessions = read_all(path);
+    sessions.insert(session_key(repo_root), session);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = serde_json::to_string_pretty(&sessions).map_err(std::io::Error::from)?;
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+fn read_all(path: &Path) -> HashMap<String, Session> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn session_key(repo_root: &Path) -> String {
+    repo_root.to_string_lossy().into_owned()
+}
@@ -43,6 +43,15 @@ pub fn restore_terminal(terminal: &mut AppTerminal) -> Result<()> {
     Ok(())
 }
 
+/// Best-effort terminal restore for abnormal exits (panics, signals).
+///
+/// Errors are ignored since there's nothing useful left to do with them.
+pub fn emergency_restore() {
+    let _ = execute!(io::stdout(), DisableMouseCapture);
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+}
+
 /// Force a complete terminal reset after external editor usage
 pub fn force_terminal_reset(terminal: &mut AppTerminal) -> Result<()> {
     // Clear the entire screen and reset cursor
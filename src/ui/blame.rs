@@ -0,0 +1,151 @@
+use crate::app::{App, FocusedPanel};
+use crate::diff::syntax::highlight_line;
+use crate::git::blame::{BlameLine, LineAge};
+use crate::ui::common::utils::{
+    apply_cursor_highlight, apply_horizontal_scroll, create_border_style,
+};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::path::Path;
+
+/// Columns reserved for the author name; longer names are truncated
+const AUTHOR_WIDTH: usize = 16;
+
+/// Width of "<short hash> <author> <date> " ahead of the line number
+const DETAILS_WIDTH: usize = 7 + 1 + AUTHOR_WIDTH + 1 + 10 + 1;
+
+/// Gutter background of a line in the heat map; lines older than a month keep the default
+pub fn heat_color(age: LineAge) -> Option<Color> {
+    match age {
+        LineAge::Day => Some(Color::Rgb(0xc0, 0x39, 0x2b)),
+        LineAge::Week => Some(Color::Rgb(0xd3, 0x6b, 0x1f)),
+        LineAge::Month => Some(Color::Rgb(0x8a, 0x6d, 0x1d)),
+        LineAge::Older => None,
+    }
+}
+
+/// Color the line-number gutter (the first span) of a full file view line by its age
+pub fn apply_heat(mut line: Line<'static>, age: LineAge) -> Line<'static> {
+    if let (Some(color), Some(gutter)) = (heat_color(age), line.spans.first_mut()) {
+        gutter.style = gutter.style.bg(color).fg(Color::White);
+    }
+    line
+}
+
+/// Width of everything before the code: "<hash> <author> <date> <line number> │ "
+pub fn gutter_width(line_count: usize) -> usize {
+    DETAILS_WIDTH + line_count.to_string().len() + 3
+}
+
+/// Draw the blame of the selected commit's file in place of the diff
+pub fn draw_blame_panel(frame: &mut Frame, app: &App, area: Rect, blame_lines: &[BlameLine]) {
+    let mut title = if app.commits.is_empty() || app.selected_index >= app.commits.len() {
+        " Blame ".to_string()
+    } else if app.commits[app.selected_index].is_working_directory {
+        " Blame (Working Dir) ".to_string()
+    } else {
+        format!(" Blame ({}) ", app.commits[app.selected_index].short_hash)
+    };
+    if app.ui_state.diff_horizontal_scroll > 0 {
+        title = format!("{} ←→", title.trim_end());
+    }
+
+    let focused = app.get_focused_panel() == Some(FocusedPanel::Diff);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(create_border_style(focused));
+
+    if blame_lines.is_empty() {
+        let paragraph = Paragraph::new("No blame available")
+            .block(block)
+            .style(Style::default().fg(Color::Gray));
+        frame.render_widget(paragraph, area);
+        return;
+    }
+
+    let file_path = app.highlight_path();
+    let number_width = blame_lines.len().to_string().len();
+
+    let lines: Vec<Line> = blame_lines
+        .iter()
+        .enumerate()
+        .skip(app.ui_state.diff_scroll)
+        .take(area.height.saturating_sub(2) as usize) // Account for borders
+        .map(|(index, blame_line)| {
+            // Only the first line of a run from the same commit repeats its details
+            let starts_group = index == 0 || blame_lines[index - 1].hash != blame_line.hash;
+            let line = blame_row(
+                blame_line,
+                index + 1,
+                number_width,
+                starts_group,
+                file_path,
+                app.highlight_language(),
+            );
+            let line = if index == app.ui_state.diff_cursor_line && focused {
+                apply_cursor_highlight(line)
+            } else {
+                line
+            };
+            apply_horizontal_scroll(
+                line,
+                app.ui_state.diff_horizontal_scroll,
+                area.width as usize,
+            )
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn blame_row(
+    blame_line: &BlameLine,
+    line_number: usize,
+    number_width: usize,
+    starts_group: bool,
+    file_path: Option<&Path>,
+    language: Option<&str>,
+) -> Line<'static> {
+    let mut spans = if starts_group {
+        let commit_color = if blame_line.is_uncommitted() {
+            Color::Magenta
+        } else {
+            Color::Cyan
+        };
+        let author: String = blame_line.author.chars().take(AUTHOR_WIDTH).collect();
+        vec![
+            Span::styled(
+                blame_line.short_hash.clone(),
+                Style::default().fg(commit_color),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("{:<width$}", author, width = AUTHOR_WIDTH),
+                Style::default().fg(Color::Blue),
+            ),
+            Span::raw(" "),
+            Span::styled(blame_line.date.clone(), Style::default().fg(Color::Yellow)),
+            Span::raw(" "),
+        ]
+    } else {
+        vec![Span::raw(" ".repeat(DETAILS_WIDTH))]
+    };
+
+    spans.push(Span::styled(
+        format!("{:>width$} │ ", line_number, width = number_width),
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    match file_path {
+        Some(path) => spans.extend(highlight_line(&blame_line.content, path, language)),
+        None => spans.push(Span::raw(blame_line.content.clone())),
+    }
+
+    Line::from(spans)
+}
@@ -1,4 +1,7 @@
-use crate::commit::Commit;
+use crate::commit::{Commit, PRStatus, SignatureStatus};
+use crate::diff::{get_search_highlight_style, DiffLineType};
+use crate::ui::common::utils::centered_rect;
+use crate::ui::state::AuthorDisplay;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
     style::{Color, Modifier, Style},
@@ -6,10 +9,27 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use regex::Regex;
+
+/// Where a search matched in the message body: the line, and the byte range within it
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageMatch {
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
 
 pub struct CommitInfoPopup {
     pub commit: Commit,
     pub scroll_position: usize,
+    pub author_display: AuthorDisplay,
+    /// Search in the message body; empty when not searching
+    pub search_query: String,
+    pub is_search_input: bool, // Currently typing the query
+    pub search_matches: Vec<MessageMatch>,
+    pub current_match: Option<usize>,
+    /// The commit's page on the `origin` remote, if there is one
+    pub web_url: Option<String>,
 }
 
 impl CommitInfoPopup {
@@ -17,6 +37,165 @@ impl CommitInfoPopup {
         Self {
             commit,
             scroll_position: 0,
+            author_display: AuthorDisplay::default(),
+            search_query: String::new(),
+            is_search_input: false,
+            search_matches: Vec::new(),
+            current_match: None,
+            web_url: None,
+        }
+    }
+
+    /// The commit as Markdown for PR descriptions and incident notes: the subject as a
+    /// heading, a table of its metadata, a link to it and its body quoted. The working
+    /// directory entry isn't a commit, so it has no hash or link.
+    pub fn to_markdown(&self) -> String {
+        let commit = &self.commit;
+        // A `|` would end the table cell early
+        let cell = |text: &str| text.replace('|', "\\|");
+
+        let mut rows = Vec::new();
+        // The working directory entry's author and dates are placeholders
+        if !commit.is_working_directory {
+            rows.push(("Commit", format!("`{}`", commit.hash)));
+            rows.push(("Author", cell(&commit.author())));
+            rows.push(("Date", cell(&commit.author_date)));
+            if commit.committer_name != commit.author_name
+                || commit.committer_email != commit.author_email
+            {
+                rows.push((
+                    "Committer",
+                    cell(&format!(
+                        "{} <{}>",
+                        commit.committer_name, commit.committer_email
+                    )),
+                ));
+            }
+        }
+        if !commit.refs.is_empty() {
+            rows.push(("Refs", cell(&commit.refs.join(", "))));
+        }
+        if let Some(ref pr_info) = commit.pr_info {
+            let mut pr = format!(
+                "[#{} {}]({})",
+                pr_info.number,
+                cell(&pr_info.title),
+                pr_info.url
+            );
+            if let Some(label) = pr_info.status.label() {
+                pr.push_str(&format!(" ({})", label));
+            }
+            rows.push(("Pull request", pr));
+        }
+        if let Some(ref stats) = commit.stats {
+            rows.push((
+                "Stats",
+                format!(
+                    "{} files, +{} -{} lines",
+                    stats.files_changed, stats.insertions, stats.deletions
+                ),
+            ));
+        }
+
+        let mut markdown = format!("## {}\n\n| | |\n|---|---|\n", commit.subject);
+        for (name, value) in rows {
+            markdown.push_str(&format!("| **{}** | {} |\n", name, value));
+        }
+        if let Some(url) = self
+            .web_url
+            .as_ref()
+            .filter(|_| !commit.is_working_directory)
+        {
+            markdown.push_str(&format!("\n[View commit {}]({})\n", commit.short_hash, url));
+        }
+        if !commit.body.trim().is_empty() {
+            markdown.push('\n');
+            for line in commit.body.trim_end().lines() {
+                if line.is_empty() {
+                    markdown.push_str(">\n");
+                } else {
+                    markdown.push_str(&format!("> {}\n", line));
+                }
+            }
+        }
+        markdown
+    }
+
+    pub fn start_search(&mut self) {
+        self.search_query.clear();
+        self.is_search_input = true;
+        self.update_search();
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.is_search_input = false;
+        self.search_matches.clear();
+        self.current_match = None;
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.is_search_input || !self.search_query.is_empty()
+    }
+
+    /// Find the query in the message body, a case-insensitive regex like the diff search,
+    /// and scroll to the first match. An invalid regex (often half typed) just matches nothing.
+    pub fn update_search(&mut self) {
+        self.search_matches.clear();
+        self.current_match = None;
+        if self.search_query.is_empty() {
+            return;
+        }
+        let Ok(regex) = Regex::new(&format!("(?i){}", self.search_query)) else {
+            return;
+        };
+
+        for (line_index, line) in self.commit.body.lines().enumerate() {
+            self.search_matches
+                .extend(
+                    regex
+                        .find_iter(line)
+                        .filter(|m| !m.is_empty())
+                        .map(|m| MessageMatch {
+                            line: line_index,
+                            start: m.start(),
+                            end: m.end(),
+                        }),
+                );
+        }
+        if !self.search_matches.is_empty() {
+            self.current_match = Some(0);
+            self.scroll_to_current_match();
+        }
+    }
+
+    pub fn next_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        self.current_match = Some(
+            self.current_match
+                .map_or(0, |i| (i + 1) % self.search_matches.len()),
+        );
+        self.scroll_to_current_match();
+    }
+
+    pub fn previous_match(&mut self) {
+        if self.search_matches.is_empty() {
+            return;
+        }
+        let count = self.search_matches.len();
+        self.current_match = Some(
+            self.current_match
+                .map_or(count - 1, |i| (i + count - 1) % count),
+        );
+        self.scroll_to_current_match();
+    }
+
+    /// Put the line of the current match at the top of the message
+    fn scroll_to_current_match(&mut self) {
+        if let Some(m) = self.current_match.and_then(|i| self.search_matches.get(i)) {
+            self.scroll_position = m.line;
         }
     }
 
@@ -34,7 +213,7 @@ impl CommitInfoPopup {
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         // Create centered popup area
-        let popup_area = self.centered_rect(80, 60, area);
+        let popup_area = centered_rect(80, 60, area);
 
         // Clear the background
         frame.render_widget(Clear, popup_area);
@@ -57,9 +236,9 @@ impl CommitInfoPopup {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(8), // Metadata section
-                Constraint::Min(5),    // Message section
-                Constraint::Length(1), // Help line
+                Constraint::Length(10), // Metadata section
+                Constraint::Min(5),     // Message section
+                Constraint::Length(1),  // Help line
             ])
             .split(inner_area);
 
@@ -95,7 +274,7 @@ impl CommitInfoPopup {
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::raw(self.commit.author()),
+            Span::raw(self.author_display.popup_label(&self.commit)),
         ]));
 
         // Author Date
@@ -160,7 +339,7 @@ impl CommitInfoPopup {
 
         // PR info
         if let Some(ref pr_info) = self.commit.pr_info {
-            lines.push(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     "PR:        ",
                     Style::default()
@@ -171,6 +350,37 @@ impl CommitInfoPopup {
                     format!("#{} - {}", pr_info.number, pr_info.title),
                     Style::default().fg(Color::Green),
                 ),
+            ];
+            if let Some(label) = pr_info.status.label() {
+                let color = match pr_info.status {
+                    PRStatus::Open => Color::Green,
+                    PRStatus::Merged => Color::Magenta,
+                    _ => Color::Red,
+                };
+                spans.push(Span::styled(
+                    format!(" [{}]", label),
+                    Style::default().fg(color),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        // Signature
+        if let Some(ref signature) = self.commit.signature {
+            let color = match signature.status {
+                SignatureStatus::Good => Color::Green,
+                SignatureStatus::Bad => Color::Red,
+                SignatureStatus::Unsigned => Color::Gray,
+                _ => Color::Yellow,
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Signature: ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(signature.describe(), Style::default().fg(color)),
             ]));
         }
 
@@ -193,6 +403,19 @@ impl CommitInfoPopup {
             ]));
         }
 
+        // Line count change of the viewed file
+        if let Some(Some(ref line_counts)) = self.commit.line_counts {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "File:      ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(line_counts.describe(), Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
         let paragraph = Paragraph::new(lines).style(Style::default().fg(Color::White));
 
         frame.render_widget(paragraph, area);
@@ -217,8 +440,8 @@ impl CommitInfoPopup {
 
         if !self.commit.body.is_empty() {
             // Only show body lines (subject is already shown in metadata above)
-            for line in self.commit.body.lines() {
-                message_lines.push(Line::from(line.to_string()));
+            for (line_index, line) in self.commit.body.lines().enumerate() {
+                message_lines.push(self.highlight_matches(line_index, line));
             }
         } else {
             // Show a message when there's no additional body content
@@ -246,8 +469,49 @@ impl CommitInfoPopup {
         frame.render_widget(paragraph, message_area);
     }
 
+    /// A body line with its search matches highlighted, the current one the strongest
+    fn highlight_matches(&self, line_index: usize, line: &str) -> Line<'static> {
+        let mut spans = Vec::new();
+        let mut position = 0;
+        for (i, m) in self.search_matches.iter().enumerate() {
+            if m.line != line_index {
+                continue;
+            }
+            if m.start > position {
+                spans.push(Span::raw(line[position..m.start].to_string()));
+            }
+            let is_current_match = self.current_match == Some(i);
+            spans.push(Span::styled(
+                line[m.start..m.end].to_string(),
+                get_search_highlight_style(is_current_match, DiffLineType::Context),
+            ));
+            position = m.end;
+        }
+        if position < line.len() || spans.is_empty() {
+            spans.push(Span::raw(line[position..].to_string()));
+        }
+        Line::from(spans)
+    }
+
     fn render_help(&self, frame: &mut Frame, area: Rect) {
-        let help_text = "[↑↓/jk] Scroll  [c] Copy hash  [m] Copy message  [q] Close";
+        let search_status = match self.current_match {
+            Some(i) => format!("{}/{}", i + 1, self.search_matches.len()),
+            None => "no matches".to_string(),
+        };
+        let help_text = if self.is_search_input {
+            format!(
+                "Search: {}_  ({})  [Enter] Done  [Esc] Cancel",
+                self.search_query, search_status
+            )
+        } else if self.is_searching() {
+            format!(
+                "/{}  ({})  [n/N] Next/prev  [Esc] Clear search  [q] Close",
+                self.search_query, search_status
+            )
+        } else {
+            "[↑↓/jk] Scroll  [/] Search  [c] Copy hash  [m] Copy message  [M] Copy as Markdown  [q] Close"
+                .to_string()
+        };
         let help = Paragraph::new(help_text)
             .style(Style::default().fg(Color::DarkGray))
             .alignment(Alignment::Center);
@@ -255,26 +519,6 @@ impl CommitInfoPopup {
         frame.render_widget(help, area);
     }
 
-    fn centered_rect(&self, percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-        let popup_layout = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage((100 - percent_y) / 2),
-                Constraint::Percentage(percent_y),
-                Constraint::Percentage((100 - percent_y) / 2),
-            ])
-            .split(r);
-
-        Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Percentage((100 - percent_x) / 2),
-                Constraint::Percentage(percent_x),
-                Constraint::Percentage((100 - percent_x) / 2),
-            ])
-            .split(popup_layout[1])[1]
-    }
-
     pub fn get_total_lines(&self) -> usize {
         if !self.commit.body.is_empty() {
             self.commit.body.lines().count()
@@ -1,6 +1,6 @@
 use crate::app::{App, FocusedPanel};
 use crate::ui::common::utils::{
-    apply_horizontal_scroll, create_border_style, create_commits_title,
+    apply_horizontal_scroll, create_border_style, create_commits_title, spinner_frame,
 };
 use ratatui::{
     layout::Rect,
@@ -18,10 +18,12 @@ pub enum CommitsPanelLayout {
 
 /// Draw commits panel that works for both unified and side-by-side layouts
 pub fn draw_commits_panel(frame: &mut Frame, app: &App, area: Rect, layout: CommitsPanelLayout) {
+    let filter = app.commits_filter_label();
     let title = create_commits_title(
         app.commits.len(),
         app.loading,
         app.ui_state.commit_horizontal_scroll,
+        filter.as_deref(),
     );
 
     let focused = app.get_focused_panel() == Some(FocusedPanel::Commits);
@@ -34,9 +36,9 @@ pub fn draw_commits_panel(frame: &mut Frame, app: &App, area: Rect, layout: Comm
 
     if app.commits.is_empty() {
         let message = if app.loading {
-            "Loading commits..."
+            format!("{} Loading commits...", spinner_frame(app.loading_started))
         } else {
-            "No commits found for this file"
+            "No commits found for this file".to_string()
         };
 
         let paragraph = Paragraph::new(message)
@@ -64,27 +66,78 @@ pub fn draw_commits_panel(frame: &mut Frame, app: &App, area: Rect, layout: Comm
     frame.render_stateful_widget(list, area, &mut list_state);
 }
 
+/// Gutter marker tying a pending diff range together: both endpoints get `►` and
+/// the rows between them a vertical bar
+fn range_marker(app: &App, index: usize) -> (&'static str, Style) {
+    let Some(start) = app.diff_range_start else {
+        return ("", Style::default());
+    };
+
+    let endpoint_style = Style::default()
+        .fg(Color::Green)
+        .add_modifier(Modifier::BOLD);
+    let (low, high) = if start <= app.selected_index {
+        (start, app.selected_index)
+    } else {
+        (app.selected_index, start)
+    };
+
+    if app.is_commit_marked_for_diff(index) || index == app.selected_index {
+        ("► ", endpoint_style)
+    } else if index > low && index < high {
+        ("│ ", Style::default().fg(Color::Green))
+    } else {
+        // Keep rows aligned while a range is being selected
+        ("  ", Style::default())
+    }
+}
+
+/// Decorations listed before a commit's subject; the rest are counted
+const MAX_DECORATIONS: usize = 3;
+
+/// A commit's branches and tags like `git log --decorate` shows them: `(main, v1.0) `
+pub fn decoration_spans(refs: &[String]) -> Vec<Span<'static>> {
+    if refs.is_empty() {
+        return Vec::new();
+    }
+
+    let punctuation = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![Span::styled("(", punctuation)];
+    for (index, name) in refs.iter().take(MAX_DECORATIONS).enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(", ", punctuation));
+        }
+        let span = match name.strip_prefix("tag:") {
+            Some(tag) => Span::styled(tag.to_string(), Style::default().fg(Color::Yellow)),
+            None => Span::styled(
+                name.trim_start_matches("branch:").to_string(),
+                Style::default().fg(Color::Green),
+            ),
+        };
+        spans.push(span);
+    }
+    if refs.len() > MAX_DECORATIONS {
+        spans.push(Span::styled(
+            format!(", +{}", refs.len() - MAX_DECORATIONS),
+            punctuation,
+        ));
+    }
+    spans.push(Span::styled(") ", punctuation));
+    spans
+}
+
 /// Create commit items for vertical layout (unified view)
 fn create_vertical_commit_items(app: &App, area: Rect) -> Vec<ListItem<'_>> {
     app.commits
         .iter()
         .enumerate()
         .map(|(index, commit)| {
-            let marker = if app.is_commit_marked_for_diff(index) {
-                "► "
-            } else {
-                ""
-            };
+            let (marker, marker_style) = range_marker(app, index);
 
             let line = if commit.is_working_directory {
                 // Special styling for working directory
                 Line::from(vec![
-                    Span::styled(
-                        marker.to_string(),
-                        Style::default()
-                            .fg(Color::Green)
-                            .add_modifier(Modifier::BOLD),
-                    ),
+                    Span::styled(marker.to_string(), marker_style),
                     Span::styled("Working".to_string(), Style::default().fg(Color::Magenta)),
                     Span::raw(" "),
                     Span::styled("Dir".to_string(), Style::default().fg(Color::Magenta)),
@@ -98,19 +151,41 @@ fn create_vertical_commit_items(app: &App, area: Rect) -> Vec<ListItem<'_>> {
                 ])
             } else {
                 // Regular commit styling
-                Line::from(vec![
-                    Span::styled(
-                        marker.to_string(),
+                let mut spans = vec![Span::styled(marker.to_string(), marker_style)];
+                let marks = app.mark_letters(&commit.hash);
+                if !marks.is_empty() {
+                    spans.push(Span::styled(
+                        format!("'{} ", marks),
                         Style::default()
-                            .fg(Color::Green)
+                            .fg(Color::Magenta)
                             .add_modifier(Modifier::BOLD),
-                    ),
+                    ));
+                }
+                spans.extend([
                     Span::styled(commit.date.clone(), Style::default().fg(Color::Yellow)),
                     Span::raw(" "),
                     Span::styled(commit.short_hash.clone(), Style::default().fg(Color::Cyan)),
                     Span::raw(" "),
-                    Span::raw(commit.subject.clone()),
-                ])
+                ]);
+                if let Some(stats) = commit.stats.as_ref().filter(|_| app.show_commit_stats) {
+                    spans.push(Span::styled(
+                        format!("+{}", stats.insertions),
+                        Style::default().fg(Color::Green),
+                    ));
+                    spans.push(Span::raw(" "));
+                    spans.push(Span::styled(
+                        format!("-{}", stats.deletions),
+                        Style::default().fg(Color::Red),
+                    ));
+                    spans.push(Span::raw(" "));
+                }
+                if let Some(author) = app.ui_state.author_display.list_label(commit) {
+                    spans.push(Span::styled(author, Style::default().fg(Color::Blue)));
+                    spans.push(Span::raw(" "));
+                }
+                spans.extend(decoration_spans(&commit.refs));
+                spans.push(Span::raw(commit.subject.clone()));
+                Line::from(spans)
             };
 
             // Apply horizontal scrolling to commit line
@@ -130,20 +205,30 @@ fn create_horizontal_commit_items(app: &App) -> Vec<ListItem<'_>> {
         .iter()
         .enumerate()
         .map(|(index, commit)| {
-            let marker = if app.is_commit_marked_for_diff(index) {
-                "► "
-            } else {
-                ""
-            };
+            let (marker, _) = range_marker(app, index);
 
             let line = if commit.is_working_directory {
                 format!("{}[Working Directory] {}", marker, commit.subject)
             } else {
+                let author = app
+                    .ui_state
+                    .author_display
+                    .list_label(commit)
+                    .map(|author| format!("{} ", author))
+                    .unwrap_or_default();
+                let marks = app.mark_letters(&commit.hash);
+                let marks = if marks.is_empty() {
+                    String::new()
+                } else {
+                    format!("'{} ", marks)
+                };
                 format!(
-                    "{}{} {} {}",
+                    "{}{}{} {} {}{}",
                     marker,
+                    marks,
                     &commit.short_hash,
                     &commit.date[..10.min(commit.date.len())], // Take first 10 chars (date part)
+                    author,
                     commit.subject
                 )
             };
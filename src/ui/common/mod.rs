@@ -2,6 +2,8 @@ pub mod commits;
 pub mod utils;
 
 use crate::app::{App, FocusedPanel};
+use crate::cli::LayoutMode;
+use crate::ui::state::SideBySideFocus;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
@@ -33,7 +35,8 @@ pub fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     if app.copy_mode.is_some() {
         let default_message =
-            "Copy mode: s=SHA, h=short, m=msg, a=author, d=date, u=URL, y=SHA".to_string();
+            "Copy mode: s=SHA, h=short, m=msg, a=author, d=date, u=URL, b=blame permalink, p=path, A=absolute path, l=path:line, o=old path, f=show, P=patch, y=SHA, ?=help"
+                .to_string();
         let message = app.copy_message.as_ref().unwrap_or(&default_message);
         let copy_mode_bar = Paragraph::new(Line::from(vec![Span::styled(
             format!(" {}", message),
@@ -43,8 +46,93 @@ pub fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
+    if let Some((first, last)) = app.ui_state.diff_selection() {
+        let selection_bar = Paragraph::new(Line::from(vec![Span::styled(
+            format!(
+                " VISUAL: {} lines | y: copy lines | Y: copy code | Alt+y: copy added lines | Esc: cancel",
+                last - first + 1
+            ),
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        )]));
+        frame.render_widget(selection_bar, area);
+        return;
+    }
+
+    if let Some(pickaxe) = app
+        .pickaxe_state
+        .as_ref()
+        .filter(|pickaxe| pickaxe.is_input_mode)
+    {
+        let flag = if pickaxe.is_regex { "-G regex" } else { "-S" };
+        let pickaxe_bar = Paragraph::new(Line::from(vec![Span::styled(
+            format!(
+                " Pickaxe ({}): {}_ | Tab: -S/-G | Enter: filter commits | Esc: cancel",
+                flag, pickaxe.pattern
+            ),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )]));
+        frame.render_widget(pickaxe_bar, area);
+        return;
+    }
+
+    if let Some(filter) = app
+        .author_filter
+        .as_ref()
+        .filter(|filter| filter.is_input_mode)
+    {
+        let author_bar = Paragraph::new(Line::from(vec![Span::styled(
+            format!(
+                " Author: {}_ | Enter: filter commits | Esc: cancel",
+                filter.query
+            ),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )]));
+        frame.render_widget(author_bar, area);
+        return;
+    }
+
+    if let Some(ref input) = app.goto_line_input {
+        let goto_bar = Paragraph::new(Line::from(vec![Span::styled(
+            format!(" Go to line: {}_ | Enter: jump | Esc: cancel", input),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )]));
+        frame.render_widget(goto_bar, area);
+        return;
+    }
+
+    if let Some(ref input) = app.goto_commit_input {
+        let goto_bar = Paragraph::new(Line::from(vec![Span::styled(
+            format!(" Go to commit: {}_ | Enter: select | Esc: cancel", input),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )]));
+        frame.render_widget(goto_bar, area);
+        return;
+    }
+
+    if let Some(pending) = app.pending_mark {
+        let prompt = match pending {
+            crate::app::PendingMark::Set => " Mark the commit as: a-z | any other key: cancel",
+            crate::app::PendingMark::Jump => {
+                " Jump to mark: a-z | ': back to the last jump | any other key: cancel"
+            }
+        };
+        let mark_bar = Paragraph::new(Line::from(vec![Span::styled(
+            prompt,
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        )]));
+        frame.render_widget(mark_bar, area);
+        return;
+    }
+
     // Check for active search mode
     if let Some(ref search_state) = app.diff_search_state {
+        let mut modes = String::new();
+        if search_state.case_sensitive {
+            modes.push_str(" [Aa]");
+        }
+        if search_state.whole_word {
+            modes.push_str(" [\\b]");
+        }
         let search_status = if search_state.is_input_mode {
             format!("Search: {}_", search_state.query)
         } else if search_state.results.is_empty() {
@@ -60,7 +148,10 @@ pub fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         };
 
         let search_bar = Paragraph::new(Line::from(vec![Span::styled(
-            format!(" {} | n/N: next/prev | q/Esc: exit search", search_status),
+            format!(
+                " {}{} | n/N: next/prev | Alt+c/w: case/word | q/Esc: exit search",
+                search_status, modes
+            ),
             Style::default().fg(Color::Black).bg(Color::Cyan),
         )]));
         frame.render_widget(search_bar, area);
@@ -68,23 +159,50 @@ pub fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     // Normal status display
-    let focus_hint = match app.get_focused_panel() {
-        Some(FocusedPanel::Commits) => {
-            "↑↓/jk: select | i/Enter: info | y: copy | d: diff | a/s: h-scroll"
-        }
-        Some(FocusedPanel::Diff) => "↑↓/jk: move cursor | PgUp/PgDn: scroll | a/s: h-scroll",
-        None => "Type to search files",
+    let side_by_side = app.effective_layout() == LayoutMode::SideBySide;
+    let (focus_hint, tab_hint) = match app.get_focused_panel() {
+        Some(FocusedPanel::Commits) => (
+            "↑↓/jk: select | i/Enter: info | y: copy | d: diff | a/s: h-scroll",
+            if side_by_side {
+                "Tab: old side"
+            } else {
+                "Tab: diff"
+            },
+        ),
+        Some(FocusedPanel::Diff) if side_by_side => match app.ui_state.side_by_side_focus {
+            SideBySideFocus::Old => (
+                "Old side: ↑↓/jk: move cursor | PgUp/PgDn: scroll | a/s: h-scroll",
+                "Tab: new side",
+            ),
+            SideBySideFocus::New => (
+                "New side: ↑↓/jk: move cursor | PgUp/PgDn: scroll | a/s: h-scroll",
+                "Tab: commits",
+            ),
+        },
+        Some(FocusedPanel::Diff) => (
+            "↑↓/jk: move cursor | PgUp/PgDn: scroll | a/s: h-scroll",
+            "Tab: commits",
+        ),
+        None => ("Type to search files", "Tab: panel"),
     };
 
-    let file_display = match app.get_file_path() {
+    let mut file_display = match app.get_file_path() {
+        Some(path) if app.is_directory_mode() => format!("{}/ (directory)", path.display()),
         Some(path) => path.display().to_string(),
         None => "File Picker".to_string(),
     };
+    if let Some(count) = app.commit_count_label() {
+        file_display.push_str(&format!(" ({})", count));
+    }
+    if !app.follow_renames && !app.is_directory_mode() && app.get_file_path().is_some() {
+        file_display.push_str(" [renames not followed]");
+    }
 
     let status = format!(
-        " {} | {} | Tab: panel | {} | h/l: resize | ?: help | q: quit ",
+        " {} | {} | {} | {} | h/l: resize | v: layout | ?: help | q: quit ",
         app.repo_root.display(),
         file_display,
+        tab_hint,
         focus_hint
     );
 
@@ -98,22 +216,6 @@ pub fn draw_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
 /// Draw the help overlay popup
 pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
-    // Calculate popup size - center it
-    let popup_width = 50;
-    let popup_height = 19;
-    let x = (area.width.saturating_sub(popup_width)) / 2;
-    let y = (area.height.saturating_sub(popup_height)) / 2;
-
-    let popup_area = Rect {
-        x,
-        y,
-        width: popup_width,
-        height: popup_height,
-    };
-
-    // Clear the background
-    frame.render_widget(Clear, popup_area);
-
     let help_text = vec![
         Line::from(vec![Span::styled(
             "Geschichte - Git File History Viewer",
@@ -124,27 +226,35 @@ pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled("Tab", Style::default().fg(Color::Yellow)),
-            Span::raw("      Switch between panels"),
+            Span::raw("      Switch panels (side-by-side: commits, old, new)"),
         ]),
         Line::from(vec![
             Span::styled("↑↓/jk", Style::default().fg(Color::Yellow)),
             Span::raw("    Navigate commits OR move cursor in diff"),
         ]),
+        Line::from(vec![
+            Span::styled("gg/G", Style::default().fg(Color::Yellow)),
+            Span::raw("     First/last commit or diff line"),
+        ]),
         Line::from(vec![
             Span::styled("h/l", Style::default().fg(Color::Yellow)),
-            Span::raw("      Resize split pane"),
+            Span::raw("      Shrink/grow the commit list"),
         ]),
         Line::from(vec![
             Span::styled("PgUp/Dn", Style::default().fg(Color::Yellow)),
             Span::raw("  Scroll diff by page (always)"),
         ]),
+        Line::from(vec![
+            Span::styled("^B/^F", Style::default().fg(Color::Yellow)),
+            Span::raw("    Scroll diff by page"),
+        ]),
         Line::from(vec![
             Span::styled("^U/^D", Style::default().fg(Color::Yellow)),
-            Span::raw("    Scroll diff (vim-style)"),
+            Span::raw("    Scroll diff by half a page"),
         ]),
         Line::from(vec![
-            Span::styled("^B/^F", Style::default().fg(Color::Yellow)),
-            Span::raw("    Scroll diff (emacs-style)"),
+            Span::styled("^Y/^E", Style::default().fg(Color::Yellow)),
+            Span::raw("    Scroll diff by line"),
         ]),
         Line::from(vec![
             Span::styled("a/s", Style::default().fg(Color::Yellow)),
@@ -159,10 +269,18 @@ pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
             Span::styled("f", Style::default().fg(Color::Green)),
             Span::raw("        Switch to another file"),
         ]),
+        Line::from(vec![
+            Span::styled("F", Style::default().fg(Color::Green)),
+            Span::raw("        Switch to a file in the same directory"),
+        ]),
         Line::from(vec![
             Span::styled("i/Enter", Style::default().fg(Color::Green)),
             Span::raw("   Show detailed commit info"),
         ]),
+        Line::from(vec![
+            Span::styled("T", Style::default().fg(Color::Green)),
+            Span::raw("        File statistics across history"),
+        ]),
         Line::from(vec![
             Span::styled("y", Style::default().fg(Color::Green)),
             Span::raw("        Copy mode (yy=full SHA, Y=short SHA)"),
@@ -171,19 +289,151 @@ pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
             Span::styled("d", Style::default().fg(Color::Green)),
             Span::raw("        Mark/diff between commits"),
         ]),
+        Line::from(vec![
+            Span::styled("5d", Style::default().fg(Color::Green)),
+            Span::raw("       Diff against the commit 5 entries older"),
+        ]),
+        Line::from(vec![
+            Span::styled("[/]", Style::default().fg(Color::Green)),
+            Span::raw("      Go to the parent/child commit"),
+        ]),
         Line::from(vec![
             Span::styled("/", Style::default().fg(Color::Green)),
-            Span::raw("        Search in diff"),
+            Span::raw("        Search in diff or the commit info message"),
+        ]),
+        Line::from(vec![
+            Span::styled("Ctrl+G", Style::default().fg(Color::Green)),
+            Span::raw("   Search every commit's diff (Enter: go to match)"),
+        ]),
+        Line::from(vec![
+            Span::styled("S", Style::default().fg(Color::Green)),
+            Span::raw("        Pickaxe: only commits adding/removing a string"),
+        ]),
+        Line::from(vec![
+            Span::styled("A", Style::default().fg(Color::Green)),
+            Span::raw("        Only commits by an author (name or email)"),
+        ]),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(Color::Green)),
+            Span::raw("        Follow renames on/off (reloads the history)"),
+        ]),
+        Line::from(vec![
+            Span::styled(":", Style::default().fg(Color::Green)),
+            Span::raw("        Go to line of the file"),
+        ]),
+        Line::from(vec![
+            Span::styled("#", Style::default().fg(Color::Green)),
+            Span::raw("        Go to a commit by hash prefix"),
+        ]),
+        Line::from(vec![
+            Span::styled("m<a-z>", Style::default().fg(Color::Green)),
+            Span::raw("   Mark the selected commit"),
+        ]),
+        Line::from(vec![
+            Span::styled("'<a-z>", Style::default().fg(Color::Green)),
+            Span::raw("   Jump to a mark ('' jumps back)"),
         ]),
         Line::from(vec![
             Span::styled("n/N", Style::default().fg(Color::Green)),
             Span::raw("      Next/previous search result"),
         ]),
+        Line::from(vec![
+            Span::styled("Alt+c/w", Style::default().fg(Color::Green)),
+            Span::raw("  Search: match case / whole word"),
+        ]),
+        Line::from(vec![
+            Span::styled("b", Style::default().fg(Color::Green)),
+            Span::raw("        Blame file at commit (Enter: go to line's commit)"),
+        ]),
+        Line::from(vec![
+            Span::styled("C", Style::default().fg(Color::Green)),
+            Span::raw("        Compare clipboard with file at commit"),
+        ]),
+        Line::from(vec![
+            Span::styled("I", Style::default().fg(Color::Green)),
+            Span::raw("        Invert diff (new side as base)"),
+        ]),
+        Line::from(vec![
+            Span::styled("@", Style::default().fg(Color::Green)),
+            Span::raw("        Cycle author: hidden/name/email/both"),
+        ]),
+        Line::from(vec![
+            Span::styled("o", Style::default().fg(Color::Green)),
+            Span::raw("        Show the whole file at the commit"),
+        ]),
+        Line::from(vec![
+            Span::styled("R", Style::default().fg(Color::Green)),
+            Span::raw("        Heat map: color the whole file by line age"),
+        ]),
+        Line::from(vec![
+            Span::styled("V", Style::default().fg(Color::Green)),
+            Span::raw("        Select diff lines (y/Y/Alt+y: copy)"),
+        ]),
+        Line::from(vec![
+            Span::styled("X", Style::default().fg(Color::Green)),
+            Span::raw("        Show every file the commit changed"),
+        ]),
+        Line::from(vec![
+            Span::styled("{ }", Style::default().fg(Color::Green)),
+            Span::raw("      Previous/next file in the whole commit"),
+        ]),
+        Line::from(vec![
+            Span::styled("O", Style::default().fg(Color::Green)),
+            Span::raw("        Open the commit in the browser"),
+        ]),
+        Line::from(vec![
+            Span::styled("Alt+p", Style::default().fg(Color::Green)),
+            Span::raw("    Save a patch of the commit or range (yP: copy it)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Alt+s/u", Style::default().fg(Color::Green)),
+            Span::raw("  Stage/unstage the file (working directory entry)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Space", Style::default().fg(Color::Green)),
+            Span::raw("    Fold/unfold the hunk under the cursor"),
+        ]),
+        Line::from(vec![
+            Span::styled("v", Style::default().fg(Color::Green)),
+            Span::raw("        Toggle unified/side-by-side layout"),
+        ]),
+        Line::from(vec![
+            Span::styled("w", Style::default().fg(Color::Green)),
+            Span::raw("        Highlight changed words in modified lines"),
+        ]),
+        Line::from(vec![
+            Span::styled(".", Style::default().fg(Color::Green)),
+            Span::raw("        Show/hide whitespace characters"),
+        ]),
+        Line::from(vec![
+            Span::styled("z", Style::default().fg(Color::Green)),
+            Span::raw("        Wrap long lines (unified layout)"),
+        ]),
+        Line::from(vec![
+            Span::styled("H", Style::default().fg(Color::Green)),
+            Span::raw("        Syntax highlighting on/off"),
+        ]),
+        Line::from(vec![
+            Span::styled("W", Style::default().fg(Color::Green)),
+            Span::raw("        Ignore whitespace-only changes (git diff -w)"),
+        ]),
+        Line::from(vec![
+            Span::styled("M", Style::default().fg(Color::Green)),
+            Span::raw("        Combined diff for merge commits (git show --cc)"),
+        ]),
+        Line::from(vec![
+            Span::styled("+/-", Style::default().fg(Color::Green)),
+            Span::raw("      More/fewer context lines"),
+        ]),
         Line::from(""),
         Line::from(vec![
             Span::styled("q", Style::default().fg(Color::Red)),
             Span::raw("        Quit"),
         ]),
+        Line::from(vec![
+            Span::styled("Q", Style::default().fg(Color::Red)),
+            Span::raw("        Quit and print the diff to stdout"),
+        ]),
         Line::from(vec![
             Span::styled("?", Style::default().fg(Color::Magenta)),
             Span::raw("        Show/hide this help"),
@@ -195,6 +445,22 @@ pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
         )]),
     ];
 
+    // Calculate popup size to fit the help text (plus borders) - center it
+    let popup_width = 50.min(area.width);
+    let popup_height = (help_text.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x,
+        y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    // Clear the background
+    frame.render_widget(Clear, popup_area);
+
     let help_paragraph = Paragraph::new(help_text)
         .block(
             Block::default()
@@ -206,3 +472,81 @@ pub fn draw_help_overlay(frame: &mut Frame, _app: &App, area: Rect) {
 
     frame.render_widget(help_paragraph, popup_area);
 }
+
+/// Draw the copy mode overlay listing each target key, what it copies and an example
+pub fn draw_copy_help_overlay(frame: &mut Frame, app: &App, area: Rect) {
+    // `h` copies the hunk under the cursor when the diff panel has focus
+    let short_target =
+        if app.get_focused_panel() == Some(FocusedPanel::Diff) && !app.show_commit_info {
+            ("h", "Hunk under cursor", "@@ -10,4 +10,6 @@ ...")
+        } else {
+            ("h", "Short SHA", "a1b2c3d")
+        };
+    let targets = [
+        ("s", "Full SHA", "a1b2c3d4e5f6..."),
+        short_target,
+        ("m", "Message", "Fix parser bug (+ body)"),
+        ("a", "Author", "Jane Doe <jane@example.com>"),
+        ("d", "Date", "2024-01-15 10:30"),
+        ("u", "Commit or PR URL", "https://github.com/..."),
+        (
+            "b",
+            "Blame permalink",
+            "https://github.com/.../blob/a1b2c3d/src/main.rs#L42",
+        ),
+        ("p", "Path", "src/main.rs"),
+        ("A", "Absolute path", "/home/jane/repo/src/main.rs"),
+        ("l", "Line reference", "src/main.rs:42"),
+        ("o", "Path at the commit", "src/old_main.rs"),
+        ("f", "Whole commit", "git show output"),
+        ("P", "Patch", "git format-patch output"),
+        ("y", "Full SHA", "same as s"),
+    ];
+
+    let mut help_text = vec![
+        Line::from(vec![Span::styled(
+            "Copy mode: press a key to copy",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+    ];
+    help_text.extend(targets.iter().map(|(key, target, example)| {
+        Line::from(vec![
+            Span::styled(format!("{:<3}", key), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{:<20}", target)),
+            Span::styled(*example, Style::default().fg(Color::DarkGray)),
+        ])
+    }));
+    help_text.push(Line::from(""));
+    help_text.push(Line::from(vec![Span::styled(
+        "Press ? to close, q or Esc to leave copy mode",
+        Style::default().fg(Color::Gray),
+    )]));
+
+    let popup_width = 56.min(area.width);
+    let popup_height = (help_text.len() as u16 + 2).min(area.height);
+    let x = (area.width.saturating_sub(popup_width)) / 2;
+    let y = (area.height.saturating_sub(popup_height)) / 2;
+
+    let popup_area = Rect {
+        x,
+        y,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    frame.render_widget(Clear, popup_area);
+
+    let help_paragraph = Paragraph::new(help_text)
+        .block(
+            Block::default()
+                .title(" Copy ")
+                .borders(Borders::ALL)
+                .style(Style::default().fg(Color::White).bg(Color::Black)),
+        )
+        .alignment(Alignment::Left);
+
+    frame.render_widget(help_paragraph, popup_area);
+}
@@ -1,6 +1,9 @@
 use ratatui::{
-    style::{Color, Style},
+    layout::{Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
 };
 
 /// Apply horizontal scrolling to a line
@@ -68,6 +71,79 @@ pub fn apply_horizontal_scroll(
     Line::from(new_spans)
 }
 
+/// Break a line into rows of at most `width` chars, keeping each span's style. Continuation
+/// rows start with `indent` blank columns in the style of the line's first span, so a
+/// highlighted gutter stays highlighted.
+pub fn wrap_line(line: Line<'static>, width: usize, indent: usize) -> Vec<Line<'static>> {
+    if width == 0 {
+        return vec![line];
+    }
+    let indent = if indent < width { indent } else { 0 };
+    let line_style = line.style;
+    let indent_style = line
+        .spans
+        .first()
+        .map(|span| span.style)
+        .unwrap_or_default();
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut row_width = 0;
+    for span in line.spans {
+        let mut chars = span.content.chars().peekable();
+        while chars.peek().is_some() {
+            if row_width == width {
+                rows.push(Line::from(std::mem::take(&mut row)).style(line_style));
+                if indent > 0 {
+                    row.push(Span::styled(" ".repeat(indent), indent_style));
+                }
+                row_width = indent;
+            }
+            let piece: String = chars.by_ref().take(width - row_width).collect();
+            row_width += piece.chars().count();
+            row.push(Span::styled(piece, span.style));
+        }
+    }
+    rows.push(Line::from(row).style(line_style));
+    rows
+}
+
+/// Apply cursor highlighting to a line by adding background color to all spans
+pub fn apply_cursor_highlight(line: Line<'static>) -> Line<'static> {
+    let theme = crate::config::theme::current();
+    let highlighted_spans: Vec<Span> = line
+        .spans
+        .into_iter()
+        .map(|span| {
+            let mut style = span.style.bg(theme.cursor_bg);
+            if theme.no_color {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Span::styled(span.content, style)
+        })
+        .collect();
+
+    Line::from(highlighted_spans)
+}
+
+/// Mark a line of the visual selection with the selection background
+pub fn apply_selection_highlight(line: Line<'static>) -> Line<'static> {
+    let theme = crate::config::theme::current();
+    let spans: Vec<Span> = line
+        .spans
+        .into_iter()
+        .map(|span| {
+            let mut style = span.style.bg(theme.selection_bg);
+            if theme.no_color {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            Span::styled(span.content, style)
+        })
+        .collect();
+
+    Line::from(spans)
+}
+
 /// Create border style based on focus state
 pub fn create_border_style(focused: bool) -> Style {
     if focused {
@@ -77,14 +153,17 @@ pub fn create_border_style(focused: bool) -> Style {
     }
 }
 
-/// Generate title for commits panel with optional horizontal scroll indicator
+/// Generate title for commits panel with any filter and an optional horizontal scroll indicator
 pub fn create_commits_title(
     commits_count: usize,
     loading: bool,
     horizontal_scroll: usize,
+    filter: Option<&str>,
 ) -> String {
     let mut title = if loading {
         " Commits (Loading...) ".to_string()
+    } else if let Some(filter) = filter {
+        format!(" Commits ({}, {}) ", commits_count, filter)
     } else {
         format!(" Commits ({}) ", commits_count)
     };
@@ -104,6 +183,7 @@ pub fn create_diff_title(
     current_diff_range: Option<(usize, usize)>,
     diff_range_start: Option<usize>,
     horizontal_scroll: usize,
+    inverted: bool,
 ) -> String {
     let mut title = if commits.is_empty() {
         " Diff ".to_string()
@@ -131,6 +211,10 @@ pub fn create_diff_title(
         " Diff ".to_string()
     };
 
+    if inverted {
+        title = append_title_tag(&title, "inverted");
+    }
+
     // Add horizontal scroll indicator
     if horizontal_scroll > 0 {
         title = format!("{} ←→", title.trim_end());
@@ -139,12 +223,86 @@ pub fn create_diff_title(
     title
 }
 
+/// Append a bracketed tag such as "[inverted]" to a panel title
+pub fn append_title_tag(title: &str, tag: &str) -> String {
+    format!("{} [{}] ", title.trim_end(), tag)
+}
+
+/// How far through `total_lines` a view showing `visible_lines` from `scroll` is, as a
+/// percentage; `None` when everything fits
+pub fn scroll_percent(scroll: usize, visible_lines: usize, total_lines: usize) -> Option<usize> {
+    let scrollable = total_lines.checked_sub(visible_lines).filter(|&n| n > 0)?;
+    Some((scroll * 100 / scrollable).min(100))
+}
+
+/// Append the scroll position, e.g. "(42%)", to a panel title when its content overflows
+pub fn append_scroll_percent(
+    title: &str,
+    scroll: usize,
+    visible_lines: usize,
+    total_lines: usize,
+) -> String {
+    match scroll_percent(scroll, visible_lines, total_lines) {
+        Some(percent) => format!("{} ({}%) ", title.trim_end(), percent),
+        None => title.to_string(),
+    }
+}
+
+/// The frame of the loading spinner, which turns every 100ms from `started`
+pub fn spinner_frame(started: Option<std::time::Instant>) -> char {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+    let elapsed = started.map_or(0, |started| started.elapsed().as_millis() / 100);
+    FRAMES[elapsed as usize % FRAMES.len()]
+}
+
+/// Draw a scrollbar over the right border of a bordered panel, unless its content fits
+pub fn draw_scrollbar(frame: &mut Frame, area: Rect, scroll: usize, total_lines: usize) {
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    if total_lines <= visible_lines {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(total_lines - visible_lines)
+        .position(scroll)
+        .viewport_content_length(visible_lines);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None);
+    // Keep the border's corners
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
+
 /// Generate title for side-by-side diff panels
 pub fn create_side_by_side_title(
     commits: &[crate::commit::Commit],
     selected_index: usize,
     current_diff_range: Option<(usize, usize)>,
     is_old_file: bool,
+    inverted: bool,
+) -> String {
+    // An inverted diff shows the new side on the left
+    let is_old_file = is_old_file != inverted;
+    let title = side_by_side_base_title(commits, selected_index, current_diff_range, is_old_file);
+
+    if inverted {
+        append_title_tag(&title, "inverted")
+    } else {
+        title
+    }
+}
+
+fn side_by_side_base_title(
+    commits: &[crate::commit::Commit],
+    selected_index: usize,
+    current_diff_range: Option<(usize, usize)>,
+    is_old_file: bool,
 ) -> String {
     if commits.is_empty() {
         return if is_old_file {
@@ -177,3 +335,24 @@ pub fn create_side_by_side_title(
         }
     }
 }
+
+/// Rect of the given percentage size centered in `r`, used for popups
+pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -7,13 +7,49 @@ use ratatui::{
     Frame,
 };
 
-use crate::git::files::{format_file_size, format_modified_time, GitFile};
+use crate::git::files::{format_file_size, format_modified_time, FileStatus, GitFile};
+use std::path::Path;
+
+/// Order of the file list while no search is typed; matches are ordered by score
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FileSort {
+    /// By path, as `git ls-files` lists them
+    #[default]
+    Name,
+    /// Most recently modified first
+    Modified,
+    /// Files with changes first
+    Status,
+}
+
+impl FileSort {
+    pub fn next(self) -> Self {
+        match self {
+            FileSort::Name => FileSort::Modified,
+            FileSort::Modified => FileSort::Status,
+            FileSort::Status => FileSort::Name,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FileSort::Name => "name",
+            FileSort::Modified => "mtime",
+            FileSort::Status => "status",
+        }
+    }
+}
 
 pub struct FilePickerState {
     pub files: Vec<GitFile>,
     pub filtered_files: Vec<(usize, Vec<usize>)>, // (file_index, highlight_indices)
     pub query: String,
     pub selected: usize,
+    /// Whether configured exclude globs are currently hiding files
+    pub hiding_excluded: bool,
+    /// Whether only files with working directory or staged changes are listed
+    pub modified_only: bool,
+    pub sort: FileSort,
     matcher: SkimMatcherV2,
 }
 
@@ -24,6 +60,9 @@ impl std::fmt::Debug for FilePickerState {
             .field("filtered_files", &self.filtered_files.len())
             .field("query", &self.query)
             .field("selected", &self.selected)
+            .field("hiding_excluded", &self.hiding_excluded)
+            .field("modified_only", &self.modified_only)
+            .field("sort", &self.sort)
             .field("matcher", &"SkimMatcherV2")
             .finish()
     }
@@ -36,6 +75,9 @@ impl Clone for FilePickerState {
             filtered_files: self.filtered_files.clone(),
             query: self.query.clone(),
             selected: self.selected,
+            hiding_excluded: self.hiding_excluded,
+            modified_only: self.modified_only,
+            sort: self.sort,
             matcher: SkimMatcherV2::default(),
         }
     }
@@ -48,6 +90,9 @@ impl FilePickerState {
             filtered_files: Vec::new(),
             query: String::new(),
             selected: 0,
+            hiding_excluded: false,
+            modified_only: false,
+            sort: FileSort::default(),
             matcher: SkimMatcherV2::default(),
         };
 
@@ -56,7 +101,6 @@ impl FilePickerState {
         state
     }
 
-    #[allow(dead_code)]
     pub fn update_query(&mut self, query: String) {
         self.query = query;
         self.selected = 0;
@@ -93,6 +137,50 @@ impl FilePickerState {
         }
     }
 
+    /// Index into `filtered_files` of the entry drawn on screen row `row`, when the file list
+    /// fills `list_area`. The list is drawn with a fresh `ListState` each frame, so it is
+    /// scrolled just far enough to keep the selection on its last row.
+    pub fn file_at_row(&self, list_area: Rect, row: u16) -> Option<usize> {
+        if row < list_area.y || row >= list_area.bottom() {
+            return None;
+        }
+
+        let rows = list_area.height.max(1) as usize;
+        let first_visible = self.selected.saturating_sub(rows - 1);
+        let index = first_visible + (row - list_area.y) as usize;
+        (index < self.filtered_files.len()).then_some(index)
+    }
+
+    /// Move the selection to `path` if it is listed. Returns whether it was found.
+    pub fn select_path(&mut self, path: &Path) -> bool {
+        let position = self
+            .filtered_files
+            .iter()
+            .position(|(file_index, _)| Path::new(&self.files[*file_index].display_path) == path);
+        if let Some(position) = position {
+            self.selected = position;
+        }
+        position.is_some()
+    }
+
+    /// List only files that are not clean, or every file again
+    pub fn toggle_modified_only(&mut self) {
+        self.modified_only = !self.modified_only;
+        self.selected = 0;
+        self.update_filter();
+    }
+
+    /// Switch to the next sort order
+    pub fn cycle_sort(&mut self) {
+        self.set_sort(self.sort.next());
+    }
+
+    pub fn set_sort(&mut self, sort: FileSort) {
+        self.sort = sort;
+        self.selected = 0;
+        self.update_filter();
+    }
+
     pub fn get_selected_file(&self) -> Option<&GitFile> {
         if let Some((file_index, _)) = self.filtered_files.get(self.selected) {
             self.files.get(*file_index)
@@ -104,20 +192,30 @@ impl FilePickerState {
     fn update_filter(&mut self) {
         self.filtered_files.clear();
 
+        let modified_only = self.modified_only;
+        let candidates = self
+            .files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| !modified_only || file.status != FileStatus::Clean);
+
         if self.query.is_empty() {
             // Show all files when no query
-            self.filtered_files = self
-                .files
-                .iter()
-                .enumerate()
-                .map(|(i, _)| (i, Vec::new()))
-                .collect();
+            let mut listed: Vec<_> = candidates.collect();
+            // Stable sorts, so ties keep the path order
+            match self.sort {
+                FileSort::Name => {}
+                FileSort::Modified => {
+                    listed.sort_by_key(|(_, file)| std::cmp::Reverse(file.modified))
+                }
+                FileSort::Status => {
+                    listed.sort_by_key(|(_, file)| file.status == FileStatus::Clean)
+                }
+            }
+            self.filtered_files = listed.into_iter().map(|(i, _)| (i, Vec::new())).collect();
         } else {
             // Fuzzy match against display path
-            let mut matches: Vec<_> = self
-                .files
-                .iter()
-                .enumerate()
+            let mut matches: Vec<_> = candidates
                 .filter_map(|(i, file)| {
                     if let Some((score, indices)) =
                         self.matcher.fuzzy_indices(&file.display_path, &self.query)
@@ -130,7 +228,7 @@ impl FilePickerState {
                 .collect();
 
             // Sort by score (higher is better)
-            matches.sort_by(|a, b| b.0.cmp(&a.0));
+            matches.sort_by_key(|m| std::cmp::Reverse(m.0));
 
             // Take the best matches
             self.filtered_files = matches
@@ -141,22 +239,44 @@ impl FilePickerState {
     }
 }
 
+/// The popup: 80% of the screen, but at least 60x20 when the screen allows
+fn popup_area(area: Rect) -> Rect {
+    let popup_width = ((area.width as f32 * 0.8).max(60.0) as u16).min(area.width);
+    let popup_height = ((area.height as f32 * 0.8).max(20.0) as u16).min(area.height);
+
+    Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    }
+}
+
+/// Split the popup inside its border into search, list, and status areas
+fn popup_chunks(popup_area: Rect) -> std::rc::Rc<[Rect]> {
+    let inner_area = popup_area.inner(Margin::new(1, 1));
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Search box
+            Constraint::Min(0),    // File list
+            Constraint::Length(1), // Status line
+        ])
+        .split(inner_area)
+}
+
+/// Where the file list is drawn on a screen of size `area`
+pub fn file_list_area(area: Rect) -> Rect {
+    popup_chunks(popup_area(area))[1]
+}
+
 pub fn draw_file_picker(
     frame: &mut Frame,
     state: &FilePickerState,
     context: &crate::app::FilePickerContext,
     area: Rect,
 ) {
-    // Calculate popup size (80% of screen, but at least 60x20)
-    let popup_width = (area.width as f32 * 0.8).max(60.0) as u16;
-    let popup_height = (area.height as f32 * 0.8).max(20.0) as u16;
-
-    let popup_area = Rect {
-        x: (area.width.saturating_sub(popup_width)) / 2,
-        y: (area.height.saturating_sub(popup_height)) / 2,
-        width: popup_width,
-        height: popup_height,
-    };
+    let popup_area = popup_area(area);
 
     // Clear the background
     frame.render_widget(Clear, popup_area);
@@ -174,16 +294,7 @@ pub fn draw_file_picker(
 
     frame.render_widget(popup_block, popup_area);
 
-    // Split popup into search, list, and status areas
-    let inner_area = popup_area.inner(Margin::new(1, 1));
-    let popup_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Search box
-            Constraint::Min(0),    // File list
-            Constraint::Length(1), // Status line
-        ])
-        .split(inner_area);
+    let popup_chunks = popup_chunks(popup_area);
 
     // Search box
     draw_search_box(frame, state, popup_chunks[0]);
@@ -297,15 +408,43 @@ fn draw_status_line(
         crate::app::FilePickerContext::SwitchFile { .. } => "return",
     };
 
-    let status_text = if state.query.is_empty() {
+    let excluded_info = if state.hiding_excluded {
+        " (excluded hidden, ^A: show)"
+    } else {
+        ""
+    };
+
+    let modified_info = if state.modified_only {
+        " • modified only (^M: all)"
+    } else {
+        ""
+    };
+
+    let status_text = if state.query.is_empty() && !state.modified_only {
         format!(
-            "📁 {} files{} • ↑↓/^P^N: navigate • Enter: select • Ctrl+Q: {} • Type to search",
-            total_files, context_info, esc_action
+            "📁 {} files{}{} • ^S: sort by {} • ↑↓/^P^N: navigate • Enter: select • Ctrl+Q: {} • Type to search",
+            total_files,
+            excluded_info,
+            context_info,
+            state.sort.label(),
+            esc_action
         )
     } else {
+        // Matches are ordered by score; the sort only applies to an unfiltered list
+        let sort_info = if state.query.is_empty() {
+            format!(" • ^S: sort by {}", state.sort.label())
+        } else {
+            String::new()
+        };
         format!(
-            "📁 {} files • {} matches{} • ↑↓/^P^N: navigate • Enter: select • Ctrl+Q: {}",
-            total_files, filtered_count, context_info, esc_action
+            "📁 {} files{}{} • {} matches{}{} • ↑↓/^P^N: navigate • Enter: select • Ctrl+Q: {}",
+            total_files,
+            excluded_info,
+            modified_info,
+            filtered_count,
+            context_info,
+            sort_info,
+            esc_action
         )
     };
 
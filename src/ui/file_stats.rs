@@ -0,0 +1,182 @@
+use crate::commit::FileHistoryStats;
+use crate::ui::common::utils::centered_rect;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+pub struct FileStatsPopup {
+    pub file_name: String,
+    pub stats: FileHistoryStats,
+    pub scroll_position: usize,
+    /// Commits whose stats have been fetched so far, out of `total`
+    pub loaded: usize,
+    pub total: usize,
+}
+
+impl FileStatsPopup {
+    pub fn new(file_name: String, total: usize) -> Self {
+        Self {
+            file_name,
+            stats: FileHistoryStats::default(),
+            scroll_position: 0,
+            loaded: 0,
+            total,
+        }
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loaded < self.total
+    }
+
+    pub fn scroll_up(&mut self) {
+        if self.scroll_position > 0 {
+            self.scroll_position -= 1;
+        }
+    }
+
+    pub fn scroll_down(&mut self, max_lines: usize, viewport_height: usize) {
+        if self.scroll_position + viewport_height < max_lines {
+            self.scroll_position += 1;
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(70, 60, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" File Statistics: {} ", self.file_name))
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+
+        frame.render_widget(block, popup_area);
+
+        let inner_area = popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 2,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),    // Summary
+                Constraint::Length(1), // Help line
+            ])
+            .split(inner_area);
+
+        let lines = self.content_lines();
+        let visible_height = chunks[0].height as usize;
+        let start_line = self.scroll_position.min(lines.len());
+        let end_line = (start_line + visible_height).min(lines.len());
+
+        let paragraph = Paragraph::new(lines[start_line..end_line].to_vec())
+            .style(Style::default().fg(Color::White));
+        frame.render_widget(paragraph, chunks[0]);
+
+        let help = Paragraph::new("[↑↓/jk] Scroll  [q/Esc] Close")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+
+    fn content_lines(&self) -> Vec<Line<'static>> {
+        let label_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let field = |label: &'static str, value: String, style: Style| {
+            Line::from(vec![
+                Span::styled(label, label_style),
+                Span::styled(value, style),
+            ])
+        };
+        let stats = &self.stats;
+        let mut lines = Vec::new();
+
+        if self.is_loading() {
+            lines.push(Line::from(Span::styled(
+                format!("Loading stats... {}/{}", self.loaded, self.total),
+                Style::default().fg(Color::Cyan),
+            )));
+            lines.push(Line::from(""));
+        }
+
+        lines.push(field(
+            "Commits:      ",
+            stats.total_commits.to_string(),
+            Style::default(),
+        ));
+        lines.push(Line::from(vec![
+            Span::styled("Changes:      ", label_style),
+            Span::styled(
+                format!("+{}", stats.insertions),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                format!("-{}", stats.deletions),
+                Style::default().fg(Color::Red),
+            ),
+            Span::styled(
+                " lines across these commits",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(field(
+            "First change: ",
+            stats
+                .first_change
+                .clone()
+                .unwrap_or_else(|| "-".to_string()),
+            Style::default(),
+        ));
+        lines.push(field(
+            "Last change:  ",
+            stats.last_change.clone().unwrap_or_else(|| "-".to_string()),
+            Style::default(),
+        ));
+
+        match stats.largest_commit {
+            Some(ref largest) => lines.push(Line::from(vec![
+                Span::styled("Largest:      ", label_style),
+                Span::styled(largest.short_hash.clone(), Style::default().fg(Color::Cyan)),
+                Span::raw(format!(
+                    " +{} -{} {}",
+                    largest.insertions, largest.deletions, largest.subject
+                )),
+            ])),
+            None => lines.push(field("Largest:      ", "-".to_string(), Style::default())),
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Top contributors", label_style)));
+        for contributor in &stats.top_contributors {
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {:>4} ", contributor.commits)),
+                Span::styled(
+                    if contributor.commits == 1 {
+                        "commit  "
+                    } else {
+                        "commits "
+                    },
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(contributor.name.clone()),
+                Span::styled(
+                    format!(" ({} lines)", contributor.lines_changed),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+
+        lines
+    }
+
+    pub fn get_total_lines(&self) -> usize {
+        self.content_lines().len()
+    }
+}
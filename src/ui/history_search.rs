@@ -0,0 +1,185 @@
+use crate::ui::common::utils::centered_rect;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// A changed line matching a history search
+#[derive(Debug, Clone)]
+pub struct HistorySearchHit {
+    pub commit_index: usize,
+    pub commit_hash: String,
+    pub short_hash: String,
+    /// Index of the line in the commit's diff
+    pub line_index: usize,
+    pub line: String,
+}
+
+/// Results of searching the diffs of every commit, filled in a batch of commits at a time
+pub struct HistorySearchPopup {
+    pub query: String,
+    pub is_input_mode: bool, // Currently typing the query
+    pub hits: Vec<HistorySearchHit>,
+    pub selected: usize,
+    /// Commits searched so far, out of `total`
+    pub searched: usize,
+    pub total: usize,
+    /// Commits in the whole history; more than `total` when the search was capped
+    pub history_len: usize,
+}
+
+impl HistorySearchPopup {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            is_input_mode: true,
+            hits: Vec::new(),
+            selected: 0,
+            searched: 0,
+            total: 0,
+            history_len: 0,
+        }
+    }
+
+    pub fn is_searching(&self) -> bool {
+        !self.is_input_mode && self.searched < self.total
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.hits.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected_hit(&self) -> Option<&HistorySearchHit> {
+        self.hits.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(80, 70, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Search History ")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+
+        frame.render_widget(block, popup_area);
+
+        let inner_area = popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 2,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1), // Query
+                Constraint::Length(1), // Progress
+                Constraint::Min(1),    // Hits
+                Constraint::Length(1), // Help line
+            ])
+            .split(inner_area);
+
+        let label_style = Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD);
+        let cursor = if self.is_input_mode { "_" } else { "" };
+        frame.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled("Pattern: ", label_style),
+                Span::raw(format!("{}{}", self.query, cursor)),
+            ])),
+            chunks[0],
+        );
+
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                self.progress_text(),
+                Style::default().fg(Color::Cyan),
+            )),
+            chunks[1],
+        );
+
+        // Keep the selected hit on screen
+        let visible_height = chunks[2].height as usize;
+        let start = self
+            .selected
+            .saturating_sub(visible_height.saturating_sub(1));
+        let lines: Vec<Line> = self
+            .hits
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(visible_height)
+            .map(|(i, hit)| {
+                let line_style = if hit.line.starts_with('+') {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default().fg(Color::Red)
+                };
+                let line = Line::from(vec![
+                    Span::styled(
+                        format!("{} ", hit.short_hash),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(hit.line.clone(), line_style),
+                ]);
+                if i == self.selected && !self.is_input_mode {
+                    line.style(Style::default().bg(Color::DarkGray))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[2]);
+
+        let help = if self.is_input_mode {
+            "[Enter] Search  [Esc] Cancel"
+        } else {
+            "[↑↓/jk] Select  [Enter] Jump  [/] New search  [q/Esc] Close"
+        };
+        let help = Paragraph::new(help)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[3]);
+    }
+
+    fn progress_text(&self) -> String {
+        if self.is_input_mode {
+            return "Regex, case-insensitive, matched against added and removed lines".to_string();
+        }
+        // Hits arrive grouped by commit
+        let mut hashes: Vec<&str> = self.hits.iter().map(|h| h.commit_hash.as_str()).collect();
+        hashes.dedup();
+        let commits = hashes.len();
+        let found = format!("{} matches in {} commits", self.hits.len(), commits);
+        if self.is_searching() {
+            format!(
+                "Searching... {}/{} commits, {}",
+                self.searched, self.total, found
+            )
+        } else if self.total < self.history_len {
+            format!(
+                "{}; searched the newest {} of {} commits",
+                found, self.total, self.history_len
+            )
+        } else {
+            found
+        }
+    }
+}
+
+impl Default for HistorySearchPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,16 +1,68 @@
+pub mod blame;
 pub mod commit_info;
 mod common;
 pub mod file_picker;
+pub mod file_stats;
+pub mod history_search;
+pub mod parent_chooser;
 mod side_by_side;
 pub mod state;
 mod unified;
 
 use crate::app::App;
 use crate::cli::LayoutMode;
-use common::draw_help_overlay;
-use ratatui::Frame;
+use common::{draw_copy_help_overlay, draw_help_overlay};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+/// Smallest terminal size the full layout can render without garbling
+pub const MIN_TERMINAL_WIDTH: u16 = 40;
+pub const MIN_TERMINAL_HEIGHT: u16 = 10;
+
+/// Split the main area (everything above the status bar) into the diff on top and the commit
+/// strip below, giving the diff `diff_ratio` of the height; mouse hit-testing uses the same split
+pub fn split_commits_bottom(area: Rect, diff_ratio: f32) -> (Rect, Rect) {
+    let diff_percent = (diff_ratio * 100.0).round() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(diff_percent),
+            Constraint::Percentage(100 - diff_percent),
+        ])
+        .split(area);
+    (chunks[0], chunks[1])
+}
+
+/// Index of the commit on the first row of a commit list panel `panel_height` rows tall. The
+/// list is drawn with a fresh `ListState` each frame, so ratatui scrolls it just far enough to
+/// keep the selected commit on the last visible row.
+pub fn first_visible_commit(selected_index: usize, panel_height: u16) -> usize {
+    let rows = panel_height.saturating_sub(2).max(1) as usize; // Borders
+    selected_index.saturating_sub(rows - 1)
+}
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    draw_screen(frame, app);
+
+    // Styles keep their modifiers, so emphasis survives on a monochrome terminal
+    if crate::config::theme::current().no_color {
+        for cell in &mut frame.buffer_mut().content {
+            cell.set_fg(Color::Reset).set_bg(Color::Reset);
+        }
+    }
+}
+
+fn draw_screen(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+        draw_too_small_message(frame, area);
+        return;
+    }
+
     match &app.mode {
         crate::app::AppMode::FilePicker {
             ref state,
@@ -30,12 +82,55 @@ pub fn draw(frame: &mut Frame, app: &App) {
         draw_help_overlay(frame, app, frame.area());
     }
 
+    // Draw the copy mode key overlay on top if shown
+    if app.copy_mode.is_some() && app.show_copy_help {
+        draw_copy_help_overlay(frame, app, frame.area());
+    }
+
     // Draw commit info popup on top if shown
     if app.show_commit_info {
         if let Some(ref popup) = app.commit_info_popup {
             popup.render(frame, frame.area());
         }
     }
+
+    // Draw file statistics popup on top if shown
+    if let Some(ref popup) = app.file_stats_popup {
+        popup.render(frame, frame.area());
+    }
+
+    // Draw history search results on top if shown
+    if let Some(ref popup) = app.history_search {
+        popup.render(frame, frame.area());
+    }
+
+    // Draw the merge parent chooser on top if shown
+    if let Some(ref chooser) = app.parent_chooser {
+        chooser.render(frame, frame.area());
+    }
+}
+
+/// Replace the whole UI with a notice until the terminal is resized large enough
+fn draw_too_small_message(frame: &mut Frame, area: Rect) {
+    let message = format!(
+        "Terminal too small (need ≥ {}x{})",
+        MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+    );
+
+    // Vertically center the message; wrapping may use a few rows on narrow terminals
+    let y = area.y + area.height.saturating_sub(1) / 2;
+    let message_area = Rect {
+        x: area.x,
+        y,
+        width: area.width,
+        height: area.height - (y - area.y),
+    };
+
+    let paragraph = Paragraph::new(message)
+        .style(Style::default().fg(Color::Yellow))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, message_area);
 }
 
 fn draw_history_ui(frame: &mut Frame, app: &App) {
@@ -0,0 +1,98 @@
+use crate::ui::common::utils::centered_rect;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// A listed commit a merge's parent leads to
+#[derive(Debug, Clone)]
+pub struct ParentChoice {
+    pub commit_index: usize,
+    pub short_hash: String,
+    pub subject: String,
+}
+
+/// Asks which parent of a merge commit to step to
+pub struct ParentChooser {
+    pub choices: Vec<ParentChoice>,
+    pub selected: usize,
+}
+
+impl ParentChooser {
+    pub fn new(choices: Vec<ParentChoice>) -> Self {
+        Self {
+            choices,
+            selected: 0,
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.choices.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected_choice(&self) -> Option<&ParentChoice> {
+        self.choices.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(50, 30, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Go to Parent ")
+            .borders(Borders::ALL)
+            .style(Style::default().fg(Color::White));
+
+        frame.render_widget(block, popup_area);
+
+        let inner_area = popup_area.inner(Margin {
+            vertical: 1,
+            horizontal: 2,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),    // Parents
+                Constraint::Length(1), // Help line
+            ])
+            .split(inner_area);
+
+        let lines: Vec<Line> = self
+            .choices
+            .iter()
+            .enumerate()
+            .map(|(i, choice)| {
+                let line = Line::from(vec![
+                    Span::styled(format!("{} ", i + 1), Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        format!("{} ", choice.short_hash),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(choice.subject.clone()),
+                ]);
+                if i == self.selected {
+                    line.style(Style::default().bg(Color::DarkGray))
+                } else {
+                    line
+                }
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let help = Paragraph::new("[↑↓/jk] Select  [Enter/1-9] Go  [q/Esc] Cancel")
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(help, chunks[1]);
+    }
+}
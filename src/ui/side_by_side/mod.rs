@@ -1,10 +1,17 @@
 use crate::app::{App, FocusedPanel};
-use crate::diff::{DiffLine, DiffLineType};
+use crate::diff::words::emphasize_ranges;
+use crate::diff::{render_whitespace, DiffLine, DiffLineType};
+use crate::ui::blame::draw_blame_panel;
 use crate::ui::common::{
     commits::{draw_commits_panel, CommitsPanelLayout},
     draw_status_bar,
-    utils::{create_border_style, create_side_by_side_title},
+    utils::{
+        append_scroll_percent, append_title_tag, apply_cursor_highlight, create_border_style,
+        create_side_by_side_title, draw_scrollbar,
+    },
 };
+use crate::ui::split_commits_bottom;
+use crate::ui::state::SideBySideFocus;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -12,7 +19,8 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use std::path::PathBuf;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 /// Draw the side-by-side layout (three-panel layout: top split panels for diffs, bottom panel for commits)
 pub fn draw(frame: &mut Frame, app: &App) {
@@ -21,36 +29,103 @@ pub fn draw(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(frame.area());
 
-    // Split main area vertically: 70% for diffs, 30% for commits
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(70), Constraint::Percentage(30)])
-        .split(chunks[0]);
-
-    // Split top area horizontally for side-by-side diffs
-    let diff_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(main_chunks[0]);
+    // Split main area vertically: diffs on top, commits below
+    let (diff_area, commits_area) =
+        split_commits_bottom(chunks[0], app.ui_state.vertical_split_ratio);
 
-    // Draw the three panels
-    draw_old_file_panel(frame, app, diff_chunks[0]);
-    draw_new_file_panel(frame, app, diff_chunks[1]);
-    draw_commits_panel(frame, app, main_chunks[1], CommitsPanelLayout::Horizontal);
+    if let Some(ref blame_lines) = app.blame_lines {
+        // Blame spans both diff panels
+        draw_blame_panel(frame, app, diff_area, blame_lines);
+    } else if app.full_file_view {
+        // There is only one side to a whole file
+        crate::ui::unified::draw_diff_panel(frame, app, diff_area);
+    } else {
+        // Split top area horizontally for side-by-side diffs
+        let diff_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(diff_area);
+
+        draw_old_file_panel(frame, app, diff_chunks[0]);
+        draw_new_file_panel(frame, app, diff_chunks[1]);
+    }
+    draw_commits_panel(frame, app, commits_area, CommitsPanelLayout::Horizontal);
 
     // Draw status bar
     draw_status_bar(frame, app, chunks[1]);
 }
 
-fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
+/// Whether one side of the diff is focused; each side gets its own border highlight
+fn is_side_focused(app: &App, side: SideBySideFocus) -> bool {
+    app.get_focused_panel() == Some(FocusedPanel::Diff) && app.ui_state.side_by_side_focus == side
+}
+
+/// The current search match is emphasized on the focused side only, where the cursor is;
+/// with the commit list focused it stays on the side that had focus last
+fn emphasizes_current_match(app: &App, side: SideBySideFocus) -> bool {
+    app.ui_state.side_by_side_focus == side
+}
+
+fn side_title(app: &App, is_old_file: bool, area: Rect) -> String {
     let title = create_side_by_side_title(
         &app.commits,
         app.selected_index,
         app.current_diff_range,
-        true, // is_old_file
+        is_old_file,
+        app.invert_diff,
     );
 
-    let focused = app.get_focused_panel() == Some(FocusedPanel::Diff); // For now, both diff panels share focus
+    // The clipboard is the old side of the comparison, the new side once inverted
+    let title = if app.comparing_clipboard && is_old_file != app.invert_diff {
+        append_title_tag(&title, "clipboard")
+    } else {
+        title
+    };
+
+    let title = if app.is_directory_mode() {
+        append_title_tag(&title, "directory")
+    } else {
+        title
+    };
+    let title = if app.showing_untracked_file() {
+        append_title_tag(&title, "new file")
+    } else {
+        title
+    };
+    let title = if app.showing_whole_commit() {
+        append_title_tag(&title, "whole commit")
+    } else {
+        title
+    };
+    let title = if app.ignore_whitespace {
+        append_title_tag(&title, "ignoring whitespace")
+    } else {
+        title
+    };
+    let title = if app.showing_combined_diff() {
+        append_title_tag(&title, "combined")
+    } else {
+        title
+    };
+
+    // Both sides scroll together, so only the right one shows where they are
+    if is_old_file {
+        title
+    } else {
+        append_scroll_percent(
+            &title,
+            app.ui_state.diff_scroll,
+            area.height.saturating_sub(2) as usize,
+            app.get_diff_line_count(),
+        )
+    }
+}
+
+fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
+    let title = side_title(app, true, area);
+
+    let focused = is_side_focused(app, SideBySideFocus::Old);
+    let emphasize_current = emphasizes_current_match(app, SideBySideFocus::Old);
     let border_style = create_border_style(focused);
 
     let block = Block::default()
@@ -67,7 +142,15 @@ fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     if let Some(ref side_by_side) = app.current_side_by_side_diff {
+        let word_highlights = app
+            .ui_state
+            .word_diff
+            .then_some(app.cached_word_highlights.as_ref())
+            .flatten();
+
         // Render the old file content using the styled lines from HighlightedDiff
+        // A whole-commit diff highlights each file's lines by that file's path
+        let mut section: Option<PathBuf> = None;
         let lines: Vec<Line> = side_by_side
             .old_lines
             .iter()
@@ -75,14 +158,31 @@ fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
             .map(|(global_line_index, line_opt)| {
                 let styled_line = match line_opt {
                     Some(line) => {
+                        if let Some(path) = crate::diff::diff_header_path(&line.content) {
+                            section = Some(path);
+                        }
+                        let highlight_path = app
+                            .highlight_path()
+                            .map(|path| section.as_deref().unwrap_or(path));
+                        let word_ranges = word_highlights
+                            .and_then(|words| words.ranges_for(section.as_deref(), line));
                         // Use the proper syntax highlighting and styling with search support
-                        style_side_by_side_line(
+                        let styled = style_side_by_side_line(
                             line,
                             true,
-                            app.get_file_path(),
+                            highlight_path,
+                            app.highlight_language(),
                             global_line_index,
                             app.diff_search_state.as_ref(),
-                        )
+                            emphasize_current,
+                            word_ranges,
+                        );
+                        if app.show_whitespace {
+                            // Only code lines have spans past the line number and marker
+                            render_whitespace(styled, 2)
+                        } else {
+                            styled
+                        }
                         // true = old file
                     }
                     None => {
@@ -110,6 +210,13 @@ fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
             .scroll((0, app.ui_state.diff_horizontal_scroll as u16));
 
         frame.render_widget(paragraph, area);
+        // Measured like the scroll percentage in the new side's title
+        draw_scrollbar(
+            frame,
+            area,
+            app.ui_state.diff_scroll,
+            app.get_diff_line_count(),
+        );
     } else {
         let paragraph = Paragraph::new("No diff selected")
             .block(block)
@@ -119,14 +226,10 @@ fn draw_old_file_panel(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn draw_new_file_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let title = create_side_by_side_title(
-        &app.commits,
-        app.selected_index,
-        app.current_diff_range,
-        false, // is_old_file
-    );
+    let title = side_title(app, false, area);
 
-    let focused = app.get_focused_panel() == Some(FocusedPanel::Diff); // For now, both diff panels share focus
+    let focused = is_side_focused(app, SideBySideFocus::New);
+    let emphasize_current = emphasizes_current_match(app, SideBySideFocus::New);
     let border_style = create_border_style(focused);
 
     let block = Block::default()
@@ -143,7 +246,15 @@ fn draw_new_file_panel(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     if let Some(ref side_by_side) = app.current_side_by_side_diff {
+        let word_highlights = app
+            .ui_state
+            .word_diff
+            .then_some(app.cached_word_highlights.as_ref())
+            .flatten();
+
         // Render the new file content using the styled lines from HighlightedDiff
+        // A whole-commit diff highlights each file's lines by that file's path
+        let mut section: Option<PathBuf> = None;
         let lines: Vec<Line> = side_by_side
             .new_lines
             .iter()
@@ -151,14 +262,31 @@ fn draw_new_file_panel(frame: &mut Frame, app: &App, area: Rect) {
             .map(|(global_line_index, line_opt)| {
                 let styled_line = match line_opt {
                     Some(line) => {
+                        if let Some(path) = crate::diff::diff_header_path(&line.content) {
+                            section = Some(path);
+                        }
+                        let highlight_path = app
+                            .highlight_path()
+                            .map(|path| section.as_deref().unwrap_or(path));
+                        let word_ranges = word_highlights
+                            .and_then(|words| words.ranges_for(section.as_deref(), line));
                         // Use the proper syntax highlighting and styling with search support
-                        style_side_by_side_line(
+                        let styled = style_side_by_side_line(
                             line,
                             false,
-                            app.get_file_path(),
+                            highlight_path,
+                            app.highlight_language(),
                             global_line_index,
                             app.diff_search_state.as_ref(),
-                        )
+                            emphasize_current,
+                            word_ranges,
+                        );
+                        if app.show_whitespace {
+                            // Only code lines have spans past the line number and marker
+                            render_whitespace(styled, 2)
+                        } else {
+                            styled
+                        }
                         // false = new file
                     }
                     None => {
@@ -186,6 +314,13 @@ fn draw_new_file_panel(frame: &mut Frame, app: &App, area: Rect) {
             .scroll((0, app.ui_state.diff_horizontal_scroll as u16));
 
         frame.render_widget(paragraph, area);
+        // Measured like the scroll percentage in the new side's title
+        draw_scrollbar(
+            frame,
+            area,
+            app.ui_state.diff_scroll,
+            app.get_diff_line_count(),
+        );
     } else {
         let paragraph = Paragraph::new("No diff selected")
             .block(block)
@@ -195,12 +330,16 @@ fn draw_new_file_panel(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 /// Style a diff line for side-by-side view with proper syntax highlighting and line numbers
+#[allow(clippy::too_many_arguments)]
 fn style_side_by_side_line(
     line: &DiffLine,
     is_old_file: bool,
-    file_path: Option<&PathBuf>,
+    file_path: Option<&Path>,
+    language: Option<&str>,
     line_index: usize,
     search_state: Option<&crate::app::DiffSearchState>,
+    emphasize_current: bool,
+    word_ranges: Option<&[Range<usize>]>,
 ) -> Line<'static> {
     match line.line_type {
         DiffLineType::Header => {
@@ -256,15 +395,16 @@ fn style_side_by_side_line(
             spans.push(Span::styled(num_str, Style::default().fg(Color::DarkGray)));
 
             // Add the diff marker with appropriate color (but only for relevant lines)
+            let theme = crate::config::theme::current();
             let (marker, marker_color, bg_color) = match line.line_type {
                 DiffLineType::Addition if !is_old_file => {
-                    ("+", Color::Green, Some(Color::Rgb(180, 235, 180)))
-                } // Medium light green - same as unified view
+                    ("+", theme.addition_fg, Some(theme.addition_bg))
+                }
                 DiffLineType::Deletion if is_old_file => {
-                    ("-", Color::Red, Some(Color::Rgb(235, 180, 180)))
-                } // Medium light red - same as unified view
-                DiffLineType::Context => (" ", Color::Gray, None),
-                _ => (" ", Color::Gray, None), // Fallback for mismatched lines
+                    ("-", theme.deletion_fg, Some(theme.deletion_bg))
+                }
+                DiffLineType::Context => (" ", theme.context_fg, None),
+                _ => (" ", theme.context_fg, None), // Fallback for mismatched lines
             };
 
             spans.push(Span::styled(
@@ -284,7 +424,7 @@ fn style_side_by_side_line(
             // Apply syntax highlighting if available
             if let Some(file_path) = file_path {
                 let highlighted_spans =
-                    crate::diff::syntax::highlight_line(&code_content, file_path);
+                    crate::diff::syntax::highlight_line(&code_content, file_path, language);
 
                 // Apply background color for additions/deletions
                 for span in highlighted_spans {
@@ -297,9 +437,9 @@ fn style_side_by_side_line(
             } else {
                 // No syntax highlighting, just use basic colors
                 let style = Style::default().fg(match line.line_type {
-                    DiffLineType::Addition if !is_old_file => Color::Green,
-                    DiffLineType::Deletion if is_old_file => Color::Red,
-                    DiffLineType::Context => Color::Gray,
+                    DiffLineType::Addition if !is_old_file => theme.addition_fg,
+                    DiffLineType::Deletion if is_old_file => theme.deletion_fg,
+                    DiffLineType::Context => theme.context_fg,
                     _ => Color::White,
                 });
 
@@ -313,12 +453,18 @@ fn style_side_by_side_line(
 
             let mut styled_line = Line::from(spans);
 
+            if let Some(ranges) = word_ranges {
+                // Skip the line number and diff marker spans
+                styled_line = emphasize_ranges(styled_line, 2, ranges, line.line_type);
+            }
+
             // Apply search highlighting if active - only for code lines
             if let Some(search_state) = search_state {
                 styled_line = apply_side_by_side_search_highlighting(
                     styled_line,
                     line_index,
                     search_state,
+                    emphasize_current,
                     line,
                 );
             }
@@ -328,27 +474,12 @@ fn style_side_by_side_line(
     }
 }
 
-/// Apply cursor highlighting to a line by adding background color to all spans
-fn apply_cursor_highlight(line: Line<'static>) -> Line<'static> {
-    let highlighted_spans: Vec<Span> = line
-        .spans
-        .into_iter()
-        .map(|span| {
-            let mut style = span.style;
-            // Use a subtle blue background for cursor highlighting
-            style = style.bg(Color::Rgb(60, 80, 120)); // Dark blue background
-            Span::styled(span.content, style)
-        })
-        .collect();
-
-    Line::from(highlighted_spans)
-}
-
 /// Apply search highlighting to a side-by-side styled line
 fn apply_side_by_side_search_highlighting(
     styled_line: Line<'static>,
     line_index: usize,
     search_state: &crate::app::DiffSearchState,
+    emphasize_current: bool,
     original_line: &crate::diff::DiffLine,
 ) -> Line<'static> {
     // Find matches for this line
@@ -384,8 +515,9 @@ fn apply_side_by_side_search_highlighting(
             .map(|s| s.content.as_ref())
             .collect();
 
-        let span_start_in_code = code_content_before.len();
-        let span_end_in_code = span_start_in_code + span_content.len();
+        // Match positions count chars, so spans are measured in chars too
+        let span_start_in_code = code_content_before.chars().count();
+        let span_end_in_code = span_start_in_code + span_content.chars().count();
 
         // Find matches that overlap with this span's content
         let overlapping_matches: Vec<&crate::app::SearchMatch> = line_matches
@@ -411,6 +543,7 @@ fn apply_side_by_side_search_highlighting(
                 span_start_in_code,
                 &overlapping_matches,
                 search_state,
+                emphasize_current,
                 original_line.line_type,
                 &mut result_spans,
             );
@@ -426,10 +559,12 @@ fn apply_side_by_side_highlighting_to_span(
     span_start_in_code: usize,
     overlapping_matches: &[&crate::app::SearchMatch],
     search_state: &crate::app::DiffSearchState,
+    emphasize_current: bool,
     line_type: crate::diff::DiffLineType,
     result_spans: &mut Vec<Span<'static>>,
 ) {
     let span_content = span.content.to_string();
+    let span_len = span_content.chars().count();
     let mut processed_chars = 0;
 
     for &search_match in overlapping_matches {
@@ -440,10 +575,10 @@ fn apply_side_by_side_highlighting_to_span(
         // Calculate positions within this specific span
         let match_start_in_span = match_start_in_code.saturating_sub(span_start_in_code);
         let match_end_in_span =
-            (match_end_in_code.saturating_sub(span_start_in_code)).min(span_content.len());
+            (match_end_in_code.saturating_sub(span_start_in_code)).min(span_len);
 
         // Skip if match doesn't actually overlap this span
-        if match_start_in_span >= span_content.len() || match_end_in_span == 0 {
+        if match_start_in_span >= span_len || match_end_in_span == 0 {
             continue;
         }
 
@@ -468,9 +603,10 @@ fn apply_side_by_side_highlighting_to_span(
             .collect::<String>();
 
         if !match_text.is_empty() {
-            let is_current_match = search_state.current_result.is_some_and(|idx| {
-                idx < search_state.results.len() && &search_state.results[idx] == search_match
-            });
+            let is_current_match = emphasize_current
+                && search_state.current_result.is_some_and(|idx| {
+                    idx < search_state.results.len() && &search_state.results[idx] == search_match
+                });
 
             // Use the same context-aware highlighting as unified view
             let highlight_style =
@@ -483,7 +619,7 @@ fn apply_side_by_side_highlighting_to_span(
     }
 
     // Add remaining text after all matches
-    if processed_chars < span_content.len() {
+    if processed_chars < span_len {
         let remaining_text = span_content
             .chars()
             .skip(processed_chars)
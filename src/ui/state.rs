@@ -1,6 +1,55 @@
+/// Which side of the side-by-side diff has focus when the diff panel is focused
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SideBySideFocus {
+    Old,
+    New,
+}
+
+/// How authors are shown in the commit list and the commit info popup
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AuthorDisplay {
+    /// No author column in the list; the popup shows `name <email>`
+    #[default]
+    Hidden,
+    Name,
+    Email,
+    NameAndEmail,
+}
+
+impl AuthorDisplay {
+    pub fn next(self) -> Self {
+        match self {
+            AuthorDisplay::Hidden => AuthorDisplay::Name,
+            AuthorDisplay::Name => AuthorDisplay::Email,
+            AuthorDisplay::Email => AuthorDisplay::NameAndEmail,
+            AuthorDisplay::NameAndEmail => AuthorDisplay::Hidden,
+        }
+    }
+
+    /// Author text for the commit list, or `None` when the column is hidden
+    pub fn list_label(self, commit: &crate::commit::Commit) -> Option<String> {
+        match self {
+            AuthorDisplay::Hidden => None,
+            _ => Some(self.popup_label(commit)),
+        }
+    }
+
+    /// Author text for the commit info popup
+    pub fn popup_label(self, commit: &crate::commit::Commit) -> String {
+        match self {
+            AuthorDisplay::Name => commit.author_name.clone(),
+            AuthorDisplay::Email if !commit.author_email.is_empty() => commit.author_email.clone(),
+            AuthorDisplay::Email => commit.author_name.clone(),
+            AuthorDisplay::Hidden | AuthorDisplay::NameAndEmail => commit.author(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct UIState {
     pub split_ratio: f32,
+    /// Share of the height given to the diff when the commit list sits below it
+    pub vertical_split_ratio: f32,
     pub show_help: bool,
     pub terminal_height: u16,
     pub terminal_width: u16,
@@ -8,12 +57,22 @@ pub struct UIState {
     pub diff_horizontal_scroll: usize,
     pub commit_horizontal_scroll: usize,
     pub diff_cursor_line: usize,
+    /// Where a visual selection (`V`) started; it runs from here to the cursor
+    pub diff_selection_anchor: Option<usize>,
+    pub side_by_side_focus: SideBySideFocus,
+    /// Unified layout stacks a commit strip below the diff instead of a left column
+    pub commits_bottom: bool,
+    pub author_display: AuthorDisplay,
+    pub word_diff: bool, // Emphasize changed words within modified lines
+    /// Wrap long diff lines instead of scrolling sideways (unified layout)
+    pub word_wrap: bool,
 }
 
 impl UIState {
     pub fn new() -> Self {
         Self {
             split_ratio: 0.4,
+            vertical_split_ratio: 0.7,
             show_help: false,
             terminal_height: 24,
             terminal_width: 80,
@@ -21,9 +80,31 @@ impl UIState {
             diff_horizontal_scroll: 0,
             commit_horizontal_scroll: 0,
             diff_cursor_line: 0,
+            diff_selection_anchor: None,
+            side_by_side_focus: SideBySideFocus::Old,
+            commits_bottom: false,
+            author_display: AuthorDisplay::default(),
+            word_diff: false,
+            word_wrap: false,
         }
     }
 
+    /// First and last diff line of the visual selection, in order
+    pub fn diff_selection(&self) -> Option<(usize, usize)> {
+        self.diff_selection_anchor.map(|anchor| {
+            (
+                anchor.min(self.diff_cursor_line),
+                anchor.max(self.diff_cursor_line),
+            )
+        })
+    }
+
+    /// Whether `line` is inside the visual selection
+    pub fn is_diff_line_selected(&self, line: usize) -> bool {
+        self.diff_selection()
+            .is_some_and(|(first, last)| (first..=last).contains(&line))
+    }
+
     pub fn handle_resize(&mut self, width: u16, height: u16) {
         self.terminal_height = height;
         self.terminal_width = width;
@@ -41,6 +122,7 @@ impl UIState {
         self.diff_scroll = 0;
         self.diff_horizontal_scroll = 0;
         self.diff_cursor_line = 0;
+        self.diff_selection_anchor = None;
     }
 
     pub fn increase_split_ratio(&mut self) {
@@ -51,28 +133,41 @@ impl UIState {
         self.split_ratio = (self.split_ratio - 0.05).max(0.2);
     }
 
+    pub fn increase_vertical_split_ratio(&mut self) {
+        self.vertical_split_ratio = (self.vertical_split_ratio + 0.05).min(0.9);
+    }
+
+    pub fn decrease_vertical_split_ratio(&mut self) {
+        self.vertical_split_ratio = (self.vertical_split_ratio - 0.05).max(0.3);
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
 
-    pub fn get_page_scroll_size(&self) -> usize {
+    /// Lines a full-page scroll moves
+    pub fn get_full_page_scroll_size(&self) -> usize {
         // Calculate scroll size based on visible diff area
         // Accounting for borders (2 lines) and status bar (1 line)
         let visible_height = self.terminal_height.saturating_sub(3) as usize;
         // Use 60% of the visible area for diff (based on split_ratio)
         let diff_height = ((visible_height as f32) * (1.0 - self.split_ratio)) as usize;
-        // Scroll by half a page for better readability
-        diff_height.saturating_sub(2) / 2
+        diff_height.saturating_sub(2)
+    }
+
+    /// Lines a half-page scroll moves
+    pub fn get_page_scroll_size(&self) -> usize {
+        self.get_full_page_scroll_size() / 2
     }
 
     // Scrolling methods
-    pub fn scroll_diff_up(&mut self) {
+    pub fn scroll_diff_line_up(&mut self) {
         if self.diff_scroll > 0 {
             self.diff_scroll -= 1;
         }
     }
 
-    pub fn scroll_diff_down(&mut self, max_lines: usize) {
+    pub fn scroll_diff_line_down(&mut self, max_lines: usize) {
         // Ensure we don't scroll past the content
         // Account for viewport height to prevent scrolling too far
         let viewport_height = self.get_visible_lines(&crate::cli::LayoutMode::Unified);
@@ -84,17 +179,31 @@ impl UIState {
     }
 
     pub fn scroll_diff_page_up(&mut self) {
-        let page_size = self.get_page_scroll_size();
-        self.diff_scroll = self.diff_scroll.saturating_sub(page_size);
+        self.scroll_diff_up_by(self.get_full_page_scroll_size());
     }
 
     pub fn scroll_diff_page_down(&mut self, max_lines: usize) {
-        let page_size = self.get_page_scroll_size();
+        self.scroll_diff_down_by(self.get_full_page_scroll_size(), max_lines);
+    }
+
+    pub fn scroll_diff_half_page_up(&mut self) {
+        self.scroll_diff_up_by(self.get_page_scroll_size());
+    }
+
+    pub fn scroll_diff_half_page_down(&mut self, max_lines: usize) {
+        self.scroll_diff_down_by(self.get_page_scroll_size(), max_lines);
+    }
+
+    fn scroll_diff_up_by(&mut self, lines: usize) {
+        self.diff_scroll = self.diff_scroll.saturating_sub(lines.max(1));
+    }
+
+    fn scroll_diff_down_by(&mut self, lines: usize, max_lines: usize) {
         let viewport_height = self.get_visible_lines(&crate::cli::LayoutMode::Unified);
         let max_scroll = max_lines.saturating_sub(viewport_height);
 
         // Ensure we don't scroll past the content
-        self.diff_scroll = (self.diff_scroll + page_size).min(max_scroll);
+        self.diff_scroll = (self.diff_scroll + lines.max(1)).min(max_scroll);
     }
 
     pub fn scroll_diff_left(&mut self) {
@@ -136,20 +245,27 @@ impl UIState {
         // Calculate how many lines are visible in the diff area
         let visible_height = self.terminal_height.saturating_sub(1) as usize; // Account for status bar
 
-        match layout_mode {
-            crate::cli::LayoutMode::SideBySide => {
-                // In side-by-side mode, diff takes 70% of height, minus borders
-                let diff_height = ((visible_height as f32) * 0.7) as usize;
-                diff_height.saturating_sub(2) // Account for panel borders
-            }
-            _ => {
-                // In unified mode, diff area uses split ratio, minus borders
-                let diff_height = ((visible_height as f32) * (1.0 - self.split_ratio)) as usize;
-                diff_height.saturating_sub(2) // Account for panel borders
-            }
+        let commits_at_bottom =
+            matches!(layout_mode, crate::cli::LayoutMode::SideBySide) || self.commits_bottom;
+
+        if commits_at_bottom {
+            // With commits at the bottom, diff takes the vertical split's share, minus borders
+            let diff_height = ((visible_height as f32) * self.vertical_split_ratio) as usize;
+            diff_height.saturating_sub(2) // Account for panel borders
+        } else {
+            // In unified mode, diff area uses split ratio, minus borders
+            let diff_height = ((visible_height as f32) * (1.0 - self.split_ratio)) as usize;
+            diff_height.saturating_sub(2) // Account for panel borders
         }
     }
 
+    /// After scrolling the view, move the cursor onto it if it was left behind, so the next
+    /// cursor move continues from what's on screen instead of jumping back
+    pub fn keep_cursor_in_view(&mut self, layout_mode: &crate::cli::LayoutMode) {
+        let last_visible = self.diff_scroll + self.get_visible_lines(layout_mode).max(1) - 1;
+        self.diff_cursor_line = self.diff_cursor_line.clamp(self.diff_scroll, last_visible);
+    }
+
     pub fn ensure_cursor_visible(&mut self, layout_mode: &crate::cli::LayoutMode) {
         let visible_lines = self.get_visible_lines(layout_mode);
 
@@ -165,9 +281,34 @@ impl UIState {
         }
     }
 
+    /// How many columns of diff text the diff panel shows (a pane's, side by side)
+    pub fn get_visible_columns(&self, layout_mode: &crate::cli::LayoutMode) -> usize {
+        let width = self.terminal_width as usize;
+        let diff_width = match layout_mode {
+            crate::cli::LayoutMode::SideBySide => width / 2,
+            _ if self.commits_bottom => width,
+            _ => width - ((width as f32) * self.split_ratio) as usize,
+        };
+        diff_width.saturating_sub(2) // Account for panel borders
+    }
+
+    /// Width of the line numbers before a diff line's marker
+    fn diff_gutter_width(layout_mode: &crate::cli::LayoutMode) -> usize {
+        match layout_mode {
+            // "NNNN "
+            crate::cli::LayoutMode::SideBySide => 5,
+            // "NNNN│NNNN "
+            _ => 10,
+        }
+    }
+
+    /// Scroll so `target_line` is on screen and put the cursor on it. With `columns`, the
+    /// chars of the line's content (marker included) from start to end, scroll sideways
+    /// too so they are visible, back to the left edge whenever they fit there.
     pub fn ensure_diff_line_visible(
         &mut self,
         target_line: usize,
+        columns: Option<(usize, usize)>,
         layout_mode: &crate::cli::LayoutMode,
     ) {
         let visible_lines = self.get_visible_lines(layout_mode);
@@ -183,6 +324,20 @@ impl UIState {
             self.diff_scroll = target_line.saturating_sub(half_viewport);
         }
 
+        if let Some((start, end)) = columns {
+            let gutter = Self::diff_gutter_width(layout_mode);
+            let (start, end) = (gutter + start, gutter + end);
+            let visible_columns = self.get_visible_columns(layout_mode);
+            if end <= visible_columns {
+                self.diff_horizontal_scroll = 0;
+            } else if start < self.diff_horizontal_scroll
+                || end > self.diff_horizontal_scroll + visible_columns
+            {
+                // Leave some of the text before the match in view
+                self.diff_horizontal_scroll = start.saturating_sub(visible_columns / 4);
+            }
+        }
+
         // Also update cursor position to the target line
         self.diff_cursor_line = target_line;
     }
@@ -1,10 +1,16 @@
-use crate::app::{App, FocusedPanel};
+use crate::app::{is_hidden_line, App, FocusedPanel};
 use crate::diff::HighlightedDiff;
+use crate::ui::blame::{apply_heat, draw_blame_panel};
 use crate::ui::common::{
     commits::{draw_commits_panel, CommitsPanelLayout},
     draw_status_bar,
-    utils::{apply_horizontal_scroll, create_border_style, create_diff_title},
+    utils::{
+        append_scroll_percent, append_title_tag, apply_cursor_highlight, apply_horizontal_scroll,
+        apply_selection_highlight, create_border_style, create_diff_title, draw_scrollbar,
+        wrap_line,
+    },
 };
+use crate::ui::split_commits_bottom;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
@@ -20,6 +26,16 @@ pub fn draw(frame: &mut Frame, app: &App) {
         .constraints([Constraint::Min(0), Constraint::Length(1)])
         .split(frame.area());
 
+    if app.ui_state.commits_bottom {
+        // Diff on top with a short commit strip below
+        let (diff_area, commits_area) =
+            split_commits_bottom(chunks[0], app.ui_state.vertical_split_ratio);
+        draw_diff_panel(frame, app, diff_area);
+        draw_commits_panel(frame, app, commits_area, CommitsPanelLayout::Horizontal);
+        draw_status_bar(frame, app, chunks[1]);
+        return;
+    }
+
     let left_percent = (app.ui_state.split_ratio * 100.0) as u16;
     let right_percent = 100 - left_percent;
 
@@ -36,13 +52,54 @@ pub fn draw(frame: &mut Frame, app: &App) {
     draw_status_bar(frame, app, chunks[1]);
 }
 
-fn draw_diff_panel(frame: &mut Frame, app: &App, area: Rect) {
-    let title = create_diff_title(
+/// Draw the diff (or the full file, or blame) filling `area`
+pub(super) fn draw_diff_panel(frame: &mut Frame, app: &App, area: Rect) {
+    if let Some(ref blame_lines) = app.blame_lines {
+        draw_blame_panel(frame, app, area, blame_lines);
+        return;
+    }
+
+    let mut title = create_diff_title(
         &app.commits,
         app.selected_index,
         app.current_diff_range,
         app.diff_range_start,
         app.ui_state.diff_horizontal_scroll,
+        app.invert_diff,
+    );
+    if app.is_directory_mode() {
+        title = append_title_tag(&title, "directory");
+    }
+    if app.comparing_clipboard {
+        title = append_title_tag(&title, "vs clipboard");
+    }
+    if app.showing_untracked_file() {
+        title = append_title_tag(&title, "new file");
+    }
+    if app.showing_whole_commit() {
+        title = append_title_tag(&title, "whole commit");
+    }
+    if app.full_file_view {
+        title = append_title_tag(&title, "full file");
+        if app.heat_map {
+            title = append_title_tag(&title, "heat map");
+        }
+    } else if app.ignore_whitespace {
+        title = append_title_tag(&title, "ignoring whitespace");
+    }
+    if app.showing_combined_diff() {
+        title = append_title_tag(&title, "combined");
+    }
+    if app.wraps_diff() {
+        title = append_title_tag(&title, "wrap");
+    }
+    // The title percentage and the scrollbar measure against the same line count
+    let line_count = app.get_diff_line_count();
+    title = append_scroll_percent(
+        &title,
+        app.ui_state.diff_scroll,
+        area.height.saturating_sub(2) as usize,
+        line_count,
     );
 
     let focused = app.get_focused_panel() == Some(FocusedPanel::Diff);
@@ -70,52 +127,119 @@ fn draw_diff_panel(frame: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    // Create a highlighted diff with syntax highlighting based on the file path
-    let file_path = app.get_file_path().map(|p| p.as_path());
-
-    let highlighted_diff = HighlightedDiff::new(&app.current_diff, file_path);
-    let all_styled_lines =
-        highlighted_diff.to_styled_lines_with_search(app.diff_search_state.as_ref());
-
-    // Apply both vertical AND horizontal scrolling with cursor highlighting
-    let styled_lines: Vec<Line> = all_styled_lines
-        .into_iter()
+    // Reuse the lines styled before this frame; style them here only if nothing did
+    let computed_lines;
+    let all_styled_lines: &[Line<'static>] = match app.cached_styled_lines {
+        Some(ref lines) => lines,
+        None => {
+            let highlighted_diff = HighlightedDiff::new(&app.current_diff, app.highlight_path())
+                .with_language(app.highlight_language())
+                .with_whitespace_markers(app.show_whitespace)
+                .with_word_diff(app.ui_state.word_diff);
+            computed_lines =
+                highlighted_diff.to_styled_lines_with_search(app.diff_search_state.as_ref());
+            &computed_lines
+        }
+    };
+
+    // Folded hunks show their header with a count in place of the body
+    let collapsed = app.collapsed_hunk_ranges();
+
+    // Apply vertical scrolling with cursor highlighting
+    let visible_height = area.height.saturating_sub(2) as usize; // Account for borders
+    let lines = all_styled_lines
+        .iter()
         .enumerate()
+        .skip(app.ui_state.diff_scroll) // Vertical scroll
+        .filter(|(global_line_index, _)| !is_hidden_line(&collapsed, *global_line_index))
         .map(|(global_line_index, line)| {
-            if global_line_index == app.ui_state.diff_cursor_line && focused {
+            match collapsed
+                .iter()
+                .find(|(header, _)| *header == global_line_index)
+            {
+                Some((_, range)) => (global_line_index, collapsed_hunk_line(line, range.len())),
+                None => (global_line_index, line.clone()),
+            }
+        })
+        .map(
+            |(global_line_index, line)| match app.heat_map_age(global_line_index) {
+                Some(age) => (global_line_index, apply_heat(line, age)),
+                None => (global_line_index, line),
+            },
+        )
+        .map(|(global_line_index, line)| {
+            let line = if global_line_index == app.ui_state.diff_cursor_line && focused {
                 // Apply cursor highlighting - add background color to all spans
                 apply_cursor_highlight(line)
+            } else if app.ui_state.is_diff_line_selected(global_line_index) {
+                apply_selection_highlight(line)
             } else {
                 line
-            }
-        })
-        .skip(app.ui_state.diff_scroll) // Vertical scroll
-        .take(area.height.saturating_sub(2) as usize) // Account for borders
-        .map(|line| {
-            apply_horizontal_scroll(
-                line,
-                app.ui_state.diff_horizontal_scroll,
-                area.width as usize,
-            )
-        })
-        .collect();
+            };
+            (global_line_index, line)
+        });
+
+    // Then either wrap long lines or scroll them horizontally
+    let styled_lines: Vec<Line> = if app.wraps_diff() {
+        wrap_rows(
+            lines,
+            area.width.saturating_sub(2) as usize,
+            visible_height,
+            app.ui_state.diff_cursor_line,
+        )
+    } else {
+        lines
+            .take(visible_height)
+            .map(|(_, line)| {
+                apply_horizontal_scroll(
+                    line,
+                    app.ui_state.diff_horizontal_scroll,
+                    area.width as usize,
+                )
+            })
+            .collect()
+    };
 
     let paragraph = Paragraph::new(styled_lines).block(block);
     frame.render_widget(paragraph, area);
+    draw_scrollbar(frame, area, app.ui_state.diff_scroll, line_count);
 }
 
-/// Apply cursor highlighting to a line by adding background color to all spans
-fn apply_cursor_highlight(line: Line<'static>) -> Line<'static> {
-    let highlighted_spans: Vec<Span> = line
-        .spans
-        .into_iter()
-        .map(|span| {
-            let mut style = span.style;
-            // Use a subtle blue background for cursor highlighting
-            style = style.bg(Color::Rgb(60, 80, 120)); // Dark blue background
-            Span::styled(span.content, style)
-        })
-        .collect();
+/// A folded hunk's header followed by how many lines it hides
+fn collapsed_hunk_line(header: &Line<'static>, hidden_lines: usize) -> Line<'static> {
+    let mut line = header.clone();
+    line.spans.push(Span::styled(
+        format!(" ({} lines hidden)", hidden_lines),
+        Style::default().fg(Color::DarkGray),
+    ));
+    line
+}
+
+/// Wrap the lines to `width` and fill `height` rows with them. Wrapping can push the cursor
+/// line below the panel, so rows are dropped off the top until its last row is in view.
+fn wrap_rows(
+    lines: impl Iterator<Item = (usize, Line<'static>)>,
+    width: usize,
+    height: usize,
+    cursor_line: usize,
+) -> Vec<Line<'static>> {
+    let mut rows = Vec::new();
+    let mut cursor_rows_end = 0;
+    for (global_line_index, line) in lines {
+        if rows.len() >= height && global_line_index > cursor_line {
+            break;
+        }
+        // Continuation rows leave the line numbers and marker blank
+        let gutter = match line.spans.first() {
+            Some(numbers) if numbers.content.contains('│') => numbers.content.chars().count() + 1,
+            _ => 0,
+        };
+        rows.extend(wrap_line(line, width, gutter));
+        if global_line_index == cursor_line {
+            cursor_rows_end = rows.len();
+        }
+    }
 
-    Line::from(highlighted_spans)
+    let first_row = cursor_rows_end.saturating_sub(height);
+    rows.into_iter().skip(first_row).take(height).collect()
 }
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod app_tests {
     use geschichte::app::App;
+    use geschichte::app::FocusedPanel;
     use geschichte::cli::LayoutMode;
     use geschichte::commit::Commit;
     use std::path::PathBuf;
@@ -120,6 +121,105 @@ mod app_tests {
         assert!(result.is_ok(), "Should handle empty commits gracefully");
     }
 
+    #[test]
+    fn test_copy_file_absolute_path() {
+        let mut app = create_test_app();
+        app.commits = create_test_commits();
+        app.start_copy_mode();
+
+        let result = app.copy_file_absolute_path();
+        assert!(result.is_ok(), "copy_file_absolute_path should not error");
+        assert!(app.copy_mode.is_none());
+
+        // The fixture repo isn't on disk, so the joined path is copied as is
+        assert_eq!(
+            app.copy_message.as_deref(),
+            Some("Copied absolute path: /test/repo/test.rs")
+        );
+    }
+
+    #[test]
+    fn test_copy_current_hunk() {
+        let mut app = create_test_app();
+        app.commits = create_test_commits();
+        app.current_diff = concat!(
+            "diff --git a/test.rs b/test.rs\n",
+            "--- a/test.rs\n",
+            "+++ b/test.rs\n",
+            "@@ -1,2 +1,2 @@\n",
+            "-old first\n",
+            "+new first\n",
+            " context\n",
+            "@@ -10,2 +10,3 @@\n",
+            " more context\n",
+            "+added\n",
+            " tail\n",
+        )
+        .to_string();
+        app.focus_panel(FocusedPanel::Diff);
+
+        app.start_copy_mode();
+        assert!(app.copy_message.as_ref().unwrap().contains("h=hunk"));
+
+        // Cursor on "+added" in the second hunk
+        app.ui_state.diff_cursor_line = 9;
+        app.copy_current_hunk().unwrap();
+        assert_eq!(app.copy_message.as_deref(), Some("Copied hunk (4 lines)"));
+        assert!(app.copy_mode.is_none());
+
+        // The file header is not part of any hunk
+        app.error_message = None;
+        app.ui_state.diff_cursor_line = 1;
+        app.copy_current_hunk().unwrap();
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Cursor is not inside a hunk")
+        );
+    }
+
+    #[test]
+    fn test_styled_lines_rebuilt_when_search_changes() {
+        let mut app = create_test_app();
+        app.current_diff = concat!("@@ -1,1 +1,1 @@\n", "-old value\n", "+new value\n").to_string();
+
+        app.prepare_styled_lines();
+        let plain = app.cached_styled_lines.clone().unwrap();
+        assert_eq!(plain.len(), 3);
+
+        // Unchanged inputs keep the cached lines
+        app.prepare_styled_lines();
+        assert_eq!(app.cached_styled_lines.as_ref(), Some(&plain));
+
+        // New search matches restyle the lines
+        app.start_diff_search();
+        app.diff_search_state.as_mut().unwrap().query = "value".to_string();
+        app.update_search_results().unwrap();
+        app.prepare_styled_lines();
+        assert_ne!(app.cached_styled_lines.as_ref(), Some(&plain));
+    }
+
+    #[test]
+    fn test_copy_historical_path() {
+        let mut app = create_test_app();
+        app.commits = create_test_commits();
+        app.rename_map
+            .insert("def456".to_string(), PathBuf::from("old/test.rs"));
+
+        // The newest commit has no rename entry, so the current path is used
+        app.copy_historical_path().unwrap();
+        let message = app.copy_message.clone().unwrap();
+        assert!(message.contains("abc123d: test.rs"), "got: {}", message);
+        assert!(!message.contains("renamed since"));
+
+        // Before the rename the file lived at its old path
+        app.selected_index = 1;
+        app.copy_historical_path().unwrap();
+        let message = app.copy_message.clone().unwrap();
+        assert!(message.contains("def456a: old/test.rs"), "got: {}", message);
+        assert!(message.contains("renamed since"));
+        assert!(app.copy_mode.is_none());
+    }
+
     #[test]
     fn test_copy_mode_help_text_includes_path_option() {
         let mut app = create_test_app();
@@ -278,6 +378,286 @@ mod app_tests {
         assert_eq!(app.effective_layout(), LayoutMode::Unified);
     }
 
+    #[test]
+    fn test_auto_width_threshold() {
+        let mut app = create_test_app();
+        app.layout_mode = LayoutMode::Auto;
+        app.auto_width = 100;
+        app.current_diff = "@@ -1 +1 @@\n-old\n+new\n".to_string();
+
+        app.handle_resize(99, 40);
+        assert_eq!(app.effective_layout(), LayoutMode::Unified);
+        assert!(app.current_side_by_side_diff.is_none());
+
+        // Resizing across the threshold switches layouts and builds the side-by-side diff
+        app.handle_resize(100, 40);
+        assert_eq!(app.effective_layout(), LayoutMode::SideBySide);
+        assert!(app.current_side_by_side_diff.is_some());
+    }
+
+    #[test]
+    fn test_toggle_layout_key() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = create_test_app();
+        app.current_diff = concat!(
+            "@@ -1,3 +1,3 @@\n",
+            " first\n",
+            "-old\n",
+            "+new\n",
+            " last\n",
+        )
+        .to_string();
+        app.layout_mode = LayoutMode::Unified;
+        app.ui_state.diff_cursor_line = 4;
+        let v = KeyEvent::new(KeyCode::Char('v'), KeyModifiers::NONE);
+
+        assert!(app.handle_ui_keys(v).unwrap());
+        assert_eq!(app.layout_mode, LayoutMode::SideBySide);
+        assert!(app.current_side_by_side_diff.is_some());
+        // The paired deletion and addition share a row, so the cursor moves onto the last row
+        assert_eq!(app.ui_state.diff_cursor_line, 3);
+
+        assert!(app.handle_ui_keys(v).unwrap());
+        assert_eq!(app.layout_mode, LayoutMode::Unified);
+        assert!(app.current_side_by_side_diff.is_none());
+    }
+
+    #[test]
+    fn test_search_scrolls_to_side_by_side_row() {
+        let mut app = create_test_app();
+        app.current_diff = concat!(
+            "@@ -1,4 +1,4 @@\n",
+            " first\n",
+            "-one\n",
+            "-two\n",
+            "+uno\n",
+            "+needle\n",
+            " last\n",
+        )
+        .to_string();
+        app.layout_mode = LayoutMode::Unified;
+        app.toggle_layout();
+
+        app.start_diff_search();
+        app.diff_search_state.as_mut().unwrap().query = "needle".to_string();
+        app.update_search_results().unwrap();
+        assert_eq!(
+            app.diff_search_state.as_ref().unwrap().results[0].line_index,
+            5
+        );
+
+        // "-two" and "+uno" share a row, so the match sits one row above its unified line
+        app.scroll_to_search_result(0).unwrap();
+        assert_eq!(app.ui_state.diff_cursor_line, 4);
+        let side_by_side = app.current_side_by_side_diff.as_ref().unwrap();
+        let row = side_by_side.new_lines[4].as_ref().unwrap();
+        assert_eq!(row.content, "+needle");
+    }
+
+    #[test]
+    fn test_goto_line_input() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = create_test_app();
+        app.current_diff = concat!(
+            "@@ -1,2 +1,2 @@\n",
+            "-old first\n",
+            "+new first\n",
+            " context\n",
+            "@@ -10,2 +10,2 @@\n",
+            " more context\n",
+            " tail\n",
+        )
+        .to_string();
+        app.cached_highlighted_diff = None;
+        app.layout_mode = LayoutMode::Unified;
+        app.focus_panel(FocusedPanel::Diff);
+        let press = |app: &mut geschichte::app::App, code| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(&mut app, KeyCode::Char(':'));
+        assert_eq!(app.goto_line_input.as_deref(), Some(""));
+        // Digits are collected and other keys don't leak out as actions
+        for code in [KeyCode::Char('1'), KeyCode::Char('q'), KeyCode::Char('1')] {
+            press(&mut app, code);
+        }
+        assert!(!app.should_quit);
+        assert_eq!(app.goto_line_input.as_deref(), Some("11"));
+
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.goto_line_input, None);
+        assert_eq!(app.ui_state.diff_cursor_line, 6);
+        assert!(app.error_message.is_none());
+
+        // A line outside the hunks lands on the nearest shown line
+        press(&mut app, KeyCode::Char(':'));
+        press(&mut app, KeyCode::Char('5'));
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.ui_state.diff_cursor_line, 3);
+        assert!(app.error_message.as_ref().unwrap().contains("line 2"));
+
+        // Escape cancels without moving
+        press(&mut app, KeyCode::Char(':'));
+        press(&mut app, KeyCode::Char('1'));
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.goto_line_input, None);
+        assert_eq!(app.ui_state.diff_cursor_line, 3);
+    }
+
+    #[test]
+    fn test_file_line_reference() {
+        let mut app = create_test_app();
+        app.commits = vec![Commit::new(
+            "abc123".to_string(),
+            "abc123".to_string(),
+            "2023-01-01 10:00:00".to_string(),
+            "Author <a@test.com>".to_string(),
+            "Change".to_string(),
+        )];
+        app.current_diff = concat!(
+            "@@ -10,2 +10,2 @@\n",
+            "-old line\n",
+            "+new line\n",
+            " context\n",
+        )
+        .to_string();
+        app.cached_highlighted_diff = None;
+        app.layout_mode = LayoutMode::Unified;
+        app.focus_panel(FocusedPanel::Diff);
+
+        app.ui_state.diff_cursor_line = 3;
+        assert_eq!(app.file_line_reference().as_deref(), Some("test.rs:11"));
+
+        // The hunk header and deleted line fall through to the next file line
+        app.ui_state.diff_cursor_line = 0;
+        assert_eq!(app.file_line_reference().as_deref(), Some("test.rs:10"));
+
+        // Nothing below the last line
+        app.ui_state.diff_cursor_line = 4;
+        assert_eq!(app.file_line_reference(), None);
+    }
+
+    #[test]
+    fn test_editor_line_number_clamps_cursor() {
+        let mut app = create_test_app();
+        app.cached_highlighted_diff = Some(geschichte::diff::HighlightedDiff::new(
+            concat!(
+                "@@ -10,2 +10,2 @@\n",
+                "-old line\n",
+                "+new line\n",
+                " context\n",
+            ),
+            None,
+        ));
+
+        app.ui_state.diff_cursor_line = 2;
+        assert_eq!(app.editor_line_number(), 10);
+
+        // Past the end of the diff, the last line is used
+        app.ui_state.diff_cursor_line = 500;
+        assert_eq!(app.editor_line_number(), 11);
+
+        // The hunk header has no file line
+        app.ui_state.diff_cursor_line = 0;
+        assert_eq!(app.editor_line_number(), 1);
+
+        app.cached_highlighted_diff = None;
+        assert_eq!(app.editor_line_number(), 1);
+    }
+
+    #[test]
+    fn test_editor_command_line_splits_arguments() {
+        use geschichte::app::editor_command_line;
+        use std::path::Path;
+
+        let path = Path::new("src/main.rs");
+        assert_eq!(
+            editor_command_line("code -w", path, 12),
+            vec!["code", "-w", "-g", "src/main.rs:12"]
+        );
+        assert_eq!(
+            editor_command_line("code -g", path, 12),
+            vec!["code", "-g", "src/main.rs:12"]
+        );
+        assert_eq!(
+            editor_command_line("/usr/bin/nvim", path, 3),
+            vec!["/usr/bin/nvim", "+3", "src/main.rs"]
+        );
+        assert_eq!(
+            editor_command_line("  ", path, 1),
+            vec!["vim", "+1", "src/main.rs"]
+        );
+        assert_eq!(
+            editor_command_line("myeditor --flag", path, 5),
+            vec!["myeditor", "--flag", "src/main.rs"]
+        );
+    }
+
+    #[test]
+    fn test_commit_web_url() {
+        let mut app = create_test_app();
+        app.commits = vec![
+            Commit::new_working_directory("Modified".to_string()),
+            Commit::new(
+                "abc123".to_string(),
+                "abc123".to_string(),
+                "2023-01-01 10:00:00".to_string(),
+                "Author <a@test.com>".to_string(),
+                "Change".to_string(),
+            ),
+        ];
+        app.selected_index = 1;
+        assert_eq!(
+            app.commit_web_url(),
+            Err("No 'origin' remote to build a URL from".to_string())
+        );
+
+        app.copier
+            .set_remote(geschichte::git::remote::RemoteRepo::parse(
+                "git@github.com:owner/repo.git",
+            ));
+        assert_eq!(
+            app.commit_web_url().as_deref(),
+            Ok("https://github.com/owner/repo/commit/abc123")
+        );
+
+        // The working directory has no page
+        app.selected_index = 0;
+        assert!(app.commit_web_url().is_err());
+    }
+
+    #[test]
+    fn test_click_maps_rows_in_scrolled_commit_list() {
+        let mut app = create_test_app();
+        app.commits = (0..100)
+            .map(|i| {
+                Commit::new(
+                    format!("hash{}", i),
+                    format!("h{}", i),
+                    "2023-01-01 10:00:00".to_string(),
+                    "Author <a@test.com>".to_string(),
+                    format!("Commit {}", i),
+                )
+            })
+            .collect();
+        app.layout_mode = LayoutMode::Unified;
+        app.handle_resize(80, 24);
+
+        // Row 0 is the panel's top border; the first commit is on the row below it
+        assert_eq!(app.commit_at_row(0), None);
+        assert_eq!(app.commit_at_row(1), Some(0));
+
+        // 23 rows above the status bar leave 21 for commits, so selecting commit 50 scrolls
+        // the list until it sits on the last row
+        app.selected_index = 50;
+        assert_eq!(app.commit_at_row(1), Some(30));
+        assert_eq!(app.commit_at_row(21), Some(50));
+        assert_eq!(app.commit_at_row(22), None);
+    }
+
     #[test]
     fn test_resize_handling() {
         let mut app = create_test_app();
@@ -144,6 +144,39 @@ mod commit_info_tests {
         assert!(matches!(pr_info.status, PRStatus::Merged));
     }
 
+    #[test]
+    fn test_signature_status_parsing() {
+        use geschichte::commit::{SignatureInfo, SignatureStatus};
+
+        let good = SignatureInfo::parse("G Jane Developer <jane@company.com>\n").unwrap();
+        assert_eq!(good.status, SignatureStatus::Good);
+        assert_eq!(good.signer, "Jane Developer <jane@company.com>");
+        assert_eq!(
+            good.describe(),
+            "good signature by Jane Developer <jane@company.com>"
+        );
+
+        let expected = [
+            ("B Mallory", SignatureStatus::Bad),
+            ("U Jane", SignatureStatus::UnknownValidity),
+            ("X Jane", SignatureStatus::Expired),
+            ("Y Jane", SignatureStatus::Expired),
+            ("R Jane", SignatureStatus::RevokedKey),
+            ("E ", SignatureStatus::CannotCheck),
+        ];
+        for (output, status) in expected {
+            assert_eq!(SignatureInfo::parse(output).unwrap().status, status);
+        }
+
+        let unsigned = SignatureInfo::parse("N \n").unwrap();
+        assert_eq!(unsigned.status, SignatureStatus::Unsigned);
+        assert!(unsigned.signer.is_empty());
+        assert_eq!(unsigned.describe(), "unsigned");
+
+        assert!(SignatureInfo::parse("").is_none());
+        assert!(SignatureInfo::parse("? what").is_none());
+    }
+
     #[test]
     fn test_commit_body_handling() {
         let commit_with_body = create_enhanced_commit();
@@ -194,4 +227,124 @@ mod commit_info_tests {
         assert!(wd_commit.stats.is_none());
         assert!(wd_commit.pr_info.is_none());
     }
+
+    #[test]
+    fn test_file_history_stats_aggregation() {
+        use geschichte::commit::FileHistoryStats;
+
+        let stats_commit = |hash: &str, author: &str, date: &str, ins: u32, del: u32| {
+            let mut commit = Commit::new_enhanced(
+                hash.to_string(),
+                hash.to_string(),
+                author.to_string(),
+                format!("{}@example.com", author),
+                date.to_string(),
+                author.to_string(),
+                format!("{}@example.com", author),
+                date.to_string(),
+                format!("Change {}", hash),
+                String::new(),
+            );
+            commit.stats = Some(CommitStats {
+                files_changed: 1,
+                insertions: ins,
+                deletions: del,
+            });
+            commit
+        };
+
+        // Newest first, as loaded from git log
+        let mut unloaded = stats_commit("ccc", "Bob", "2023-03-01 09:00:00", 0, 0);
+        unloaded.stats = None;
+        let commits = vec![
+            Commit::new_working_directory("Modified".to_string()),
+            unloaded,
+            stats_commit("bbb", "Alice", "2023-02-01 09:00:00", 40, 2),
+            stats_commit("aaa", "Alice", "2023-01-01 09:00:00", 10, 0),
+        ];
+
+        let stats = FileHistoryStats::from_commits(&commits);
+
+        assert_eq!(stats.total_commits, 3);
+        assert_eq!(stats.insertions, 50);
+        assert_eq!(stats.deletions, 2);
+        assert_eq!(stats.first_change.as_deref(), Some("2023-01-01 09:00:00"));
+        assert_eq!(stats.last_change.as_deref(), Some("2023-03-01 09:00:00"));
+        assert_eq!(stats.largest_commit.unwrap().short_hash, "bbb");
+        assert_eq!(stats.top_contributors[0].name, "Alice");
+        assert_eq!(stats.top_contributors[0].commits, 2);
+        assert_eq!(stats.top_contributors[0].lines_changed, 52);
+        assert_eq!(stats.top_contributors[1].name, "Bob");
+    }
+
+    #[test]
+    fn test_commit_info_popup_search() {
+        let mut popup = CommitInfoPopup::new(create_enhanced_commit());
+
+        popup.start_search();
+        assert!(popup.is_search_input);
+        popup.search_query = "jwt".to_string();
+        popup.update_search();
+
+        // Case-insensitive, on the body lines
+        let lines: Vec<usize> = popup.search_matches.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![0, 3]);
+        assert_eq!(popup.current_match, Some(0));
+        assert_eq!(popup.scroll_position, 0);
+        assert_eq!(
+            (popup.search_matches[1].start, popup.search_matches[1].end),
+            (2, 5)
+        );
+
+        popup.next_match();
+        assert_eq!(popup.current_match, Some(1));
+        assert_eq!(popup.scroll_position, 3);
+        popup.next_match();
+        assert_eq!(popup.current_match, Some(0));
+        popup.previous_match();
+        assert_eq!(popup.current_match, Some(1));
+
+        // A half-typed regex matches nothing rather than failing
+        popup.search_query = "jwt (".to_string();
+        popup.update_search();
+        assert!(popup.search_matches.is_empty());
+        assert_eq!(popup.current_match, None);
+
+        popup.clear_search();
+        assert!(!popup.is_searching());
+    }
+
+    #[test]
+    fn test_commit_info_markdown_export() {
+        let mut popup = CommitInfoPopup::new(create_enhanced_commit());
+        popup.web_url = Some("https://github.com/company/repo/commit/abc123def456789".to_string());
+
+        let markdown = popup.to_markdown();
+        assert!(markdown.starts_with("## Implement user authentication\n"));
+        assert!(markdown.contains("| **Commit** | `abc123def456789` |"));
+        assert!(markdown.contains("| **Author** | Jane Developer <jane@company.com> |"));
+        assert!(markdown.contains("| **Refs** | branch:main, tag:v1.0.0 |"));
+        assert!(markdown.contains(
+            "[#42 Add authentication system](https://github.com/company/repo/pull/42) (merged)"
+        ));
+        assert!(markdown.contains("| **Stats** | 5 files, +120 -30 lines |"));
+        assert!(markdown.contains(
+            "[View commit abc123d](https://github.com/company/repo/commit/abc123def456789)"
+        ));
+        assert!(markdown.contains(
+            "> Added JWT token support and password hashing.\n>\n> This commit includes:"
+        ));
+
+        // The working directory entry has neither a hash nor a link
+        let mut working = create_enhanced_commit();
+        working.is_working_directory = true;
+        working.subject = "Modified | staged".to_string();
+        let mut popup = CommitInfoPopup::new(working);
+        popup.web_url = Some("https://example.com".to_string());
+        let markdown = popup.to_markdown();
+        assert!(!markdown.contains("**Commit**"));
+        assert!(!markdown.contains("**Author**"));
+        assert!(!markdown.contains("View commit"));
+        assert!(markdown.starts_with("## Modified | staged\n"));
+    }
 }
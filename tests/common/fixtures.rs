@@ -116,6 +116,35 @@ impl TestRepo {
         repo
     }
 
+    /// `first.rs` is added, renamed to `second.rs`, edited, then renamed to `third.rs`
+    pub fn new_with_double_rename() -> Self {
+        let repo = Self::new();
+        let repo_path = repo.path();
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(repo_path)
+                .output()
+                .unwrap();
+        };
+        let body = "fn main() {\n    let a = 1;\n    let b = 2;\n    println!(\"{}\", a + b);\n}\n";
+
+        fs::write(repo_path.join("first.rs"), body).unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-m", "Add first.rs"]);
+
+        git(&["mv", "first.rs", "second.rs"]);
+        git(&["commit", "-m", "Rename to second.rs"]);
+
+        fs::write(repo_path.join("second.rs"), format!("{}// edited\n", body)).unwrap();
+        git(&["commit", "-am", "Edit second.rs"]);
+
+        git(&["mv", "second.rs", "third.rs"]);
+        git(&["commit", "-m", "Rename to third.rs"]);
+
+        repo
+    }
+
     pub fn new_with_many_files(count: usize) -> Self {
         let repo = Self::new();
         let repo_path = repo.path();
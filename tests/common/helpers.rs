@@ -55,9 +55,25 @@ pub fn create_test_app_with_commits() -> geschichte::app::App {
         &PathBuf::from("test.txt"),
         false,
         false,
+        0,
+        None,
     )
     .unwrap();
 
     app.commits = commits;
     app
 }
+
+/// A history-mode app on `test.txt` in `test_repo`, with its history loaded
+pub fn create_loaded_history_app(test_repo: &TestRepo) -> geschichte::app::App {
+    let mut app = geschichte::app::App::new_history(
+        test_repo.path().to_path_buf(),
+        PathBuf::from("test.txt"),
+        3,
+        true,
+        false,
+        geschichte::cli::LayoutMode::Unified,
+    );
+    app.load_git_data().unwrap();
+    app
+}
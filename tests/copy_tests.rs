@@ -2,6 +2,7 @@
 mod copy_tests {
     use geschichte::commit::Commit;
     use geschichte::copy::{CommitCopier, CopyFormat};
+    use geschichte::git::remote::{RemoteHost, RemoteRepo};
 
     fn create_test_commit() -> Commit {
         Commit::new_enhanced(
@@ -103,15 +104,128 @@ mod copy_tests {
     #[test]
     fn test_copy_github_url_default() {
         let mut copier = CommitCopier::new();
+        copier.set_remote(RemoteRepo::parse("git@github.com:owner/repo.git"));
         let commit = create_test_commit();
 
         let result = copier.copy_commit_info(&commit, CopyFormat::GitHubUrl);
         match result {
-            Ok(content) => assert_eq!(content, "https://github.com/repo/commit/abc123def456"),
+            Ok(content) => assert_eq!(content, "https://github.com/owner/repo/commit/abc123def456"),
             Err(_) => println!("Clipboard not available for testing"),
         }
     }
 
+    #[test]
+    fn test_copy_github_url_without_remote() {
+        let mut copier = CommitCopier::new();
+        let commit = create_test_commit();
+
+        // No fake URL is made up when the repository has no remote
+        let result = copier.copy_commit_info(&commit, CopyFormat::GitHubUrl);
+        assert_eq!(
+            result,
+            Err("No 'origin' remote to build a URL from".to_string())
+        );
+    }
+
+    #[test]
+    fn test_remote_url_forms() {
+        let cases = [
+            ("git@github.com:owner/repo.git", "github.com", "owner/repo"),
+            (
+                "https://github.com/owner/repo.git",
+                "github.com",
+                "owner/repo",
+            ),
+            ("https://github.com/owner/repo/", "github.com", "owner/repo"),
+            (
+                "ssh://git@github.com/owner/repo.git",
+                "github.com",
+                "owner/repo",
+            ),
+            (
+                "ssh://git@gitlab.com:2222/group/sub/repo.git",
+                "gitlab.com",
+                "group/sub/repo",
+            ),
+            (
+                "https://user@bitbucket.org/owner/repo.git",
+                "bitbucket.org",
+                "owner/repo",
+            ),
+        ];
+
+        for (url, domain, path) in cases {
+            let remote =
+                RemoteRepo::parse(url).unwrap_or_else(|| panic!("failed to parse {}", url));
+            assert_eq!(remote.domain, domain, "{}", url);
+            assert_eq!(remote.path, path, "{}", url);
+        }
+
+        assert_eq!(RemoteRepo::parse("/srv/git/repo.git"), None);
+        assert_eq!(RemoteRepo::parse("../repo"), None);
+    }
+
+    #[test]
+    fn test_remote_commit_and_pr_urls_per_host() {
+        let github = RemoteRepo::parse("git@github.com:owner/repo.git").unwrap();
+        assert_eq!(github.host, RemoteHost::GitHub);
+        assert_eq!(
+            github.commit_url("abc"),
+            "https://github.com/owner/repo/commit/abc"
+        );
+        assert_eq!(
+            github.pull_request_url(7),
+            "https://github.com/owner/repo/pull/7"
+        );
+
+        let gitlab = RemoteRepo::parse("https://gitlab.com/group/repo.git").unwrap();
+        assert_eq!(gitlab.host, RemoteHost::GitLab);
+        assert_eq!(
+            gitlab.commit_url("abc"),
+            "https://gitlab.com/group/repo/-/commit/abc"
+        );
+        assert_eq!(
+            gitlab.pull_request_url(7),
+            "https://gitlab.com/group/repo/-/merge_requests/7"
+        );
+
+        let bitbucket = RemoteRepo::parse("git@bitbucket.org:team/repo.git").unwrap();
+        assert_eq!(bitbucket.host, RemoteHost::Bitbucket);
+        assert_eq!(
+            bitbucket.commit_url("abc"),
+            "https://bitbucket.org/team/repo/commits/abc"
+        );
+        assert_eq!(
+            bitbucket.pull_request_url(7),
+            "https://bitbucket.org/team/repo/pull-requests/7"
+        );
+    }
+
+    #[test]
+    fn test_remote_blob_urls_per_host() {
+        use std::path::Path;
+
+        let path = Path::new("src/old.rs");
+        let cases = [
+            (
+                "git@github.com:owner/repo.git",
+                "https://github.com/owner/repo/blob/abc/src/old.rs#L42",
+            ),
+            (
+                "https://gitlab.com/group/repo.git",
+                "https://gitlab.com/group/repo/-/blob/abc/src/old.rs#L42",
+            ),
+            (
+                "git@bitbucket.org:team/repo.git",
+                "https://bitbucket.org/team/repo/src/abc/src/old.rs#lines-42",
+            ),
+        ];
+        for (remote_url, expected) in cases {
+            let remote = RemoteRepo::parse(remote_url).unwrap();
+            assert_eq!(remote.blob_url("abc", path, 42), expected);
+        }
+    }
+
     #[test]
     fn test_commit_author_format() {
         let commit = create_test_commit();
@@ -133,4 +247,39 @@ mod copy_tests {
         assert_eq!(wd_commit.short_hash, "WD");
         assert_eq!(wd_commit.subject, "Modified");
     }
+
+    #[test]
+    fn test_osc52_sequence() {
+        use geschichte::copy::{osc52_writes, Multiplexer, OSC52_MAX_PAYLOAD};
+
+        assert_eq!(
+            osc52_writes("hello", Multiplexer::None).unwrap().concat(),
+            "\x1b]52;c;aGVsbG8=\x07"
+        );
+        // Payloads needing no padding, two padding characters and one
+        for (text, payload) in [("foo", "Zm9v"), ("f", "Zg=="), ("fo", "Zm8=")] {
+            assert_eq!(
+                osc52_writes(text, Multiplexer::None).unwrap().concat(),
+                format!("\x1b]52;c;{}\x07", payload)
+            );
+        }
+        assert_eq!(
+            osc52_writes("hi", Multiplexer::Tmux).unwrap().concat(),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+
+        // Long payloads go out in chunks
+        let message = "commit message line\n".repeat(1000);
+        let writes = osc52_writes(&message, Multiplexer::None).unwrap();
+        assert!(writes.len() > 3);
+        assert!(writes.iter().all(|write| write.len() <= 4096));
+
+        // Screen gets one short control string per piece
+        let writes = osc52_writes(&message, Multiplexer::Screen).unwrap();
+        assert!(writes
+            .iter()
+            .all(|write| write.starts_with("\x1bP") && write.ends_with("\x1b\\")));
+
+        assert!(osc52_writes(&"x".repeat(OSC52_MAX_PAYLOAD), Multiplexer::None).is_err());
+    }
 }
@@ -18,7 +18,7 @@ mod git_history_tests {
             "".to_string(),
         );
 
-        let pr_info = detect_pr_info(&commit);
+        let pr_info = detect_pr_info(&commit, None);
         assert!(pr_info.is_some());
 
         let pr = pr_info.unwrap();
@@ -42,7 +42,7 @@ mod git_history_tests {
             "".to_string(),
         );
 
-        let pr_info = detect_pr_info(&commit);
+        let pr_info = detect_pr_info(&commit, None);
         assert!(pr_info.is_some());
 
         let pr = pr_info.unwrap();
@@ -66,7 +66,7 @@ mod git_history_tests {
             "".to_string(),
         );
 
-        let pr_info = detect_pr_info(&commit);
+        let pr_info = detect_pr_info(&commit, None);
         assert!(pr_info.is_none());
     }
 
@@ -96,7 +96,7 @@ mod git_history_tests {
                 "".to_string(),
             );
 
-            let pr_info = detect_pr_info(&commit);
+            let pr_info = detect_pr_info(&commit, None);
             match expected {
                 Some(num) => {
                     assert!(pr_info.is_some(), "Should detect PR in: {}", message);
@@ -193,4 +193,29 @@ mod git_history_tests {
 
         assert_eq!(commit_no_email.author(), "John Doe");
     }
+
+    #[test]
+    fn test_parse_gh_pr_view() {
+        use geschichte::git::pr::parse_pr_view;
+
+        let pr = parse_pr_view(
+            42,
+            r#"{"state":"OPEN","title":"Add login","url":"https://github.com/o/r/pull/42"}"#,
+        )
+        .unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.title, "Add login");
+        assert_eq!(pr.url, "https://github.com/o/r/pull/42");
+        assert!(matches!(pr.status, PRStatus::Open));
+        assert_eq!(pr.status.label(), Some("open"));
+
+        let merged = parse_pr_view(7, r#"{"state":"MERGED","title":"t","url":"u"}"#).unwrap();
+        assert!(matches!(merged.status, PRStatus::Merged));
+        let closed = parse_pr_view(7, r#"{"state":"CLOSED","title":"t","url":"u"}"#).unwrap();
+        assert!(matches!(closed.status, PRStatus::Closed));
+
+        // Anything else leaves the heuristic in place
+        assert!(parse_pr_view(7, "no pull requests found").is_none());
+        assert_eq!(PRStatus::Unknown.label(), None);
+    }
 }
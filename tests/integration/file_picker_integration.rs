@@ -7,7 +7,11 @@ mod test {
     #[test]
     fn test_file_picker_with_large_repository() {
         let test_repo = TestRepo::new_with_many_files(1000);
-        let files = geschichte::git::files::get_git_files(test_repo.path()).unwrap();
+        let files = geschichte::git::files::get_git_files(
+            test_repo.path(),
+            &geschichte::git::files::ExcludeFilter::default(),
+        )
+        .unwrap();
 
         let mut picker = geschichte::ui::file_picker::FilePickerState::new(files);
 
@@ -25,7 +29,11 @@ mod test {
     #[test]
     fn test_file_picker_performance_search() {
         let test_repo = TestRepo::new_with_many_files(500);
-        let files = geschichte::git::files::get_git_files(test_repo.path()).unwrap();
+        let files = geschichte::git::files::get_git_files(
+            test_repo.path(),
+            &geschichte::git::files::ExcludeFilter::default(),
+        )
+        .unwrap();
 
         let mut picker = geschichte::ui::file_picker::FilePickerState::new(files);
 
@@ -42,7 +50,11 @@ mod test {
     #[test]
     fn test_file_picker_memory_usage() {
         let test_repo = TestRepo::new_with_many_files(100);
-        let files = geschichte::git::files::get_git_files(test_repo.path()).unwrap();
+        let files = geschichte::git::files::get_git_files(
+            test_repo.path(),
+            &geschichte::git::files::ExcludeFilter::default(),
+        )
+        .unwrap();
 
         let picker = geschichte::ui::file_picker::FilePickerState::new(files);
 
@@ -53,7 +65,11 @@ mod test {
     #[test]
     fn test_navigation_with_many_files() {
         let test_repo = TestRepo::new_with_many_files(100);
-        let files = geschichte::git::files::get_git_files(test_repo.path()).unwrap();
+        let files = geschichte::git::files::get_git_files(
+            test_repo.path(),
+            &geschichte::git::files::ExcludeFilter::default(),
+        )
+        .unwrap();
 
         let mut picker = geschichte::ui::file_picker::FilePickerState::new(files);
         let original_count = picker.filtered_files.len();
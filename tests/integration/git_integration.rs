@@ -14,6 +14,8 @@ mod test {
             &PathBuf::from("test.txt"),
             false,
             false,
+            0,
+            None,
         );
         assert!(result.is_ok());
         assert!(!result.unwrap().is_empty());
@@ -28,6 +30,8 @@ mod test {
             &PathBuf::from("new_name.rs"),
             true,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -36,6 +40,8 @@ mod test {
             &PathBuf::from("new_name.rs"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -52,6 +58,8 @@ mod test {
             &PathBuf::from("test.txt"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
         let duration = start.elapsed();
@@ -65,7 +73,11 @@ mod test {
         let test_repo = TestRepo::new_with_many_files(50);
 
         let start = std::time::Instant::now();
-        let files = geschichte::git::files::get_git_files(test_repo.path()).unwrap();
+        let files = geschichte::git::files::get_git_files(
+            test_repo.path(),
+            &geschichte::git::files::ExcludeFilter::default(),
+        )
+        .unwrap();
         let duration = start.elapsed();
 
         assert!(!files.is_empty());
@@ -80,6 +92,8 @@ mod test {
             &PathBuf::from("test.txt"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -91,6 +105,8 @@ mod test {
             &commits[0].hash,
             &PathBuf::from("test.txt"),
             3,
+            false,
+            None,
         )
         .unwrap();
 
@@ -108,10 +124,397 @@ mod test {
             test_repo.path(),
             &PathBuf::from("test.txt"),
             3,
+            false,
+            None,
         )
         .unwrap();
 
         assert!(!diff.is_empty());
         assert!(diff.contains("New content"));
     }
+
+    #[test]
+    fn test_json_output() {
+        let test_repo = TestRepo::new_with_commits(2);
+
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_geschichte"))
+            .args(["--json", "--with-stats", "test.txt"])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let commits: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let commits = commits.as_array().unwrap();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0]["subject"], "Commit 1");
+        assert_eq!(commits[0]["author"], "Test User");
+        assert_eq!(commits[0]["short_hash"].as_str().unwrap().len(), 7);
+        assert_eq!(commits[1]["stats"]["files_changed"], 1);
+        assert!(commits[0].get("body").is_none());
+
+        // Dates follow the configured format
+        std::fs::write(
+            test_repo.path().join(".geschichte.toml"),
+            "date_format = \"%d.%m.%Y\"",
+        )
+        .unwrap();
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_geschichte"))
+            .args(["--json", "test.txt"])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        let commits: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        let date = commits[0]["author_date"].as_str().unwrap();
+        assert_eq!(date.len(), 10);
+        assert_eq!(&date[2..3], ".");
+
+        // Without a FILE there is nothing to print: a usage error
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_geschichte"))
+            .arg("--json")
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("FILE"));
+    }
+
+    #[test]
+    fn test_ignore_whitespace_diff() {
+        let test_repo = TestRepo::new();
+        let file = PathBuf::from("test.txt");
+        let original = std::fs::read_to_string(test_repo.path().join(&file)).unwrap();
+
+        // Reindent every line without changing anything else
+        let reindented = format!("    {}", original.replace('\n', "\n    "));
+        std::fs::write(test_repo.path().join(&file), reindented).unwrap();
+
+        let diff = |ignore_whitespace| {
+            geschichte::git::working::fetch_working_directory_diff(
+                test_repo.path(),
+                &file,
+                3,
+                ignore_whitespace,
+                None,
+            )
+            .unwrap()
+        };
+        assert!(diff(false).contains("@@"));
+        assert!(!diff(true).contains("@@"));
+    }
+
+    #[test]
+    fn test_latin1_diff_decoding() {
+        use geschichte::git::encoding::{encoding_for_label, TextEncoding};
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let repo_path = test_repo.path();
+        let file = PathBuf::from("latin1.txt");
+
+        // "café" and "café crème" encoded as Latin-1
+        std::fs::write(repo_path.join(&file), b"caf\xe9\n").unwrap();
+        Command::new("git")
+            .args(["add", "latin1.txt"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Add Latin-1 file"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        std::fs::write(repo_path.join(&file), b"caf\xe9 cr\xe8me\n").unwrap();
+
+        let latin1 = encoding_for_label("latin1").unwrap();
+
+        let diff = geschichte::git::working::fetch_working_directory_diff(
+            repo_path,
+            &file,
+            3,
+            false,
+            Some(TextEncoding::Forced(latin1)),
+        )
+        .unwrap();
+        assert!(diff.contains("-café"));
+        assert!(diff.contains("+café crème"));
+
+        // Fallback decoding leaves valid UTF-8 alone but rescues Latin-1
+        let fallback = Some(TextEncoding::Fallback(latin1));
+        assert_eq!(
+            geschichte::git::encoding::decode("crème".as_bytes(), fallback),
+            "crème"
+        );
+        assert_eq!(
+            geschichte::git::encoding::decode(b"cr\xe8me", fallback),
+            "crème"
+        );
+
+        // Without an encoding, the bytes decode lossily
+        let lossy = geschichte::git::working::fetch_working_directory_diff(
+            repo_path, &file, 3, false, None,
+        )
+        .unwrap();
+        assert!(!lossy.contains("café"));
+    }
+
+    #[test]
+    fn test_fetch_file_line_count() {
+        use geschichte::commit::FileLineCounts;
+        use geschichte::git::history::fetch_file_line_count;
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let repo_path = test_repo.path();
+        let file = PathBuf::from("test.txt");
+
+        std::fs::write(repo_path.join(&file), "a\nb\nc\n").unwrap();
+        Command::new("git")
+            .args(["commit", "-am", "Grow file"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+
+        assert_eq!(
+            fetch_file_line_count(repo_path, "HEAD", &file).unwrap(),
+            Some(3)
+        );
+        // The initial content has no trailing newline but still counts as a line
+        assert_eq!(
+            fetch_file_line_count(repo_path, "HEAD^", &file).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            fetch_file_line_count(repo_path, "HEAD^^", &file).unwrap(),
+            None
+        );
+        assert_eq!(
+            fetch_file_line_count(repo_path, "HEAD", &PathBuf::from("missing.txt")).unwrap(),
+            None
+        );
+
+        let counts = FileLineCounts {
+            before: Some(1),
+            after: Some(3),
+        };
+        assert_eq!(counts.describe(), "1 → 3 lines (+2)");
+    }
+
+    #[test]
+    fn test_multi_paragraph_commit_body() {
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let repo_path = test_repo.path();
+
+        std::fs::write(repo_path.join("test.txt"), "Changed content").unwrap();
+        Command::new("git")
+            .args([
+                "commit",
+                "-am",
+                "Rework parser",
+                "-m",
+                "First paragraph explains why.\nIt wraps onto a second line.",
+                "-m",
+                "Second paragraph with details.",
+            ])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+
+        let commits = geschichte::git::history::fetch_commit_history(
+            repo_path,
+            &PathBuf::from("test.txt"),
+            true,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].subject, "Rework parser");
+        assert_eq!(
+            commits[0].body,
+            "First paragraph explains why.\nIt wraps onto a second line.\n\n\
+             Second paragraph with details."
+        );
+        assert_eq!(commits[0].author_name, "Test User");
+        // The record after a multi-line body is still parsed on its own
+        assert_eq!(commits[1].subject, "Initial commit");
+        assert!(commits[1].body.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_blame() {
+        use geschichte::git::blame::{fetch_blame, NOT_COMMITTED_AUTHOR};
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let repo_path = test_repo.path();
+        let file = PathBuf::from("test.txt");
+
+        std::fs::write(repo_path.join(&file), "Initial content\nSecond line\n").unwrap();
+        Command::new("git")
+            .args(["commit", "-am", "Add second line"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        let commits =
+            geschichte::git::history::fetch_commit_history(repo_path, &file, false, false, 0, None)
+                .unwrap();
+
+        let blame = fetch_blame(repo_path, &file, Some(&commits[1].hash), None).unwrap();
+        assert_eq!(blame.len(), 1);
+        assert_eq!(blame[0].hash, commits[1].hash);
+        assert_eq!(blame[0].author, "Test User");
+        assert_eq!(blame[0].content, "Initial content");
+        assert_eq!(blame[0].date.len(), "YYYY-MM-DD".len());
+
+        let blame = fetch_blame(repo_path, &file, Some(&commits[0].hash), None).unwrap();
+        assert_eq!(blame.len(), 2);
+        assert_eq!(blame[1].short_hash, commits[0].short_hash);
+        assert_eq!(blame[1].content, "Second line");
+
+        // Blaming the working tree marks edited lines as uncommitted
+        std::fs::write(repo_path.join(&file), "Initial content\nEdited line\n").unwrap();
+        let blame = fetch_blame(repo_path, &file, None, None).unwrap();
+        assert!(!blame[0].is_uncommitted());
+        assert!(blame[1].is_uncommitted());
+        assert_eq!(blame[1].author, NOT_COMMITTED_AUTHOR);
+        assert_eq!(blame[1].content, "Edited line");
+    }
+
+    #[test]
+    fn test_fetch_full_show() {
+        let test_repo = TestRepo::new_with_commits(2);
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &PathBuf::from("test.txt"),
+            false,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+
+        let show =
+            geschichte::git::show::fetch_full_show(test_repo.path(), &commits[0].hash).unwrap();
+        assert!(show.starts_with(&format!("commit {}", commits[0].hash)));
+        assert!(show.contains("diff --git a/test.txt b/test.txt"));
+
+        assert!(geschichte::git::show::fetch_full_show(test_repo.path(), "not-a-commit").is_err());
+    }
+
+    #[test]
+    fn test_diff_text_against_file() {
+        use geschichte::git::diff::diff_text_against_file;
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let file = PathBuf::from("test.txt");
+        let committed =
+            geschichte::git::show::fetch_file_at_commit(test_repo.path(), "HEAD", &file).unwrap();
+        let committed = String::from_utf8(committed).unwrap();
+
+        let diff = diff_text_against_file(
+            test_repo.path(),
+            "clipboard snippet\n",
+            Some("HEAD"),
+            &file,
+            3,
+            None,
+        )
+        .unwrap();
+        assert!(diff.contains("--- a/clipboard"));
+        assert!(diff.contains("+++ b/test.txt"));
+        assert!(diff.contains("-clipboard snippet"));
+
+        let same =
+            diff_text_against_file(test_repo.path(), &committed, Some("HEAD"), &file, 3, None)
+                .unwrap();
+        assert_eq!(same, "No differences from the clipboard\n");
+
+        assert!(diff_text_against_file(
+            test_repo.path(),
+            "text",
+            Some("HEAD"),
+            &PathBuf::from("missing.txt"),
+            3,
+            None
+        )
+        .is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_file_argument_resolution() {
+        use geschichte::git::files::verify_file_in_repo;
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let repo_path = test_repo.path();
+
+        // Track a symlink to test.txt, and leave a second one untracked
+        std::os::unix::fs::symlink("test.txt", repo_path.join("link.txt")).unwrap();
+        Command::new("git")
+            .args(["add", "link.txt"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Add symlink"])
+            .current_dir(repo_path)
+            .output()
+            .unwrap();
+        std::os::unix::fs::symlink("test.txt", repo_path.join("untracked_link.txt")).unwrap();
+
+        // A tracked symlink resolves to itself by default...
+        let resolved = verify_file_in_repo(repo_path, &repo_path.join("link.txt"), false).unwrap();
+        assert_eq!(resolved, PathBuf::from("link.txt"));
+
+        // ...and to its target when following symlinks
+        let resolved = verify_file_in_repo(repo_path, &repo_path.join("link.txt"), true).unwrap();
+        assert_eq!(resolved, PathBuf::from("test.txt"));
+
+        // An untracked symlink falls back to the tracked target
+        let resolved =
+            verify_file_in_repo(repo_path, &repo_path.join("untracked_link.txt"), false).unwrap();
+        assert_eq!(resolved, PathBuf::from("test.txt"));
+
+        // The tracked symlink has its own history, separate from its target
+        let commits = geschichte::git::history::fetch_commit_history(
+            repo_path,
+            &PathBuf::from("link.txt"),
+            false,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(commits.len(), 1);
+    }
+
+    #[test]
+    fn test_line_origin_follows_renames() {
+        use geschichte::git::blame::fetch_line_origin;
+
+        let test_repo = TestRepo::new_with_renames();
+        let origin =
+            fetch_line_origin(test_repo.path(), &PathBuf::from("new_name.rs"), None, 2).unwrap();
+
+        // The println line was added by "Modify file", before the rename
+        let subject = std::process::Command::new("git")
+            .args(["log", "-1", "--format=%s", &origin.hash])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&subject.stdout).trim(),
+            "Modify file"
+        );
+        assert_eq!(origin.path, PathBuf::from("original.rs"));
+        assert_eq!(origin.line, 2);
+        assert!(!origin.is_uncommitted());
+    }
 }
@@ -188,3 +188,188 @@ fn test_regex_search_patterns() {
     let search_state = app.diff_search_state.as_ref().unwrap();
     assert_eq!(search_state.results.len(), 0); // Should show no results for invalid regex
 }
+
+#[test]
+fn test_search_case_and_whole_word_toggles() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    let repo_root = PathBuf::from("/tmp");
+    let file_path = PathBuf::from("test.rs");
+    let mut app = App::new_history(repo_root, file_path, 3, false, false, LayoutMode::Unified);
+
+    app.current_diff = "function test() {\n  hello world\n  say_hello()\n}".to_string();
+    app.start_diff_search();
+    if let Some(ref mut search_state) = app.diff_search_state {
+        search_state.query = "HELLO".to_string();
+    }
+    app.update_search_results().unwrap();
+    let match_count = |app: &App| app.diff_search_state.as_ref().unwrap().results.len();
+    assert_eq!(match_count(&app), 2);
+
+    // Case-sensitive: `HELLO` no longer matches `hello`
+    let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+    app.handle_key(alt('c')).unwrap();
+    assert!(app.diff_search_state.as_ref().unwrap().case_sensitive);
+    assert_eq!(match_count(&app), 0);
+    app.handle_key(alt('c')).unwrap();
+    assert_eq!(match_count(&app), 2);
+
+    // Whole word skips the `hello` inside `say_hello`, also after typing finished
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+        .unwrap();
+    app.handle_key(alt('w')).unwrap();
+    let search_state = app.diff_search_state.as_ref().unwrap();
+    assert!(search_state.whole_word);
+    assert_eq!(search_state.results.len(), 1);
+    assert_eq!(search_state.results[0].line_index, 1);
+    assert_eq!(search_state.current_result, Some(0));
+}
+
+#[test]
+fn test_search_highlighting_without_syntax_highlighting() {
+    use geschichte::diff::{get_search_highlight_style, DiffLineType};
+
+    let repo_root = PathBuf::from("/tmp");
+    let file_path = PathBuf::from("test.rs");
+    let mut app = App::new_history(repo_root, file_path, 3, false, false, LayoutMode::Unified);
+    app.syntax_highlighting = false;
+    assert!(app.highlight_path().is_none());
+
+    app.current_diff =
+        "diff --git a/test.rs b/test.rs\n@@ -1,2 +1,2 @@\n fn main() {\n+    hello();\n }"
+            .to_string();
+    app.start_diff_search();
+    if let Some(ref mut search_state) = app.diff_search_state {
+        search_state.query = "hello".to_string();
+    }
+    app.update_search_results().unwrap();
+    app.navigate_to_next_search_result().unwrap();
+
+    app.prepare_styled_lines();
+    let lines = app.cached_styled_lines.as_ref().unwrap();
+    let added = &lines[3];
+    let matched = added
+        .spans
+        .iter()
+        .find(|span| span.content == "hello")
+        .expect("the match is split into its own span");
+    assert_eq!(
+        matched.style,
+        get_search_highlight_style(true, DiffLineType::Addition)
+    );
+    // The rest of the line keeps the plain addition color
+    assert!(added
+        .spans
+        .iter()
+        .any(|span| span.content == "();" && span.style.fg == Some(ratatui::style::Color::Green)));
+}
+
+#[test]
+fn test_no_color_search_matches_use_modifiers() {
+    use geschichte::config::theme::Theme;
+    use geschichte::diff::DiffLineType;
+    use ratatui::style::Modifier;
+
+    let theme = Theme::DEFAULT.without_color();
+    for line_type in [
+        DiffLineType::Addition,
+        DiffLineType::Deletion,
+        DiffLineType::Context,
+    ] {
+        let current = theme.search_match_style(true, line_type);
+        let other = theme.search_match_style(false, line_type);
+        // Colors are dropped when drawing, so the modifiers alone tell matches apart
+        assert!(current
+            .add_modifier
+            .contains(Modifier::REVERSED | Modifier::BOLD));
+        assert!(other
+            .add_modifier
+            .contains(Modifier::UNDERLINED | Modifier::BOLD));
+        assert!(!other.add_modifier.contains(Modifier::REVERSED));
+    }
+
+    // With colors the modifiers are unchanged
+    let colored = Theme::DEFAULT.search_match_style(false, DiffLineType::Context);
+    assert_eq!(colored.add_modifier, Modifier::BOLD);
+}
+
+#[test]
+fn test_search_highlighting_with_multibyte_text() {
+    let repo_root = PathBuf::from("/tmp");
+    let file_path = PathBuf::from("test.rs");
+    let mut app = App::new_history(repo_root, file_path, 3, false, false, LayoutMode::Unified);
+    app.syntax_highlighting = false;
+
+    app.current_diff = "diff --git a/test.rs b/test.rs\n@@ -1,2 +1,2 @@\n let café = \"日本語\";\n+let menu = \"café 日本語 crème\";\n"
+        .to_string();
+
+    for query in ["日本語", "crème", "café"] {
+        app.start_diff_search();
+        if let Some(ref mut search_state) = app.diff_search_state {
+            search_state.query = query.to_string();
+        }
+        app.update_search_results().unwrap();
+        let results = &app.diff_search_state.as_ref().unwrap().results;
+        assert!(!results.is_empty(), "no match for {}", query);
+
+        app.cached_styled_lines = None;
+        app.prepare_styled_lines();
+        let lines = app.cached_styled_lines.as_ref().unwrap();
+        for result in &app.diff_search_state.as_ref().unwrap().results {
+            let line = &lines[result.line_index];
+            // The highlighted spans cover exactly the matched text
+            let highlighted: Vec<&str> = line
+                .spans
+                .iter()
+                .skip(2)
+                .filter(|span| {
+                    span.style
+                        .add_modifier
+                        .contains(ratatui::style::Modifier::BOLD)
+                })
+                .map(|span| span.content.as_ref())
+                .collect();
+            assert_eq!(highlighted, [query], "query {}", query);
+        }
+        app.clear_diff_search();
+    }
+}
+
+#[test]
+fn test_search_scrolls_sideways_to_long_line_match() {
+    let repo_root = PathBuf::from("/tmp");
+    let file_path = PathBuf::from("test.rs");
+    let mut app = App::new_history(repo_root, file_path, 3, false, false, LayoutMode::Unified);
+    app.ui_state.handle_resize(100, 30);
+
+    // The only "needle" starts at column 200 of the line's content
+    let long_line = format!("+{}needle", "x".repeat(199));
+    app.current_diff = format!("@@ -1,2 +1,2 @@\n short start\n{}", long_line);
+    app.start_diff_search();
+    if let Some(ref mut search_state) = app.diff_search_state {
+        search_state.query = "needle|short".to_string();
+    }
+    app.update_search_results().unwrap();
+    let results = &app.diff_search_state.as_ref().unwrap().results;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].char_start, 200);
+
+    app.navigate_to_next_search_result().unwrap();
+    app.navigate_to_next_search_result().unwrap();
+    let scroll = app.ui_state.diff_horizontal_scroll;
+    let visible = app.ui_state.get_visible_columns(&LayoutMode::Unified);
+    assert!(scroll > 0);
+    // The match, after the line numbers, lies within the viewport
+    assert!(scroll <= 10 + 200 && 10 + 206 <= scroll + visible);
+
+    // A match near the left edge scrolls back
+    app.navigate_to_next_search_result().unwrap();
+    assert_eq!(app.ui_state.diff_horizontal_scroll, 0);
+
+    // Side by side only the old or new line number comes first
+    app.layout_mode = LayoutMode::SideBySide;
+    app.navigate_to_next_search_result().unwrap();
+    let scroll = app.ui_state.diff_horizontal_scroll;
+    let visible = app.ui_state.get_visible_columns(&LayoutMode::SideBySide);
+    assert!(scroll > 0 && scroll <= 5 + 200 && 5 + 206 <= scroll + visible);
+}
@@ -3,6 +3,7 @@ use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 mod test {
     use super::*;
     use crate::common::*;
+    use geschichte::app::App;
 
     #[test]
     fn test_commit_range_selection() {
@@ -21,6 +22,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_line_and_page_scroll_keys() {
+        let mut app = create_test_app();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.handle_resize(80, 40);
+        app.current_diff = (0..200).map(|i| format!(" line {}\n", i)).collect();
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        // Ctrl+E scrolls a line, taking the cursor along only once it would fall off the top
+        app.handle_key(ctrl('e')).unwrap();
+        assert_eq!(app.ui_state.diff_scroll, 1);
+        assert_eq!(app.ui_state.diff_cursor_line, 1);
+        app.handle_key(ctrl('y')).unwrap();
+        assert_eq!(app.ui_state.diff_scroll, 0);
+        assert_eq!(app.ui_state.diff_cursor_line, 1);
+
+        // Ctrl+D moves half as far as Ctrl+F
+        let half_page = app.get_page_scroll_size();
+        app.handle_key(ctrl('d')).unwrap();
+        assert_eq!(app.ui_state.diff_scroll, half_page);
+        app.handle_key(ctrl('u')).unwrap();
+        app.handle_key(ctrl('f')).unwrap();
+        assert_eq!(
+            app.ui_state.diff_scroll,
+            app.ui_state.get_full_page_scroll_size()
+        );
+        assert!(app.ui_state.diff_scroll >= 2 * half_page);
+    }
+
     #[test]
     fn test_navigation_clears_range() {
         let mut app = create_test_app();
@@ -92,6 +122,114 @@ mod test {
         assert!(app.should_quit);
     }
 
+    #[test]
+    fn test_ctrl_c_quits() {
+        let mut app = create_test_app();
+        app.diff_range_start = Some(1);
+
+        // Ctrl+C quits immediately, unlike 'q' which unwinds state first
+        app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL))
+            .unwrap();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_invert_diff() {
+        use geschichte::diff::parser::invert_diff;
+
+        let diff = "diff --git a/test.rs b/test.rs\n\
+--- a/test.rs\n\
++++ b/test.rs\n\
+@@ -1,3 +1,4 @@ fn main()\n \
+keep\n\
+-old line\n\
++new line\n\
++extra line\n \
+tail\n";
+
+        let inverted = invert_diff(diff);
+        assert_eq!(
+            inverted,
+            "diff --git a/test.rs b/test.rs\n\
+--- b/test.rs\n\
++++ a/test.rs\n\
+@@ -1,4 +1,3 @@ fn main()\n \
+keep\n\
+-new line\n\
+-extra line\n\
++old line\n \
+tail\n"
+        );
+
+        // Inverting twice restores the original diff
+        assert_eq!(invert_diff(&inverted), diff);
+
+        // 'I' toggles the inverted view
+        let mut app = create_test_app();
+        app.handle_key(KeyEvent::new(KeyCode::Char('I'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert!(app.invert_diff);
+        app.handle_key(KeyEvent::new(KeyCode::Char('I'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert!(!app.invert_diff);
+    }
+
+    #[test]
+    fn test_initial_focus() {
+        use geschichte::app::FocusedPanel;
+
+        let mut app = create_test_app();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Commits));
+
+        app.set_initial_focus(FocusedPanel::Diff);
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+
+        // Opening another file keeps the configured focus
+        let _ = app.switch_to_history(std::path::PathBuf::from("other.txt"), false);
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+    }
+
+    #[test]
+    fn test_enter_action_focus_diff() {
+        use geschichte::app::FocusedPanel;
+        use geschichte::config::EnterAction;
+
+        let mut app = create_test_app();
+        app.enter_action = EnterAction::FocusDiff;
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+        assert!(!app.show_commit_info);
+    }
+
+    #[test]
+    fn test_tab_cycles_side_by_side_panels() {
+        use geschichte::app::FocusedPanel;
+        use geschichte::ui::state::SideBySideFocus;
+
+        let mut app = create_test_app();
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+
+        // Unified layout toggles between the two panels
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.handle_key(tab).unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+        app.handle_key(tab).unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Commits));
+
+        // Side-by-side cycles Commits -> Old -> New -> Commits
+        app.layout_mode = geschichte::cli::LayoutMode::SideBySide;
+        app.handle_key(tab).unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+        assert_eq!(app.ui_state.side_by_side_focus, SideBySideFocus::Old);
+        app.handle_key(tab).unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+        assert_eq!(app.ui_state.side_by_side_focus, SideBySideFocus::New);
+        app.handle_key(tab).unwrap();
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Commits));
+    }
+
     #[test]
     fn test_scroll_adapts_to_terminal_size() {
         let mut app = create_test_app();
@@ -343,7 +481,6 @@ mod test {
             app::*,
             git::files::{FileStatus, GitFile},
         };
-        use std::collections::HashMap;
         use std::path::PathBuf;
 
         // Create test files
@@ -366,40 +503,17 @@ mod test {
 
         // Start with file picker
         let file_picker_state = FilePickerState::new(files);
-        let mut app = App {
-            repo_root: PathBuf::from("."),
-            should_quit: false,
-            context_lines: 3,
-            follow_renames: true,
-            first_parent: false,
-            mode: AppMode::FilePicker {
-                state: file_picker_state,
-                context: FilePickerContext::Initial,
-            },
-            commits: Vec::new(),
-            selected_index: 0,
-            rename_map: HashMap::new(),
-            current_diff: String::new(),
-            current_side_by_side_diff: None,
-            diff_cache: geschichte::cache::DiffCache::new(10),
-            ui_state: geschichte::ui::state::UIState::new(),
-            layout_mode: geschichte::cli::LayoutMode::Unified,
-            loading: false,
-            error_message: None,
-            diff_range_start: None,
-            current_diff_range: None,
-            copy_mode: None,
-            copier: geschichte::copy::CommitCopier::new(),
-            copy_message: None,
-            show_commit_info: false,
-            commit_info_popup: None,
-            current_changes: Vec::new(),
-            current_change_index: None,
-            message_timer: None,
-            diff_search_state: None,
-            came_from_file_picker: false,
-            redraw_tui: false,
-            cached_highlighted_diff: None,
+        let mut app = App::new_history(
+            PathBuf::from("."),
+            PathBuf::from("README.md"),
+            3,
+            true,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.mode = AppMode::FilePicker {
+            state: file_picker_state,
+            context: FilePickerContext::Initial,
         };
 
         // Initially, came_from_file_picker should be false
@@ -421,4 +535,1193 @@ mod test {
         assert!(matches!(app.mode, AppMode::FilePicker { .. }));
         assert!(!app.came_from_file_picker);
     }
+
+    #[test]
+    fn test_quit_while_loading_returns_to_file_picker() {
+        use geschichte::app::AppMode;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.came_from_file_picker = true;
+        app.load_git_data_in_background().unwrap();
+        assert!(app.is_loading());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(!app.should_quit);
+        assert!(matches!(app.mode, AppMode::FilePicker { .. }));
+        assert!(!app.is_loading());
+        assert!(!app.loading);
+    }
+
+    #[test]
+    fn test_file_picker_in_current_directory() {
+        use geschichte::app::{App, AppMode};
+        use std::path::PathBuf;
+
+        // Runs against this repository's own tracked files
+        let mut app = App::new_history(
+            PathBuf::from("."),
+            PathBuf::from("src/app/mod.rs"),
+            3,
+            true,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+
+        app.switch_to_file_picker_in_directory().unwrap();
+
+        let AppMode::FilePicker { ref state, .. } = app.mode else {
+            panic!("App should be in FilePicker mode");
+        };
+        assert_eq!(state.query, "src/app/");
+        let top_file = state.get_selected_file().unwrap();
+        assert!(top_file.display_path.starts_with("src/app/"));
+    }
+
+    #[test]
+    fn test_untracked_file_opens_as_new_file() {
+        let test_repo = TestRepo::new();
+        std::fs::write(test_repo.path().join("fresh.rs"), "fn main() {}\n").unwrap();
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("fresh.rs"),
+            3,
+            true,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+
+        // No commits yet, just the working directory entry with the whole file added
+        assert_eq!(app.commits.len(), 1);
+        assert_eq!(app.commits[0].subject, "Untracked");
+        assert!(app.showing_untracked_file());
+        assert!(app.current_diff.contains("+fn main() {}"));
+    }
+
+    #[test]
+    fn test_toggle_follow_renames_reloads_history() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_renames();
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("new_name.rs"),
+            3,
+            true,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        assert_eq!(app.commits.len(), 3);
+        app.selected_index = 2;
+
+        // Without --follow only the commit made under the current name is left
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(!app.follow_renames);
+        assert_eq!(app.commits.len(), 1);
+        assert_eq!(app.selected_index, 0);
+        assert!(app.rename_map.is_empty());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.follow_renames);
+        assert_eq!(app.commits.len(), 3);
+    }
+
+    #[test]
+    fn test_heat_map_colors_full_file_by_age() {
+        use geschichte::git::blame::LineAge;
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let commit = |message: &str, date: Option<&str>| {
+            let mut cmd = Command::new("git");
+            cmd.args(["commit", "-am", message])
+                .current_dir(test_repo.path());
+            if let Some(date) = date {
+                cmd.env("GIT_AUTHOR_DATE", date);
+            }
+            cmd.output().unwrap();
+        };
+        std::fs::write(test_repo.path().join("test.txt"), "old line\n").unwrap();
+        commit("Old change", Some("2001-01-01T00:00:00"));
+        std::fs::write(test_repo.path().join("test.txt"), "old line\nnew line\n").unwrap();
+        commit("New change", None);
+
+        let mut app = create_loaded_history_app(&test_repo);
+        assert_eq!(app.heat_map_age(1), None);
+
+        // Turning the heat map on shows the full file, its lines aged by their blame
+        app.handle_key(KeyEvent::new(KeyCode::Char('R'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.full_file_view);
+        assert_eq!(app.heat_map_age(0), None); // The hunk header
+        assert_eq!(app.heat_map_age(1), Some(LineAge::Older));
+        assert_eq!(app.heat_map_age(2), Some(LineAge::Day));
+
+        // Turning it off keeps the full file
+        app.toggle_heat_map().unwrap();
+        assert!(app.full_file_view);
+        assert_eq!(app.heat_map_age(2), None);
+    }
+
+    #[test]
+    fn test_full_file_view_at_commit() {
+        let test_repo = TestRepo::new_with_commits(3);
+        std::fs::write(test_repo.path().join("test.txt"), "edited\nsecond line\n").unwrap();
+        let mut app = create_loaded_history_app(&test_repo);
+        assert!(app.commits[0].is_working_directory);
+
+        // The working directory entry shows the file on disk
+        app.toggle_full_file_view().unwrap();
+        assert!(app.full_file_view);
+        assert_eq!(app.current_diff, "@@ -1,2 +1,2 @@\n edited\n second line\n");
+
+        // Older commits show the file as committed, without diff markers
+        app.selected_index = 2;
+        app.load_diff_for_selected_commit().unwrap();
+        assert_eq!(app.current_diff, "@@ -1,1 +1,1 @@\n Content version 1\n");
+
+        app.toggle_full_file_view().unwrap();
+        assert!(!app.full_file_view);
+        assert!(app.current_diff.contains("+Content version 1"));
+    }
+
+    #[test]
+    fn test_whole_commit_view_jumps_between_files() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(1);
+        std::fs::write(test_repo.path().join("test.txt"), "changed\n").unwrap();
+        std::fs::write(test_repo.path().join("other.rs"), "fn main() {}\n").unwrap();
+        for args in [&["add", "."][..], &["commit", "-m", "Touch two files"]] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        }
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        app.load_diff_for_selected_commit().unwrap();
+        assert!(!app.current_diff.contains("other.rs"));
+
+        app.toggle_whole_commit_view().unwrap();
+        assert!(app.showing_whole_commit());
+        assert!(app
+            .current_diff
+            .contains("diff --git a/other.rs b/other.rs"));
+        assert!(app
+            .current_diff
+            .contains("diff --git a/test.txt b/test.txt"));
+
+        // The cursor steps from one file header to the next and back
+        app.next_file_in_diff();
+        let second_file = app.ui_state.diff_cursor_line;
+        assert!(second_file > 0);
+        assert_eq!(
+            geschichte::diff::diff_header_path(app.current_diff.lines().nth(second_file).unwrap()),
+            Some(std::path::PathBuf::from("test.txt"))
+        );
+        app.next_file_in_diff();
+        assert_eq!(app.ui_state.diff_cursor_line, second_file);
+        app.prev_file_in_diff();
+        assert_eq!(app.ui_state.diff_cursor_line, 0);
+
+        app.toggle_whole_commit_view().unwrap();
+        assert!(!app.current_diff.contains("other.rs"));
+    }
+
+    #[test]
+    fn test_compare_two_refs() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        std::process::Command::new("git")
+            .args(["branch", "base", "HEAD~2"])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.compared_refs = Some(("base".to_string(), "HEAD".to_string()));
+        app.load_git_data().unwrap();
+
+        // Only the two refs are listed, newest first, and their diff is shown
+        let labels: Vec<&str> = app.commits.iter().map(|c| c.short_hash.as_str()).collect();
+        assert_eq!(labels, ["HEAD", "base"]);
+        assert_eq!(app.current_diff_range, Some((1, 0)));
+        assert!(app.current_diff.contains("+Content version 2"));
+        assert!(!app.load_more().unwrap());
+    }
+
+    #[test]
+    fn test_select_commit_by_hash_prefix() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        std::fs::write(test_repo.path().join("other.txt"), "other\n").unwrap();
+        for args in [&["add", "."][..], &["commit", "-m", "Touch another file"]] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        }
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        app.load_diff_for_selected_commit().unwrap();
+
+        let oldest = app.commits.last().unwrap().hash.clone();
+        app.select_commit_by_hash(&oldest[..7].to_uppercase())
+            .unwrap();
+        assert_eq!(app.selected_index, app.commits.len() - 1);
+        assert!(app.error_message.is_none());
+
+        // HEAD didn't change test.txt, so the change before it is selected
+        let head = geschichte::git::history::resolve_commit(test_repo.path(), "HEAD")
+            .unwrap()
+            .unwrap();
+        app.select_commit_by_hash(&head[..7]).unwrap();
+        assert_eq!(app.selected_index, 0);
+        assert!(app
+            .error_message
+            .as_ref()
+            .unwrap()
+            .contains("didn't change"));
+
+        app.selected_index = 1;
+        app.select_commit_by_hash("0000000").unwrap();
+        assert_eq!(app.selected_index, 1);
+        assert!(app.error_message.as_ref().unwrap().contains("No commit"));
+    }
+
+    #[test]
+    fn test_visual_selection_copies_lines() {
+        use geschichte::app::{App, FocusedPanel, SelectionCopy};
+
+        let test_repo = TestRepo::new();
+        for content in ["one\ntwo\n", "one\nTWO\nthree\n"] {
+            std::fs::write(test_repo.path().join("test.txt"), content).unwrap();
+            std::process::Command::new("git")
+                .args(["commit", "-am", "Edit"])
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        }
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        app.load_diff_for_selected_commit().unwrap();
+        app.set_initial_focus(FocusedPanel::Diff);
+
+        // Anchor on the context line and extend over the three changed lines
+        app.ui_state.diff_cursor_line = 5;
+        app.handle_key(KeyEvent::new(KeyCode::Char('V'), KeyModifiers::SHIFT))
+            .unwrap();
+        for _ in 0..3 {
+            app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE))
+                .unwrap();
+        }
+        assert_eq!(app.ui_state.diff_selection(), Some((5, 8)));
+
+        assert_eq!(
+            app.selected_diff_text(SelectionCopy::Raw).unwrap(),
+            " one\n-two\n+TWO\n+three\n"
+        );
+        assert_eq!(
+            app.selected_diff_text(SelectionCopy::Code).unwrap(),
+            "one\ntwo\nTWO\nthree\n"
+        );
+        assert_eq!(
+            app.selected_diff_text(SelectionCopy::Added).unwrap(),
+            "TWO\nthree\n"
+        );
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.ui_state.diff_selection().is_none());
+        assert!(app.selected_diff_text(SelectionCopy::Raw).is_none());
+    }
+
+    #[test]
+    fn test_pickaxe_narrows_and_restores_commits() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        let mut app = create_loaded_history_app(&test_repo);
+        assert_eq!(app.commits.len(), 3);
+        let press = |app: &mut App, code| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(&mut app, KeyCode::Char('S'));
+        for c in "version 1".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        // The commits adding and removing the string, newest first
+        assert_eq!(app.commits.len(), 2);
+        assert_eq!(app.commits[0].subject, "Commit 2");
+        assert_eq!(app.commits[1].subject, "Commit 1");
+        assert_eq!(
+            app.commits_filter_label().as_deref(),
+            Some("pickaxe: \"version 1\"")
+        );
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.commits.len(), 3);
+        assert_eq!(app.commits_filter_label(), None);
+    }
+
+    #[test]
+    fn test_author_filter_narrows_and_restores_commits() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        std::fs::write(test_repo.path().join("test.txt"), "Alice's version").unwrap();
+        std::process::Command::new("git")
+            .args([
+                "commit",
+                "-am",
+                "By Alice",
+                "--author",
+                "Alice Smith <alice@example.com>",
+            ])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+
+        let mut app = create_loaded_history_app(&test_repo);
+        assert_eq!(app.commits.len(), 4);
+        app.selected_index = 2;
+        let press = |app: &mut App, code| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(&mut app, KeyCode::Char('A'));
+        for c in "ALICE".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+
+        assert_eq!(app.commits.len(), 1);
+        assert_eq!(app.commits[0].subject, "By Alice");
+        assert_eq!(app.selected_index, 0);
+        assert_eq!(app.commits_filter_label().as_deref(), Some("author: ALICE"));
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.commits.len(), 4);
+        assert_eq!(app.commits_filter_label(), None);
+    }
+
+    #[test]
+    fn test_gg_and_shift_g_jump_to_ends() {
+        use geschichte::app::{App, FocusedPanel};
+
+        let test_repo = TestRepo::new_with_commits(5);
+        let mut app = create_loaded_history_app(&test_repo);
+        let press = |app: &mut App, code| {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.selected_index, 4);
+        assert_eq!(app.commits[4].subject, "Initial commit");
+
+        // A single `g` waits for the second; any other key cancels it
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.selected_index, 4);
+        press(&mut app, KeyCode::Char('k'));
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.selected_index, 3);
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.selected_index, 0);
+
+        app.focus_panel(FocusedPanel::Diff);
+        let last_line = app.get_diff_line_count() - 1;
+        press(&mut app, KeyCode::Char('G'));
+        assert_eq!(app.ui_state.diff_cursor_line, last_line);
+        press(&mut app, KeyCode::Char('g'));
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(app.ui_state.diff_cursor_line, 0);
+    }
+
+    #[test]
+    fn test_history_search_finds_and_jumps_to_changed_lines() {
+        use geschichte::app::FocusedPanel;
+
+        let test_repo = TestRepo::new_with_commits(4);
+        let mut app = create_loaded_history_app(&test_repo);
+
+        app.start_history_search();
+        app.search_all_commit_diffs("VERSION 2").unwrap();
+        while app
+            .history_search
+            .as_ref()
+            .is_some_and(|popup| popup.is_searching())
+        {
+            app.load_pending_history_search();
+        }
+
+        // "Commit 3" removes the line that "Commit 2" added
+        let popup = app.history_search.as_ref().unwrap();
+        let hits: Vec<(usize, &str)> = popup
+            .hits
+            .iter()
+            .map(|hit| (hit.commit_index, hit.line.as_str()))
+            .collect();
+        assert_eq!(
+            hits,
+            vec![(0, "-Content version 2"), (1, "+Content version 2")]
+        );
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE))
+            .unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+
+        assert!(app.history_search.is_none());
+        assert_eq!(app.commits[app.selected_index].subject, "Commit 2");
+        assert_eq!(app.get_focused_panel(), Some(FocusedPanel::Diff));
+        let cursor_line = app.current_diff.lines().nth(app.ui_state.diff_cursor_line);
+        assert_eq!(cursor_line, Some("+Content version 2"));
+    }
+
+    #[test]
+    fn test_history_search_anchors_at_code_and_maps_side_by_side_rows() {
+        let test_repo = TestRepo::new_with_commits(4);
+        let mut app = create_loaded_history_app(&test_repo);
+        app.layout_mode = geschichte::cli::LayoutMode::SideBySide;
+
+        // `^` anchors at the start of the code rather than at the +/- marker
+        app.start_history_search();
+        app.search_all_commit_diffs("^Content version 2$").unwrap();
+        while app
+            .history_search
+            .as_ref()
+            .is_some_and(|popup| popup.is_searching())
+        {
+            app.load_pending_history_search();
+        }
+        assert_eq!(app.history_search.as_ref().unwrap().hits.len(), 2);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE))
+            .unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+
+        // The addition shares a row with the deletion before it, one above its unified index
+        let side_by_side = app.current_side_by_side_diff.as_ref().unwrap();
+        let row = &side_by_side.new_lines[app.ui_state.diff_cursor_line];
+        assert_eq!(
+            row.as_ref().map(|line| line.content.as_str()),
+            Some("+Content version 2")
+        );
+    }
+
+    #[test]
+    fn test_context_lines_keys() {
+        use geschichte::app::{App, MAX_CONTEXT_LINES};
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let mut app = create_loaded_history_app(&test_repo);
+        let press = |app: &mut App, c| {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(&mut app, '+');
+        assert_eq!(app.context_lines, 4);
+        assert_eq!(app.copy_message.as_deref(), Some("Context: 4"));
+        assert!(app.current_diff.contains("@@"));
+
+        for _ in 0..5 {
+            press(&mut app, '-');
+        }
+        assert_eq!(app.context_lines, 0);
+
+        // --full-file context drops to the key range
+        app.context_lines = 9999;
+        press(&mut app, '+');
+        assert_eq!(app.context_lines, MAX_CONTEXT_LINES);
+    }
+
+    #[test]
+    fn test_history_loads_in_pages() {
+        use geschichte::app::COMMIT_PAGE_SIZE;
+
+        let test_repo = TestRepo::new_with_commits(500);
+        let mut app = create_loaded_history_app(&test_repo);
+        assert_eq!(app.commits.len(), COMMIT_PAGE_SIZE);
+        assert!(!app.history_complete);
+        assert_eq!(app.rename_map.len(), COMMIT_PAGE_SIZE);
+
+        // Nearing the end of the loaded commits fetches the next page
+        app.selected_index = COMMIT_PAGE_SIZE - 2;
+        app.move_selection_down().unwrap();
+        assert_eq!(app.commits.len(), 2 * COMMIT_PAGE_SIZE);
+        assert_eq!(app.selected_index, COMMIT_PAGE_SIZE - 1);
+
+        // The last, partial page completes the history
+        assert!(app.load_more().unwrap());
+        assert_eq!(app.commits.len(), 500);
+        assert!(app.history_complete);
+        assert!(!app.load_more().unwrap());
+        assert_eq!(app.commits.last().unwrap().subject, "Initial commit");
+        assert_eq!(app.rename_map.len(), 500);
+    }
+
+    #[test]
+    fn test_range_diff_direction_in_both_orders() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        for reverse in [false, true] {
+            let mut app = App::new_history(
+                test_repo.path().to_path_buf(),
+                std::path::PathBuf::from("test.txt"),
+                3,
+                true,
+                false,
+                geschichte::cli::LayoutMode::Unified,
+            );
+            app.reverse = reverse;
+            app.load_git_data().unwrap();
+
+            let (oldest, newest) = if reverse { (0, 2) } else { (2, 0) };
+            assert_eq!(app.commits[oldest].subject, "Initial commit");
+            assert_eq!(app.commits[newest].subject, "Commit 2");
+
+            // Marking the newest commit first and the oldest second still diffs old to new
+            app.selected_index = newest;
+            app.toggle_diff_range_selection().unwrap();
+            app.selected_index = oldest;
+            app.toggle_diff_range_selection().unwrap();
+
+            assert_eq!(app.current_diff_range, Some((oldest, newest)));
+            assert!(app.current_diff.contains("-Initial content"));
+            assert!(app.current_diff.contains("+Content version 2"));
+        }
+    }
+
+    #[test]
+    fn test_line_counts_follow_renames_in_both_orders() {
+        use geschichte::commit::FileLineCounts;
+
+        let test_repo = TestRepo::new_with_renames();
+        for reverse in [false, true] {
+            let mut app = App::new_history(
+                test_repo.path().to_path_buf(),
+                std::path::PathBuf::from("new_name.rs"),
+                3,
+                true,
+                false,
+                geschichte::cli::LayoutMode::Unified,
+            );
+            app.reverse = reverse;
+            app.load_git_data().unwrap();
+
+            let (added, renamed) = if reverse { (0, 2) } else { (2, 0) };
+            assert_eq!(app.commits[added].subject, "Add original file");
+            assert_eq!(app.commits[renamed].subject, "Rename file");
+
+            // The rename reads the old name from the commit before it, whichever way the list
+            // runs; the commit that added the file has nothing before
+            for (index, before, after) in [(renamed, Some(3), Some(3)), (added, None, Some(1))] {
+                app.selected_index = index;
+                app.show_commit_info_popup().unwrap();
+                assert_eq!(
+                    app.commits[index].line_counts,
+                    Some(Some(FileLineCounts { before, after }))
+                );
+            }
+        }
+
+        // A root commit has no parent, yet still counts as adding the file
+        let test_repo = TestRepo::new();
+        let mut app = create_loaded_history_app(&test_repo);
+        app.show_commit_info_popup().unwrap();
+        assert_eq!(
+            app.commits[0].line_counts,
+            Some(Some(FileLineCounts {
+                before: None,
+                after: Some(1)
+            }))
+        );
+    }
+
+    #[test]
+    fn test_watch_refreshes_working_directory_entry() {
+        use std::time::{Duration, SystemTime};
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let file = test_repo.path().join("test.txt");
+        let mut app = create_loaded_history_app(&test_repo);
+        app.watch_interval = Some(Duration::from_millis(1));
+        app.selected_index = 1;
+        let commit_count = app.commits.len();
+        assert!(!app.commits[0].is_working_directory);
+
+        // Nothing changed yet, so no entry appears
+        app.poll_working_tree();
+        assert_eq!(app.commits.len(), commit_count);
+
+        std::fs::write(&file, "edited\n").unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(2));
+        app.poll_working_tree();
+
+        assert!(app.commits[0].is_working_directory);
+        assert_eq!(app.commits.len(), commit_count + 1);
+        // The selection stays on the same commit
+        assert_eq!(app.selected_index, 2);
+    }
+
+    #[test]
+    fn test_stage_and_unstage_working_directory_file() {
+        let test_repo = TestRepo::new_with_commits(2);
+        std::fs::write(test_repo.path().join("test.txt"), "edited\n").unwrap();
+        let mut app = create_loaded_history_app(&test_repo);
+        assert_eq!(app.commits[0].subject, "Modified");
+        let alt = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT);
+
+        // Only the working directory entry can be staged
+        app.selected_index = 1;
+        app.handle_key(alt('s')).unwrap();
+        assert_eq!(app.commits[0].subject, "Modified");
+        assert!(app.error_message.is_some());
+
+        app.error_message = None;
+        app.selected_index = 0;
+        app.handle_key(alt('s')).unwrap();
+        assert_eq!(app.commits[0].subject, "Staged");
+        assert_eq!(app.copy_message.as_deref(), Some("Staged test.txt"));
+        assert!(app.current_diff.contains("+edited"));
+
+        app.handle_key(alt('u')).unwrap();
+        assert_eq!(app.commits[0].subject, "Modified");
+        assert!(app.error_message.is_none());
+    }
+
+    #[test]
+    fn test_directory_history_mode() {
+        use geschichte::app::App;
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let dir = test_repo.path().join("src");
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::write(dir.join("a.txt"), "alpha\n").unwrap();
+        std::fs::write(dir.join("b.txt"), "beta\n").unwrap();
+        for args in [&["add", "."][..], &["commit", "-m", "Add src"]] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        }
+
+        let relative =
+            geschichte::git::files::verify_file_in_repo(test_repo.path(), &dir, false).unwrap();
+        assert_eq!(relative, std::path::PathBuf::from("src"));
+
+        // Following renames is asked for, but doesn't apply to a directory
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            relative,
+            3,
+            true,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        app.load_diff_for_selected_commit().unwrap();
+
+        assert!(app.is_directory_mode());
+        assert_eq!(app.commits.len(), 1);
+        assert_eq!(app.commits[0].subject, "Add src");
+        assert!(app.current_diff.contains("+alpha"));
+        assert!(app.current_diff.contains("+beta"));
+        assert!(!app.current_diff.contains("test.txt"));
+
+        app.toggle_full_file_view().unwrap();
+        assert!(!app.full_file_view);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        use geschichte::app::App;
+        use geschichte::cli::LayoutMode;
+        use std::path::PathBuf;
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let state_dir = tempfile::tempdir().unwrap();
+        let session_file = state_dir.path().join("geschichte").join("sessions.json");
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            PathBuf::from("test.txt"),
+            3,
+            true,
+            false,
+            LayoutMode::Unified,
+        );
+        app.session_file = Some(session_file.clone());
+        app.ui_state.split_ratio = 0.55;
+        app.ui_state.vertical_split_ratio = 0.5;
+        app.layout_mode = LayoutMode::SideBySide;
+        app.save_session().unwrap();
+
+        let mut picker = App::new_file_picker(
+            test_repo.path().to_path_buf(),
+            3,
+            true,
+            false,
+            LayoutMode::Unified,
+            Default::default(),
+        )
+        .unwrap();
+        picker.session_file = Some(session_file.clone());
+        let last_file = picker.load_session(true);
+        assert_eq!(last_file, Some(PathBuf::from("test.txt")));
+        assert_eq!(picker.ui_state.split_ratio, 0.55);
+        assert_eq!(picker.ui_state.vertical_split_ratio, 0.5);
+        assert_eq!(picker.layout_mode, LayoutMode::SideBySide);
+        assert!(picker.select_in_file_picker(&last_file.unwrap()));
+
+        // Quitting from the picker keeps the last file; an explicit layout isn't overridden
+        picker.layout_mode = LayoutMode::Unified;
+        picker.save_session().unwrap();
+        picker.layout_mode = LayoutMode::Auto;
+        assert_eq!(picker.load_session(false), Some(PathBuf::from("test.txt")));
+        assert_eq!(picker.layout_mode, LayoutMode::Auto);
+
+        // Sessions are kept per repository
+        let other_repo = TestRepo::new();
+        let mut other = App::new_history(
+            other_repo.path().to_path_buf(),
+            PathBuf::from("test.txt"),
+            3,
+            true,
+            false,
+            LayoutMode::Unified,
+        );
+        other.session_file = Some(session_file);
+        assert_eq!(other.load_session(true), None);
+        assert_eq!(other.ui_state.split_ratio, 0.4);
+    }
+
+    #[test]
+    fn test_patch_source_follows_selection() {
+        use geschichte::app::App;
+        use geschichte::git::show::PatchSource;
+        use geschichte::git::working::WorkingDirectoryStatus;
+        use std::path::PathBuf;
+
+        let test_repo = TestRepo::new_with_commits(3);
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+
+        app.selected_index = 1;
+        assert_eq!(
+            app.patch_source(),
+            Ok(PatchSource::Commit(app.commits[1].hash.clone()))
+        );
+
+        app.current_diff_range = Some((2, 0));
+        assert_eq!(
+            app.patch_source(),
+            Ok(PatchSource::Range {
+                older: app.commits[2].hash.clone(),
+                newer: app.commits[0].hash.clone(),
+            })
+        );
+        app.current_diff_range = None;
+
+        // Uncommitted changes show up as the working directory entry
+        std::fs::write(test_repo.path().join("test.txt"), "Uncommitted\n").unwrap();
+        app.load_git_data().unwrap();
+        assert!(app.commits[0].is_working_directory);
+        app.selected_index = 0;
+        assert_eq!(
+            app.patch_source(),
+            Ok(PatchSource::WorkingDirectory {
+                file_path: PathBuf::from("test.txt"),
+                status: WorkingDirectoryStatus::Modified,
+            })
+        );
+
+        std::process::Command::new("git")
+            .args(["add", "test.txt"])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        assert!(matches!(
+            app.patch_source(),
+            Ok(PatchSource::WorkingDirectory {
+                status: WorkingDirectoryStatus::Staged,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parent_and_child_navigation() {
+        use geschichte::app::App;
+        use std::path::PathBuf;
+
+        let test_repo = TestRepo::new_with_commits(2);
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        };
+        // The latest commit's parent doesn't touch the file
+        std::fs::write(test_repo.path().join("other.txt"), "other\n").unwrap();
+        git(&["add", "other.txt"]);
+        git(&["commit", "-m", "Unrelated"]);
+        std::fs::write(test_repo.path().join("test.txt"), "Latest\n").unwrap();
+        git(&["commit", "-am", "Latest"]);
+
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        assert_eq!(app.commits.len(), 3);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('['), KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.selected_index, 1);
+        app.go_to_parent_commit().unwrap();
+        assert_eq!(app.selected_index, 2);
+
+        // The initial commit has no parent
+        app.go_to_parent_commit().unwrap();
+        assert_eq!(app.selected_index, 2);
+        assert!(app.error_message.is_some());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char(']'), KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.selected_index, 1);
+        app.go_to_child_commit().unwrap();
+        assert_eq!(app.selected_index, 0);
+        assert!(app.parent_chooser.is_none());
+
+        // Without a trail, the child is found among the newer commits
+        app.selected_index = 2;
+        app.go_to_child_commit().unwrap();
+        assert_eq!(app.selected_index, 1);
+    }
+
+    #[test]
+    fn test_quit_and_print_keeps_the_diff() {
+        let mut app = create_test_app();
+        app.current_diff = "@@ -1 +1 @@\n-old\n+new".to_string();
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('Q'), KeyModifiers::SHIFT))
+            .unwrap();
+        assert!(app.should_quit);
+        assert_eq!(
+            app.on_exit_output.as_deref(),
+            Some(app.current_diff.as_str())
+        );
+
+        // A plain quit prints only with --print-on-exit
+        let mut app = create_test_app();
+        app.current_diff = "+new".to_string();
+        app.quit();
+        assert!(app.on_exit_output.is_none());
+        app.print_on_exit = true;
+        app.quit();
+        assert_eq!(app.on_exit_output.as_deref(), Some("+new"));
+
+        let mut plain = Vec::new();
+        geschichte::export::write_diff(&mut plain, "@@ -1 +1 @@\n-old\n+new", false).unwrap();
+        assert_eq!(
+            String::from_utf8(plain).unwrap(),
+            "@@ -1 +1 @@\n-old\n+new\n"
+        );
+        let mut colored = Vec::new();
+        geschichte::export::write_diff(&mut colored, "-old\n+new", true).unwrap();
+        assert_eq!(
+            String::from_utf8(colored).unwrap(),
+            "\x1b[31m-old\x1b[0m\n\x1b[32m+new\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_background_history_load() {
+        let test_repo = TestRepo::new_with_commits(3);
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data_in_background().unwrap();
+        assert!(app.is_loading());
+        assert!(app.loading);
+
+        // Keys other than quit wait for the history
+        app.handle_key(KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(app.selected_index, 0);
+
+        let started = std::time::Instant::now();
+        while app.is_loading() {
+            assert!(started.elapsed() < std::time::Duration::from_secs(10));
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            app.poll_history_load();
+        }
+        assert!(!app.loading);
+        assert_eq!(app.commits.len(), 3);
+        assert!(!app.current_diff.is_empty());
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_max_count_caps_history() {
+        let test_repo = TestRepo::new_with_commits(3);
+        std::fs::write(test_repo.path().join("test.txt"), "edited\n").unwrap();
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.max_commits = Some(2);
+        app.load_git_data().unwrap();
+
+        // Exactly two commits follow the working directory entry, and no more load
+        assert_eq!(app.commits.len(), 3);
+        assert!(app.commits[0].is_working_directory);
+        assert!(app.history_complete);
+        assert!(!app.load_more().unwrap());
+        assert_eq!(app.commits.len(), 3);
+        assert!(app.history_truncated);
+        assert_eq!(app.commits_filter_label().as_deref(), Some("truncated"));
+        // The count leaves out the working directory entry
+        assert_eq!(app.total_commits, Some(3));
+        assert_eq!(
+            app.commit_count_label().as_deref(),
+            Some("showing 2 of 3 commits")
+        );
+
+        // A cap the history fits within cuts nothing
+        app.max_commits = Some(3);
+        app.load_git_data().unwrap();
+        assert_eq!(app.commits.len(), 4);
+        assert!(!app.history_truncated);
+        assert_eq!(app.commits_filter_label(), None);
+        assert_eq!(app.commit_count_label().as_deref(), Some("3 commits"));
+    }
+
+    #[test]
+    fn test_count_before_d_diffs_across_commits() {
+        let test_repo = TestRepo::new_with_commits(6);
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        let press = |app: &mut App, c: char| {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        };
+
+        app.selected_index = 1;
+        press(&mut app, '3');
+        assert_eq!(app.pending_count, Some(3));
+        press(&mut app, 'd');
+        assert_eq!(app.current_diff_range, Some((4, 1)));
+        assert_eq!(app.diff_range_start, None);
+        assert_eq!(app.pending_count, None);
+
+        // Counts past the oldest commit stop there
+        app.selected_index = 0;
+        press(&mut app, '1');
+        press(&mut app, '0');
+        press(&mut app, 'd');
+        assert_eq!(app.current_diff_range, Some((5, 0)));
+
+        // Any other key drops the count, leaving `d` to mark as usual
+        press(&mut app, '2');
+        press(&mut app, 'j');
+        press(&mut app, 'd');
+        assert_eq!(app.diff_range_start, Some(1));
+    }
+
+    #[test]
+    fn test_marks_jump_between_commits() {
+        let test_repo = TestRepo::new_with_commits(3);
+        let mut app = App::new_history(
+            test_repo.path().to_path_buf(),
+            std::path::PathBuf::from("test.txt"),
+            3,
+            false,
+            false,
+            geschichte::cli::LayoutMode::Unified,
+        );
+        app.load_git_data().unwrap();
+        let press = |app: &mut App, c: char| {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                .unwrap();
+        };
+
+        app.selected_index = 2;
+        press(&mut app, 'm');
+        press(&mut app, 'a');
+        assert_eq!(app.marks[&'a'], app.commits[2].hash);
+        assert_eq!(app.mark_letters(&app.commits[2].hash), "a");
+
+        app.selected_index = 0;
+        press(&mut app, '\'');
+        press(&mut app, 'a');
+        assert_eq!(app.selected_index, 2);
+
+        // '' flips back and forth
+        press(&mut app, '\'');
+        press(&mut app, '\'');
+        assert_eq!(app.selected_index, 0);
+        press(&mut app, '\'');
+        press(&mut app, '\'');
+        assert_eq!(app.selected_index, 2);
+
+        // An unset mark leaves the selection alone; the key after `m` isn't acted on
+        press(&mut app, '\'');
+        press(&mut app, 'b');
+        assert_eq!(app.selected_index, 2);
+        assert!(app.error_message.is_some());
+        press(&mut app, 'm');
+        press(&mut app, 'j');
+        assert_eq!(app.selected_index, 2);
+        assert_eq!(app.mark_letters(&app.commits[2].hash), "aj");
+    }
+
+    #[test]
+    fn test_blame_permalink_follows_the_blame_cursor() {
+        use geschichte::git::remote::RemoteRepo;
+        use std::process::Command;
+
+        let test_repo = TestRepo::new();
+        let commit = |message: &str| {
+            Command::new("git")
+                .args(["commit", "-am", message])
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+            let hash = Command::new("git")
+                .args(["rev-parse", "HEAD"])
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+            String::from_utf8_lossy(&hash.stdout).trim().to_string()
+        };
+        std::fs::write(test_repo.path().join("test.txt"), "old line\n").unwrap();
+        let old_hash = commit("Old change");
+        std::fs::write(test_repo.path().join("test.txt"), "old line\nnew line\n").unwrap();
+        let new_hash = commit("New change");
+
+        // In the blame view the cursor is on a line of the file, blamed to its own commit
+        let mut app = create_loaded_history_app(&test_repo);
+        app.toggle_blame();
+        assert_eq!(
+            app.blame_permalink(),
+            Err("No 'origin' remote to build a URL from".to_string())
+        );
+        app.copier
+            .set_remote(RemoteRepo::parse("git@github.com:owner/repo.git"));
+        assert_eq!(
+            app.blame_permalink().unwrap(),
+            format!(
+                "https://github.com/owner/repo/blob/{}/test.txt#L1",
+                old_hash
+            )
+        );
+        app.ui_state.diff_cursor_line = 1;
+        assert_eq!(
+            app.blame_permalink().unwrap(),
+            format!(
+                "https://github.com/owner/repo/blob/{}/test.txt#L2",
+                new_hash
+            )
+        );
+    }
 }
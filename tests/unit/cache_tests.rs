@@ -58,4 +58,66 @@ mod test {
         assert_eq!(cache.get("key1").unwrap(), "diff1_updated");
         assert_eq!(cache.len(), 1);
     }
+
+    #[test]
+    fn test_invalidate_containing() {
+        let mut cache = geschichte::cache::DiffCache::new(10);
+
+        cache.put("WORKING_DIR".to_string(), "wd diff".to_string());
+        cache.put("abc123..WORKING_DIR".to_string(), "range diff".to_string());
+        cache.put("abc123".to_string(), "commit diff".to_string());
+
+        cache.invalidate_containing("WORKING_DIR");
+
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get("abc123").is_some());
+    }
+
+    #[test]
+    fn test_disk_cache_survives_restart() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let mut cache = geschichte::cache::DiffCache::new(10);
+        cache.set_disk_dir(dir.path().to_path_buf());
+        cache.put("abc123:test.txt:U3".to_string(), "commit diff".to_string());
+        cache.put_in_memory("WORKING_DIR:test.txt:U3".to_string(), "wd diff".to_string());
+
+        // A fresh cache over the same directory sees committed diffs only
+        let mut cache = geschichte::cache::DiffCache::new(10);
+        cache.set_disk_dir(dir.path().to_path_buf());
+        assert_eq!(
+            cache.get("abc123:test.txt:U3").map(String::as_str),
+            Some("commit diff")
+        );
+        assert!(cache.get("abc123:test.txt:U5").is_none());
+        assert!(cache.get("WORKING_DIR:test.txt:U3").is_none());
+
+        // Clearing memory keeps the disk entry
+        cache.clear();
+        assert!(cache.get("abc123:test.txt:U3").is_some());
+    }
+
+    #[test]
+    fn test_disk_cache_stays_bounded_while_writing() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let ttl = std::time::Duration::from_secs(60);
+
+        let mut cache = geschichte::cache::DiffCache::new(10);
+        cache.set_disk_dir_with_limits(dir.path().to_path_buf(), 3, ttl);
+        for n in 0..10 {
+            cache.put(format!("commit{}:test.txt:U3", n), "diff".to_string());
+        }
+        // Rewriting an entry doesn't count it twice
+        cache.put("commit9:test.txt:U3".to_string(), "diff".to_string());
+
+        let files: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert!(files.len() <= 3, "{:?}", files);
+        // No temp files are left behind
+        assert!(files
+            .iter()
+            .all(|path| path.extension().is_some_and(|ext| ext == "diff")));
+    }
 }
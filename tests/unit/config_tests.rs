@@ -0,0 +1,235 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use geschichte::config::keys::{Action, KeyMap};
+use geschichte::config::theme::{Palette, Theme};
+use geschichte::config::{Config, EnterAction};
+
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = Config::from_toml_str("").unwrap();
+        assert_eq!(config.enter_action, EnterAction::Info);
+    }
+
+    #[test]
+    fn test_config_enter_action() {
+        let config = Config::from_toml_str("enter_action = \"focus-diff\"").unwrap();
+        assert_eq!(config.enter_action, EnterAction::FocusDiff);
+
+        let config = Config::from_toml_str("enter_action = \"open-editor\"").unwrap();
+        assert_eq!(config.enter_action, EnterAction::OpenEditor);
+
+        assert!(Config::from_toml_str("enter_action = \"explode\"").is_err());
+    }
+
+    #[test]
+    fn test_config_date_format() {
+        use geschichte::git::history::{date_style, DEFAULT_DATE_STYLE};
+
+        let config = Config::from_toml_str("date_format = \"relative\"").unwrap();
+        assert_eq!(config.date_format.as_deref(), Some("relative"));
+        assert!(Config::from_toml_str("").unwrap().date_format.is_none());
+
+        assert_eq!(date_style("relative").as_deref(), Some("relative"));
+        assert_eq!(date_style("%d.%m.%Y").as_deref(), Some("format:%d.%m.%Y"));
+        assert_eq!(date_style("  ").as_deref(), Some(DEFAULT_DATE_STYLE));
+        assert!(date_style("yesterday-ish").is_none());
+    }
+
+    #[test]
+    fn test_config_exclude_globs() {
+        assert!(Config::from_toml_str("").unwrap().exclude.is_empty());
+
+        let config = Config::from_toml_str("exclude = [\"node_modules/\", \"*.min.js\"]").unwrap();
+        assert_eq!(config.exclude, vec!["node_modules/", "*.min.js"]);
+    }
+
+    #[test]
+    fn test_config_auto_width() {
+        assert!(Config::from_toml_str("").unwrap().auto_width.is_none());
+        let config = Config::from_toml_str("auto_width = 100").unwrap();
+        assert_eq!(config.auto_width, Some(100));
+    }
+
+    #[test]
+    fn test_config_syntax_overrides() {
+        assert!(Config::from_toml_str("")
+            .unwrap()
+            .syntax_overrides
+            .is_empty());
+
+        let mut config =
+            Config::from_toml_str("[syntax_overrides]\n\"*.inc\" = \"php\"\nJustfile = \"make\"")
+                .unwrap();
+        // A later layer adds to the overrides and replaces the ones it repeats
+        config
+            .merge_toml_str("[syntax_overrides]\n\"*.inc\" = \"c\"")
+            .unwrap();
+        assert_eq!(config.syntax_overrides.len(), 2);
+        assert_eq!(config.syntax_overrides["*.inc"], "c");
+        assert_eq!(config.syntax_overrides["Justfile"], "make");
+    }
+
+    #[test]
+    fn test_repo_config_overrides_global_per_field() {
+        let mut config =
+            Config::from_toml_str("enter_action = \"focus-diff\"\nexclude = [\"vendor/\"]")
+                .unwrap();
+
+        // The repo layer only sets `exclude`, so the global enter_action survives
+        config
+            .merge_toml_str("exclude = [\"node_modules/\"]")
+            .unwrap();
+        assert_eq!(config.enter_action, EnterAction::FocusDiff);
+        assert_eq!(config.exclude, vec!["node_modules/"]);
+    }
+
+    #[test]
+    fn test_load_reads_repo_config() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            repo.path().join(Config::REPO_FILE),
+            "enter_action = \"none\"",
+        )
+        .unwrap();
+        let user_dir = tempfile::TempDir::new().unwrap();
+        let user_path = user_dir.path().join("config.toml");
+        std::fs::write(
+            &user_path,
+            "enter_action = \"focus-diff\"\nexclude = [\"vendor/\"]",
+        )
+        .unwrap();
+
+        // The user config is layered underneath; the repo file wins for the fields it sets
+        let config = Config::load_layers(Some(user_path), repo.path()).unwrap();
+        assert_eq!(config.enter_action, EnterAction::None);
+        assert_eq!(config.exclude, vec!["vendor/"]);
+
+        // A missing user config leaves only the repo file
+        let missing = user_dir.path().join("missing.toml");
+        let config = Config::load_layers(Some(missing), repo.path()).unwrap();
+        assert_eq!(config.enter_action, EnterAction::None);
+        assert!(config.exclude.is_empty());
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_default_keymap_matches_builtin_keys() {
+        let keymap = KeyMap::default();
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('j'), KeyModifiers::NONE)),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('d'), KeyModifiers::CONTROL)),
+            Some(Action::HalfPageDown)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('f'), KeyModifiers::CONTROL)),
+            Some(Action::PageDown)
+        );
+        // Terminals may or may not report Shift with an uppercase letter
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('N'), KeyModifiers::SHIFT)),
+            Some(Action::PreviousChange)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('N'), KeyModifiers::NONE)),
+            Some(Action::PreviousChange)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('x'), KeyModifiers::NONE)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_default_keys_are_bound_once() {
+        use geschichte::config::keys::KeySpec;
+        use std::collections::HashMap;
+
+        let mut bound: HashMap<KeySpec, Action> = HashMap::new();
+        for &action in Action::ALL {
+            for spec in action.default_keys() {
+                let key = KeySpec::parse(spec).unwrap();
+                if let Some(other) = bound.insert(key, action) {
+                    panic!("\"{}\" is bound to both {:?} and {:?}", spec, other, action);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_keymap_overrides_replace_default_keys() {
+        let keymap =
+            KeyMap::from_toml_str("move_down = [\"ctrl+n\", \"down\"]\nmove_up = \"ctrl+p\"")
+                .unwrap();
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('p'), KeyModifiers::CONTROL)),
+            Some(Action::MoveUp)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('j'), KeyModifiers::NONE)),
+            None
+        );
+        // Untouched actions keep their defaults
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('n'), KeyModifiers::NONE)),
+            Some(Action::NextChange)
+        );
+    }
+
+    #[test]
+    fn test_keymap_rejects_unknown_actions_and_keys() {
+        let err = KeyMap::from_toml_str("jump_around = \"j\"").unwrap_err();
+        assert!(err.to_string().contains("unknown action \"jump_around\""));
+
+        let err = KeyMap::from_toml_str("move_up = \"hyper+k\"").unwrap_err();
+        assert!(err.to_string().contains("hyper+k"));
+
+        // "n" is still next_change, so it can't also move down
+        let err = KeyMap::from_toml_str("move_down = \"n\"").unwrap_err();
+        assert!(err.to_string().contains("next_change"));
+        assert!(KeyMap::from_toml_str("move_down = \"n\"\nnext_change = \"ctrl+n\"").is_ok());
+    }
+
+    #[test]
+    fn test_theme_overrides_palette() {
+        use ratatui::style::Color;
+
+        assert_eq!(Theme::from_toml_str("", None).unwrap(), Theme::DEFAULT);
+
+        let theme = Theme::from_toml_str(
+            "palette = \"high-contrast\"\naddition_bg = \"#1f3f6f\"\ndeletion_fg = \"yellow\"",
+            None,
+        )
+        .unwrap();
+        assert_eq!(theme.addition_bg, Color::Rgb(0x1f, 0x3f, 0x6f));
+        assert_eq!(theme.deletion_fg, Color::Yellow);
+        assert_eq!(theme.cursor_bg, Theme::HIGH_CONTRAST.cursor_bg);
+
+        // --palette wins over the palette the file names
+        let theme =
+            Theme::from_toml_str("palette = \"high-contrast\"", Some(Palette::Default)).unwrap();
+        assert_eq!(theme, Theme::DEFAULT);
+    }
+
+    #[test]
+    fn test_theme_rejects_unknown_colors() {
+        let err = Theme::from_toml_str("addition = \"green\"", None).unwrap_err();
+        assert!(err.to_string().contains("unknown theme color \"addition\""));
+
+        let err = Theme::from_toml_str("cursor_bg = \"not-a-color\"", None).unwrap_err();
+        assert!(err.to_string().contains("not-a-color"));
+
+        assert!(Theme::from_toml_str("palette = \"neon\"", None).is_err());
+    }
+}
@@ -0,0 +1,307 @@
+use geschichte::diff::{count_hunk_changes, parse_diff, render_whitespace};
+use ratatui::text::{Line, Span};
+
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hunk_change_counts() {
+        let diff = "diff --git a/test.rs b/test.rs\n\
+--- a/test.rs\n\
++++ b/test.rs\n\
+@@ -1,4 +1,5 @@\n \
+keep\n\
+-old\n\
++new\n\
++extra\n\
++more\n\
+@@ -20,3 +21,2 @@ fn tail()\n \
+keep\n\
+-gone\n";
+
+        let lines = parse_diff(diff);
+        let counts = count_hunk_changes(&lines);
+
+        // Hunk headers sit at indices 3 and 9
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts.get(&3), Some(&(3, 1)));
+        assert_eq!(counts.get(&9), Some(&(0, 1)));
+    }
+
+    #[test]
+    fn test_hunk_line_numbers_start_at_hunk_header() {
+        let lines = parse_diff(concat!(
+            "@@ -10,3 +12,3 @@\n",
+            " keep\n",
+            "-old\n",
+            "+new\n",
+        ));
+
+        assert_eq!(
+            (lines[1].old_line_num, lines[1].new_line_num),
+            (Some(10), Some(12))
+        );
+        assert_eq!(lines[2].old_line_num, Some(11));
+        assert_eq!(lines[3].new_line_num, Some(13));
+    }
+
+    #[test]
+    fn test_no_newline_marker_keeps_line_numbers() {
+        let lines = parse_diff(concat!(
+            "@@ -1,2 +1,2 @@\n",
+            " first\n",
+            "-last\n",
+            "\\ No newline at end of file\n",
+            "+last\n",
+            "\\ No newline at end of file\n",
+            "@@ -10,1 +10,2 @@\n",
+            " ten\n",
+            "+eleven\n",
+        ));
+
+        assert_eq!(
+            (lines[2].old_line_num, lines[2].new_line_num),
+            (Some(2), None)
+        );
+        assert_eq!((lines[3].old_line_num, lines[3].new_line_num), (None, None));
+        assert_eq!(lines[4].new_line_num, Some(2));
+        assert_eq!(lines[5].new_line_num, None);
+        assert_eq!(lines[8].new_line_num, Some(11));
+    }
+
+    #[test]
+    fn test_extended_headers_are_not_file_lines() {
+        use geschichte::diff::DiffLineType;
+
+        let lines = parse_diff(concat!(
+            "diff --git a/old.sh b/new.sh\n",
+            "old mode 100644\n",
+            "new mode 100755\n",
+            "similarity index 90%\n",
+            "rename from old.sh\n",
+            "rename to new.sh\n",
+            "index 1111111..2222222\n",
+            "--- a/old.sh\n",
+            "+++ b/new.sh\n",
+            "@@ -1,2 +1,2 @@\n",
+            " #!/bin/sh\n",
+            "-echo old\n",
+            "+echo new\n",
+        ));
+
+        assert!(lines[..9]
+            .iter()
+            .all(|line| line.line_type == DiffLineType::Header));
+        assert_eq!(
+            (lines[10].old_line_num, lines[10].new_line_num),
+            (Some(1), Some(1))
+        );
+        assert_eq!(lines[11].old_line_num, Some(2));
+        assert_eq!(lines[12].new_line_num, Some(2));
+    }
+
+    #[test]
+    fn test_combined_diff_lines() {
+        use geschichte::diff::DiffLineType;
+
+        let lines = parse_diff(concat!(
+            "diff --cc test.txt\n",
+            "index 1111111,2222222..3333333\n",
+            "--- a/test.txt\n",
+            "+++ b/test.txt\n",
+            "@@@ -1,3 -1,3 +1,4 @@@\n",
+            "  shared\n",
+            "- ours\n",
+            " -theirs\n",
+            "++resolved\n",
+            "+ kept from theirs\n",
+        ));
+
+        let types: Vec<DiffLineType> = lines.iter().map(|l| l.line_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                DiffLineType::Header,
+                DiffLineType::Header,
+                DiffLineType::Header,
+                DiffLineType::Header,
+                DiffLineType::HunkHeader,
+                DiffLineType::Context,
+                DiffLineType::Deletion,
+                DiffLineType::Deletion,
+                DiffLineType::Addition,
+                DiffLineType::Addition,
+            ]
+        );
+
+        // Content keeps one marker, like a normal diff line
+        assert_eq!(lines[5].content, " shared");
+        assert_eq!(lines[8].content, "+resolved");
+
+        // Old line numbers follow the first parent
+        assert_eq!(
+            (lines[5].old_line_num, lines[5].new_line_num),
+            (Some(1), Some(1))
+        );
+        assert_eq!(lines[6].old_line_num, Some(2));
+        assert_eq!(lines[7].old_line_num, None);
+        assert_eq!(lines[8].new_line_num, Some(2));
+        assert_eq!(lines[9].new_line_num, Some(3));
+    }
+
+    #[test]
+    fn test_render_whitespace() {
+        let line = Line::from(vec![
+            Span::raw("   1│   1 "),
+            Span::raw("+"),
+            Span::raw("\tlet x = 1;  "),
+        ]);
+        let width_before: usize = line.spans.iter().map(|s| s.content.chars().count()).sum();
+
+        let rendered = render_whitespace(line, 2);
+        let text: String = rendered.spans.iter().map(|s| s.content.as_ref()).collect();
+
+        // Line number and marker spans are untouched; glyphs keep the same width
+        assert_eq!(text, "   1│   1 +→let·x·=·1;··");
+        assert_eq!(text.chars().count(), width_before);
+    }
+
+    #[test]
+    fn test_resolve_syntax_theme() {
+        use geschichte::diff::syntax::{resolve_theme, DEFAULT_THEME};
+
+        assert_eq!(resolve_theme(None, None), DEFAULT_THEME);
+        assert_eq!(
+            resolve_theme(Some("base16-ocean.dark"), None),
+            "base16-ocean.dark"
+        );
+        // bat theme names map to a bundled theme and take precedence when enabled
+        assert_eq!(
+            resolve_theme(Some("base16-ocean.dark"), Some("Dracula")),
+            "base16-eighties.dark"
+        );
+        // Unknown names fall through to the next source
+        assert_eq!(
+            resolve_theme(Some("nope"), Some("also-nope")),
+            DEFAULT_THEME
+        );
+    }
+
+    #[test]
+    fn test_syntax_for_dockerfile() {
+        use geschichte::diff::syntax::syntax_name;
+        use std::path::Path;
+
+        let shell = Some("Bourne Again Shell (bash)");
+        assert_eq!(syntax_name(Path::new("Dockerfile"), None), shell);
+        assert_eq!(syntax_name(Path::new("docker/Dockerfile.dev"), None), shell);
+        // A forced language wins over the path
+        assert_eq!(
+            syntax_name(Path::new("Dockerfile"), Some("py")),
+            Some("Python")
+        );
+    }
+
+    #[test]
+    fn test_syntax_from_shebang() {
+        use geschichte::diff::syntax::{file_language, syntax_name};
+        use std::path::Path;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("deploy"),
+            "#!/usr/bin/env python3\nprint('hi')\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("notes"), "just some text\n").unwrap();
+
+        let language = file_language(dir.path(), Path::new("deploy"), None);
+        assert_eq!(language.as_deref(), Some("Python"));
+        assert_eq!(
+            syntax_name(Path::new("deploy"), language.as_deref()),
+            Some("Python")
+        );
+        assert_eq!(file_language(dir.path(), Path::new("notes"), None), None);
+        // `--syntax` is taken as given, without reading the file
+        assert_eq!(
+            file_language(dir.path(), Path::new("missing"), Some("rs")).as_deref(),
+            Some("rs")
+        );
+    }
+
+    #[test]
+    fn test_word_diff_highlights_changed_word() {
+        use geschichte::diff::words::{ADDITION_EMPHASIS_BG, DELETION_EMPHASIS_BG};
+        use geschichte::diff::HighlightedDiff;
+
+        let diff = "diff --git a/test.txt b/test.txt\n\
+--- a/test.txt\n\
++++ b/test.txt\n\
+@@ -1,1 +1,1 @@\n\
+-let greeting = hello(world);\n\
++let greeting = hello(there);\n";
+
+        let emphasized = |line: &Line, bg| -> Vec<String> {
+            line.spans
+                .iter()
+                .filter(|span| span.style.bg == Some(bg))
+                .map(|span| span.content.to_string())
+                .collect()
+        };
+
+        let lines = HighlightedDiff::new(diff, None)
+            .with_word_diff(true)
+            .to_styled_lines_with_search(None);
+        assert_eq!(emphasized(&lines[4], DELETION_EMPHASIS_BG), vec!["world"]);
+        assert_eq!(emphasized(&lines[5], ADDITION_EMPHASIS_BG), vec!["there"]);
+
+        // The text itself is unchanged by the extra styling
+        let text: String = lines[5].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert!(text.ends_with("+let greeting = hello(there);"));
+
+        // Off by default
+        let lines = HighlightedDiff::new(diff, None).to_styled_lines_with_search(None);
+        assert!(emphasized(&lines[5], ADDITION_EMPHASIS_BG).is_empty());
+    }
+
+    #[test]
+    fn test_word_diff_skips_rewritten_lines() {
+        use geschichte::diff::words::changed_word_ranges;
+
+        let (old, new) = changed_word_ranges("a + b", "a - b").unwrap();
+        assert_eq!(old, vec![2..3]);
+        assert_eq!(new, vec![2..3]);
+
+        // Lines with almost nothing in common are not compared word by word
+        assert!(changed_word_ranges("fn alpha()", "return beta + gamma;").is_none());
+    }
+
+    #[test]
+    fn test_word_diff_keeps_files_of_a_whole_commit_apart() {
+        use geschichte::diff::words::WordHighlights;
+        use std::path::Path;
+
+        // Both files change their line 1, but only a.txt's change is a small edit
+        let diff = "diff --git a/a.txt b/a.txt\n\
+--- a/a.txt\n\
++++ b/a.txt\n\
+@@ -1,1 +1,1 @@\n\
+-let x = 1;\n\
++let x = 2;\n\
+diff --git a/b.txt b/b.txt\n\
+--- a/b.txt\n\
++++ b/b.txt\n\
+@@ -1,1 +1,1 @@\n\
+-fn alpha()\n\
++return beta + gamma;\n";
+        let lines = parse_diff(diff);
+        let words = WordHighlights::from_lines(&lines);
+
+        let a = Some(Path::new("a.txt"));
+        let b = Some(Path::new("b.txt"));
+        let changed = words.ranges_for(a, &lines[5]).unwrap();
+        assert_eq!((changed.len(), changed[0].clone()), (1, 8..9));
+        assert_eq!(words.ranges_for(b, &lines[5]), None);
+        assert_eq!(words.ranges_for(b, &lines[11]), None);
+    }
+}
@@ -74,4 +74,97 @@ mod test {
         // Search should work with lowercase
         assert!(!picker.filtered_files.is_empty());
     }
+
+    #[test]
+    fn test_file_at_row_follows_list_scroll() {
+        use ratatui::layout::Rect;
+
+        let mut picker = geschichte::ui::file_picker::FilePickerState::new(sample_git_files());
+        let list_area = Rect::new(5, 10, 40, 2);
+
+        assert_eq!(picker.file_at_row(list_area, 9), None);
+        assert_eq!(picker.file_at_row(list_area, 10), Some(0));
+        assert_eq!(picker.file_at_row(list_area, 11), Some(1));
+        assert_eq!(picker.file_at_row(list_area, 12), None);
+
+        // With the last file selected the list scrolls to keep it on the bottom row
+        picker.selected = 2;
+        assert_eq!(picker.file_at_row(list_area, 10), Some(1));
+        assert_eq!(picker.file_at_row(list_area, 11), Some(2));
+
+        // Rows past the end of a short list hold no file
+        let tall_area = Rect::new(5, 10, 40, 10);
+        picker.selected = 0;
+        assert_eq!(picker.file_at_row(tall_area, 12), Some(2));
+        assert_eq!(picker.file_at_row(tall_area, 13), None);
+    }
+
+    #[test]
+    fn test_modified_only_filter() {
+        let mut picker = geschichte::ui::file_picker::FilePickerState::new(sample_git_files());
+        picker.move_down();
+
+        picker.toggle_modified_only();
+        assert_eq!(picker.selected, 0);
+        assert_eq!(picker.filtered_files.len(), 1);
+        assert_eq!(
+            picker.get_selected_file().unwrap().display_path,
+            "src/app.rs"
+        );
+
+        // Typing narrows within the modified files
+        picker.append_char('m');
+        picker.append_char('a');
+        picker.append_char('i');
+        picker.append_char('n');
+        assert!(picker.filtered_files.is_empty());
+
+        picker.toggle_modified_only();
+        assert_eq!(picker.filtered_files.len(), 1);
+        assert_eq!(
+            picker.get_selected_file().unwrap().display_path,
+            "src/main.rs"
+        );
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        use geschichte::ui::file_picker::{FilePickerState, FileSort};
+        use std::time::{Duration, SystemTime};
+
+        let mut files = sample_git_files();
+        files[0].modified = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(100));
+        files[2].modified = Some(SystemTime::UNIX_EPOCH + Duration::from_secs(200));
+        let mut picker = FilePickerState::new(files);
+        let listed = |picker: &FilePickerState| -> Vec<String> {
+            picker
+                .filtered_files
+                .iter()
+                .map(|(i, _)| picker.files[*i].display_path.clone())
+                .collect()
+        };
+
+        assert_eq!(picker.sort, FileSort::Name);
+        assert_eq!(
+            listed(&picker),
+            ["src/main.rs", "src/app.rs", "tests/test.rs"]
+        );
+
+        picker.cycle_sort();
+        assert_eq!(picker.sort, FileSort::Modified);
+        assert_eq!(
+            listed(&picker),
+            ["tests/test.rs", "src/main.rs", "src/app.rs"]
+        );
+
+        picker.cycle_sort();
+        assert_eq!(picker.sort, FileSort::Status);
+        assert_eq!(
+            listed(&picker),
+            ["src/app.rs", "src/main.rs", "tests/test.rs"]
+        );
+
+        picker.cycle_sort();
+        assert_eq!(picker.sort, FileSort::Name);
+    }
 }
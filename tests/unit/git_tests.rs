@@ -4,6 +4,25 @@ mod test {
     use super::*;
     use crate::common::*;
 
+    #[test]
+    fn test_parse_line_origin() {
+        use geschichte::git::blame::parse_line_origin;
+
+        let hash = "a".repeat(40);
+        let porcelain = format!(
+            "{hash} 7 3 1\n\
+             author Alice\n\
+             filename src/old.rs\n\
+             \tlet x = 1;\n"
+        );
+        let origin = parse_line_origin(&porcelain).unwrap();
+        assert_eq!(origin.hash, hash);
+        assert_eq!(origin.path, PathBuf::from("src/old.rs"));
+        assert_eq!(origin.line, 7);
+
+        assert!(parse_line_origin("").is_none());
+    }
+
     #[test]
     fn test_fetch_diff_with_context() {
         let test_repo = TestRepo::new_with_commits(3);
@@ -12,6 +31,8 @@ mod test {
             &PathBuf::from("test.txt"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -23,6 +44,8 @@ mod test {
             None,
             &PathBuf::from("test.txt"),
             5,
+            false,
+            None,
         );
 
         assert!(result.is_ok());
@@ -38,6 +61,8 @@ mod test {
             &PathBuf::from("test.txt"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -49,6 +74,8 @@ mod test {
             &commits[0].hash,
             &PathBuf::from("test.txt"),
             3,
+            false,
+            None,
         );
 
         assert!(result.is_ok());
@@ -65,6 +92,8 @@ mod test {
             &PathBuf::from("new_name.rs"),
             true,
             false,
+            0,
+            None,
         )
         .unwrap();
 
@@ -73,12 +102,330 @@ mod test {
             &PathBuf::from("new_name.rs"),
             false,
             false,
+            0,
+            None,
         )
         .unwrap();
 
         assert!(commits_with_follow.len() > commits_without_follow.len());
     }
 
+    #[test]
+    fn test_rename_map_across_two_renames() {
+        let test_repo = TestRepo::new_with_double_rename();
+        let file_path = PathBuf::from("third.rs");
+
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &file_path,
+            true,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(commits.len(), 4);
+
+        // Each commit, newest first, resolves to the name the file had at that commit
+        let expected = ["third.rs", "second.rs", "second.rs", "first.rs"];
+        let rename_map = geschichte::git::history::build_rename_map(
+            test_repo.path(),
+            &file_path,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        for (commit, path) in commits.iter().zip(expected) {
+            assert_eq!(rename_map[&commit.hash], PathBuf::from(path));
+        }
+
+        // A later page knows about the renames in the commits before it
+        let page = geschichte::git::history::build_rename_map(
+            test_repo.path(),
+            &file_path,
+            false,
+            2,
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[&commits[2].hash], PathBuf::from("second.rs"));
+        assert_eq!(page[&commits[3].hash], PathBuf::from("first.rs"));
+
+        // The diff of every commit is found at its own path
+        for (commit, path) in commits.iter().zip(expected) {
+            let parents =
+                geschichte::git::history::get_commit_parents(test_repo.path(), &commit.hash)
+                    .unwrap();
+            let diff = geschichte::git::diff::fetch_diff(
+                test_repo.path(),
+                &commit.hash,
+                parents.first().map(String::as_str),
+                &PathBuf::from(path),
+                3,
+                false,
+                None,
+            )
+            .unwrap();
+            assert!(!diff.trim().is_empty(), "no diff for {}", commit.hash);
+        }
+    }
+
+    #[test]
+    fn test_binary_file_diff_shows_sizes() {
+        let test_repo = TestRepo::new();
+        let image = test_repo.path().join("logo.png");
+        let commit = |message: &str| {
+            for args in [&["add", "."][..], &["commit", "-m", message]] {
+                std::process::Command::new("git")
+                    .args(args)
+                    .current_dir(test_repo.path())
+                    .output()
+                    .unwrap();
+            }
+        };
+        std::fs::write(&image, [0u8, 1, 2, 3]).unwrap();
+        commit("Add logo");
+        std::fs::write(&image, [0u8, 1, 2, 3, 4, 5]).unwrap();
+        commit("Grow logo");
+
+        let file_path = PathBuf::from("logo.png");
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &file_path,
+            false,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(commits.len(), 2);
+
+        let diff = geschichte::git::diff::fetch_diff(
+            test_repo.path(),
+            &commits[0].hash,
+            Some(&commits[1].hash),
+            &file_path,
+            3,
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(diff.contains("Binary file — 4 → 6 bytes (+2 bytes changed)"));
+        assert!(!diff.contains("Binary files"));
+
+        let added = geschichte::git::diff::fetch_diff(
+            test_repo.path(),
+            &commits[1].hash,
+            geschichte::git::history::get_commit_parents(test_repo.path(), &commits[1].hash)
+                .unwrap()
+                .first()
+                .map(String::as_str),
+            &file_path,
+            3,
+            false,
+            None,
+        )
+        .unwrap();
+        assert!(added.contains("Binary file added — 4 bytes"));
+
+        // The placeholder is a header, so it is neither highlighted nor word-diffed
+        let placeholder = geschichte::diff::parse_diff(&diff)
+            .into_iter()
+            .find(|line| line.content.starts_with("Binary file"))
+            .unwrap();
+        assert_eq!(
+            placeholder.line_type,
+            geschichte::diff::DiffLineType::Header
+        );
+    }
+
+    #[test]
+    fn test_line_age_buckets() {
+        use geschichte::git::blame::LineAge;
+
+        let now = 1_700_000_000;
+        let day = 86_400;
+        assert_eq!(LineAge::of(now - 60, now), LineAge::Day);
+        assert_eq!(LineAge::of(now - 3 * day, now), LineAge::Week);
+        assert_eq!(LineAge::of(now - 7 * day, now), LineAge::Month);
+        assert_eq!(LineAge::of(now - 29 * day, now), LineAge::Month);
+        assert_eq!(LineAge::of(now - 400 * day, now), LineAge::Older);
+    }
+
+    #[test]
+    fn test_parse_blame_porcelain() {
+        use geschichte::git::blame::parse_porcelain;
+
+        let sha1 = "a".repeat(40);
+        let sha256 = "b".repeat(64);
+        let porcelain = format!(
+            "{sha1} 1 1 1\n\
+             author Alice\n\
+             author-time 1700000000\n\
+             author-tz -0800\n\
+             \tfirst\n\
+             {sha256} 2 2 1\n\
+             author Bob\n\
+             author-time 1700000000\n\
+             author-tz +1400\n\
+             \tsecond\n\
+             {sha1} 3 3 1\n\
+             \tthird\n"
+        );
+
+        let lines = parse_porcelain(porcelain.as_bytes(), None);
+        assert_eq!(lines.len(), 3);
+
+        // 2023-11-14 22:13 UTC falls on different dates on either side of the date line
+        assert_eq!(
+            (lines[0].author.as_str(), lines[0].date.as_str()),
+            ("Alice", "2023-11-14")
+        );
+        assert_eq!(lines[1].hash, sha256);
+        assert_eq!(lines[1].short_hash, "bbbbbbb");
+        assert_eq!(
+            (lines[1].author.as_str(), lines[1].date.as_str()),
+            ("Bob", "2023-11-15")
+        );
+
+        // Repeated commits reuse the details printed the first time
+        assert_eq!(lines[2].author, "Alice");
+        assert_eq!(lines[2].content, "third");
+    }
+
+    #[test]
+    fn test_count_commits() {
+        use geschichte::git::history::count_commits;
+
+        let test_repo = TestRepo::new_with_renames();
+        let file_path = PathBuf::from("new_name.rs");
+
+        // Following renames counts the commits made under the old name too
+        assert_eq!(
+            count_commits(test_repo.path(), &file_path, true, false).unwrap(),
+            3
+        );
+        assert_eq!(
+            count_commits(test_repo.path(), &file_path, false, false).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_history_reads_ref_decorations() {
+        use geschichte::git::history::parse_decorations;
+
+        assert_eq!(
+            parse_decorations("HEAD -> main, origin/main, origin/HEAD, tag: v1.0"),
+            ["branch:main", "branch:origin/main", "tag:v1.0"]
+        );
+        assert!(parse_decorations("").is_empty());
+
+        let test_repo = TestRepo::new_with_commits(2);
+        std::process::Command::new("git")
+            .args(["tag", "v1.0", "HEAD~1"])
+            .current_dir(test_repo.path())
+            .output()
+            .unwrap();
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &PathBuf::from("test.txt"),
+            true,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        assert_eq!(commits[0].refs.len(), 1);
+        assert!(commits[0].refs[0].starts_with("branch:"));
+        assert_eq!(commits[1].refs, ["tag:v1.0"]);
+    }
+
+    #[test]
+    fn test_date_range_args() {
+        use geschichte::git::history::date_range_args;
+
+        assert!(date_range_args(None, None).is_empty());
+        assert_eq!(
+            date_range_args(Some("2 weeks ago"), None),
+            ["--since=2 weeks ago"]
+        );
+        assert_eq!(
+            date_range_args(Some("2024-01-01"), Some("2024-01-31")),
+            ["--since=2024-01-01", "--until=2024-01-31"]
+        );
+    }
+
+    #[test]
+    fn test_fetch_all_commit_stats() {
+        let test_repo = TestRepo::new_with_commits(2);
+        std::fs::write(test_repo.path().join("test.txt"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(test_repo.path().join("other.txt"), "other\n").unwrap();
+        for args in [&["add", "."][..], &["commit", "-m", "Touch two files"]] {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(test_repo.path())
+                .output()
+                .unwrap();
+        }
+
+        let file_path = PathBuf::from("test.txt");
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &file_path,
+            true,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+        let stats = geschichte::git::history::fetch_all_commit_stats(
+            test_repo.path(),
+            &file_path,
+            true,
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(stats.len(), commits.len());
+        // Every file the commit changed counts, not just the viewed one
+        let newest = &stats[&commits[0].hash];
+        assert_eq!(
+            (newest.files_changed, newest.insertions, newest.deletions),
+            (2, 4, 1)
+        );
+        let initial = &stats[&commits[2].hash];
+        assert_eq!((initial.insertions, initial.deletions), (1, 0));
+    }
+
+    #[test]
+    fn test_untracked_file_diff_shows_every_line_added() {
+        use geschichte::git::working::{
+            check_working_directory_status, fetch_working_directory_diff, WorkingDirectoryStatus,
+        };
+
+        let test_repo = TestRepo::new();
+        std::fs::write(test_repo.path().join("fresh.rs"), "fn main() {}\n// done\n").unwrap();
+        let file_path = PathBuf::from("fresh.rs");
+
+        assert_eq!(
+            check_working_directory_status(test_repo.path(), &file_path).unwrap(),
+            WorkingDirectoryStatus::Untracked
+        );
+        assert_eq!(
+            geschichte::git::files::verify_file_in_repo(test_repo.path(), &file_path, false)
+                .unwrap(),
+            file_path
+        );
+
+        let diff =
+            fetch_working_directory_diff(test_repo.path(), &file_path, 3, false, None).unwrap();
+        assert!(diff.contains("new file mode"));
+        assert!(diff.contains("+fn main() {}\n+// done"));
+    }
+
     #[test]
     fn test_working_directory_diff() {
         let test_repo = TestRepo::new();
@@ -89,6 +436,8 @@ mod test {
             test_repo.path(),
             &PathBuf::from("test.txt"),
             3,
+            false,
+            None,
         );
 
         assert!(result.is_ok());
@@ -96,4 +445,166 @@ mod test {
         assert!(!diff.is_empty());
         assert!(diff.contains("Modified content"));
     }
+
+    #[test]
+    fn test_exclude_filter_globs() {
+        use geschichte::git::files::ExcludeFilter;
+
+        let filter = ExcludeFilter::new(&[
+            "# generated code",
+            "node_modules/",
+            "*.min.js",
+            "/dist",
+            "src/generated/**",
+        ]);
+
+        assert!(filter.is_excluded("node_modules/react/index.js"));
+        assert!(filter.is_excluded("web/node_modules/lib.js"));
+        assert!(filter.is_excluded("static/app.min.js"));
+        assert!(filter.is_excluded("dist/bundle.js"));
+        assert!(filter.is_excluded("src/generated/proto/api.rs"));
+
+        assert!(!filter.is_excluded("src/node_modules.rs"));
+        assert!(!filter.is_excluded("src/app.js"));
+        assert!(!filter.is_excluded("web/dist/bundle.js"));
+        assert!(!filter.is_excluded("src/main.rs"));
+        assert!(ExcludeFilter::new::<&str>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_exclude_filter_negation_and_double_star() {
+        use geschichte::git::files::ExcludeFilter;
+
+        let filter = ExcludeFilter::new(&[
+            "vendor/",
+            "!vendor/keep/",
+            "**/fixtures/*.json",
+            "docs/**/*.png",
+            "*.log",
+            "!important.log",
+        ]);
+
+        // A later `!` pattern re-includes paths an earlier one excluded
+        assert!(filter.is_excluded("vendor/lib/a.rs"));
+        assert!(!filter.is_excluded("vendor/keep/b.rs"));
+        assert!(filter.is_excluded("logs/debug.log"));
+        assert!(!filter.is_excluded("logs/important.log"));
+
+        // `**/` matches zero or more directories; `*` stays within one segment
+        assert!(filter.is_excluded("fixtures/a.json"));
+        assert!(filter.is_excluded("tests/unit/fixtures/a.json"));
+        assert!(!filter.is_excluded("tests/fixtures/nested/a.json"));
+        assert!(filter.is_excluded("docs/logo.png"));
+        assert!(filter.is_excluded("docs/img/dark/logo.png"));
+        assert!(!filter.is_excluded("site/docs/logo.png"));
+
+        // Directory-only patterns don't match a file of the same name
+        assert!(!ExcludeFilter::new(&["build/"]).is_excluded("build"));
+        assert!(ExcludeFilter::new(&["build"]).is_excluded("build"));
+    }
+
+    #[test]
+    fn test_get_git_files_applies_ignore_file() {
+        use geschichte::git::files::{get_git_files, ExcludeFilter};
+
+        let test_repo = TestRepo::new_with_commits(1);
+        std::fs::create_dir_all(test_repo.path().join("vendor")).unwrap();
+        std::fs::write(test_repo.path().join("vendor/lib.rs"), "// vendored\n").unwrap();
+        std::fs::write(
+            test_repo.path().join(ExcludeFilter::IGNORE_FILE),
+            "vendor/\n",
+        )
+        .unwrap();
+
+        let all = get_git_files(test_repo.path(), &ExcludeFilter::default()).unwrap();
+        assert!(all.iter().any(|f| f.display_path == "vendor/lib.rs"));
+
+        let filter = ExcludeFilter::load(test_repo.path(), &[]);
+        let filtered = get_git_files(test_repo.path(), &filter).unwrap();
+        assert!(!filtered.iter().any(|f| f.display_path == "vendor/lib.rs"));
+        assert!(filtered.len() < all.len());
+    }
+
+    #[test]
+    fn test_patch_git_args_per_selection() {
+        use geschichte::git::show::PatchSource;
+        use geschichte::git::working::WorkingDirectoryStatus;
+        use std::path::Path;
+
+        let commit = PatchSource::Commit("abc123".to_string());
+        assert_eq!(
+            commit.git_args(None),
+            ["format-patch", "-1", "--stdout", "abc123"]
+        );
+        assert_eq!(
+            commit.git_args(Some(Path::new("/tmp/patches"))),
+            ["format-patch", "-1", "-o", "/tmp/patches", "abc123"]
+        );
+
+        let range = PatchSource::Range {
+            older: "aaa".to_string(),
+            newer: "bbb".to_string(),
+        };
+        assert_eq!(
+            range.git_args(None),
+            ["format-patch", "--stdout", "aaa..bbb"]
+        );
+
+        let working = |status| PatchSource::WorkingDirectory {
+            file_path: PathBuf::from("src/main.rs"),
+            status,
+        };
+        assert_eq!(
+            working(WorkingDirectoryStatus::Modified).git_args(None),
+            ["diff", "--", "src/main.rs"]
+        );
+        assert_eq!(
+            working(WorkingDirectoryStatus::Staged).git_args(None),
+            ["diff", "--staged", "--", "src/main.rs"]
+        );
+        assert_eq!(
+            working(WorkingDirectoryStatus::ModifiedAndStaged).git_args(None),
+            ["diff", "HEAD", "--", "src/main.rs"]
+        );
+    }
+
+    #[test]
+    fn test_fetch_and_save_patch() {
+        use geschichte::git::show::{fetch_patch, save_patch, PatchSource};
+
+        let test_repo = TestRepo::new_with_commits(3);
+        let commits = geschichte::git::history::fetch_commit_history(
+            test_repo.path(),
+            &PathBuf::from("test.txt"),
+            false,
+            false,
+            0,
+            None,
+        )
+        .unwrap();
+
+        let patch = fetch_patch(
+            test_repo.path(),
+            &PatchSource::Commit(commits[0].hash.clone()),
+        )
+        .unwrap();
+        assert!(patch.starts_with(&format!("From {}", commits[0].hash)));
+        assert!(patch.contains("+Content version 2"));
+
+        // The range leaves out its older end
+        let out_dir = tempfile::tempdir().unwrap();
+        let written = save_patch(
+            test_repo.path(),
+            &PatchSource::Range {
+                older: commits[2].hash.clone(),
+                newer: commits[0].hash.clone(),
+            },
+            out_dir.path(),
+        )
+        .unwrap();
+        assert_eq!(written.len(), 2);
+        let name = written[0].file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("0001-") && name.ends_with(".patch"));
+        assert!(written.iter().all(|path| path.exists()));
+    }
 }
@@ -1,5 +1,8 @@
 mod app_tests;
 mod cache_tests;
+mod config_tests;
+mod diff_tests;
 mod file_picker_tests;
 mod git_tests;
 mod snapshot_tests;
+mod ui_tests;
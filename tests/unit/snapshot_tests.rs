@@ -10,7 +10,7 @@ mod syntax_highlighting_snapshots {
         let rust_code = "fn main() { println!(\"Hello, world!\"); }";
         let file_path = Path::new("main.rs");
 
-        let highlighted = highlight_line(rust_code, file_path);
+        let highlighted = highlight_line(rust_code, file_path, None);
 
         // Convert the highlighted spans to a more snapshot-friendly format
         let snapshot_data: Vec<(String, String)> = highlighted
@@ -30,7 +30,7 @@ mod syntax_highlighting_snapshots {
         let json_code = r#"{"name": "test", "value": 42, "enabled": true}"#;
         let file_path = Path::new("config.json");
 
-        let highlighted = highlight_line(json_code, file_path);
+        let highlighted = highlight_line(json_code, file_path, None);
 
         // Convert to snapshot-friendly format
         let snapshot_data: Vec<(String, String)> = highlighted
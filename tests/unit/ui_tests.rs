@@ -0,0 +1,294 @@
+use ratatui::{backend::TestBackend, Terminal};
+
+mod test {
+    use super::*;
+    use crate::common::*;
+
+    fn render_to_string(app: &geschichte::app::App, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| geschichte::ui::draw(frame, app))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_terminal_too_small_message() {
+        let mut app = create_test_app();
+        app.ui_state.show_help = true;
+
+        let rendered = render_to_string(&app, 30, 6);
+        assert!(rendered.contains("Terminal too small"));
+
+        // Recovers once the terminal is large enough
+        app.ui_state.show_help = false;
+        let rendered = render_to_string(&app, 80, 24);
+        assert!(!rendered.contains("Terminal too small"));
+    }
+
+    #[test]
+    fn test_tiny_terminal_renders_without_panicking() {
+        use geschichte::ui::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
+
+        let mut app = create_test_app_with_commits();
+        let notice = format!(
+            "Terminal too small (need ≥ {}x{})",
+            MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT
+        );
+
+        // An 8x4 terminal only has room for the start of the wrapped notice
+        let rendered = render_to_string(&app, 8, 4);
+        let words: Vec<&str> = rendered.split_whitespace().collect();
+        assert_eq!(words, ["Terminal", "too", "small"]);
+
+        // Just below the minimum in either direction, the whole notice fits on one row
+        for (width, height) in [
+            (MIN_TERMINAL_WIDTH - 1, MIN_TERMINAL_HEIGHT),
+            (MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT - 1),
+        ] {
+            assert!(render_to_string(&app, width, height).contains(&notice));
+        }
+
+        // Just past the minimum, every layout and the file picker still fit
+        for layout in [
+            geschichte::cli::LayoutMode::Unified,
+            geschichte::cli::LayoutMode::SideBySide,
+        ] {
+            app.layout_mode = layout;
+            for (width, height) in [(40, 10), (41, 11), (45, 12)] {
+                render_to_string(&app, width, height);
+            }
+        }
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.ui_state.commits_bottom = true;
+        render_to_string(&app, 40, 10);
+        app.ui_state.show_help = true;
+        render_to_string(&app, 40, 10);
+        app.ui_state.show_help = false;
+
+        app.mode = geschichte::app::AppMode::FilePicker {
+            state: geschichte::ui::file_picker::FilePickerState::new(sample_git_files()),
+            context: geschichte::app::FilePickerContext::Initial,
+        };
+        render_to_string(&app, 40, 10);
+        render_to_string(&app, 8, 4);
+    }
+
+    #[test]
+    fn test_range_selection_marks_both_endpoints() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.diff_range_start = Some(0);
+        app.selected_index = 3;
+
+        let rendered = render_to_string(&app, 100, 20);
+        assert_eq!(rendered.matches('►').count(), 2);
+        // Rows strictly between the endpoints get a gutter bar next to the left border
+        let bar_rows = rendered
+            .lines()
+            .filter(|row| row.starts_with("││ "))
+            .count();
+        assert_eq!(bar_rows, 2);
+    }
+
+    #[test]
+    fn test_diff_scrollbar_follows_terminal_size() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.current_diff = (1..=100).map(|i| format!("+line {}\n", i)).collect();
+        app.cached_styled_lines = None;
+        app.ui_state.diff_scroll = 50;
+
+        let rendered = render_to_string(&app, 100, 20);
+        assert!(rendered.contains("%)"));
+        assert!(rendered.lines().any(|row| row.ends_with('█')));
+
+        // Once the whole diff fits, neither the scrollbar nor the percentage is shown
+        app.ui_state.diff_scroll = 0;
+        let rendered = render_to_string(&app, 100, 120);
+        assert!(!rendered.contains("%)"));
+        assert!(!rendered.lines().any(|row| row.ends_with('█')));
+    }
+
+    #[test]
+    fn test_full_file_line_count_in_side_by_side_layout() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::SideBySide;
+        app.current_diff = (1..=100).map(|i| format!("line {}\n", i)).collect();
+        app.current_side_by_side_diff =
+            Some(geschichte::diff::side_by_side::SideBySideDiff::from_unified(&[]));
+        app.full_file_view = true;
+
+        // The unified panel draws the file, so its lines set the scrollbar and percentage
+        assert_eq!(app.get_diff_line_count(), 100);
+    }
+
+    #[test]
+    fn test_collapsed_hunk_hides_its_body() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        let diff: String = std::iter::once("@@ -1,5 +1,5 @@\n".to_string())
+            .chain((1..=5).map(|i| format!("+first {}\n", i)))
+            .chain(std::iter::once("@@ -20,2 +20,2 @@\n".to_string()))
+            .chain((1..=2).map(|i| format!("+second {}\n", i)))
+            .collect();
+        app.current_diff = diff.clone();
+        app.cached_highlighted_diff = Some(geschichte::diff::HighlightedDiff::new(&diff, None));
+        app.cached_styled_lines = None;
+
+        let rendered = render_to_string(&app, 100, 30);
+        assert_eq!(rendered.matches("+first").count(), 5);
+
+        app.ui_state.diff_cursor_line = 3;
+        app.toggle_hunk_under_cursor();
+        assert_eq!(app.ui_state.diff_cursor_line, 0);
+
+        let rendered = render_to_string(&app, 100, 30);
+        assert_eq!(rendered.matches("+first").count(), 0);
+        assert_eq!(rendered.matches("+second").count(), 2);
+        assert!(rendered.contains("(5 lines hidden)"));
+
+        // Toggling again shows the body
+        app.toggle_hunk_under_cursor();
+        let rendered = render_to_string(&app, 100, 30);
+        assert_eq!(rendered.matches("+first").count(), 5);
+    }
+
+    #[test]
+    fn test_commits_bottom_layout() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.ui_state.commits_bottom = true;
+
+        let rendered = render_to_string(&app, 100, 20);
+        let rows: Vec<&str> = rendered.lines().collect();
+
+        // The diff spans the full width at the top, the commit strip sits below it
+        assert!(rows[0].starts_with("┌ Diff"));
+        let commits_row = rows
+            .iter()
+            .position(|row| row.contains("Commits"))
+            .expect("commits panel title");
+        assert!(commits_row >= 10);
+    }
+
+    #[test]
+    fn test_side_by_side_vertical_split_resizes() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::SideBySide;
+        let commits_row = |app: &geschichte::app::App| {
+            render_to_string(app, 120, 41)
+                .lines()
+                .position(|row| row.contains("Commits"))
+                .expect("commits panel title")
+        };
+        let default_row = commits_row(&app);
+
+        // `h` shrinks the commit strip, giving the diffs more height, up to the clamp
+        let h = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::NONE);
+        for _ in 0..10 {
+            app.handle_ui_keys(h).unwrap();
+        }
+        assert_eq!(app.ui_state.vertical_split_ratio, 0.9);
+        assert!(commits_row(&app) > default_row);
+
+        let l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE);
+        for _ in 0..20 {
+            app.handle_ui_keys(l).unwrap();
+        }
+        assert_eq!(app.ui_state.vertical_split_ratio, 0.3);
+        assert!(commits_row(&app) < default_row);
+    }
+
+    #[test]
+    fn test_author_display_cycles_in_commit_list() {
+        use geschichte::ui::state::AuthorDisplay;
+
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        assert_eq!(app.ui_state.author_display, AuthorDisplay::Hidden);
+        assert!(!render_to_string(&app, 120, 20).contains("Test User"));
+
+        app.cycle_author_display();
+        let rendered = render_to_string(&app, 120, 20);
+        assert!(rendered.contains("Test User"));
+        assert!(!rendered.contains("test@example.com"));
+
+        app.cycle_author_display();
+        assert!(render_to_string(&app, 120, 20).contains("test@example.com"));
+
+        app.cycle_author_display();
+        assert_eq!(app.ui_state.author_display, AuthorDisplay::NameAndEmail);
+        app.cycle_author_display();
+        assert_eq!(app.ui_state.author_display, AuthorDisplay::Hidden);
+    }
+
+    #[test]
+    fn test_decorations_before_commit_subject() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.ui_state.split_ratio = 0.7;
+        app.commits[0].refs = ["branch:main", "tag:v1.0", "branch:dev", "branch:old"]
+            .map(String::from)
+            .to_vec();
+
+        // Only the first few are listed, the rest counted
+        let rendered = render_to_string(&app, 120, 20);
+        assert!(rendered.contains("(main, v1.0, dev, +1) "));
+        assert!(!rendered.contains("old"));
+    }
+
+    #[test]
+    fn test_copy_mode_help_overlay() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = create_test_app_with_commits();
+        app.start_copy_mode();
+        assert!(!render_to_string(&app, 120, 30).contains("Absolute path"));
+
+        // `?` shows the targets without leaving copy mode
+        app.handle_copy_keys(KeyEvent::new(KeyCode::Char('?'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(app.copy_mode.is_some());
+        let rendered = render_to_string(&app, 120, 30);
+        assert!(rendered.contains("Absolute path"));
+        assert!(rendered.contains("src/main.rs:42"));
+
+        app.cancel_copy_mode();
+        assert!(!render_to_string(&app, 120, 30).contains("Absolute path"));
+    }
+
+    #[test]
+    fn test_word_wrap_shows_the_end_of_long_lines() {
+        let mut app = create_test_app_with_commits();
+        app.layout_mode = geschichte::cli::LayoutMode::Unified;
+        app.current_diff = format!("@@ -1 +1 @@\n+{}END\n", "a".repeat(150));
+        app.cached_styled_lines = None;
+
+        let rendered = render_to_string(&app, 100, 20);
+        assert!(!rendered.contains("END"));
+
+        app.ui_state.word_wrap = true;
+        let rendered = render_to_string(&app, 100, 20);
+        assert!(rendered.contains("END"));
+        assert!(rendered.contains("[wrap]"));
+        // Continuation rows leave the line numbers and marker blank
+        let continuation = format!("│{}aaaa", " ".repeat(11));
+        assert!(
+            rendered
+                .lines()
+                .filter(|row| row.contains(&continuation))
+                .count()
+                >= 2
+        );
+    }
+}